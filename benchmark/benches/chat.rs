@@ -1,12 +1,9 @@
 use chat::{Q, S};
-use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use graphql_benchmark::{parse, run, serialize};
+use criterion::{criterion_group, criterion_main, Criterion};
+use graphql_benchmark::bench_schema;
 
 pub fn bench(c: &mut Criterion) {
-    c.bench_function("chat run", |b| b.iter(|| run(&S, black_box(Q))));
-    c.bench_function("chat parse", |b| b.iter(|| parse(black_box(Q))));
-    let res = run(&S, Q);
-    c.bench_function("chat serialize", |b| b.iter(|| serialize(black_box(&res))));
+    bench_schema(c, "chat", &S, Q);
 }
 
 criterion_group!(chat, bench);
@@ -0,0 +1,11 @@
+use async_graphql::INTROSPECTION_QUERY;
+use chat::S;
+use criterion::{criterion_group, criterion_main, Criterion};
+use graphql_benchmark::bench_schema;
+
+pub fn bench(c: &mut Criterion) {
+    bench_schema(c, "introspection", &S, INTROSPECTION_QUERY);
+}
+
+criterion_group!(introspection, bench);
+criterion_main!(introspection);
@@ -0,0 +1,10 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use graphql_benchmark::bench_schema;
+use simple::{S, WIDE_Q};
+
+pub fn bench(c: &mut Criterion) {
+    bench_schema(c, "wide", &S, WIDE_Q);
+}
+
+criterion_group!(wide, bench);
+criterion_main!(wide);
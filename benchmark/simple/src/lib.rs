@@ -13,6 +13,11 @@ impl QueryRoot {
     async fn obj(&self) -> MyObj {
         MyObj
     }
+
+    #[field]
+    async fn obj_list(&self, count: i32) -> Vec<MyObj> {
+        (0..count).map(|_| MyObj).collect()
+    }
 }
 
 pub struct MyObj;
@@ -35,6 +40,12 @@ impl MyObj {
     }
 }
 
+pub const WIDE_Q: &str = r#"{
+    objList(count: 1000) {
+        valueI32 valueList obj { valueI32 valueList }
+    }
+}"#;
+
 lazy_static::lazy_static! {
     pub static ref S: Schema<QueryRoot, EmptyMutation, EmptySubscription> = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
     // static ref D: Document = parse_query(Q).unwrap();
@@ -1,4 +1,11 @@
-use async_graphql::{resolver_utils::ObjectType, Response, Schema, SubscriptionType};
+//! Helpers for benchmarking `async-graphql` schemas.
+//!
+//! Each stage of a request (parse, validate, execute and serialize) can be measured on its own,
+//! and with the `harness` feature [`bench_schema`](fn.bench_schema.html) registers all of them
+//! with `criterion` for any schema, so you can benchmark your own schemas the same way as the
+//! ones in this crate.
+
+use async_graphql::{resolver_utils::ObjectType, Response, Schema, SubscriptionType, Variables};
 use async_graphql_parser::{parse_query, types::ExecutableDocument};
 use async_std::task;
 
@@ -6,6 +13,7 @@ use async_std::task;
 #[global_allocator]
 static ALLOC: jemallocator::Jemalloc = jemallocator::Jemalloc;
 
+/// Parse, validate and execute a query, panicking if it fails.
 pub fn run<Query, Mutation, Subscription>(
     s: &Schema<Query, Mutation, Subscription>,
     q: &str,
@@ -18,18 +26,56 @@ where
     task::block_on(async { s.execute(q).await.into_result().unwrap() })
 }
 
+/// Parse a query, panicking if it fails.
 pub fn parse(q: &str) -> ExecutableDocument {
     parse_query(q).unwrap()
 }
 
-// pub fn validate() {
-//     check_rules(&S.env.registry, &D, S.validation_mode).unwrap();
-// }
-//
-// pub fn resolve() {
-//     do_resolve(...).unwrap();
-// }
+/// Validate a parsed query against a schema, panicking if it fails.
+pub fn validate<Query, Mutation, Subscription>(
+    s: &Schema<Query, Mutation, Subscription>,
+    doc: &ExecutableDocument,
+) where
+    Query: ObjectType + Send + Sync + 'static,
+    Mutation: ObjectType + Send + Sync + 'static,
+    Subscription: SubscriptionType + Send + Sync + 'static,
+{
+    s.validate(doc, &Variables::default()).unwrap();
+}
 
+/// Serialize a response to JSON.
 pub fn serialize(r: &async_graphql::Response) -> String {
     serde_json::to_string(r).unwrap()
 }
+
+/// Register the `run`, `parse`, `validate` and `serialize` benchmarks of a query with `criterion`.
+///
+/// The benchmarks are named `"{name} run"`, `"{name} parse"` and so on.
+#[cfg(feature = "harness")]
+pub fn bench_schema<Query, Mutation, Subscription>(
+    c: &mut criterion::Criterion,
+    name: &str,
+    s: &Schema<Query, Mutation, Subscription>,
+    q: &str,
+) where
+    Query: ObjectType + Send + Sync + 'static,
+    Mutation: ObjectType + Send + Sync + 'static,
+    Subscription: SubscriptionType + Send + Sync + 'static,
+{
+    use criterion::black_box;
+
+    c.bench_function(&format!("{} run", name), |b| {
+        b.iter(|| run(s, black_box(q)))
+    });
+    c.bench_function(&format!("{} parse", name), |b| {
+        b.iter(|| parse(black_box(q)))
+    });
+    let doc = parse(q);
+    c.bench_function(&format!("{} validate", name), |b| {
+        b.iter(|| validate(s, black_box(&doc)))
+    });
+    let res = run(s, q);
+    c.bench_function(&format!("{} serialize", name), |b| {
+        b.iter(|| serialize(black_box(&res)))
+    });
+}
@@ -1,31 +1,21 @@
 use crate::utils::{
-    get_rustdoc, parse_default, parse_default_with, parse_guards, parse_post_guards,
-    parse_validator,
+    check_name, check_type_name, get_rustdoc, parse_default, parse_default_with, parse_guards,
+    parse_post_guards, parse_validator,
 };
 use proc_macro2::TokenStream;
 use quote::quote;
 use syn::{Attribute, AttributeArgs, Error, Lit, Meta, MetaList, NestedMeta, Result, Type};
 
+/// The values that a `cache_control` attribute sets, the others are inherited.
+#[derive(Default, Clone, Copy)]
 pub struct CacheControl {
-    pub public: bool,
-    pub max_age: usize,
-}
-
-impl Default for CacheControl {
-    fn default() -> Self {
-        Self {
-            public: true,
-            max_age: 0,
-        }
-    }
+    pub public: Option<bool>,
+    pub max_age: Option<usize>,
 }
 
 impl CacheControl {
     pub fn parse(ls: &MetaList) -> Result<Self> {
-        let mut cache_control = Self {
-            public: true,
-            max_age: 0,
-        };
+        let mut cache_control = Self::default();
 
         for meta in &ls.nested {
             match meta {
@@ -33,7 +23,7 @@ impl CacheControl {
                     if nv.path.is_ident("max_age") {
                         if let Lit::Int(n) = &nv.lit {
                             match n.base10_parse::<usize>() {
-                                Ok(n) => cache_control.max_age = n,
+                                Ok(n) => cache_control.max_age = Some(n),
                                 Err(err) => {
                                     return Err(Error::new_spanned(&nv.lit, err));
                                 }
@@ -48,9 +38,9 @@ impl CacheControl {
                 }
                 NestedMeta::Meta(Meta::Path(p)) => {
                     if p.is_ident("public") {
-                        cache_control.public = true;
+                        cache_control.public = Some(true);
                     } else if p.is_ident("private") {
-                        cache_control.public = false;
+                        cache_control.public = Some(false);
                     }
                 }
                 _ => {}
@@ -59,6 +49,14 @@ impl CacheControl {
 
         Ok(cache_control)
     }
+
+    /// Returns the values of a field, with the values it does not set taken from its `object`.
+    pub fn inherit(&self, object: &CacheControl) -> CacheControl {
+        CacheControl {
+            public: self.public.or(object.public),
+            max_age: self.max_age.or(object.max_age),
+        }
+    }
 }
 
 pub struct Object {
@@ -67,6 +65,9 @@ pub struct Object {
     pub desc: Option<String>,
     pub cache_control: CacheControl,
     pub extends: bool,
+    pub from: Option<Type>,
+    pub desc_file: Option<syn::LitStr>,
+    pub complex: bool,
 }
 
 impl Object {
@@ -76,6 +77,9 @@ impl Object {
         let mut desc = None;
         let mut cache_control = CacheControl::default();
         let mut extends = false;
+        let mut from = None;
+        let mut desc_file = None;
+        let mut complex = false;
 
         for arg in args {
             match arg {
@@ -85,10 +89,13 @@ impl Object {
                 NestedMeta::Meta(Meta::Path(p)) if p.is_ident("extends") => {
                     extends = true;
                 }
+                NestedMeta::Meta(Meta::Path(p)) if p.is_ident("complex") => {
+                    complex = true;
+                }
                 NestedMeta::Meta(Meta::NameValue(nv)) => {
                     if nv.path.is_ident("name") {
                         if let syn::Lit::Str(lit) = nv.lit {
-                            name = Some(lit.value());
+                            name = Some(check_type_name(&lit)?);
                         } else {
                             return Err(Error::new_spanned(
                                 &nv.lit,
@@ -104,6 +111,24 @@ impl Object {
                                 "Attribute 'desc' should be a string.",
                             ));
                         }
+                    } else if nv.path.is_ident("from") {
+                        if let syn::Lit::Str(lit) = &nv.lit {
+                            from = Some(lit.parse::<Type>()?);
+                        } else {
+                            return Err(Error::new_spanned(
+                                &nv.lit,
+                                "Attribute 'from' should be a string.",
+                            ));
+                        }
+                    } else if nv.path.is_ident("desc_file") {
+                        if let syn::Lit::Str(lit) = nv.lit {
+                            desc_file = Some(lit);
+                        } else {
+                            return Err(Error::new_spanned(
+                                &nv.lit,
+                                "Attribute 'desc_file' should be a string.",
+                            ));
+                        }
                     }
                 }
                 NestedMeta::Meta(Meta::List(ls)) => {
@@ -121,6 +146,9 @@ impl Object {
             desc,
             cache_control,
             extends,
+            from,
+            desc_file,
+            complex,
         })
     }
 }
@@ -131,6 +159,7 @@ pub struct Argument {
     pub default: Option<TokenStream>,
     pub validator: TokenStream,
     pub key: bool, // for entity
+    pub inject: Option<TokenStream>,
 }
 
 impl Argument {
@@ -140,6 +169,7 @@ impl Argument {
         let mut default = None;
         let mut validator = quote! { None };
         let mut key = false;
+        let mut inject = None;
 
         for attr in attrs {
             match attr.parse_meta()? {
@@ -154,7 +184,7 @@ impl Argument {
                         } else if let NestedMeta::Meta(Meta::NameValue(nv)) = meta {
                             if nv.path.is_ident("name") {
                                 if let syn::Lit::Str(lit) = &nv.lit {
-                                    name = Some(lit.value());
+                                    name = Some(check_name(lit)?);
                                 } else {
                                     return Err(Error::new_spanned(
                                         &nv.lit,
@@ -174,6 +204,15 @@ impl Argument {
                                 default = Some(parse_default(&nv.lit)?);
                             } else if nv.path.is_ident("default_with") {
                                 default = Some(parse_default_with(&nv.lit)?);
+                            } else if nv.path.is_ident("inject") {
+                                if let syn::Lit::Str(lit) = &nv.lit {
+                                    inject = Some(lit.value().parse::<TokenStream>()?);
+                                } else {
+                                    return Err(Error::new_spanned(
+                                        &nv.lit,
+                                        "Attribute 'inject' should be a string.",
+                                    ));
+                                }
                             }
                         }
                     }
@@ -190,10 +229,16 @@ impl Argument {
             default,
             validator,
             key,
+            inject,
         })
     }
 }
 
+pub enum Complexity {
+    Const(usize),
+    Expr(syn::Expr),
+}
+
 pub struct Field {
     pub name: Option<String>,
     pub desc: Option<String>,
@@ -202,10 +247,18 @@ pub struct Field {
     pub external: bool,
     pub provides: Option<String>,
     pub requires: Option<String>,
+    pub idempotent: bool,
+    pub complexity: Option<Complexity>,
+    pub requires_feature: Option<String>,
+    pub shared: bool,
     pub owned: bool,
     pub guard: Option<TokenStream>,
     pub post_guard: Option<TokenStream>,
     pub features: Vec<String>,
+    pub from_field: Option<syn::Ident>,
+    pub from_with: Option<TokenStream>,
+    pub cleanup: Option<TokenStream>,
+    pub variants: Vec<syn::Ident>,
 }
 
 impl Field {
@@ -217,10 +270,19 @@ impl Field {
         let mut external = false;
         let mut provides = None;
         let mut requires = None;
+        let mut idempotent = false;
+        let mut complexity = None;
+        let mut requires_feature = None;
+        let mut shared = false;
         let mut features = Vec::new();
         let mut owned = false;
         let mut guard = None;
         let mut post_guard = None;
+        let mut from_field = None;
+        let mut from_with = None;
+        let mut cleanup = None;
+        let mut variants = Vec::new();
+        let mut requires_scopes = Vec::new();
 
         for attr in attrs {
             match attr.parse_meta()? {
@@ -235,6 +297,12 @@ impl Field {
                             NestedMeta::Meta(Meta::Path(p)) if p.is_ident("external") => {
                                 external = true;
                             }
+                            NestedMeta::Meta(Meta::Path(p)) if p.is_ident("idempotent") => {
+                                idempotent = true;
+                            }
+                            NestedMeta::Meta(Meta::Path(p)) if p.is_ident("shared") => {
+                                shared = true;
+                            }
                             NestedMeta::Meta(Meta::Path(p)) if p.is_ident("owned") => {
                                 owned = true;
                             }
@@ -247,7 +315,7 @@ impl Field {
                             NestedMeta::Meta(Meta::NameValue(nv)) => {
                                 if nv.path.is_ident("name") {
                                     if let syn::Lit::Str(lit) = &nv.lit {
-                                        name = Some(lit.value());
+                                        name = Some(check_name(lit)?);
                                     } else {
                                         return Err(Error::new_spanned(
                                             &nv.lit,
@@ -290,6 +358,31 @@ impl Field {
                                             "Attribute 'requires' should be a string.",
                                         ));
                                     }
+                                } else if nv.path.is_ident("requires_feature_header") {
+                                    if let syn::Lit::Str(lit) = &nv.lit {
+                                        requires_feature = Some(lit.value());
+                                    } else {
+                                        return Err(Error::new_spanned(
+                                            &nv.lit,
+                                            "Attribute 'requires_feature_header' should be a string.",
+                                        ));
+                                    }
+                                } else if nv.path.is_ident("complexity") {
+                                    match &nv.lit {
+                                        syn::Lit::Int(lit) => {
+                                            complexity =
+                                                Some(Complexity::Const(lit.base10_parse()?));
+                                        }
+                                        syn::Lit::Str(lit) => {
+                                            complexity = Some(Complexity::Expr(lit.parse()?));
+                                        }
+                                        _ => {
+                                            return Err(Error::new_spanned(
+                                                &nv.lit,
+                                                "Attribute 'complexity' should be an integer or a string.",
+                                            ));
+                                        }
+                                    }
                                 } else if nv.path.is_ident("feature") {
                                     if let syn::Lit::Str(lit) = &nv.lit {
                                         features = lit
@@ -304,6 +397,78 @@ impl Field {
                                             "Attribute 'feature' should be a string.",
                                         ));
                                     }
+                                } else if nv.path.is_ident("variant") {
+                                    if let syn::Lit::Str(lit) = &nv.lit {
+                                        for name in lit.value().split(',') {
+                                            let name = name.trim();
+                                            if name.is_empty() {
+                                                continue;
+                                            }
+                                            let mut variant: syn::Ident = syn::parse_str(name)
+                                                .map_err(|_| {
+                                                    Error::new_spanned(
+                                                        &nv.lit,
+                                                        format!("Invalid variant name '{}'.", name),
+                                                    )
+                                                })?;
+                                            variant.set_span(lit.span());
+                                            variants.push(variant);
+                                        }
+                                    } else {
+                                        return Err(Error::new_spanned(
+                                            &nv.lit,
+                                            "Attribute 'variant' should be a string.",
+                                        ));
+                                    }
+                                } else if nv.path.is_ident("requires_scopes") {
+                                    if let syn::Lit::Str(lit) = &nv.lit {
+                                        requires_scopes.extend(
+                                            lit.value().split_whitespace().map(ToString::to_string),
+                                        );
+                                    } else {
+                                        return Err(Error::new_spanned(
+                                            &nv.lit,
+                                            "Attribute 'requires_scopes' should be a string.",
+                                        ));
+                                    }
+                                } else if nv.path.is_ident("from_field") {
+                                    if let syn::Lit::Str(lit) = &nv.lit {
+                                        let value = lit.value();
+                                        let mut ident: syn::Ident = syn::parse_str(&value)
+                                            .map_err(|_| {
+                                                Error::new_spanned(
+                                                    &nv.lit,
+                                                    format!("Invalid field name '{}'.", value),
+                                                )
+                                            })?;
+                                        ident.set_span(lit.span());
+                                        from_field = Some(ident);
+                                    } else {
+                                        return Err(Error::new_spanned(
+                                            &nv.lit,
+                                            "Attribute 'from_field' should be a string.",
+                                        ));
+                                    }
+                                } else if nv.path.is_ident("from_with") {
+                                    if let syn::Lit::Str(lit) = &nv.lit {
+                                        let path = lit.parse::<syn::Path>()?;
+                                        from_with = Some(quote! { #path });
+                                    } else {
+                                        return Err(Error::new_spanned(
+                                            &nv.lit,
+                                            "Attribute 'from_with' should be a string.",
+                                        ));
+                                    }
+                                } else if nv.path.is_ident("cleanup") {
+                                    if let syn::Lit::Str(lit) = &nv.lit {
+                                        let path = lit.parse::<syn::Path>()?;
+                                        cleanup = Some(quote! { #path });
+                                    } else {
+                                        return Err(Error::new_spanned(
+                                            &nv.lit,
+                                            "Attribute 'cleanup' should be a string.",
+                                        ));
+                                    }
                                 }
                             }
                             NestedMeta::Meta(Meta::List(ls)) => {
@@ -323,6 +488,15 @@ impl Field {
             desc = get_rustdoc(attrs)?;
         }
 
+        if !requires_scopes.is_empty() {
+            let scope_guard =
+                quote! { #crate_name::guard::ScopeGuard::new(&[#(#requires_scopes),*]) };
+            guard = Some(match guard {
+                Some(guard) => quote! { #crate_name::guard::GuardExt::and(#scope_guard, #guard) },
+                None => scope_guard,
+            });
+        }
+
         Ok(Some(Self {
             name,
             desc,
@@ -331,10 +505,18 @@ impl Field {
             external,
             provides,
             requires,
+            idempotent,
+            complexity,
+            requires_feature,
+            shared,
             owned,
             guard,
             post_guard,
             features,
+            from_field,
+            from_with,
+            cleanup,
+            variants,
         }))
     }
 }
@@ -359,7 +541,7 @@ impl Enum {
                 NestedMeta::Meta(Meta::NameValue(nv)) => {
                     if nv.path.is_ident("name") {
                         if let syn::Lit::Str(lit) = nv.lit {
-                            name = Some(lit.value());
+                            name = Some(check_name(&lit)?);
                         } else {
                             return Err(Error::new_spanned(
                                 &nv.lit,
@@ -408,7 +590,7 @@ impl EnumItem {
                         if let NestedMeta::Meta(Meta::NameValue(nv)) = meta {
                             if nv.path.is_ident("name") {
                                 if let syn::Lit::Str(lit) = nv.lit {
-                                    name = Some(lit.value());
+                                    name = Some(check_name(&lit)?);
                                 } else {
                                     return Err(Error::new_spanned(
                                         &nv.lit,
@@ -466,6 +648,7 @@ impl InputField {
         let mut desc = None;
         let mut default = None;
         let mut validator = quote! { None };
+        let mut has_validator = false;
         let mut flatten = false;
 
         for attr in attrs {
@@ -488,7 +671,7 @@ impl InputField {
                             NestedMeta::Meta(Meta::NameValue(nv)) => {
                                 if nv.path.is_ident("name") {
                                     if let syn::Lit::Str(lit) = &nv.lit {
-                                        name = Some(lit.value());
+                                        name = Some(check_name(lit)?);
                                     } else {
                                         return Err(Error::new_spanned(
                                             &nv.lit,
@@ -515,6 +698,16 @@ impl InputField {
                     }
 
                     validator = parse_validator(crate_name, &args)?;
+                    has_validator |= args.nested.iter().any(|meta| {
+                        matches!(meta, NestedMeta::Meta(Meta::List(ls)) if ls.path.is_ident("validator"))
+                    });
+                }
+
+                if flatten && (name.is_some() || default.is_some() || has_validator) {
+                    return Err(Error::new_spanned(
+                        attr,
+                        "Flattened fields cannot have a name, a default value or a validator.",
+                    ));
                 }
             }
         }
@@ -533,10 +726,67 @@ impl InputField {
     }
 }
 
+pub struct OneofObjectItem {
+    pub name: Option<String>,
+    pub desc: Option<String>,
+    pub validator: TokenStream,
+}
+
+impl OneofObjectItem {
+    pub fn parse(crate_name: &TokenStream, attrs: &[Attribute]) -> Result<Self> {
+        let mut name = None;
+        let mut desc = None;
+        let mut validator = quote! { None };
+
+        for attr in attrs {
+            if attr.path.is_ident("item") {
+                if let Meta::List(args) = &attr.parse_meta()? {
+                    for meta in &args.nested {
+                        if let NestedMeta::Meta(Meta::NameValue(nv)) = meta {
+                            if nv.path.is_ident("name") {
+                                if let syn::Lit::Str(lit) = &nv.lit {
+                                    name = Some(check_name(lit)?);
+                                } else {
+                                    return Err(Error::new_spanned(
+                                        &nv.lit,
+                                        "Attribute 'name' should be a string.",
+                                    ));
+                                }
+                            } else if nv.path.is_ident("desc") {
+                                if let syn::Lit::Str(lit) = &nv.lit {
+                                    desc = Some(lit.value());
+                                } else {
+                                    return Err(Error::new_spanned(
+                                        &nv.lit,
+                                        "Attribute 'desc' should be a string.",
+                                    ));
+                                }
+                            }
+                        }
+                    }
+
+                    validator = parse_validator(crate_name, &args)?;
+                }
+            }
+        }
+
+        if desc.is_none() {
+            desc = get_rustdoc(attrs)?;
+        }
+
+        Ok(Self {
+            name,
+            desc,
+            validator,
+        })
+    }
+}
+
 pub struct InputObject {
     pub internal: bool,
     pub name: Option<String>,
     pub desc: Option<String>,
+    pub deny_unknown_fields: Option<bool>,
 }
 
 impl InputObject {
@@ -544,16 +794,23 @@ impl InputObject {
         let mut internal = false;
         let mut name = None;
         let mut desc = None;
+        let mut deny_unknown_fields = None;
 
         for arg in args {
             match arg {
                 NestedMeta::Meta(Meta::Path(p)) if p.is_ident("internal") => {
                     internal = true;
                 }
+                NestedMeta::Meta(Meta::Path(p)) if p.is_ident("deny_unknown_fields") => {
+                    deny_unknown_fields = Some(true);
+                }
+                NestedMeta::Meta(Meta::Path(p)) if p.is_ident("ignore_unknown_fields") => {
+                    deny_unknown_fields = Some(false);
+                }
                 NestedMeta::Meta(Meta::NameValue(nv)) => {
                     if nv.path.is_ident("name") {
                         if let syn::Lit::Str(lit) = nv.lit {
-                            name = Some(lit.value());
+                            name = Some(check_name(&lit)?);
                         } else {
                             return Err(Error::new_spanned(
                                 &nv.lit,
@@ -579,6 +836,7 @@ impl InputObject {
             internal,
             name,
             desc,
+            deny_unknown_fields,
         })
     }
 }
@@ -605,7 +863,7 @@ impl InterfaceFieldArgument {
             } else if let NestedMeta::Meta(Meta::NameValue(nv)) = meta {
                 if nv.path.is_ident("name") {
                     if let syn::Lit::Str(lit) = &nv.lit {
-                        name = Some(lit.value());
+                        name = Some(check_name(lit)?);
                     } else {
                         return Err(Error::new_spanned(
                             &nv.lit,
@@ -691,7 +949,7 @@ impl InterfaceField {
                 NestedMeta::Meta(Meta::NameValue(nv)) => {
                     if nv.path.is_ident("name") {
                         if let syn::Lit::Str(lit) = &nv.lit {
-                            name = Some(lit.value());
+                            name = Some(check_name(lit)?);
                         } else {
                             return Err(Error::new_spanned(
                                 &nv.lit,
@@ -793,6 +1051,7 @@ pub struct Interface {
     pub desc: Option<String>,
     pub fields: Vec<InterfaceField>,
     pub extends: bool,
+    pub implements: Vec<syn::Type>,
 }
 
 impl Interface {
@@ -802,6 +1061,7 @@ impl Interface {
         let mut desc = None;
         let mut fields = Vec::new();
         let mut extends = false;
+        let mut implements = Vec::new();
 
         for arg in args {
             match arg {
@@ -814,7 +1074,7 @@ impl Interface {
                 NestedMeta::Meta(Meta::NameValue(nv)) => {
                     if nv.path.is_ident("name") {
                         if let syn::Lit::Str(lit) = nv.lit {
-                            name = Some(lit.value());
+                            name = Some(check_name(&lit)?);
                         } else {
                             return Err(Error::new_spanned(
                                 &nv.lit,
@@ -830,6 +1090,19 @@ impl Interface {
                                 "Attribute 'desc' should be a string.",
                             ));
                         }
+                    } else if nv.path.is_ident("implements") {
+                        if let syn::Lit::Str(lit) = &nv.lit {
+                            if let Ok(ty) = syn::parse_str::<syn::Type>(&lit.value()) {
+                                implements.push(ty);
+                            } else {
+                                return Err(Error::new_spanned(&lit, "Expect type"));
+                            }
+                        } else {
+                            return Err(Error::new_spanned(
+                                &nv.lit,
+                                "Attribute 'implements' should be a string.",
+                            ));
+                        }
                     }
                 }
                 NestedMeta::Meta(Meta::List(ls)) if ls.path.is_ident("field") => {
@@ -845,14 +1118,42 @@ impl Interface {
             desc,
             fields,
             extends,
+            implements,
         })
     }
 }
 
+pub struct UnionItem {
+    pub flatten: bool,
+}
+
+impl UnionItem {
+    pub fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut flatten = false;
+
+        for attr in attrs {
+            if attr.path.is_ident("graphql") {
+                if let Meta::List(args) = attr.parse_meta()? {
+                    for meta in args.nested {
+                        if let NestedMeta::Meta(Meta::Path(p)) = meta {
+                            if p.is_ident("flatten") {
+                                flatten = true;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(Self { flatten })
+    }
+}
+
 pub struct Scalar {
     pub internal: bool,
     pub name: Option<String>,
     pub desc: Option<String>,
+    pub specified_by_url: Option<String>,
 }
 
 impl Scalar {
@@ -860,6 +1161,7 @@ impl Scalar {
         let mut internal = false;
         let mut name = None;
         let mut desc = None;
+        let mut specified_by_url = None;
 
         for arg in args {
             match arg {
@@ -871,7 +1173,7 @@ impl Scalar {
                 NestedMeta::Meta(Meta::NameValue(nv)) => {
                     if nv.path.is_ident("name") {
                         if let syn::Lit::Str(lit) = nv.lit {
-                            name = Some(lit.value());
+                            name = Some(check_name(&lit)?);
                         } else {
                             return Err(Error::new_spanned(
                                 &nv.lit,
@@ -887,6 +1189,15 @@ impl Scalar {
                                 "Attribute 'desc' should be a string.",
                             ));
                         }
+                    } else if nv.path.is_ident("specified_by_url") {
+                        if let syn::Lit::Str(lit) = nv.lit {
+                            specified_by_url = Some(lit.value());
+                        } else {
+                            return Err(Error::new_spanned(
+                                &nv.lit,
+                                "Attribute 'specified_by_url' should be a string.",
+                            ));
+                        }
                     }
                 }
                 _ => {}
@@ -897,6 +1208,7 @@ impl Scalar {
             internal,
             name,
             desc,
+            specified_by_url,
         })
     }
 }
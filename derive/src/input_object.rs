@@ -43,6 +43,7 @@ pub fn generate(object_args: &args::InputObject, input: &DeriveInput) -> Result<
         .unwrap_or_else(|| quote! {None});
 
     let mut get_fields = Vec::new();
+    let mut get_fields_with_context = Vec::new();
     let mut put_fields = Vec::new();
     let mut fields = Vec::new();
     let mut schema_fields = Vec::new();
@@ -60,16 +61,26 @@ pub fn generate(object_args: &args::InputObject, input: &DeriveInput) -> Result<
             flatten_fields.push((ident, ty));
 
             schema_fields.push(quote! {
-                #ty::create_type_info(registry);
-                if let Some(#crate_name::registry::MetaType::InputObject{ input_fields, .. }) =
-                    registry.types.remove(&*<#ty as #crate_name::Type>::type_name()) {
-                    fields.extend(input_fields);
+                {
+                    let type_name = <#ty as #crate_name::Type>::type_name();
+                    let registered = registry.types.contains_key(&*type_name);
+                    <#ty as #crate_name::Type>::create_type_info(registry);
+                    if let Some(#crate_name::registry::MetaType::InputObject{ input_fields, .. }) =
+                        registry.types.get(&*type_name) {
+                        fields.extend(input_fields.clone());
+                    }
+                    if !registered {
+                        registry.types.remove(&*type_name);
+                    }
                 }
             });
 
             get_fields.push(quote! {
                 let #ident: #ty = #crate_name::InputValueType::parse(Some(#crate_name::Value::Object(obj.clone())))?;
             });
+            get_fields_with_context.push(quote! {
+                let #ident: #ty = #crate_name::InputValueType::parse_with_context(Some(#crate_name::Value::Object(obj.clone())), ctx)?;
+            });
 
             fields.push(ident);
 
@@ -99,14 +110,29 @@ pub fn generate(object_args: &args::InputObject, input: &DeriveInput) -> Result<
             get_fields.push(quote! {
                 let #ident: #ty = {
                     match obj.get(#name) {
-                        Some(value) => #crate_name::InputValueType::parse(Some(value.clone()))?,
+                        Some(value) => <#ty as #crate_name::InputValueType>::parse(Some(value.clone()))
+                            .map_err(|err| err.with_field::<#ty>(#name))?,
+                        None => #default,
+                    }
+                };
+            });
+            get_fields_with_context.push(quote! {
+                let #ident: #ty = {
+                    match obj.get(#name) {
+                        Some(value) => <#ty as #crate_name::InputValueType>::parse_with_context(Some(value.clone()), &ctx.object_field(#name))
+                            .map_err(|err| err.with_field::<#ty>(#name))?,
                         None => #default,
                     }
                 };
             });
         } else {
             get_fields.push(quote! {
-                let #ident:#ty = #crate_name::InputValueType::parse(obj.get(#name).cloned())?;
+                let #ident:#ty = <#ty as #crate_name::InputValueType>::parse(obj.get(#name).cloned())
+                    .map_err(|err| err.with_field::<#ty>(#name))?;
+            });
+            get_fields_with_context.push(quote! {
+                let #ident:#ty = <#ty as #crate_name::InputValueType>::parse_with_context(obj.get(#name).cloned(), &ctx.object_field(#name))
+                    .map_err(|err| err.with_field::<#ty>(#name))?;
             });
         }
 
@@ -129,6 +155,12 @@ pub fn generate(object_args: &args::InputObject, input: &DeriveInput) -> Result<
         })
     }
 
+    let unknown_fields = match object_args.deny_unknown_fields {
+        Some(true) => quote! { Some(#crate_name::UnknownInputFields::Deny) },
+        Some(false) => quote! { Some(#crate_name::UnknownInputFields::Ignore) },
+        None => quote! { None },
+    };
+
     let expanded = quote! {
         #[allow(clippy::all, clippy::pedantic)]
         impl #crate_name::Type for #ident {
@@ -144,7 +176,9 @@ pub fn generate(object_args: &args::InputObject, input: &DeriveInput) -> Result<
                         let mut fields = #crate_name::indexmap::IndexMap::new();
                         #(#schema_fields)*
                         fields
-                    }
+                    },
+                    unknown_fields: #unknown_fields,
+                    oneof: false,
                 })
             }
         }
@@ -160,6 +194,18 @@ pub fn generate(object_args: &args::InputObject, input: &DeriveInput) -> Result<
                 }
             }
 
+            fn parse_with_context(
+                value: Option<#crate_name::Value>,
+                ctx: &#crate_name::ParseContext<'_>,
+            ) -> #crate_name::InputValueResult<Self> {
+                if let Some(#crate_name::Value::Object(obj)) = value {
+                    #(#get_fields_with_context)*
+                    Ok(Self { #(#fields),* })
+                } else {
+                    Err(#crate_name::InputValueError::ExpectedType(value.unwrap_or_default()))
+                }
+            }
+
             fn to_value(&self) -> #crate_name::Value {
                 let mut map = ::std::collections::BTreeMap::new();
                 #(#put_fields)*
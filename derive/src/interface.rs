@@ -40,6 +40,14 @@ pub fn generate(interface_args: &args::Interface, input: &DeriveInput) -> Result
 
     let mut registry_types = Vec::new();
     let mut possible_types = Vec::new();
+
+    for ty in &interface_args.implements {
+        registry_types.push(quote! {
+            <#ty as #crate_name::Type>::create_type_info(registry);
+            registry.add_implements(#gql_typename, &<#ty as #crate_name::Type>::type_name());
+        });
+    }
+
     let mut get_introspection_typename = Vec::new();
     let mut collect_all_fields = Vec::new();
 
@@ -232,19 +240,16 @@ pub fn generate(interface_args: &args::Interface, input: &DeriveInput) -> Result
                 external: #external,
                 provides: #provides,
                 requires: #requires,
+                idempotent: false,
+                compute_complexity: None,
+                requires_feature: None,
             });
         });
 
-        let resolve_obj = quote! {
-            self.#method_name(#(#use_params),*).await.
-                map_err(|err| err.into_error_with_path(ctx.item.pos, ctx.path_node.as_ref()))?
-        };
-
         resolvers.push(quote! {
             if ctx.item.node.name.node == #name {
                 #(#get_params)*
-                let ctx_obj = ctx.with_selection_set(&ctx.item.node.selection_set);
-                return #crate_name::OutputValueType::resolve(&#resolve_obj, &ctx_obj, ctx.item).await;
+                return #crate_name::resolver_utils::resolve_field_result(ctx, self.#method_name(#(#use_params),*).await).await;
             }
         });
     }
@@ -10,6 +10,7 @@ mod interface;
 mod merged_object;
 mod merged_subscription;
 mod object;
+mod oneof_object;
 mod output_type;
 mod scalar;
 mod simple_object;
@@ -36,6 +37,20 @@ pub fn Object(args: TokenStream, input: TokenStream) -> TokenStream {
     }
 }
 
+#[proc_macro_attribute]
+#[allow(non_snake_case)]
+pub fn ComplexObject(args: TokenStream, input: TokenStream) -> TokenStream {
+    let object_args = match args::Object::parse(parse_macro_input!(args as AttributeArgs)) {
+        Ok(object_args) => object_args,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let mut item_impl = parse_macro_input!(input as ItemImpl);
+    match object::generate_complex(&object_args, &mut item_impl) {
+        Ok(expanded) => expanded,
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
 #[proc_macro_derive(SimpleObject, attributes(field, graphql))]
 pub fn derive_simple_object(input: TokenStream) -> TokenStream {
     let (args, input) = match parse_derive(input.into()) {
@@ -84,6 +99,22 @@ pub fn derive_input_object(input: TokenStream) -> TokenStream {
     }
 }
 
+#[proc_macro_derive(OneofObject, attributes(item, graphql))]
+pub fn derive_oneof_object(input: TokenStream) -> TokenStream {
+    let (args, input) = match parse_derive(input.into()) {
+        Ok(r) => r,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let object_args = match args::InputObject::parse(parse_macro_input!(args as AttributeArgs)) {
+        Ok(object_args) => object_args,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    match oneof_object::generate(&object_args, &input) {
+        Ok(expanded) => expanded,
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
 #[proc_macro_derive(Interface, attributes(graphql))]
 pub fn derive_interface(input: TokenStream) -> TokenStream {
     let (args, input) = match parse_derive(input.into()) {
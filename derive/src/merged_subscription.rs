@@ -1,5 +1,5 @@
 use crate::args;
-use crate::utils::{get_crate_name, get_rustdoc};
+use crate::utils::{generate_type_name, get_crate_name, get_rustdoc};
 use proc_macro::TokenStream;
 use proc_macro2::Span;
 use quote::quote;
@@ -12,6 +12,7 @@ pub fn generate(object_args: &args::Object, input: &DeriveInput) -> Result<Token
         .name
         .clone()
         .unwrap_or_else(|| ident.to_string());
+    let type_name = generate_type_name(&crate_name, &gql_typename, &Default::default())?;
 
     let desc = object_args
         .desc
@@ -42,7 +43,7 @@ pub fn generate(object_args: &args::Object, input: &DeriveInput) -> Result<Token
     };
 
     let merged_type = {
-        let mut obj = quote! { #crate_name::MergedObjectTail };
+        let mut obj = quote! { #crate_name::MergedObjectSubscriptionTail };
         for ty in &types {
             obj = quote! { #crate_name::MergedObject::<#ty, #obj> };
         }
@@ -53,7 +54,7 @@ pub fn generate(object_args: &args::Object, input: &DeriveInput) -> Result<Token
         #[allow(clippy::all, clippy::pedantic)]
         impl #crate_name::Type for #ident {
             fn type_name() -> ::std::borrow::Cow<'static, str> {
-                ::std::borrow::Cow::Borrowed(#gql_typename)
+                #type_name
             }
 
             fn create_type_info(registry: &mut #crate_name::registry::Registry) -> String {
@@ -70,7 +71,7 @@ pub fn generate(object_args: &args::Object, input: &DeriveInput) -> Result<Token
                     }
 
                     #crate_name::registry::MetaType::Object {
-                        name: #gql_typename.to_string(),
+                        name: <Self as #crate_name::Type>::type_name().into_owned(),
                         description: #desc,
                         fields,
                         cache_control: Default::default(),
@@ -83,6 +84,10 @@ pub fn generate(object_args: &args::Object, input: &DeriveInput) -> Result<Token
 
         #[allow(clippy::all, clippy::pedantic)]
         impl #crate_name::SubscriptionType for #ident {
+            fn has_field(name: &str) -> bool {
+                <#merged_type as #crate_name::SubscriptionType>::has_field(name)
+            }
+
             fn create_field_stream<'a>(
                 &'a self,
                 ctx: &'a #crate_name::Context<'a>
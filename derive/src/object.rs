@@ -1,13 +1,34 @@
 use crate::args;
 use crate::output_type::OutputType;
-use crate::utils::{feature_block, get_crate_name, get_param_getter_ident, get_rustdoc};
+use crate::utils::{
+    check_duplicate, feature_block, generate_complexity, generate_type_name, get_crate_name,
+    get_param_getter_ident, get_rustdoc, load_desc_file,
+};
 use inflector::Inflector;
 use proc_macro::TokenStream;
 use quote::quote;
+use std::collections::HashMap;
 use syn::ext::IdentExt;
 use syn::{Block, Error, FnArg, ImplItem, ItemImpl, Pat, Result, ReturnType, Type, TypeReference};
 
 pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<TokenStream> {
+    generate_object(object_args, item_impl, false)
+}
+
+/// Generates the `ComplexObject` implementation of the fields that a `#[ComplexObject]` impl block
+/// adds to a `SimpleObject`.
+pub fn generate_complex(
+    object_args: &args::Object,
+    item_impl: &mut ItemImpl,
+) -> Result<TokenStream> {
+    generate_object(object_args, item_impl, true)
+}
+
+fn generate_object(
+    object_args: &args::Object,
+    item_impl: &mut ItemImpl,
+    complex: bool,
+) -> Result<TokenStream> {
     let crate_name = get_crate_name(object_args.internal);
     let (self_ty, self_name) = match item_impl.self_ty.as_ref() {
         Type::Path(path) => (
@@ -28,6 +49,7 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
         .name
         .clone()
         .unwrap_or_else(|| self_name.clone());
+    let type_name = generate_type_name(&crate_name, &gql_typename, generics)?;
 
     let desc = object_args
         .desc
@@ -36,11 +58,19 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
         .map(|s| quote! { Some(#s) })
         .unwrap_or_else(|| quote! {None});
 
+    let desc_file = object_args
+        .desc_file
+        .as_ref()
+        .map(|lit| load_desc_file(lit, &gql_typename))
+        .transpose()?;
+    let desc_file_include = desc_file.as_ref().map(|desc_file| &desc_file.include);
+
     let mut resolvers = Vec::new();
     let mut schema_fields = Vec::new();
     let mut find_entities = Vec::new();
     let mut add_keys = Vec::new();
     let mut create_entity_types = Vec::new();
+    let mut field_names = HashMap::new();
 
     for item in &mut item_impl.items {
         if let ImplItem::Method(method) = item {
@@ -210,9 +240,20 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
                     .name
                     .clone()
                     .unwrap_or_else(|| method.sig.ident.unraw().to_string().to_camel_case());
+                check_duplicate(
+                    &mut field_names,
+                    &field_name,
+                    method.sig.ident.span(),
+                    "field",
+                )?;
                 let field_desc = field
                     .desc
                     .as_ref()
+                    .or_else(|| {
+                        desc_file
+                            .as_ref()
+                            .and_then(|desc_file| desc_file.fields.get(&field_name))
+                    })
                     .map(|s| quote! {Some(#s)})
                     .unwrap_or_else(|| quote! {None});
                 let field_deprecation = field
@@ -221,6 +262,7 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
                     .map(|s| quote! {Some(#s)})
                     .unwrap_or_else(|| quote! {None});
                 let external = field.external;
+                let idempotent = field.idempotent;
                 let features = field.features;
                 let requires = match &field.requires {
                     Some(requires) => quote! { Some(#requires) },
@@ -230,6 +272,10 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
                     Some(provides) => quote! { Some(#provides) },
                     None => quote! { None },
                 };
+                let requires_feature = match &field.requires_feature {
+                    Some(feature) => quote! { Some(#feature) },
+                    None => quote! { None },
+                };
                 let ty = match &method.sig.output {
                     ReturnType::Type(_, ty) => OutputType::parse(ty)?,
                     ReturnType::Default => {
@@ -237,8 +283,9 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
                     }
                 };
                 let cache_control = {
-                    let public = field.cache_control.public;
-                    let max_age = field.cache_control.max_age;
+                    let cache_control = field.cache_control.inherit(&object_args.cache_control);
+                    let public = cache_control.public.unwrap_or(true);
+                    let max_age = cache_control.max_age.unwrap_or(0);
                     quote! {
                         #crate_name::CacheControl {
                             public: #public,
@@ -303,6 +350,10 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
                 let mut schema_args = Vec::new();
                 let mut use_params = Vec::new();
                 let mut get_params = Vec::new();
+                let mut complexity_params = Vec::new();
+                let mut arg_names = HashMap::new();
+                // The guards read the arguments with their getters.
+                let has_guard = field.guard.is_some() || field.post_guard.is_some();
 
                 for (
                     ident,
@@ -312,13 +363,24 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
                         desc,
                         default,
                         validator,
+                        inject,
                         ..
                     },
                 ) in args
                 {
+                    if let Some(inject) = inject {
+                        let param_ident = &ident.ident;
+                        use_params.push(quote! { #param_ident });
+                        get_params.push(quote! {
+                            let #ident: #ty = #crate_name::resolver_utils::field_result(ctx, (#inject)(ctx))?;
+                        });
+                        continue;
+                    }
+
                     let name = name
                         .clone()
                         .unwrap_or_else(|| ident.ident.unraw().to_string().to_camel_case());
+                    check_duplicate(&mut arg_names, &name, ident.ident.span(), "argument")?;
                     let desc = desc
                         .as_ref()
                         .map(|s| quote! {Some(#s)})
@@ -347,14 +409,33 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
                         Some(default) => quote! { Some(|| -> #ty { #default }) },
                         None => quote! { None },
                     };
-                    let param_getter_name = get_param_getter_ident(&ident.ident.to_string());
-                    get_params.push(quote! {
-                        let #param_getter_name = || -> #crate_name::Result<#ty> { ctx.param_value(#name, #default) };
-                        let #ident: #ty = #param_getter_name()?;
+                    complexity_params.push(quote! {
+                        #[allow(unused_variables, unused_mut)]
+                        let #ident: #ty = ctx.param_value(#name, #default)?;
                     });
+                    if has_guard {
+                        let param_getter_name = get_param_getter_ident(&ident.ident.to_string());
+                        get_params.push(quote! {
+                            let #param_getter_name = || -> #crate_name::Result<#ty> { ctx.param_value(#name, #default) };
+                            let #ident: #ty = #param_getter_name()?;
+                        });
+                    } else {
+                        get_params.push(quote! {
+                            let #ident: #ty = ctx.param_value(#name, #default)?;
+                        });
+                    }
                 }
 
                 let schema_ty = ty.value_type();
+                let schema_ty = if field.variants.is_empty() {
+                    quote! { #schema_ty }
+                } else {
+                    // The field is null for the other variants
+                    quote! { ::std::option::Option<#schema_ty> }
+                };
+
+                let compute_complexity =
+                    generate_complexity(&crate_name, &field.complexity, &complexity_params);
 
                 schema_fields.push(quote! {
                     fields.insert(#field_name.to_string(), #crate_name::registry::MetaField {
@@ -371,6 +452,9 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
                         external: #external,
                         provides: #provides,
                         requires: #requires,
+                        idempotent: #idempotent,
+                        compute_complexity: #compute_complexity,
+                        requires_feature: #requires_feature,
                     });
                 });
 
@@ -397,34 +481,45 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
                     }))
                     .expect("invalid block");
 
-                let resolve_obj = quote! {
-                    {
-                        let res = self.#field_ident(ctx, #(#use_params),*).await;
-                        res.map_err(|err| err.into_error_with_path(ctx.item.pos, ctx.path_node.as_ref()))?
+                let guard = field.guard.map(|guard| {
+                    quote! {
+                        #crate_name::resolver_utils::field_result(ctx, #guard.check(ctx).await)?;
                     }
-                };
+                });
+                let post_guard = field.post_guard.map(|guard| {
+                    quote! {
+                        #crate_name::resolver_utils::field_result(ctx, #guard.check(ctx, &res).await)?;
+                    }
+                });
 
-                let guard = field
-                    .guard
-                    .map(|guard| quote! {
-                        #guard.check(ctx).await
-                            .map_err(|err| err.into_error_with_path(ctx.item.pos, ctx.path_node.as_ref()))?;
-                    });
-                let post_guard = field
-                    .post_guard
-                    .map(|guard| quote! {
-                        #guard.check(ctx, &res).await
-                            .map_err(|err| err.into_error_with_path(ctx.item.pos, ctx.path_node.as_ref()))?;
-                    });
+                let check_variant = if field.variants.is_empty() {
+                    None
+                } else {
+                    let variants = &field.variants;
+                    Some(quote! {
+                        match self {
+                            #(Self::#variants { .. })|* => {}
+                            _ => return Ok(#crate_name::serde_json::Value::Null),
+                        }
+                    })
+                };
 
+                let resolve = match post_guard {
+                    Some(post_guard) => quote! {
+                        let res = #crate_name::resolver_utils::field_result(ctx, self.#field_ident(ctx, #(#use_params),*).await)?;
+                        #post_guard
+                        #crate_name::resolver_utils::resolve_field_value(ctx, &res).await
+                    },
+                    None => quote! {
+                        #crate_name::resolver_utils::resolve_field_result(ctx, self.#field_ident(ctx, #(#use_params),*).await).await
+                    },
+                };
                 resolvers.push(quote! {
-                    if ctx.item.node.name.node == #field_name {
+                    #field_name => {
+                        #check_variant
                         #(#get_params)*
                         #guard
-                        let ctx_obj = ctx.with_selection_set(&ctx.item.node.selection_set);
-                        let res = #resolve_obj;
-                        #post_guard
-                        return #crate_name::OutputValueType::resolve(&res, &ctx_obj, ctx.item).await;
+                        #resolve
                     }
                 });
 
@@ -447,9 +542,13 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
         }
     }
 
+    if let Some(desc_file) = &desc_file {
+        desc_file.check_fields(&field_names)?;
+    }
+
     let cache_control = {
-        let public = object_args.cache_control.public;
-        let max_age = object_args.cache_control.max_age;
+        let public = object_args.cache_control.public.unwrap_or(true);
+        let max_age = object_args.cache_control.max_age.unwrap_or(0);
         quote! {
             #crate_name::CacheControl {
                 public: #public,
@@ -458,21 +557,60 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
         }
     };
 
+    if complex {
+        if !find_entities.is_empty() {
+            return Err(Error::new_spanned(
+                &item_impl.self_ty,
+                "Entities can only be defined with #[Object].",
+            ));
+        }
+
+        let expanded = quote! {
+            #desc_file_include
+
+            #item_impl
+
+            #[allow(clippy::all, clippy::pedantic, clippy::suspicious_else_formatting)]
+            #[allow(unused_braces, unused_variables, unused_parens, unused_mut)]
+            #[#crate_name::async_trait::async_trait]
+            impl#generics #crate_name::resolver_utils::ComplexObject for #self_ty #where_clause {
+                fn fields(registry: &mut #crate_name::registry::Registry) -> #crate_name::indexmap::IndexMap<String, #crate_name::registry::MetaField> {
+                    let mut fields = #crate_name::indexmap::IndexMap::new();
+                    #(#schema_fields)*
+                    fields
+                }
+
+                async fn resolve_field(&self, ctx: &#crate_name::Context<'_>) -> #crate_name::Result<#crate_name::serde_json::Value> {
+                    match ctx.item.node.name.node.as_str() {
+                        #(#resolvers)*
+                        _ => Err(#crate_name::QueryError::FieldNotFound {
+                            field_name: ctx.item.node.name.to_string(),
+                            object: <Self as #crate_name::Type>::type_name().to_string(),
+                        }.into_error(ctx.item.pos)),
+                    }
+                }
+            }
+        };
+        return Ok(expanded.into());
+    }
+
     find_entities.sort_by(|(a, _), (b, _)| b.cmp(a));
     let find_entities_iter = find_entities.iter().map(|(_, code)| code);
 
     let expanded = quote! {
+        #desc_file_include
+
         #item_impl
 
         #[allow(clippy::all, clippy::pedantic)]
         impl #generics #crate_name::Type for #self_ty #where_clause {
             fn type_name() -> ::std::borrow::Cow<'static, str> {
-                ::std::borrow::Cow::Borrowed(#gql_typename)
+                #type_name
             }
 
             fn create_type_info(registry: &mut #crate_name::registry::Registry) -> String {
                 let ty = registry.create_type::<Self, _>(|registry| #crate_name::registry::MetaType::Object {
-                    name: #gql_typename.to_string(),
+                    name: <Self as #crate_name::Type>::type_name().into_owned(),
                     description: #desc,
                     fields: {
                         let mut fields = #crate_name::indexmap::IndexMap::new();
@@ -494,11 +632,13 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
         #[#crate_name::async_trait::async_trait]
         impl#generics #crate_name::resolver_utils::ObjectType for #self_ty #where_clause {
             async fn resolve_field(&self, ctx: &#crate_name::Context<'_>) -> #crate_name::Result<#crate_name::serde_json::Value> {
-                #(#resolvers)*
-                Err(#crate_name::QueryError::FieldNotFound {
-                    field_name: ctx.item.node.name.to_string(),
-                    object: #gql_typename.to_string(),
-                }.into_error(ctx.item.pos))
+                match ctx.item.node.name.node.as_str() {
+                    #(#resolvers)*
+                    _ => Err(#crate_name::QueryError::FieldNotFound {
+                        field_name: ctx.item.node.name.to_string(),
+                        object: <Self as #crate_name::Type>::type_name().into_owned(),
+                    }.into_error(ctx.item.pos)),
+                }
             }
 
             async fn find_entity(&self, ctx: &#crate_name::Context<'_>, params: &#crate_name::Value) -> #crate_name::Result<#crate_name::serde_json::Value> {
@@ -0,0 +1,178 @@
+use crate::args;
+use crate::utils::{get_crate_name, get_rustdoc};
+use inflector::Inflector;
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::{Data, DeriveInput, Error, Fields, Result};
+
+pub fn generate(object_args: &args::InputObject, input: &DeriveInput) -> Result<TokenStream> {
+    let crate_name = get_crate_name(object_args.internal);
+    let ident = &input.ident;
+    let e = match &input.data {
+        Data::Enum(e) => e,
+        _ => {
+            return Err(Error::new_spanned(
+                input,
+                "OneofObject can only be applied to an enum.",
+            ))
+        }
+    };
+
+    let gql_typename = object_args
+        .name
+        .clone()
+        .unwrap_or_else(|| ident.to_string());
+
+    let desc = object_args
+        .desc
+        .clone()
+        .or_else(|| get_rustdoc(&input.attrs).ok().flatten())
+        .map(|s| quote! { Some(#s) })
+        .unwrap_or_else(|| quote! {None});
+
+    let mut names = Vec::new();
+    let mut schema_fields = Vec::new();
+    let mut parse_fields = Vec::new();
+    let mut parse_fields_with_context = Vec::new();
+    let mut put_fields = Vec::new();
+
+    for variant in &e.variants {
+        let ty = match &variant.fields {
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => &fields.unnamed[0].ty,
+            _ => {
+                return Err(Error::new_spanned(
+                    variant,
+                    "Only single value variants are supported",
+                ))
+            }
+        };
+        let item_args = args::OneofObjectItem::parse(&crate_name, &variant.attrs)?;
+        let variant_ident = &variant.ident;
+        let name = item_args
+            .name
+            .unwrap_or_else(|| variant_ident.unraw().to_string().to_camel_case());
+        let validator = &item_args.validator;
+        let desc = item_args
+            .desc
+            .as_ref()
+            .map(|s| quote! {Some(#s)})
+            .unwrap_or_else(|| quote! {None});
+
+        names.push(name.clone());
+        schema_fields.push(quote! {
+            fields.insert(#name.to_string(), #crate_name::registry::MetaInputValue {
+                name: #name,
+                description: #desc,
+                ty: <::std::option::Option<#ty> as #crate_name::Type>::create_type_info(registry),
+                default_value: None,
+                validator: #validator,
+            });
+        });
+        parse_fields.push(quote! {
+            if name.as_str() == #name {
+                return <#ty as #crate_name::InputValueType>::parse(Some(value.clone()))
+                    .map(#ident::#variant_ident)
+                    .map_err(|err| err.with_field::<#ty>(#name));
+            }
+        });
+        parse_fields_with_context.push(quote! {
+            if name.as_str() == #name {
+                return <#ty as #crate_name::InputValueType>::parse_with_context(Some(value.clone()), &ctx.object_field(#name))
+                    .map(#ident::#variant_ident)
+                    .map_err(|err| err.with_field::<#ty>(#name));
+            }
+        });
+        put_fields.push(quote! {
+            #ident::#variant_ident(value) => map.insert(
+                #crate_name::parser::types::Name::new_unchecked(#name.to_owned()),
+                #crate_name::InputValueType::to_value(value)
+            ),
+        });
+    }
+
+    let unknown_fields = match object_args.deny_unknown_fields {
+        Some(true) => quote! { Some(#crate_name::UnknownInputFields::Deny) },
+        Some(false) => quote! { Some(#crate_name::UnknownInputFields::Ignore) },
+        None => quote! { None },
+    };
+
+    // Only the fields of the object are counted, like input objects ignore unknown fields when
+    // they are parsed.
+    let find_field = quote! {
+        let mut fields = obj.iter().filter(|(name, _)| [#(#names),*].contains(&name.as_str()));
+        let field = match (fields.next(), fields.next()) {
+            (Some((_, #crate_name::Value::Null)), None) => None,
+            (Some(field), None) => Some(field),
+            _ => None,
+        };
+    };
+    let invalid = quote! {
+        Err(#crate_name::InputValueError::invalid(
+            format!("exactly one non-null field of \"{}\"", #gql_typename),
+            #crate_name::Value::Object(obj),
+        ))
+    };
+
+    let expanded = quote! {
+        #[allow(clippy::all, clippy::pedantic)]
+        impl #crate_name::Type for #ident {
+            fn type_name() -> ::std::borrow::Cow<'static, str> {
+                ::std::borrow::Cow::Borrowed(#gql_typename)
+            }
+
+            fn create_type_info(registry: &mut #crate_name::registry::Registry) -> String {
+                registry.create_type::<Self, _>(|registry| #crate_name::registry::MetaType::InputObject {
+                    name: #gql_typename.to_string(),
+                    description: #desc,
+                    input_fields: {
+                        let mut fields = #crate_name::indexmap::IndexMap::new();
+                        #(#schema_fields)*
+                        fields
+                    },
+                    unknown_fields: #unknown_fields,
+                    oneof: true,
+                })
+            }
+        }
+
+        #[allow(clippy::all, clippy::pedantic)]
+        impl #crate_name::InputValueType for #ident {
+            fn parse(value: Option<#crate_name::Value>) -> #crate_name::InputValueResult<Self> {
+                if let Some(#crate_name::Value::Object(obj)) = value {
+                    #find_field
+                    if let Some((name, value)) = field {
+                        #(#parse_fields)*
+                    }
+                    #invalid
+                } else {
+                    Err(#crate_name::InputValueError::ExpectedType(value.unwrap_or_default()))
+                }
+            }
+
+            fn parse_with_context(
+                value: Option<#crate_name::Value>,
+                ctx: &#crate_name::ParseContext<'_>,
+            ) -> #crate_name::InputValueResult<Self> {
+                if let Some(#crate_name::Value::Object(obj)) = value {
+                    #find_field
+                    if let Some((name, value)) = field {
+                        #(#parse_fields_with_context)*
+                    }
+                    #invalid
+                } else {
+                    Err(#crate_name::InputValueError::ExpectedType(value.unwrap_or_default()))
+                }
+            }
+
+            fn to_value(&self) -> #crate_name::Value {
+                let mut map = ::std::collections::BTreeMap::new();
+                match self {
+                    #(#put_fields)*
+                };
+                #crate_name::Value::Object(map)
+            }
+        }
+    };
+    Ok(expanded.into())
+}
@@ -24,6 +24,11 @@ pub fn generate(scalar_args: &args::Scalar, item_impl: &mut ItemImpl) -> Result<
         .or_else(|| get_rustdoc(&item_impl.attrs).ok().flatten())
         .map(|s| quote! { Some(#s) })
         .unwrap_or_else(|| quote! {None});
+    let specified_by_url = scalar_args
+        .specified_by_url
+        .as_ref()
+        .map(|url| quote! { Some(#url) })
+        .unwrap_or_else(|| quote! {None});
     let self_ty = &item_impl.self_ty;
     let generic = &item_impl.generics;
     let where_clause = &item_impl.generics.where_clause;
@@ -42,6 +47,7 @@ pub fn generate(scalar_args: &args::Scalar, item_impl: &mut ItemImpl) -> Result<
                     name: #gql_typename.to_string(),
                     description: #desc,
                     is_valid: |value| <#self_ty as #crate_name::ScalarType>::is_valid(value),
+                    specified_by_url: #specified_by_url,
                 })
             }
         }
@@ -52,6 +58,13 @@ pub fn generate(scalar_args: &args::Scalar, item_impl: &mut ItemImpl) -> Result<
                 <#self_ty as #crate_name::ScalarType>::parse(value.unwrap_or_default())
             }
 
+            fn parse_with_context(
+                value: Option<#crate_name::Value>,
+                ctx: &#crate_name::ParseContext<'_>,
+            ) -> #crate_name::InputValueResult<Self> {
+                <#self_ty as #crate_name::ScalarType>::parse_with_context(value.unwrap_or_default(), ctx)
+            }
+
             fn to_value(&self) -> #crate_name::Value {
                 <#self_ty as #crate_name::ScalarType>::to_value(self)
             }
@@ -1,8 +1,12 @@
 use crate::args;
-use crate::utils::{feature_block, get_crate_name, get_rustdoc};
+use crate::utils::{
+    check_duplicate, feature_block, generate_complexity, generate_type_name, get_crate_name,
+    get_rustdoc, load_desc_file,
+};
 use inflector::Inflector;
 use proc_macro::TokenStream;
 use quote::quote;
+use std::collections::HashMap;
 use syn::ext::IdentExt;
 use syn::{Data, DeriveInput, Error, Fields, Result};
 
@@ -16,6 +20,7 @@ pub fn generate(object_args: &args::Object, input: &DeriveInput) -> Result<Token
         .name
         .clone()
         .unwrap_or_else(|| ident.to_string());
+    let type_name = generate_type_name(&crate_name, &gql_typename, generics)?;
 
     let desc = object_args
         .desc
@@ -24,6 +29,13 @@ pub fn generate(object_args: &args::Object, input: &DeriveInput) -> Result<Token
         .map(|s| quote! { Some(#s) })
         .unwrap_or_else(|| quote! {None});
 
+    let desc_file = object_args
+        .desc_file
+        .as_ref()
+        .map(|lit| load_desc_file(lit, &gql_typename))
+        .transpose()?;
+    let desc_file_include = desc_file.as_ref().map(|desc_file| &desc_file.include);
+
     let s = match &input.data {
         Data::Struct(e) => e,
         _ => return Err(Error::new_spanned(input, "It should be a struct")),
@@ -31,6 +43,8 @@ pub fn generate(object_args: &args::Object, input: &DeriveInput) -> Result<Token
     let mut getters = Vec::new();
     let mut resolvers = Vec::new();
     let mut schema_fields = Vec::new();
+    let mut from_fields = Vec::new();
+    let mut field_names = HashMap::new();
     let fields = match &s.fields {
         Fields::Named(fields) => Some(fields),
         Fields::Unit => None,
@@ -39,7 +53,24 @@ pub fn generate(object_args: &args::Object, input: &DeriveInput) -> Result<Token
 
     if let Some(fields) = fields {
         for item in &fields.named {
-            if let Some(field) = args::Field::parse(&crate_name, &item.attrs)? {
+            let field = args::Field::parse(&crate_name, &item.attrs)?;
+
+            if object_args.from.is_some() {
+                let ident = item.ident.as_ref().unwrap();
+                let value = match &field {
+                    Some(field) => {
+                        let src_ident = field.from_field.as_ref().unwrap_or(ident);
+                        match &field.from_with {
+                            Some(from_with) => quote! { #from_with(value.#src_ident) },
+                            None => quote! { ::std::convert::Into::into(value.#src_ident) },
+                        }
+                    }
+                    None => quote! { ::std::default::Default::default() },
+                };
+                from_fields.push(quote! { #ident: #value });
+            }
+
+            if let Some(field) = field {
                 let field_name = field.name.clone().unwrap_or_else(|| {
                     item.ident
                         .as_ref()
@@ -48,9 +79,20 @@ pub fn generate(object_args: &args::Object, input: &DeriveInput) -> Result<Token
                         .to_string()
                         .to_camel_case()
                 });
+                check_duplicate(
+                    &mut field_names,
+                    &field_name,
+                    item.ident.as_ref().unwrap().span(),
+                    "field",
+                )?;
                 let field_desc = field
                     .desc
                     .as_ref()
+                    .or_else(|| {
+                        desc_file
+                            .as_ref()
+                            .and_then(|desc_file| desc_file.fields.get(&field_name))
+                    })
                     .map(|s| quote! {Some(#s)})
                     .unwrap_or_else(|| quote! {None});
                 let field_deprecation = field
@@ -59,6 +101,7 @@ pub fn generate(object_args: &args::Object, input: &DeriveInput) -> Result<Token
                     .map(|s| quote! {Some(#s)})
                     .unwrap_or_else(|| quote! {None});
                 let external = field.external;
+                let idempotent = field.idempotent;
                 let requires = match &field.requires {
                     Some(requires) => quote! { Some(#requires) },
                     None => quote! { None },
@@ -67,12 +110,17 @@ pub fn generate(object_args: &args::Object, input: &DeriveInput) -> Result<Token
                     Some(provides) => quote! { Some(#provides) },
                     None => quote! { None },
                 };
+                let requires_feature = match &field.requires_feature {
+                    Some(feature) => quote! { Some(#feature) },
+                    None => quote! { None },
+                };
                 let vis = &item.vis;
                 let ty = &item.ty;
 
                 let cache_control = {
-                    let public = field.cache_control.public;
-                    let max_age = field.cache_control.max_age;
+                    let cache_control = field.cache_control.inherit(&object_args.cache_control);
+                    let public = cache_control.public.unwrap_or(true);
+                    let max_age = cache_control.max_age.unwrap_or(0);
                     quote! {
                         #crate_name::CacheControl {
                             public: #public,
@@ -81,6 +129,8 @@ pub fn generate(object_args: &args::Object, input: &DeriveInput) -> Result<Token
                     }
                 };
 
+                let compute_complexity = generate_complexity(&crate_name, &field.complexity, &[]);
+
                 schema_fields.push(quote! {
                     fields.insert(#field_name.to_string(), #crate_name::registry::MetaField {
                         name: #field_name.to_string(),
@@ -92,16 +142,26 @@ pub fn generate(object_args: &args::Object, input: &DeriveInput) -> Result<Token
                         external: #external,
                         provides: #provides,
                         requires: #requires,
+                        idempotent: #idempotent,
+                        compute_complexity: #compute_complexity,
+                        requires_feature: #requires_feature,
                     });
                 });
 
                 let ident = &item.ident;
-                let guard = field
-                    .guard
-                    .map(|guard| quote! { #guard.check(ctx).await.map_err(|err| err.into_error_with_path(ctx.item.pos, ctx.path_node.as_ref()))?; });
-                let post_guard = field
-                    .post_guard
-                    .map(|guard| quote! { #guard.check(ctx, &res).await.map_err(|err| err.into_error_with_path(ctx.item.pos, ctx.path_node.as_ref()))?; });
+                let guard = field.guard.map(|guard| {
+                    quote! { #crate_name::resolver_utils::field_result(ctx, #guard.check(ctx).await)?; }
+                });
+                let resolve = match field.post_guard {
+                    Some(guard) => quote! {
+                        let res = #crate_name::resolver_utils::field_result(ctx, self.#ident(ctx).await)?;
+                        #crate_name::resolver_utils::field_result(ctx, #guard.check(ctx, &res).await)?;
+                        #crate_name::resolver_utils::resolve_field_value(ctx, &res).await
+                    },
+                    None => quote! {
+                        #crate_name::resolver_utils::resolve_field_result(ctx, self.#ident(ctx).await).await
+                    },
+                };
 
                 let features = &field.features;
                 getters.push(if !field.owned {
@@ -135,21 +195,22 @@ pub fn generate(object_args: &args::Object, input: &DeriveInput) -> Result<Token
                 });
 
                 resolvers.push(quote! {
-                    if ctx.item.node.name.node == #field_name {
+                    #field_name => {
                         #guard
-                        let res = self.#ident(ctx).await.map_err(|err| err.into_error_with_path(ctx.item.pos, ctx.path_node.as_ref()))?;
-                        let ctx_obj = ctx.with_selection_set(&ctx.item.node.selection_set);
-                        #post_guard
-                        return #crate_name::OutputValueType::resolve(&res, &ctx_obj, ctx.item).await;
+                        #resolve
                     }
                 });
             }
         }
     }
 
+    if let Some(desc_file) = &desc_file {
+        desc_file.check_fields(&field_names)?;
+    }
+
     let cache_control = {
-        let public = object_args.cache_control.public;
-        let max_age = object_args.cache_control.max_age;
+        let public = object_args.cache_control.public.unwrap_or(true);
+        let max_age = object_args.cache_control.max_age.unwrap_or(0);
         quote! {
             #crate_name::CacheControl {
                 public: #public,
@@ -158,25 +219,72 @@ pub fn generate(object_args: &args::Object, input: &DeriveInput) -> Result<Token
         }
     };
 
+    let complex_fields = if object_args.complex {
+        Some(quote! {
+            for (name, field) in <Self as #crate_name::resolver_utils::ComplexObject>::fields(registry) {
+                if fields.contains_key(&name) {
+                    panic!(
+                        "Field \"{}\" of \"{}\" is defined by both the struct and its ComplexObject impl.",
+                        name, <Self as #crate_name::Type>::type_name()
+                    );
+                }
+                fields.insert(name, field);
+            }
+        })
+    } else {
+        None
+    };
+
+    let resolve_complex_field = if object_args.complex {
+        quote! {
+            <Self as #crate_name::resolver_utils::ComplexObject>::resolve_field(self, ctx).await
+        }
+    } else {
+        quote! {
+            Err(#crate_name::QueryError::FieldNotFound {
+                field_name: ctx.item.node.name.to_string(),
+                object: <Self as #crate_name::Type>::type_name().into_owned(),
+            }.into_error(ctx.item.pos))
+        }
+    };
+
+    let from_impl = object_args.from.as_ref().map(|from| {
+        quote! {
+            #[allow(clippy::all, clippy::pedantic)]
+            impl #generics ::std::convert::From<#from> for #ident #generics #where_clause {
+                fn from(value: #from) -> Self {
+                    Self {
+                        #(#from_fields),*
+                    }
+                }
+            }
+        }
+    });
+
     let expanded = quote! {
+        #desc_file_include
+
         #[allow(clippy::all, clippy::pedantic)]
         impl #generics #ident #where_clause {
             #(#getters)*
         }
 
+        #from_impl
+
         #[allow(clippy::all, clippy::pedantic)]
         impl #generics #crate_name::Type for #ident #generics #where_clause {
             fn type_name() -> ::std::borrow::Cow<'static, str> {
-                ::std::borrow::Cow::Borrowed(#gql_typename)
+                #type_name
             }
 
             fn create_type_info(registry: &mut #crate_name::registry::Registry) -> String {
                 registry.create_type::<Self, _>(|registry| #crate_name::registry::MetaType::Object {
-                    name: #gql_typename.to_string(),
+                    name: <Self as #crate_name::Type>::type_name().into_owned(),
                     description: #desc,
                     fields: {
                         let mut fields = #crate_name::indexmap::IndexMap::new();
                         #(#schema_fields)*
+                        #complex_fields
                         fields
                     },
                     cache_control: #cache_control,
@@ -190,11 +298,10 @@ pub fn generate(object_args: &args::Object, input: &DeriveInput) -> Result<Token
         #[#crate_name::async_trait::async_trait]
         impl #generics #crate_name::resolver_utils::ObjectType for #ident #generics #where_clause {
             async fn resolve_field(&self, ctx: &#crate_name::Context<'_>) -> #crate_name::Result<#crate_name::serde_json::Value> {
-                #(#resolvers)*
-                Err(#crate_name::QueryError::FieldNotFound {
-                    field_name: ctx.item.node.name.to_string(),
-                    object: #gql_typename.to_string(),
-                }.into_error(ctx.item.pos))
+                match ctx.item.node.name.node.as_str() {
+                    #(#resolvers)*
+                    _ => #resolve_complex_field,
+                }
             }
         }
 
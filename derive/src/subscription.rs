@@ -1,6 +1,8 @@
 use crate::args;
 use crate::output_type::OutputType;
-use crate::utils::{feature_block, get_crate_name, get_param_getter_ident, get_rustdoc};
+use crate::utils::{
+    feature_block, generate_type_name, get_crate_name, get_param_getter_ident, get_rustdoc,
+};
 use inflector::Inflector;
 use proc_macro::TokenStream;
 use quote::quote;
@@ -30,6 +32,7 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
         .name
         .clone()
         .unwrap_or_else(|| self_name.clone());
+    let type_name = generate_type_name(&crate_name, &gql_typename, generics)?;
 
     let desc = object_args
         .desc
@@ -40,6 +43,7 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
 
     let mut create_stream = Vec::new();
     let mut schema_fields = Vec::new();
+    let mut field_names = Vec::new();
 
     for item in &mut item_impl.items {
         if let ImplItem::Method(method) = item {
@@ -144,10 +148,20 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
                         desc,
                         default,
                         validator,
+                        inject,
                         ..
                     },
                 ) in args
                 {
+                    if let Some(inject) = inject {
+                        let param_ident = &ident.ident;
+                        use_params.push(quote! { #param_ident });
+                        get_params.push(quote! {
+                            let #ident: #ty = #crate_name::resolver_utils::field_result(ctx, (#inject)(ctx))?;
+                        });
+                        continue;
+                    }
+
                     let name = name
                         .clone()
                         .unwrap_or_else(|| ident.ident.unraw().to_string().to_camel_case());
@@ -178,9 +192,17 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
                         Some(default) => quote! { Some(|| -> #ty { #default }) },
                         None => quote! { None },
                     };
-                    let param_getter_name = get_param_getter_ident(&ident.ident.to_string());
+                    let getter = if field.guard.is_some() {
+                        // The guard reads the argument with its getter.
+                        let param_getter_name = get_param_getter_ident(&ident.ident.to_string());
+                        Some(quote! {
+                            let #param_getter_name = || -> #crate_name::Result<#ty> { ctx.param_value(#name, #default) };
+                        })
+                    } else {
+                        None
+                    };
                     get_params.push(quote! {
-                        let #param_getter_name = || -> #crate_name::Result<#ty> { ctx.param_value(#name, #default) };
+                        #getter
                         let #ident: #ty = ctx.param_value(#name, #default)?;
                     });
                 }
@@ -229,19 +251,34 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
                         external: false,
                         requires: None,
                         provides: None,
+                        idempotent: false,
+                        compute_complexity: None,
+                        requires_feature: None,
                     });
                 });
 
-                let create_field_stream = quote! {
-                    self.#ident(ctx, #(#use_params),*)
-                        .await
-                        .map_err(|err| {
-                            err.into_error_with_path(ctx.item.pos, ctx.path_node.as_ref())
-                        })?
+                let mut create_field_stream = if field.shared {
+                    quote! {
+                        #crate_name::resolver_utils::shared_field_stream(ctx, move || async move {
+                            #crate_name::resolver_utils::field_result(ctx, self.#ident(ctx, #(#use_params),*).await)
+                        }).await?
+                    }
+                } else {
+                    quote! {
+                        #crate_name::resolver_utils::field_result(ctx, self.#ident(ctx, #(#use_params),*).await)?
+                    }
                 };
 
-                let guard = field.guard.map(|guard| quote! {
-                    #guard.check(ctx).await.map_err(|err| err.into_error_with_path(ctx.item.pos, ctx.path_node.as_ref()))?;
+                if let Some(cleanup) = &field.cleanup {
+                    create_field_stream = quote! {
+                        #crate_name::StreamGuard::new(ctx, #create_field_stream, #cleanup(ctx))
+                    };
+                }
+
+                let guard = field.guard.map(|guard| {
+                    quote! {
+                        #crate_name::resolver_utils::field_result(ctx, #guard.check(ctx).await)?;
+                    }
                 });
                 if field.post_guard.is_some() {
                     return Err(Error::new_spanned(
@@ -268,16 +305,16 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
                             let field_name = field_name.clone();
                             async move {
                                 let resolve_id = ::std::sync::atomic::AtomicUsize::default();
-                                let ctx_selection_set = query_env.create_context(
+                                let ctx_field = query_env.create_context(
                                     &schema_env,
                                     Some(#crate_name::QueryPathNode {
                                         parent: None,
                                         segment: #crate_name::QueryPathSegment::Name(&field_name),
                                     }),
-                                    &field.node.selection_set,
+                                    &*field,
                                     &resolve_id,
                                 );
-                                #crate_name::OutputValueType::resolve(&msg, &ctx_selection_set, &*field)
+                                #crate_name::resolver_utils::resolve_field_value(&ctx_field, &msg)
                                     .await
                                     .map(|value| {
                                         #crate_name::serde_json::json!({
@@ -302,6 +339,7 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
                     ))
                 };
 
+                field_names.push(field_name.clone());
                 create_stream.push(quote! {
                     if ctx.item.node.name.node == #field_name {
                         return ::std::boxed::Box::pin(
@@ -330,13 +368,13 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
         #[allow(clippy::all, clippy::pedantic)]
         impl #generics #crate_name::Type for #self_ty #where_clause {
             fn type_name() -> ::std::borrow::Cow<'static, str> {
-                ::std::borrow::Cow::Borrowed(#gql_typename)
+                #type_name
             }
 
             #[allow(bare_trait_objects)]
             fn create_type_info(registry: &mut #crate_name::registry::Registry) -> String {
                 registry.create_type::<Self, _>(|registry| #crate_name::registry::MetaType::Object {
-                    name: #gql_typename.to_string(),
+                    name: <Self as #crate_name::Type>::type_name().into_owned(),
                     description: #desc,
                     fields: {
                         let mut fields = #crate_name::indexmap::IndexMap::new();
@@ -353,6 +391,10 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
         #[allow(clippy::all, clippy::pedantic)]
         #[allow(unused_braces, unused_variables)]
         impl #crate_name::SubscriptionType for #self_ty #where_clause {
+            fn has_field(name: &str) -> bool {
+                [#(#field_names),*].contains(&name)
+            }
+
             fn create_field_stream<'a>(
                 &'a self,
                 ctx: &'a #crate_name::Context<'a>,
@@ -360,7 +402,7 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
                 #(#create_stream)*
                 let error = #crate_name::QueryError::FieldNotFound {
                     field_name: ctx.item.node.name.to_string(),
-                    object: #gql_typename.to_string(),
+                    object: <Self as #crate_name::Type>::type_name().into_owned(),
                 }
                     .into_error(ctx.item.pos);
                 ::std::boxed::Box::pin(#crate_name::futures::stream::once(async { Err(error) }))
@@ -58,6 +58,8 @@ pub fn generate(union_args: &args::Interface, input: &DeriveInput) -> Result<Tok
                 ))
             }
         };
+        let item_args = args::UnionItem::parse(&variant.attrs)?;
+
         if let Type::Path(p) = &field.ty {
             // This validates that the field type wasn't already used
             if !enum_items.insert(p) {
@@ -79,12 +81,24 @@ pub fn generate(union_args: &args::Interface, input: &DeriveInput) -> Result<Tok
             registry_types.push(quote! {
                 <#p as #crate_name::Type>::create_type_info(registry);
             });
-            possible_types.push(quote! {
-                possible_types.insert(<#p as #crate_name::Type>::type_name().to_string());
-            });
-            get_introspection_typename.push(quote! {
-                #ident::#enum_name(obj) => <#p as #crate_name::Type>::type_name()
-            });
+            if item_args.flatten {
+                possible_types.push(quote! {
+                    if let Some(#crate_name::registry::MetaType::Union { possible_types: inner_possible_types, .. }) =
+                        registry.types.get(&*<#p as #crate_name::Type>::type_name()) {
+                        possible_types.extend(inner_possible_types.iter().cloned());
+                    }
+                });
+                get_introspection_typename.push(quote! {
+                    #ident::#enum_name(obj) => #crate_name::Type::introspection_type_name(obj)
+                });
+            } else {
+                possible_types.push(quote! {
+                    possible_types.insert(<#p as #crate_name::Type>::type_name().to_string());
+                });
+                get_introspection_typename.push(quote! {
+                    #ident::#enum_name(obj) => <#p as #crate_name::Type>::type_name()
+                });
+            }
             collect_all_fields.push(quote! {
                 #ident::#enum_name(obj) => obj.collect_all_fields(ctx, fields)
             });
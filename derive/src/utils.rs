@@ -1,8 +1,14 @@
+use crate::args::Complexity;
 use itertools::Itertools;
 use proc_macro2::{Span, TokenStream, TokenTree};
 use proc_macro_crate::crate_name;
 use quote::quote;
-use syn::{Attribute, DeriveInput, Error, Expr, Ident, Lit, Meta, MetaList, NestedMeta, Result};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use syn::{
+    Attribute, DeriveInput, Error, Expr, Generics, Ident, Lit, LitStr, Meta, MetaList, NestedMeta,
+    Result,
+};
 
 pub fn get_crate_name(internal: bool) -> TokenStream {
     if internal {
@@ -114,45 +120,41 @@ pub fn parse_validator(crate_name: &TokenStream, args: &MetaList) -> Result<Toke
 }
 
 pub fn parse_guards(crate_name: &TokenStream, args: &MetaList) -> Result<Option<TokenStream>> {
+    parse_guard_list(
+        args,
+        "guard",
+        &quote! { #crate_name::guard::GuardExt },
+        &["and", "or", "race"],
+    )
+}
+
+pub fn parse_post_guards(crate_name: &TokenStream, args: &MetaList) -> Result<Option<TokenStream>> {
+    parse_guard_list(
+        args,
+        "post_guard",
+        &quote! { #crate_name::guard::PostGuardExt },
+        &["and", "or"],
+    )
+}
+
+/// Parses the guards of the `name` attribute, which are all required to pass. `combinators` are
+/// the methods of the extension trait `ext` that can also be written like `or(GuardA, GuardB)`.
+fn parse_guard_list(
+    args: &MetaList,
+    name: &str,
+    ext: &TokenStream,
+    combinators: &[&str],
+) -> Result<Option<TokenStream>> {
     for arg in &args.nested {
         if let NestedMeta::Meta(Meta::List(ls)) = arg {
-            if ls.path.is_ident("guard") {
+            if ls.path.is_ident(name) {
                 let mut guards = None;
                 for item in &ls.nested {
-                    if let NestedMeta::Meta(Meta::List(ls)) = item {
-                        let ty = &ls.path;
-                        let mut params = Vec::new();
-                        for attr in &ls.nested {
-                            if let NestedMeta::Meta(Meta::NameValue(nv)) = attr {
-                                let name = &nv.path;
-                                if let Lit::Str(value) = &nv.lit {
-                                    let value_str = value.value();
-                                    if value_str.starts_with('@') {
-                                        let getter_name = get_param_getter_ident(&value_str[1..]);
-                                        params.push(quote! { #name: #getter_name()? });
-                                    } else {
-                                        let expr = syn::parse_str::<Expr>(&value_str)?;
-                                        params.push(quote! { #name: (#expr).into() });
-                                    }
-                                } else {
-                                    return Err(Error::new_spanned(
-                                        &nv.lit,
-                                        "Value must be string literal",
-                                    ));
-                                }
-                            } else {
-                                return Err(Error::new_spanned(attr, "Invalid property for guard"));
-                            }
-                        }
-                        let guard = quote! { #ty { #(#params),* } };
-                        if guards.is_none() {
-                            guards = Some(guard);
-                        } else {
-                            guards =
-                                Some(quote! { #crate_name::guard::GuardExt::and(#guard, #guards) });
-                        }
+                    let guard = parse_guard(item, ext, combinators)?;
+                    if let Some(other) = guards {
+                        guards = Some(quote! { #ext::and(#guard, #other) });
                     } else {
-                        return Err(Error::new_spanned(item, "Invalid guard"));
+                        guards = Some(guard);
                     }
                 }
                 return Ok(guards);
@@ -162,54 +164,55 @@ pub fn parse_guards(crate_name: &TokenStream, args: &MetaList) -> Result<Option<
     Ok(None)
 }
 
-pub fn parse_post_guards(crate_name: &TokenStream, args: &MetaList) -> Result<Option<TokenStream>> {
-    for arg in &args.nested {
-        if let NestedMeta::Meta(Meta::List(ls)) = arg {
-            if ls.path.is_ident("post_guard") {
-                let mut guards = None;
-                for item in &ls.nested {
-                    if let NestedMeta::Meta(Meta::List(ls)) = item {
-                        let ty = &ls.path;
-                        let mut params = Vec::new();
-                        for attr in &ls.nested {
-                            if let NestedMeta::Meta(Meta::NameValue(nv)) = attr {
-                                let name = &nv.path;
-                                if let Lit::Str(value) = &nv.lit {
-                                    let value_str = value.value();
-                                    if value_str.starts_with('@') {
-                                        let getter_name = get_param_getter_ident(&value_str[1..]);
-                                        params.push(quote! { #name: #getter_name()? });
-                                    } else {
-                                        let expr = syn::parse_str::<Expr>(&value_str)?;
-                                        params.push(quote! { #name: (#expr).into() });
-                                    }
-                                } else {
-                                    return Err(Error::new_spanned(
-                                        &nv.lit,
-                                        "Value must be string literal",
-                                    ));
-                                }
-                            } else {
-                                return Err(Error::new_spanned(attr, "Invalid property for guard"));
-                            }
-                        }
-                        let guard = quote! { #ty { #(#params),* } };
-                        if guards.is_none() {
-                            guards = Some(guard);
-                        } else {
-                            guards = Some(
-                                quote! { #crate_name::guard::PostGuardExt::and(#guard, #guards) },
-                            );
-                        }
-                    } else {
-                        return Err(Error::new_spanned(item, "Invalid guard"));
-                    }
+fn parse_guard(item: &NestedMeta, ext: &TokenStream, combinators: &[&str]) -> Result<TokenStream> {
+    let ls = match item {
+        NestedMeta::Meta(Meta::List(ls)) => ls,
+        _ => return Err(Error::new_spanned(item, "Invalid guard")),
+    };
+
+    if let Some(combinator) = combinators.iter().find(|c| ls.path.is_ident(c)) {
+        let combinator = Ident::new(combinator, Span::call_site());
+        let mut guards = ls
+            .nested
+            .iter()
+            .map(|item| parse_guard(item, ext, combinators));
+        let first = match guards.next() {
+            Some(guard) => guard?,
+            None => {
+                return Err(Error::new_spanned(
+                    ls,
+                    format!("\"{}\" requires at least one guard", combinator),
+                ))
+            }
+        };
+        return guards.try_fold(first, |acc, guard| {
+            let guard = guard?;
+            Ok(quote! { #ext::#combinator(#acc, #guard) })
+        });
+    }
+
+    let ty = &ls.path;
+    let mut params = Vec::new();
+    for attr in &ls.nested {
+        if let NestedMeta::Meta(Meta::NameValue(nv)) = attr {
+            let name = &nv.path;
+            if let Lit::Str(value) = &nv.lit {
+                let value_str = value.value();
+                if value_str.starts_with('@') {
+                    let getter_name = get_param_getter_ident(&value_str[1..]);
+                    params.push(quote! { #name: #getter_name()? });
+                } else {
+                    let expr = syn::parse_str::<Expr>(&value_str)?;
+                    params.push(quote! { #name: (#expr).into() });
                 }
-                return Ok(guards);
+            } else {
+                return Err(Error::new_spanned(&nv.lit, "Value must be string literal"));
             }
+        } else {
+            return Err(Error::new_spanned(attr, "Invalid property for guard"));
         }
     }
-    Ok(None)
+    Ok(quote! { #ty { #(#params),* } })
 }
 
 pub fn get_rustdoc(attrs: &[Attribute]) -> Result<Option<String>> {
@@ -274,10 +277,160 @@ pub fn parse_default_with(lit: &Lit) -> Result<TokenStream> {
     }
 }
 
+/// Returns the value of a `name = "..."` attribute, or an error spanned on the literal if it is
+/// not a valid GraphQL name or uses the `__` prefix reserved for introspection.
+pub fn check_name(lit: &LitStr) -> Result<String> {
+    let name = lit.value();
+    let mut chars = name.chars();
+    let is_valid = match chars.next() {
+        Some(c) if c == '_' || c.is_ascii_alphabetic() => {
+            chars.all(|c| c == '_' || c.is_ascii_alphanumeric())
+        }
+        _ => false,
+    };
+    if !is_valid {
+        return Err(Error::new_spanned(
+            lit,
+            format!(
+                "\"{}\" is not a valid GraphQL name, names must match /[_A-Za-z][_0-9A-Za-z]*/.",
+                name
+            ),
+        ));
+    }
+    if name.starts_with("__") {
+        return Err(Error::new_spanned(
+            lit,
+            format!(
+                "\"{}\" must not begin with \"__\", which is reserved by GraphQL introspection.",
+                name
+            ),
+        ));
+    }
+    Ok(name)
+}
+
+/// Like `check_name`, but the name can contain type parameters between braces, such as
+/// `Edge_{T}`, so that each instantiation of a generic type has its own name.
+pub fn check_type_name(lit: &LitStr) -> Result<String> {
+    let name = lit.value();
+    match split_type_name(&name) {
+        Some((format, _)) => {
+            check_name(&LitStr::new(&format.replace("{}", "T"), lit.span()))?;
+            Ok(name)
+        }
+        None => Err(Error::new_spanned(
+            lit,
+            format!(
+                "\"{}\" has unbalanced braces, type parameters are written like \"Edge_{{T}}\".",
+                name
+            ),
+        )),
+    }
+}
+
+/// Split a type name such as `Edge_{T}` into a format string and the names of its type
+/// parameters, or return `None` if the braces are unbalanced.
+fn split_type_name(name: &str) -> Option<(String, Vec<String>)> {
+    let mut format = String::new();
+    let mut params = Vec::new();
+    let mut rest = name;
+    while let Some(start) = rest.find('{') {
+        let end = start + rest[start..].find('}')?;
+        if rest[..start].contains('}') || rest[start + 1..end].contains('{') {
+            return None;
+        }
+        format.push_str(&rest[..start]);
+        format.push_str("{}");
+        params.push(rest[start + 1..end].trim().to_string());
+        rest = &rest[end + 1..];
+    }
+    if rest.contains('}') {
+        return None;
+    }
+    format.push_str(rest);
+    Some((format, params))
+}
+
+/// Returns an expression of type `Cow<'static, str>` with the name of a type, in which the type
+/// parameters of `name` are replaced by the names of the types that `generics` are instantiated
+/// with, see `registry::type_param_name`.
+pub fn generate_type_name(
+    crate_name: &TokenStream,
+    name: &str,
+    generics: &Generics,
+) -> Result<TokenStream> {
+    let (format, params) = split_type_name(name).unwrap_or_else(|| (name.to_string(), Vec::new()));
+    if params.is_empty() {
+        return Ok(quote! { ::std::borrow::Cow::Borrowed(#name) });
+    }
+
+    let mut types = Vec::new();
+    for param in &params {
+        match generics.type_params().find(|ty| ty.ident == param) {
+            Some(ty) => {
+                let ident = &ty.ident;
+                types.push(quote! {
+                    #crate_name::registry::type_param_name(&<#ident as #crate_name::Type>::qualified_type_name())
+                });
+            }
+            None => {
+                return Err(Error::new(
+                    Span::call_site(),
+                    format!("\"{}\" is not a type parameter of \"{}\".", param, name),
+                ))
+            }
+        }
+    }
+    Ok(quote! { ::std::borrow::Cow::Owned(format!(#format, #(#types),*)) })
+}
+
+/// Records that `name` is defined at `span`, or returns an error pointing at both definitions if
+/// it has already been defined.
+pub fn check_duplicate(
+    names: &mut HashMap<String, Span>,
+    name: &str,
+    span: Span,
+    kind: &str,
+) -> Result<()> {
+    if let Some(first) = names.get(name) {
+        let mut err = Error::new(span, format!("Duplicate {} \"{}\".", kind, name));
+        err.combine(Error::new(
+            *first,
+            format!("The {} \"{}\" is first defined here.", kind, name),
+        ));
+        return Err(err);
+    }
+    names.insert(name.to_string(), span);
+    Ok(())
+}
+
 pub fn get_param_getter_ident(name: &str) -> Ident {
     Ident::new(&format!("__{}_getter", name), Span::call_site())
 }
 
+/// Generates the `compute_complexity` of a `MetaField`. `params` parse the arguments of the field
+/// from a `ComplexityContext` named `ctx`, for the expression.
+pub fn generate_complexity(
+    crate_name: &TokenStream,
+    complexity: &Option<Complexity>,
+    params: &[TokenStream],
+) -> TokenStream {
+    match complexity {
+        Some(Complexity::Const(n)) => {
+            quote! { Some(#crate_name::registry::ComplexityType::Const(#n)) }
+        }
+        Some(Complexity::Expr(expr)) => quote! {
+            Some(#crate_name::registry::ComplexityType::Fn(|ctx, child_complexity| {
+                #[allow(unused_variables)]
+                let child_complexity: usize = child_complexity;
+                #(#params)*
+                Ok(#expr)
+            }))
+        },
+        None => quote! { None },
+    }
+}
+
 pub fn feature_block(
     crate_name: &TokenStream,
     features: &[String],
@@ -304,3 +457,100 @@ pub fn feature_block(
         block
     }
 }
+
+/// Field descriptions of a type loaded from a `desc_file`.
+pub struct DescFile {
+    /// Makes the compiler track the file, so the crate is rebuilt when it changes.
+    pub include: TokenStream,
+    pub fields: HashMap<String, String>,
+    lit: LitStr,
+    type_name: String,
+    path: String,
+}
+
+impl DescFile {
+    /// Returns an error for every description in the file that doesn't belong to one of
+    /// `field_names`, which are the names of the fields of the type.
+    pub fn check_fields(&self, field_names: &HashMap<String, Span>) -> Result<()> {
+        let mut unknown = self
+            .fields
+            .keys()
+            .filter(|name| !field_names.contains_key(*name))
+            .collect::<Vec<_>>();
+        unknown.sort();
+
+        let mut errors = unknown.into_iter().map(|name| {
+            Error::new_spanned(
+                &self.lit,
+                format!(
+                    "`{}.{}` in `{}` is not a field of `{}`.",
+                    self.type_name, name, self.path, self.type_name
+                ),
+            )
+        });
+        match errors.next() {
+            Some(mut err) => {
+                errors.for_each(|other| err.combine(other));
+                Err(err)
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+/// Load the descriptions of the fields of `type_name` from a TOML file.
+///
+/// The path is relative to the directory containing the `Cargo.toml` of the crate being
+/// compiled, and the file contains one table per type, keyed by field name:
+///
+/// ```toml
+/// [MyObject]
+/// value = "The value"
+/// ```
+pub fn load_desc_file(lit: &LitStr, type_name: &str) -> Result<DescFile> {
+    let mut path = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default());
+    path.push(lit.value());
+
+    let content = std::fs::read_to_string(&path).map_err(|err| {
+        Error::new_spanned(lit, format!("Failed to read `{}`: {}", path.display(), err))
+    })?;
+    let table = content.parse::<toml::Value>().map_err(|err| {
+        Error::new_spanned(
+            lit,
+            format!("Failed to parse `{}`: {}", path.display(), err),
+        )
+    })?;
+
+    let mut fields = HashMap::new();
+    if let Some(type_table) = table.get(type_name) {
+        let type_table = type_table.as_table().ok_or_else(|| {
+            Error::new_spanned(
+                lit,
+                format!("`{}` in `{}` should be a table.", type_name, path.display()),
+            )
+        })?;
+        for (name, value) in type_table {
+            let desc = value.as_str().ok_or_else(|| {
+                Error::new_spanned(
+                    lit,
+                    format!(
+                        "The description of `{}.{}` in `{}` should be a string.",
+                        type_name,
+                        name,
+                        path.display()
+                    ),
+                )
+            })?;
+            fields.insert(name.clone(), desc.to_string());
+        }
+    }
+
+    let path = path.display().to_string();
+    Ok(DescFile {
+        include: quote! { const _: &str = include_str!(#path); },
+        fields,
+        lit: lit.clone(),
+        type_name: type_name.to_string(),
+        path,
+    })
+}
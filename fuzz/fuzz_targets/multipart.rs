@@ -0,0 +1,15 @@
+#![no_main]
+
+use async_graphql::http::{receive_batch_body, MultipartOptions};
+use bytes::Bytes;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|body: &[u8]| {
+    let _ = futures::executor::block_on(receive_batch_body(
+        Some("multipart/form-data; boundary=boundary"),
+        futures::stream::iter(vec![Ok::<_, std::io::Error>(Bytes::copy_from_slice(body))]),
+        MultipartOptions::default()
+            .max_file_size(1024)
+            .max_num_files(4),
+    ));
+});
@@ -0,0 +1,13 @@
+#![no_main]
+
+use async_graphql::parser::{check_token_limit, parse_query};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|query: &str| {
+    let document = parse_query(query);
+
+    // The token scanner must accept everything the parser accepts.
+    if document.is_ok() {
+        assert!(check_token_limit(query, usize::MAX).is_ok());
+    }
+});
@@ -0,0 +1,86 @@
+#![no_main]
+
+use arbitrary::Arbitrary;
+use async_graphql::http::WebSocket;
+use async_graphql::*;
+use futures::{Stream, StreamExt};
+use libfuzzer_sys::fuzz_target;
+
+struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    async fn value(&self) -> i32 {
+        10
+    }
+}
+
+struct SubscriptionRoot;
+
+#[Subscription]
+impl SubscriptionRoot {
+    async fn values(&self, count: i32) -> impl Stream<Item = i32> {
+        futures::stream::iter(0..count.max(0).min(10))
+    }
+
+    async fn error(&self) -> FieldResult<impl Stream<Item = i32>> {
+        Err("error".into())
+    }
+}
+
+/// The messages of a client, which are built from the input so that most of them are valid.
+#[derive(Arbitrary, Debug)]
+enum ClientMessage {
+    ConnectionInit { payload: Option<String> },
+    Start { id: u8, query: String },
+    StartValues { id: u8, count: i32 },
+    Stop { id: u8 },
+    ConnectionTerminate,
+    Raw(Vec<u8>),
+}
+
+impl ClientMessage {
+    fn into_bytes(self) -> Vec<u8> {
+        let message = match self {
+            ClientMessage::ConnectionInit { payload } => {
+                let payload = payload.map(|payload| {
+                    serde_json::from_str::<serde_json::Value>(&payload)
+                        .unwrap_or_else(|_| payload.into())
+                });
+                serde_json::json!({ "type": "connection_init", "payload": payload })
+            }
+            ClientMessage::Start { id, query } => serde_json::json!({
+                "type": "start",
+                "id": id.to_string(),
+                "payload": { "query": query },
+            }),
+            ClientMessage::StartValues { id, count } => serde_json::json!({
+                "type": "start",
+                "id": id.to_string(),
+                "payload": {
+                    "query": "subscription($count: Int!) { values(count: $count) }",
+                    "variables": { "count": count },
+                },
+            }),
+            ClientMessage::Stop { id } => serde_json::json!({
+                "type": "stop",
+                "id": id.to_string(),
+            }),
+            ClientMessage::ConnectionTerminate => serde_json::json!({
+                "type": "connection_terminate",
+            }),
+            ClientMessage::Raw(bytes) => return bytes,
+        };
+        serde_json::to_vec(&message).unwrap()
+    }
+}
+
+fuzz_target!(|messages: Vec<ClientMessage>| {
+    let schema = Schema::new(QueryRoot, EmptyMutation, SubscriptionRoot);
+    let messages = futures::stream::iter(messages.into_iter().map(ClientMessage::into_bytes));
+    let replies = futures::executor::block_on(WebSocket::new(schema, messages).collect::<Vec<_>>());
+
+    for reply in replies {
+        serde_json::from_str::<serde_json::Value>(&reply).unwrap();
+    }
+});
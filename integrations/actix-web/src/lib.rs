@@ -8,14 +8,12 @@ mod subscription;
 use actix_web::dev::{HttpResponseBuilder, Payload, PayloadStream};
 use actix_web::http::StatusCode;
 use actix_web::{http, web, Error, FromRequest, HttpRequest, HttpResponse, Responder};
-use async_graphql::http::MultipartOptions;
+use async_graphql::http::{HealthStatus, MultipartOptions};
 use async_graphql::{ParseRequestError, Request, Response};
 use futures::channel::mpsc;
 use futures::future::Ready;
-use futures::io::ErrorKind;
-use futures::{Future, SinkExt, StreamExt, TryFutureExt, TryStreamExt};
+use futures::{Future, SinkExt, StreamExt, TryFutureExt};
 use http::Method;
-use std::io;
 use std::pin::Pin;
 pub use subscription::WSSubscription;
 
@@ -67,19 +65,16 @@ impl FromRequest for GQLRequest {
 
             Box::pin(async move {
                 Ok(GQLRequest(
-                    async_graphql::http::receive_body(
-                        content_type,
-                        rx.map_err(|err| io::Error::new(ErrorKind::Other, err))
-                            .into_async_read(),
-                        config,
-                    )
-                    .map_err(|err| match err {
-                        ParseRequestError::PayloadTooLarge => {
-                            actix_web::error::ErrorPayloadTooLarge(err)
-                        }
-                        _ => actix_web::error::ErrorBadRequest(err),
-                    })
-                    .await?,
+                    async_graphql::http::receive_body(content_type, rx, config)
+                        .map_err(|err| match err {
+                            ParseRequestError::PayloadTooLarge
+                            | ParseRequestError::PartTooLarge { .. }
+                            | ParseRequestError::TooManyFiles { .. } => {
+                                actix_web::error::ErrorPayloadTooLarge(err)
+                            }
+                            _ => actix_web::error::ErrorBadRequest(err),
+                        })
+                        .await?,
                 ))
             })
         }
@@ -100,9 +95,41 @@ impl Responder for GQLResponse {
     type Future = Ready<Result<HttpResponse, Error>>;
 
     fn respond_to(self, _req: &HttpRequest) -> Self::Future {
-        let mut res = HttpResponse::build(StatusCode::OK);
+        let mut res = HttpResponse::build(if self.0.is_request_error() {
+            StatusCode::BAD_REQUEST
+        } else {
+            StatusCode::OK
+        });
         res.content_type("application/json");
         add_cache_control(&mut res, &self.0);
+        add_schema_fingerprint(&mut res, &self.0);
+        add_cost_headers(&mut res, &self.0);
+        let res = res.body(serde_json::to_string(&self.0).unwrap());
+        futures::future::ok(res)
+    }
+}
+
+/// Responder for the [`HealthStatus`](../async_graphql/http/struct.HealthStatus.html) of a
+/// health check, with the status `503 Service Unavailable` if a check failed.
+pub struct HealthResponse(HealthStatus);
+
+impl From<HealthStatus> for HealthResponse {
+    fn from(status: HealthStatus) -> Self {
+        HealthResponse(status)
+    }
+}
+
+impl Responder for HealthResponse {
+    type Error = Error;
+    type Future = Ready<Result<HttpResponse, Error>>;
+
+    fn respond_to(self, _req: &HttpRequest) -> Self::Future {
+        let mut res = HttpResponse::build(if self.0.healthy {
+            StatusCode::OK
+        } else {
+            StatusCode::SERVICE_UNAVAILABLE
+        });
+        res.content_type("application/json");
         let res = res.body(serde_json::to_string(&self.0).unwrap());
         futures::future::ok(res)
     }
@@ -115,3 +142,15 @@ fn add_cache_control(builder: &mut HttpResponseBuilder, resp: &Response) {
         }
     }
 }
+
+fn add_schema_fingerprint(builder: &mut HttpResponseBuilder, resp: &Response) {
+    if let Some(fingerprint) = resp.fingerprint() {
+        builder.header("x-schema-fingerprint", fingerprint);
+    }
+}
+
+fn add_cost_headers(builder: &mut HttpResponseBuilder, resp: &Response) {
+    for (name, value) in resp.cost_headers() {
+        builder.header(name, value);
+    }
+}
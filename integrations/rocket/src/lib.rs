@@ -3,8 +3,10 @@
 #![warn(missing_docs)]
 #![forbid(unsafe_code)]
 
-use async_graphql::http::MultipartOptions;
+use async_graphql::http::{HealthStatus, MultipartOptions};
 use async_graphql::{resolver_utils::ObjectType, Schema, SubscriptionType, Variables};
+use bytes::Bytes;
+use futures::AsyncReadExt;
 use log::{error, info};
 use rocket::{
     data::{self, FromData},
@@ -15,7 +17,10 @@ use rocket::{
     response::{self, Responder, ResponseBuilder},
     Request, Response, State,
 };
-use std::{io::Cursor, sync::Arc};
+use std::{
+    io::{self, Cursor},
+    sync::Arc,
+};
 use tokio_util::compat::Tokio02AsyncReadCompatExt;
 use yansi::Paint;
 
@@ -247,9 +252,19 @@ impl FromData for GQLRequest {
 
         let limit = req.limits().get("graphql");
         let stream = data.open(limit.unwrap_or_else(|| 128.kibibytes()));
+        let body = futures::stream::try_unfold(stream.compat(), |mut stream| async move {
+            let mut buf = vec![0; 4096];
+            let size = stream.read(&mut buf).await?;
+            buf.truncate(size);
+            Ok::<_, io::Error>(if size == 0 {
+                None
+            } else {
+                Some((Bytes::from(buf), stream))
+            })
+        });
         let request = async_graphql::http::receive_body(
             req.headers().get_one("Content-Type"),
-            stream.compat(),
+            body,
             MultipartOptions::clone(&opts),
         )
         .await;
@@ -269,12 +284,43 @@ pub struct GQLResponse(pub async_graphql::Response);
 impl<'r> Responder<'r, 'static> for GQLResponse {
     fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
         let body = serde_json::to_string(&self.0).unwrap();
+        let status = if self.0.is_request_error() {
+            Status::BadRequest
+        } else {
+            Status::Ok
+        };
 
-        Response::build()
+        let mut builder = Response::build();
+        builder
             .header(ContentType::new("application", "json"))
-            .status(Status::Ok)
+            .status(status)
             .sized_body(body.len(), Cursor::new(body))
             .cache_control(&self.0)
+            .cost_headers(&self.0);
+        if let Some(fingerprint) = self.0.fingerprint() {
+            builder.header(Header::new("x-schema-fingerprint", fingerprint.to_string()));
+        }
+        builder.ok()
+    }
+}
+
+/// Wrapper around `async_graphql::http::HealthStatus` for implementing the trait
+/// `rocket::response::responder::Responder`, with the status `503 Service Unavailable` if a
+/// check failed.
+pub struct HealthResponse(pub HealthStatus);
+
+impl<'r> Responder<'r, 'static> for HealthResponse {
+    fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
+        let body = serde_json::to_string(&self.0).unwrap();
+
+        Response::build()
+            .header(ContentType::new("application", "json"))
+            .status(if self.0.healthy {
+                Status::Ok
+            } else {
+                Status::ServiceUnavailable
+            })
+            .sized_body(body.len(), Cursor::new(body))
             .ok()
     }
 }
@@ -283,6 +329,10 @@ impl<'r> Responder<'r, 'static> for GQLResponse {
 pub trait CacheControl {
     /// Add the `async-graphql::Response` cache control value as header to the Rocket response.
     fn cache_control(&mut self, resp: &async_graphql::Response) -> &mut Self;
+
+    /// Add the `async-graphql::Response` query cost and rate limit, if any, as the
+    /// `x-graphql-cost` and `x-ratelimit-*` headers to the Rocket response.
+    fn cost_headers(&mut self, resp: &async_graphql::Response) -> &mut Self;
 }
 
 impl<'r> CacheControl for ResponseBuilder<'r> {
@@ -294,4 +344,11 @@ impl<'r> CacheControl for ResponseBuilder<'r> {
         }
         self
     }
+
+    fn cost_headers(&mut self, resp: &async_graphql::Response) -> &mut ResponseBuilder<'r> {
+        for (name, value) in resp.cost_headers() {
+            self.header(Header::new(name, value));
+        }
+        self
+    }
 }
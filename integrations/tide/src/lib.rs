@@ -5,9 +5,12 @@
 #![allow(clippy::needless_doctest_main)]
 #![forbid(unsafe_code)]
 
-use async_graphql::http::MultipartOptions;
+use async_graphql::http::{HealthStatus, MultipartOptions};
 use async_graphql::{resolver_utils::ObjectType, Schema, SubscriptionType};
 use async_trait::async_trait;
+use bytes::Bytes;
+use futures::AsyncReadExt;
+use std::io;
 use std::str::FromStr;
 use tide::{
     http::{headers, Method},
@@ -108,7 +111,17 @@ impl<State: Clone + Send + Sync + 'static> RequestExt<State> for Request<State>
             let content_type = self
                 .header(&headers::CONTENT_TYPE)
                 .and_then(|values| values.get(0).map(|value| value.to_string()));
-            async_graphql::http::receive_body(content_type, self, opts)
+            let body = futures::stream::try_unfold(self, |mut body| async move {
+                let mut buf = vec![0; 4096];
+                let size = body.read(&mut buf).await?;
+                buf.truncate(size);
+                Ok::<_, io::Error>(if size == 0 {
+                    None
+                } else {
+                    Some((Bytes::from(buf), body))
+                })
+            });
+            async_graphql::http::receive_body(content_type, body, opts)
                 .await
                 .map_err(|err| tide::Error::new(StatusCode::BadRequest, err))
         }
@@ -118,16 +131,38 @@ impl<State: Clone + Send + Sync + 'static> RequestExt<State> for Request<State>
 /// Tide response extension
 ///
 pub trait ResponseExt: Sized {
-    /// Set body as the result of a GraphQL query.
+    /// Set body as the result of a GraphQL query, and the status to `400 Bad Request` if the
+    /// request was not executed because of its error.
     fn body_graphql(self, res: async_graphql::Response) -> tide::Result<Self>;
+
+    /// Set body as the status of a health check, and the status to `503 Service Unavailable`
+    /// if a check failed.
+    fn body_health(self, status: &HealthStatus) -> tide::Result<Self>;
 }
 
 impl ResponseExt for Response {
-    fn body_graphql(self, res: async_graphql::Response) -> tide::Result<Self> {
+    fn body_graphql(mut self, res: async_graphql::Response) -> tide::Result<Self> {
+        if res.is_request_error() {
+            self.set_status(StatusCode::BadRequest);
+        }
         let mut resp = add_cache_control(self, &res);
+        if let Some(fingerprint) = res.fingerprint() {
+            resp.insert_header("x-schema-fingerprint", fingerprint);
+        }
+        for (name, value) in res.cost_headers() {
+            resp.insert_header(name, value);
+        }
         resp.set_body(Body::from_json(&res)?);
         Ok(resp)
     }
+
+    fn body_health(mut self, status: &HealthStatus) -> tide::Result<Self> {
+        if !status.healthy {
+            self.set_status(StatusCode::ServiceUnavailable);
+        }
+        self.set_body(Body::from_json(status)?);
+        Ok(self)
+    }
 }
 
 fn add_cache_control(mut http_resp: Response, resp: &async_graphql::Response) -> Response {
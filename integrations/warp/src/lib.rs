@@ -5,15 +5,25 @@
 #![allow(clippy::needless_doctest_main)]
 #![forbid(unsafe_code)]
 
-use async_graphql::http::MultipartOptions;
+use async_graphql::http::{
+    graphiql_source_with_config, playground_source, GraphQLPlaygroundConfig, GraphiQLConfig,
+    HealthCheck, HealthStatus, MultipartOptions, OutMessage, Protocols, WebSocket,
+    WebSocketEncoding, WebSocketProtocol,
+};
 use async_graphql::{
-    resolver_utils::ObjectType, Data, FieldResult, Request, Schema, SubscriptionType,
+    resolver_utils::ObjectType, BatchRequest, BatchResponse, Data, FieldResult, PreviewFeatures,
+    Request, Schema, SubscriptionType, PREVIEW_HEADER,
 };
-use futures::{future, StreamExt, TryStreamExt};
+use futures::{future, Future, StreamExt, TryStreamExt};
+use hyper::body::HttpBody;
 use hyper::Method;
-use std::io::{self, ErrorKind};
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::hash::Hash;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use warp::filters::ws;
+use warp::http::{HeaderMap, StatusCode};
 use warp::reject::Reject;
 use warp::reply::Response;
 use warp::{Buf, Filter, Rejection, Reply};
@@ -35,6 +45,9 @@ impl Reject for BadRequest {}
 ///
 /// It outputs a tuple containing the `async_graphql::Schema` and `async_graphql::Request`.
 ///
+/// The preview features listed in the `X-GraphQL-Preview` header are inserted into the data of
+/// the request as [`PreviewFeatures`](../async_graphql/struct.PreviewFeatures.html).
+///
 /// # Examples
 ///
 /// *[Full Example](<https://github.com/async-graphql/examples/blob/master/warp/starwars/src/main.rs>)*
@@ -100,44 +113,307 @@ where
     Query: ObjectType + Send + Sync + 'static,
     Mutation: ObjectType + Send + Sync + 'static,
     Subscription: SubscriptionType + Send + Sync + 'static,
+{
+    graphql_opts_with_provider(move || schema.clone(), opts)
+}
+
+/// Similar to graphql, but the schema is obtained from `provider` for each request.
+///
+/// This allows the schema to be replaced while the server is running, for example when it is
+/// built from configuration, by sharing it behind a lock (or an `ArcSwap`) and cloning the
+/// current one in the provider. Cloning a schema is cheap.
+///
+/// # Examples
+///
+/// ```no_run
+/// use async_graphql::*;
+/// use async_graphql_warp::*;
+/// use warp::Filter;
+/// use std::convert::Infallible;
+/// use std::sync::{Arc, RwLock};
+///
+/// struct QueryRoot;
+///
+/// #[Object]
+/// impl QueryRoot {
+///     async fn value(&self) -> i32 {
+///         10
+///     }
+/// }
+///
+/// type MySchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let current = Arc::new(RwLock::new(Schema::new(QueryRoot, EmptyMutation, EmptySubscription)));
+///
+///     // Replace the schema at any time with `*current.write().unwrap() = new_schema;`
+///     let filter = async_graphql_warp::graphql_with_provider({
+///         let current = current.clone();
+///         move || current.read().unwrap().clone()
+///     })
+///     .and_then(|(schema, request): (MySchema, async_graphql::Request)| async move {
+///         Ok::<_, Infallible>(GQLResponse::from(schema.execute(request).await))
+///     });
+///     warp::serve(filter).run(([0, 0, 0, 0], 8000)).await;
+/// }
+/// ```
+pub fn graphql_with_provider<Query, Mutation, Subscription, F>(
+    provider: F,
+) -> impl Filter<
+    Extract = ((
+        Schema<Query, Mutation, Subscription>,
+        async_graphql::Request,
+    ),),
+    Error = Rejection,
+> + Clone
+where
+    Query: ObjectType + Send + Sync + 'static,
+    Mutation: ObjectType + Send + Sync + 'static,
+    Subscription: SubscriptionType + Send + Sync + 'static,
+    F: Fn() -> Schema<Query, Mutation, Subscription> + Send + Sync + Clone + 'static,
+{
+    graphql_opts_with_provider(provider, Default::default())
+}
+
+/// Similar to graphql_with_provider, but you can set the options `async_graphql::MultipartOptions`.
+pub fn graphql_opts_with_provider<Query, Mutation, Subscription, F>(
+    provider: F,
+    opts: MultipartOptions,
+) -> impl Filter<
+    Extract = ((
+        Schema<Query, Mutation, Subscription>,
+        async_graphql::Request,
+    ),),
+    Error = Rejection,
+> + Clone
+where
+    Query: ObjectType + Send + Sync + 'static,
+    Mutation: ObjectType + Send + Sync + 'static,
+    Subscription: SubscriptionType + Send + Sync + 'static,
+    F: Fn() -> Schema<Query, Mutation, Subscription> + Send + Sync + Clone + 'static,
+{
+    graphql_opts_with_schema_filter(warp::any().map(provider), opts)
+}
+
+/// Similar to graphql_opts, but the schema is extracted by `schema_filter` for each request.
+///
+/// Combined with [`select_schema`](fn.select_schema.html) this serves several schemas from one
+/// route, for example one per tenant.
+///
+/// This is specific to warp because its filters capture the schema when the route is built. The
+/// actix-web, tide and rocket integrations take the schema in the request handler
+/// (`GQLRequest::execute`, `async_graphql_tide::graphql`, `WSSubscription::new`), so a handler
+/// there can look the schema up in a map by path or tenant itself.
+///
+/// # Examples
+///
+/// ```no_run
+/// use async_graphql::*;
+/// use async_graphql_warp::*;
+/// use warp::Filter;
+/// use std::collections::HashMap;
+/// use std::convert::Infallible;
+///
+/// struct QueryRoot;
+///
+/// #[Object]
+/// impl QueryRoot {
+///     async fn value(&self) -> i32 {
+///         10
+///     }
+/// }
+///
+/// type MySchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let mut schemas = HashMap::new();
+///     schemas.insert("a".to_string(), Schema::new(QueryRoot, EmptyMutation, EmptySubscription));
+///     schemas.insert("b".to_string(), Schema::new(QueryRoot, EmptyMutation, EmptySubscription));
+///
+///     // Serves `/a` and `/b`
+///     let filter = async_graphql_warp::graphql_opts_with_schema_filter(
+///         select_schema(warp::path::param::<String>(), schemas),
+///         Default::default(),
+///     )
+///     .and_then(|(schema, request): (MySchema, async_graphql::Request)| async move {
+///         Ok::<_, Infallible>(GQLResponse::from(schema.execute(request).await))
+///     });
+///     warp::serve(filter).run(([0, 0, 0, 0], 8000)).await;
+/// }
+/// ```
+pub fn graphql_opts_with_schema_filter<Query, Mutation, Subscription, S>(
+    schema_filter: S,
+    opts: MultipartOptions,
+) -> impl Filter<
+    Extract = ((
+        Schema<Query, Mutation, Subscription>,
+        async_graphql::Request,
+    ),),
+    Error = Rejection,
+> + Clone
+where
+    Query: ObjectType + Send + Sync + 'static,
+    Mutation: ObjectType + Send + Sync + 'static,
+    Subscription: SubscriptionType + Send + Sync + 'static,
+    S: Filter<Extract = (Schema<Query, Mutation, Subscription>,), Error = Rejection>
+        + Send
+        + Sync
+        + Clone
+        + 'static,
+{
+    graphql_batch_opts_with_schema_filter(schema_filter, opts).and_then(
+        |(schema, batch): (Schema<Query, Mutation, Subscription>, BatchRequest)| {
+            future::ready(
+                batch
+                    .into_single()
+                    .map(|request| (schema, request))
+                    .map_err(|err| warp::reject::custom(BadRequest(err.into()))),
+            )
+        },
+    )
+}
+
+/// GraphQL batch request filter
+///
+/// Similar to graphql, but the body may also be an array of requests, as sent by the batch link
+/// of Apollo Client. Reply with a [`GQLBatchResponse`](struct.GQLBatchResponse.html), which is
+/// an array of responses for an array of requests.
+///
+/// # Examples
+///
+/// ```no_run
+/// use async_graphql::*;
+/// use async_graphql_warp::*;
+/// use warp::Filter;
+/// use std::convert::Infallible;
+///
+/// struct QueryRoot;
+///
+/// #[Object]
+/// impl QueryRoot {
+///     async fn value(&self) -> i32 {
+///         10
+///     }
+/// }
+///
+/// type MySchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let schema = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
+///     let filter = async_graphql_warp::graphql_batch(schema).and_then(
+///         |(schema, batch): (MySchema, BatchRequest)| async move {
+///             Ok::<_, Infallible>(GQLBatchResponse::from(schema.execute_batch(batch).await))
+///         },
+///     );
+///     warp::serve(filter).run(([0, 0, 0, 0], 8000)).await;
+/// }
+/// ```
+pub fn graphql_batch<Query, Mutation, Subscription>(
+    schema: Schema<Query, Mutation, Subscription>,
+) -> impl Filter<Extract = ((Schema<Query, Mutation, Subscription>, BatchRequest),), Error = Rejection>
+       + Clone
+where
+    Query: ObjectType + Send + Sync + 'static,
+    Mutation: ObjectType + Send + Sync + 'static,
+    Subscription: SubscriptionType + Send + Sync + 'static,
+{
+    graphql_batch_opts(schema, Default::default())
+}
+
+/// Similar to graphql_batch, but you can set the options `async_graphql::MultipartOptions`.
+pub fn graphql_batch_opts<Query, Mutation, Subscription>(
+    schema: Schema<Query, Mutation, Subscription>,
+    opts: MultipartOptions,
+) -> impl Filter<Extract = ((Schema<Query, Mutation, Subscription>, BatchRequest),), Error = Rejection>
+       + Clone
+where
+    Query: ObjectType + Send + Sync + 'static,
+    Mutation: ObjectType + Send + Sync + 'static,
+    Subscription: SubscriptionType + Send + Sync + 'static,
+{
+    graphql_batch_opts_with_schema_filter(warp::any().map(move || schema.clone()), opts)
+}
+
+/// Similar to graphql_batch_opts, but the schema is extracted by `schema_filter` for each
+/// request, see [`graphql_opts_with_schema_filter`](fn.graphql_opts_with_schema_filter.html).
+pub fn graphql_batch_opts_with_schema_filter<Query, Mutation, Subscription, S>(
+    schema_filter: S,
+    opts: MultipartOptions,
+) -> impl Filter<Extract = ((Schema<Query, Mutation, Subscription>, BatchRequest),), Error = Rejection>
+       + Clone
+where
+    Query: ObjectType + Send + Sync + 'static,
+    Mutation: ObjectType + Send + Sync + 'static,
+    Subscription: SubscriptionType + Send + Sync + 'static,
+    S: Filter<Extract = (Schema<Query, Mutation, Subscription>,), Error = Rejection>
+        + Send
+        + Sync
+        + Clone
+        + 'static,
 {
     let opts = Arc::new(opts);
     warp::any()
         .and(warp::method())
         .and(warp::query::raw().or(warp::any().map(String::new)).unify())
         .and(warp::header::optional::<String>("content-type"))
+        .and(warp::header::optional::<String>(PREVIEW_HEADER))
         .and(warp::body::stream())
         .and(warp::any().map(move || opts.clone()))
-        .and(warp::any().map(move || schema.clone()))
+        .and(schema_filter)
         .and_then(
             |method,
              query: String,
              content_type,
+             previews: Option<String>,
              body,
              opts: Arc<MultipartOptions>,
              schema| async move {
-                if method == Method::GET {
+                let mut batch = if method == Method::GET {
                     let request: Request = serde_urlencoded::from_str(&query)
                         .map_err(|err| warp::reject::custom(BadRequest(err.into())))?;
-                    Ok::<_, Rejection>((schema, request))
+                    BatchRequest::Single(request)
                 } else {
-                    let request = async_graphql::http::receive_body(
+                    async_graphql::http::receive_batch_body(
                         content_type,
-                        futures::TryStreamExt::map_err(body, |err| io::Error::new(ErrorKind::Other, err))
-                            .map_ok(|mut buf| Buf::to_bytes(&mut buf))
-                            .into_async_read(),
+                        body.map_ok(|mut buf| Buf::to_bytes(&mut buf)),
                         MultipartOptions::clone(&opts),
                     )
                     .await
-                    .map_err(|err| warp::reject::custom(BadRequest(err.into())))?;
-                    Ok::<_, Rejection>((schema, request))
+                    .map_err(|err| warp::reject::custom(BadRequest(err.into())))?
+                };
+                if let Some(previews) = previews {
+                    add_preview_features(&mut batch, PreviewFeatures::from_header(&previews));
                 }
+                Ok::<_, Rejection>((schema, batch))
             },
         )
 }
 
+fn add_preview_features(batch: &mut BatchRequest, previews: PreviewFeatures) {
+    match batch {
+        BatchRequest::Single(request) => request.data.insert(previews),
+        BatchRequest::Batch(requests) => {
+            for request in requests {
+                request.data.insert(previews.clone());
+            }
+        }
+    }
+}
+
 /// GraphQL subscription filter
 ///
+/// The protocol is negotiated with the `Sec-WebSocket-Protocol` header, see
+/// [`Protocols`](../async_graphql/http/enum.Protocols.html): the `graphql-transport-ws` protocol
+/// of graphql-ws clients, or the legacy `graphql-ws` protocol of subscriptions-transport-ws
+/// clients, which is also used when the client does not ask for one.
+///
+/// Clients can ask for binary messages with the subprotocols of
+/// [`WebSocketEncoding`](../async_graphql/http/enum.WebSocketEncoding.html) when the `msgpack` or
+/// `cbor` feature is enabled.
+///
 /// # Examples
 ///
 /// ```no_run
@@ -195,34 +471,619 @@ where
     Mutation: ObjectType + Sync + Send + 'static,
     Subscription: SubscriptionType + Send + Sync + 'static,
     F: FnOnce(serde_json::Value) -> FieldResult<Data> + Send + Sync + Clone + 'static,
+{
+    graphql_subscription_with_schema_filter(warp::any().map(move || schema.clone()), initializer)
+}
+
+/// GraphQL subscription filter
+///
+/// Similar to graphql_subscription_with_data, but the function that converts the init payload
+/// to data is asynchronous, for example to validate a token with a remote service, see
+/// [`WebSocket::async_initializer`](../async_graphql/http/struct.WebSocket.html#method.async_initializer).
+///
+/// ```no_run
+/// use async_graphql::*;
+/// use async_graphql_warp::*;
+///
+/// struct UserId(String);
+///
+/// async fn verify_token(token: &str) -> FieldResult<String> {
+///     // Ask the auth service.
+/// #   Ok(token.to_string())
+/// }
+///
+/// # struct QueryRoot;
+/// # #[Object]
+/// # impl QueryRoot {}
+/// # #[tokio::main]
+/// # async fn main() {
+/// let schema = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
+/// let filter = graphql_subscription_with_async_data(schema, |payload: serde_json::Value| async move {
+///     let token = payload["token"].as_str().ok_or("missing token")?;
+///     let mut data = Data::default();
+///     data.insert(UserId(verify_token(token).await?));
+///     Ok::<_, FieldError>(data)
+/// });
+/// warp::serve(filter).run(([0, 0, 0, 0], 8000)).await;
+/// # }
+/// ```
+pub fn graphql_subscription_with_async_data<Query, Mutation, Subscription, F, Fut>(
+    schema: Schema<Query, Mutation, Subscription>,
+    initializer: F,
+) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone
+where
+    Query: ObjectType + Sync + Send + 'static,
+    Mutation: ObjectType + Sync + Send + 'static,
+    Subscription: SubscriptionType + Send + Sync + 'static,
+    F: FnOnce(serde_json::Value) -> Fut + Send + Sync + Clone + 'static,
+    Fut: Future<Output = FieldResult<Data>> + Send + 'static,
+{
+    subscriptions_enabled(schema.has_subscriptions())
+        .and(warp::ws())
+        .and(websocket_protocol())
+        .map(
+            move |ws: ws::Ws, protocol: (Protocols, WebSocketEncoding)| {
+                let connection =
+                    WebSocketProtocol::new(schema.clone()).async_initializer(initializer.clone());
+                subscription_reply(ws, connection, protocol)
+            },
+        )
+}
+
+/// GraphQL subscription filter
+///
+/// Similar to graphql_subscription_with_data, but the schema is extracted by `schema_filter` for
+/// each connection, see [`graphql_opts_with_schema_filter`](fn.graphql_opts_with_schema_filter.html).
+///
+/// The subscription filters reject the request as not found if the schema has no subscriptions,
+/// see `Schema::has_subscriptions`.
+pub fn graphql_subscription_with_schema_filter<Query, Mutation, Subscription, S, F>(
+    schema_filter: S,
+    initializer: Option<F>,
+) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone
+where
+    Query: ObjectType + Sync + Send + 'static,
+    Mutation: ObjectType + Sync + Send + 'static,
+    Subscription: SubscriptionType + Send + Sync + 'static,
+    S: Filter<Extract = (Schema<Query, Mutation, Subscription>,), Error = Rejection>
+        + Send
+        + Sync
+        + Clone
+        + 'static,
+    F: FnOnce(serde_json::Value) -> FieldResult<Data> + Send + Sync + Clone + 'static,
 {
     warp::any()
         .and(warp::ws())
-        .and(warp::any().map(move || schema.clone()))
+        .and(
+            schema_filter.and_then(|schema: Schema<Query, Mutation, Subscription>| {
+                future::ready(if schema.has_subscriptions() {
+                    Ok(schema)
+                } else {
+                    Err(warp::reject::not_found())
+                })
+            }),
+        )
         .and(warp::any().map(move || initializer.clone()))
+        .and(websocket_protocol())
         .map(
-            |ws: ws::Ws, schema: Schema<Query, Mutation, Subscription>, initializer: Option<F>| {
-                ws.on_upgrade(move |websocket| {
-                    let (ws_sender, ws_receiver) = websocket.split();
-
-                    async move {
-                        let _ = async_graphql::http::WebSocket::with_data(
-                            schema,
-                            ws_receiver
-                                .take_while(|msg| future::ready(msg.is_ok()))
-                                .map(Result::unwrap)
-                                .map(ws::Message::into_bytes),
-                            initializer,
+            |ws: ws::Ws,
+             schema: Schema<Query, Mutation, Subscription>,
+             initializer: Option<F>,
+             protocol: (Protocols, WebSocketEncoding)| {
+                subscription_reply(
+                    ws,
+                    WebSocketProtocol::with_data(schema, initializer),
+                    protocol,
+                )
+            },
+        )
+}
+
+/// GraphQL subscription filter
+///
+/// Similar to graphql_subscription_with_data, but `upgrade` builds the initial data of each
+/// connection from the headers and the query parameters of the HTTP upgrade request, or rejects
+/// it. Browser clients cannot set the headers of a websocket, so they often pass a token in the
+/// URL instead. The data returned by `initializer` is added to it.
+///
+/// ```no_run
+/// use async_graphql::*;
+/// use async_graphql_warp::*;
+/// use warp::Filter;
+///
+/// struct Token(String);
+///
+/// # struct QueryRoot;
+/// # #[Object]
+/// # impl QueryRoot {}
+/// # #[tokio::main]
+/// # async fn main() {
+/// let schema = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
+/// let filter = graphql_subscription_with_upgrade_data::<_, _, _, _, fn(serde_json::Value) -> FieldResult<Data>>(
+///     schema,
+///     |_headers, query| {
+///         let token = query.get("token").ok_or_else(warp::reject::not_found)?;
+///         let mut data = Data::default();
+///         data.insert(Token(token.clone()));
+///         Ok(data)
+///     },
+///     None,
+/// );
+/// warp::serve(filter).run(([0, 0, 0, 0], 8000)).await;
+/// # }
+/// ```
+pub fn graphql_subscription_with_upgrade_data<Query, Mutation, Subscription, U, F>(
+    schema: Schema<Query, Mutation, Subscription>,
+    upgrade: U,
+    initializer: Option<F>,
+) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone
+where
+    Query: ObjectType + Sync + Send + 'static,
+    Mutation: ObjectType + Sync + Send + 'static,
+    Subscription: SubscriptionType + Send + Sync + 'static,
+    U: Fn(&HeaderMap, &HashMap<String, String>) -> Result<Data, Rejection>
+        + Send
+        + Sync
+        + Clone
+        + 'static,
+    F: FnOnce(serde_json::Value) -> FieldResult<Data> + Send + Sync + Clone + 'static,
+{
+    subscriptions_enabled(schema.has_subscriptions())
+        .and(warp::ws())
+        .and(warp::header::headers_cloned())
+        .and(warp::query::<HashMap<String, String>>())
+        .and_then(
+            move |ws: ws::Ws, headers: HeaderMap, query: HashMap<String, String>| {
+                future::ready(upgrade(&headers, &query).map(|data| (ws, data)))
+            },
+        )
+        .untuple_one()
+        .and(websocket_protocol())
+        .map(
+            move |ws: ws::Ws, data: Data, protocol: (Protocols, WebSocketEncoding)| {
+                let connection = WebSocketProtocol::with_data(schema.clone(), initializer.clone())
+                    .connection_data(data);
+                subscription_reply(ws, connection, protocol)
+            },
+        )
+}
+
+/// Rejects the request as not found if the schema has no subscriptions.
+fn subscriptions_enabled(
+    has_subscriptions: bool,
+) -> impl Filter<Extract = (), Error = Rejection> + Clone {
+    warp::any()
+        .and_then(move || {
+            future::ready(if has_subscriptions {
+                Ok(())
+            } else {
+                Err(warp::reject::not_found())
+            })
+        })
+        .untuple_one()
+}
+
+/// Negotiates the protocol and the encoding of the messages with the `Sec-WebSocket-Protocol`
+/// header, the legacy protocol with JSON if the client does not ask for a supported one.
+fn websocket_protocol(
+) -> impl Filter<Extract = ((Protocols, WebSocketEncoding),), Error = Rejection> + Clone {
+    warp::header::optional::<String>("sec-websocket-protocol").map(|protocols: Option<String>| {
+        protocols
+            .as_deref()
+            .and_then(Protocols::negotiate)
+            .unwrap_or_default()
+    })
+}
+
+fn subscription_reply<Query, Mutation, Subscription, F>(
+    ws: ws::Ws,
+    connection: WebSocketProtocol<F, Query, Mutation, Subscription>,
+    (protocol, encoding): (Protocols, WebSocketEncoding),
+) -> impl Reply
+where
+    Query: ObjectType + Sync + Send + 'static,
+    Mutation: ObjectType + Sync + Send + 'static,
+    Subscription: SubscriptionType + Send + Sync + 'static,
+    F: FnOnce(serde_json::Value) -> FieldResult<Data> + Send + Sync + 'static,
+{
+    let reply = ws.on_upgrade(move |websocket| {
+        let (ws_sender, ws_receiver) = websocket.split();
+
+        async move {
+            let _ = WebSocket::from_protocol(
+                connection,
+                ws_receiver
+                    .take_while(|msg| future::ready(msg.is_ok()))
+                    .map(Result::unwrap)
+                    .map(ws::Message::into_bytes),
+            )
+            .encoding(encoding)
+            .protocol(protocol)
+            .into_messages()
+            .map(|message| match message {
+                OutMessage::Text(text) => ws::Message::text(text),
+                OutMessage::Binary(bytes) => ws::Message::binary(bytes),
+                OutMessage::Close => ws::Message::close(),
+                OutMessage::CloseWithCode { code, reason } => ws::Message::close_with(code, reason),
+            })
+            .map(Ok)
+            .forward(ws_sender)
+            .await;
+        }
+    });
+    warp::reply::with_header(
+        reply,
+        "Sec-WebSocket-Protocol",
+        protocol.sec_websocket_protocol(encoding),
+    )
+}
+
+/// GraphQL subscription filter over Server-Sent Events
+///
+/// Serves the requests with the `Accept: text/event-stream` header in the distinct connections
+/// mode of the [GraphQL over SSE protocol](https://github.com/enisdenjo/graphql-sse/blob/master/PROTOCOL.md),
+/// for clients behind proxies that block websockets. Every response of the operation is sent as
+/// a `next` event, then a `complete` event ends the stream. Other requests are rejected, so it
+/// is usually combined with [`graphql`](fn.graphql.html) with `or`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use async_graphql::*;
+/// use async_graphql_warp::*;
+/// use warp::Filter;
+/// use futures::{Stream, StreamExt};
+/// use std::convert::Infallible;
+/// use std::time::Duration;
+///
+/// struct QueryRoot;
+///
+/// #[Object]
+/// impl QueryRoot {
+///     async fn value(&self) -> i32 {
+///         10
+///     }
+/// }
+///
+/// struct SubscriptionRoot;
+///
+/// #[Subscription]
+/// impl SubscriptionRoot {
+///     async fn tick(&self) -> impl Stream<Item = String> {
+///         tokio::time::interval(Duration::from_secs(1)).map(|n| format!("{}", n.elapsed().as_secs_f32()))
+///     }
+/// }
+///
+/// type MySchema = Schema<QueryRoot, EmptyMutation, SubscriptionRoot>;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let schema = Schema::new(QueryRoot, EmptyMutation, SubscriptionRoot);
+///     let filter = async_graphql_warp::graphql_subscription_sse(schema.clone()).or(
+///         async_graphql_warp::graphql(schema).and_then(
+///             |(schema, request): (MySchema, async_graphql::Request)| async move {
+///                 Ok::<_, Infallible>(GQLResponse::from(schema.execute(request).await))
+///             },
+///         ),
+///     );
+///     warp::serve(filter).run(([0, 0, 0, 0], 8000)).await;
+/// }
+/// ```
+pub fn graphql_subscription_sse<Query, Mutation, Subscription>(
+    schema: Schema<Query, Mutation, Subscription>,
+) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone
+where
+    Query: ObjectType + Send + Sync + 'static,
+    Mutation: ObjectType + Send + Sync + 'static,
+    Subscription: SubscriptionType + Send + Sync + 'static,
+{
+    warp::header::<String>("accept")
+        .and_then(|accept: String| async move {
+            if accept.contains("text/event-stream") {
+                Ok(())
+            } else {
+                Err(warp::reject::not_found())
+            }
+        })
+        .untuple_one()
+        .and(graphql(schema))
+        .map(
+            |(schema, request): (
+                Schema<Query, Mutation, Subscription>,
+                async_graphql::Request,
+            )| {
+                let events = schema
+                    .execute_stream(request)
+                    .map(|response| {
+                        (
+                            warp::sse::event("next"),
+                            warp::sse::data(serde_json::to_string(&response).unwrap()),
                         )
-                        .map(ws::Message::text)
-                        .map(Ok)
-                        .forward(ws_sender)
-                        .await;
-                    }
-                })
+                    })
+                    .chain(futures::stream::once(async {
+                        (warp::sse::event("complete"), warp::sse::data(String::new()))
+                    }))
+                    .map(Ok::<_, Infallible>);
+                warp::sse::reply(warp::sse::keep_alive().stream(events))
             },
         )
-        .map(|reply| warp::reply::with_header(reply, "Sec-WebSocket-Protocol", "graphql-ws"))
+}
+
+/// Extracts one of `schemas` by the key extracted by `key_filter`, for example a path segment or
+/// a header, and rejects the request as not found if there is no schema for the key.
+pub fn select_schema<Query, Mutation, Subscription, K, KF>(
+    key_filter: KF,
+    schemas: HashMap<K, Schema<Query, Mutation, Subscription>>,
+) -> impl Filter<Extract = (Schema<Query, Mutation, Subscription>,), Error = Rejection> + Clone
+where
+    Query: ObjectType + Send + Sync + 'static,
+    Mutation: ObjectType + Send + Sync + 'static,
+    Subscription: SubscriptionType + Send + Sync + 'static,
+    K: Eq + Hash + Send + Sync + 'static,
+    KF: Filter<Extract = (K,), Error = Rejection> + Send + Sync + Clone + 'static,
+{
+    let schemas = Arc::new(schemas);
+    key_filter.and_then(move |key: K| {
+        let schema = schemas.get(&key).cloned();
+        async move { schema.ok_or_else(warp::reject::not_found) }
+    })
+}
+
+/// The metrics of a GraphQL request, recorded by [`with_metrics`](fn.with_metrics.html).
+#[derive(Debug, Clone)]
+pub struct RequestMetrics {
+    /// The name of the executed operation, if the request has one.
+    pub operation_name: Option<String>,
+    /// The time from the start of the request, including reading the body, to the reply.
+    pub duration: Duration,
+    /// The status code of the reply.
+    pub status: StatusCode,
+    /// The size of the body of the reply in bytes.
+    pub response_size: usize,
+    /// Whether the response has errors.
+    pub has_errors: bool,
+}
+
+/// Records the metrics of the GraphQL requests, for example as counters and histograms of a
+/// metrics library.
+///
+/// It is implemented for closures taking a `&RequestMetrics`.
+pub trait MetricsRecorder: Send + Sync + 'static {
+    /// Record the metrics of a request.
+    fn record(&self, metrics: &RequestMetrics);
+}
+
+impl<F> MetricsRecorder for F
+where
+    F: Fn(&RequestMetrics) + Send + Sync + 'static,
+{
+    fn record(&self, metrics: &RequestMetrics) {
+        self(metrics)
+    }
+}
+
+/// Executes the requests extracted by `filter` and replies with a
+/// [`GQLResponse`](struct.GQLResponse.html), recording the [metrics](struct.RequestMetrics.html)
+/// of each request with `recorder`.
+///
+/// Unlike a generic warp middleware, the metrics are labeled with the operation name of the
+/// request. Requests rejected by `filter` are not recorded.
+///
+/// # Examples
+///
+/// ```no_run
+/// use async_graphql::*;
+/// use async_graphql_warp::*;
+/// use warp::Filter;
+///
+/// struct QueryRoot;
+///
+/// #[Object]
+/// impl QueryRoot {
+///     async fn value(&self) -> i32 {
+///         10
+///     }
+/// }
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let schema = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
+///     let filter = with_metrics(
+///         async_graphql_warp::graphql(schema),
+///         |metrics: &RequestMetrics| {
+///             println!(
+///                 "{} {} {:?} {} bytes",
+///                 metrics.operation_name.as_deref().unwrap_or("-"),
+///                 metrics.status,
+///                 metrics.duration,
+///                 metrics.response_size,
+///             );
+///         },
+///     );
+///     warp::serve(filter).run(([0, 0, 0, 0], 8000)).await;
+/// }
+/// ```
+pub fn with_metrics<Query, Mutation, Subscription, S, R>(
+    filter: S,
+    recorder: R,
+) -> impl Filter<Extract = (Response,), Error = Rejection> + Clone
+where
+    Query: ObjectType + Send + Sync + 'static,
+    Mutation: ObjectType + Send + Sync + 'static,
+    Subscription: SubscriptionType + Send + Sync + 'static,
+    S: Filter<
+            Extract = ((
+                Schema<Query, Mutation, Subscription>,
+                async_graphql::Request,
+            ),),
+            Error = Rejection,
+        > + Send
+        + Sync
+        + Clone
+        + 'static,
+    R: MetricsRecorder,
+{
+    let recorder = Arc::new(recorder);
+    warp::any()
+        .map(Instant::now)
+        .and(filter)
+        .and_then(move |start, (schema, request)| {
+            let recorder = recorder.clone();
+            async move {
+                Ok::<_, Infallible>(execute_with_metrics(start, schema, request, &*recorder).await)
+            }
+        })
+}
+
+async fn execute_with_metrics<Query, Mutation, Subscription>(
+    start: Instant,
+    schema: Schema<Query, Mutation, Subscription>,
+    request: async_graphql::Request,
+    recorder: &dyn MetricsRecorder,
+) -> Response
+where
+    Query: ObjectType + Send + Sync + 'static,
+    Mutation: ObjectType + Send + Sync + 'static,
+    Subscription: SubscriptionType + Send + Sync + 'static,
+{
+    let operation_name = request.operation_name.clone();
+    let resp = schema.execute(request).await;
+    let has_errors = resp.is_err();
+    let http_resp = GQLResponse(resp).into_response();
+    recorder.record(&RequestMetrics {
+        operation_name,
+        duration: start.elapsed(),
+        status: http_resp.status(),
+        response_size: http_resp.body().size_hint().exact().unwrap_or_default() as usize,
+        has_errors,
+    });
+    http_resp
+}
+
+/// Health check filter
+///
+/// Serves `GET /live` with the [liveness](../async_graphql/http/struct.HealthCheck.html#method.liveness)
+/// and `GET /ready` with the [readiness](../async_graphql/http/struct.HealthCheck.html#method.readiness)
+/// of the server as JSON, with the status `503 Service Unavailable` if a check failed.
+///
+/// # Examples
+///
+/// ```no_run
+/// use async_graphql::*;
+/// use async_graphql::http::HealthCheck;
+/// use warp::Filter;
+///
+/// struct QueryRoot;
+///
+/// #[Object]
+/// impl QueryRoot {
+///     async fn value(&self) -> i32 {
+///         10
+///     }
+/// }
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let schema = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
+///     let health = HealthCheck::new().max_subscriptions(1000);
+///     let filter = warp::path("health").and(async_graphql_warp::health(schema, health));
+///     warp::serve(filter).run(([0, 0, 0, 0], 8000)).await;
+/// }
+/// ```
+pub fn health<Query, Mutation, Subscription>(
+    schema: Schema<Query, Mutation, Subscription>,
+    health: HealthCheck,
+) -> impl Filter<Extract = (Response,), Error = Rejection> + Clone
+where
+    Query: ObjectType + Send + Sync + 'static,
+    Mutation: ObjectType + Send + Sync + 'static,
+    Subscription: SubscriptionType + Send + Sync + 'static,
+{
+    let live = warp::path("live")
+        .and(warp::path::end())
+        .and(warp::get())
+        .map({
+            let schema = schema.clone();
+            let health = health.clone();
+            move || health_reply(&health.liveness(&schema))
+        });
+    let ready = warp::path("ready")
+        .and(warp::path::end())
+        .and(warp::get())
+        .and_then(move || {
+            let schema = schema.clone();
+            let health = health.clone();
+            async move { Ok::<_, Infallible>(health_reply(&health.readiness(&schema).await)) }
+        });
+    live.or(ready).unify()
+}
+
+fn health_reply(status: &HealthStatus) -> Response {
+    let code = if status.healthy {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    warp::reply::with_status(warp::reply::json(status), code).into_response()
+}
+
+/// GraphQL Playground filter
+///
+/// Serves `GET` with the page of [`playground_source`](../async_graphql/http/fn.playground_source.html)
+/// for `config`, as `text/html; charset=utf-8`.
+///
+/// The scripts of the page are allowed by a nonce that is generated for each response, which is
+/// sent in a `Content-Security-Policy` header that only allows those scripts. Any nonce set in
+/// `config` is replaced.
+///
+/// # Examples
+///
+/// ```no_run
+/// use async_graphql::http::GraphQLPlaygroundConfig;
+/// use warp::Filter;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let filter = warp::path("playground").and(async_graphql_warp::graphql_playground(
+///         GraphQLPlaygroundConfig::new("/").subscription_endpoint("/"),
+///     ));
+///     warp::serve(filter).run(([0, 0, 0, 0], 8000)).await;
+/// }
+/// ```
+pub fn graphql_playground(
+    config: GraphQLPlaygroundConfig<'_>,
+) -> impl Filter<Extract = (Response,), Error = Rejection> + Clone {
+    ide_filter(playground_source(config.nonce(NONCE_PLACEHOLDER)))
+}
+
+/// GraphIQL filter
+///
+/// Serves `GET` with the page of
+/// [`graphiql_source_with_config`](../async_graphql/http/fn.graphiql_source_with_config.html) for
+/// `config`, like [`graphql_playground`](fn.graphql_playground.html).
+pub fn graphiql(
+    config: GraphiQLConfig<'_>,
+) -> impl Filter<Extract = (Response,), Error = Rejection> + Clone {
+    ide_filter(graphiql_source_with_config(config.nonce(NONCE_PLACEHOLDER)))
+}
+
+const NONCE_PLACEHOLDER: &str = "ASYNC_GRAPHQL_NONCE";
+
+fn ide_filter(source: String) -> impl Filter<Extract = (Response,), Error = Rejection> + Clone {
+    let source: Arc<str> = source.into();
+    warp::path::end().and(warp::get()).map(move || {
+        let nonce = uuid::Uuid::new_v4().to_simple().to_string();
+        let mut resp = warp::reply::html(source.replace(NONCE_PLACEHOLDER, &nonce)).into_response();
+        if let Ok(value) = format!(
+            "script-src 'nonce-{}' 'strict-dynamic' 'unsafe-inline' https: http:; object-src 'none'; base-uri 'none'",
+            nonce
+        )
+        .parse()
+        {
+            resp.headers_mut().insert("content-security-policy", value);
+        }
+        resp
+    })
 }
 
 /// GraphQL reply
@@ -244,6 +1105,24 @@ fn add_cache_control(http_resp: &mut Response, resp: &async_graphql::Response) {
     }
 }
 
+fn add_schema_fingerprint(http_resp: &mut Response, resp: &async_graphql::Response) {
+    if let Some(fingerprint) = resp.fingerprint() {
+        if let Ok(value) = fingerprint.parse() {
+            http_resp
+                .headers_mut()
+                .insert("x-schema-fingerprint", value);
+        }
+    }
+}
+
+fn add_cost_headers(http_resp: &mut Response, headers: Vec<(&'static str, String)>) {
+    for (name, value) in headers {
+        if let Ok(value) = value.parse() {
+            http_resp.headers_mut().insert(name, value);
+        }
+    }
+}
+
 impl Reply for GQLResponse {
     fn into_response(self) -> Response {
         let mut resp = warp::reply::with_header(
@@ -252,7 +1131,52 @@ impl Reply for GQLResponse {
             "application/json",
         )
         .into_response();
+        if self.0.is_request_error() {
+            *resp.status_mut() = StatusCode::BAD_REQUEST;
+        }
         add_cache_control(&mut resp, &self.0);
+        add_schema_fingerprint(&mut resp, &self.0);
+        add_cost_headers(&mut resp, self.0.cost_headers());
         resp
     }
 }
+
+/// GraphQL reply for batch requests
+pub struct GQLBatchResponse(BatchResponse);
+
+impl From<BatchResponse> for GQLBatchResponse {
+    fn from(resp: BatchResponse) -> Self {
+        GQLBatchResponse(resp)
+    }
+}
+
+impl Reply for GQLBatchResponse {
+    fn into_response(self) -> Response {
+        let cache_control = self.0.cache_control();
+        let is_ok = self.0.is_ok();
+        let cost_headers = self.0.cost_headers();
+        match self.0 {
+            BatchResponse::Single(resp) => GQLResponse(resp).into_response(),
+            BatchResponse::Batch(resps) => {
+                let mut resp = warp::reply::with_header(
+                    warp::reply::json(&resps),
+                    "content-type",
+                    "application/json",
+                )
+                .into_response();
+                if is_ok {
+                    if let Some(cache_control) = cache_control.value() {
+                        if let Ok(value) = cache_control.parse() {
+                            resp.headers_mut().insert("cache-control", value);
+                        }
+                    }
+                }
+                if let Some(first) = resps.first() {
+                    add_schema_fingerprint(&mut resp, first);
+                }
+                add_cost_headers(&mut resp, cost_headers);
+                resp
+            }
+        }
+    }
+}
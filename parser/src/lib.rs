@@ -5,15 +5,17 @@
 //! it into Rust types.
 #![forbid(unsafe_code)]
 
-use pest::error::LineColLocation;
+use pest::error::{ErrorVariant, LineColLocation};
 use pest::RuleType;
 use std::fmt;
 
+pub use limits::check_token_limit;
 pub use parse::{parse_query, parse_schema};
 pub use pos::{Pos, Positioned};
 
 pub mod types;
 
+mod limits;
 mod parse;
 mod pos;
 
@@ -24,6 +26,12 @@ pub struct Error {
     pub pos: Pos,
     /// The error message.
     pub message: String,
+    /// The token at which the error occurred, or `None` at the end of the input.
+    pub token: Option<String>,
+    /// The tokens that were expected instead, such as `name` or `selection_set`.
+    pub expected: Vec<String>,
+    /// The line of the source in which the error occurred.
+    pub source_line: Option<String>,
 }
 
 impl Error {
@@ -32,8 +40,55 @@ impl Error {
         Self {
             pos,
             message: message.into(),
+            token: None,
+            expected: Vec::new(),
+            source_line: None,
         }
     }
+
+    /// Fill the token and the line of the source at the position of the error.
+    pub(crate) fn with_source(mut self, input: &str) -> Self {
+        if let Some(line) = self
+            .pos
+            .line
+            .checked_sub(1)
+            .and_then(|line| input.lines().nth(line))
+        {
+            let rest = line
+                .char_indices()
+                .nth(self.pos.column.saturating_sub(1))
+                .map(|(idx, _)| &line[idx..])
+                .unwrap_or("");
+            self.token = token_at(rest);
+            self.source_line = Some(line.to_string());
+        }
+        self
+    }
+}
+
+fn token_at(input: &str) -> Option<String> {
+    const PUNCTUATORS: &str = "!$&():=@[]{}|";
+
+    if input.starts_with("...") {
+        return Some("...".to_string());
+    }
+    let first = input.chars().next()?;
+    if PUNCTUATORS.contains(first) {
+        return Some(first.to_string());
+    }
+    if first == '"' {
+        let end = input[1..].find('"').map_or(input.len(), |idx| idx + 2);
+        return Some(input[..end].to_string());
+    }
+    let token: String = input
+        .chars()
+        .take_while(|c| !c.is_whitespace() && *c != ',' && *c != '"' && !PUNCTUATORS.contains(*c))
+        .collect();
+    if token.is_empty() {
+        None
+    } else {
+        Some(token)
+    }
 }
 
 impl fmt::Display for Error {
@@ -54,6 +109,14 @@ impl<R: RuleType> From<pest::error::Error<R>> for Error {
                 }
             },
             message: err.to_string(),
+            token: None,
+            expected: match &err.variant {
+                ErrorVariant::ParsingError { positives, .. } => {
+                    positives.iter().map(|rule| format!("{:?}", rule)).collect()
+                }
+                ErrorVariant::CustomError { .. } => Vec::new(),
+            },
+            source_line: None,
         }
     }
 }
@@ -0,0 +1,152 @@
+use crate::{Error, Pos, Result};
+use std::iter::Peekable;
+use std::str::Chars;
+
+/// Check that a document has at most `limit` tokens.
+///
+/// This only scans the tokens of the document without parsing it, so that enormous documents
+/// can be rejected cheaply before [`parse_query`](fn.parse_query.html) is called.
+///
+/// # Errors
+///
+/// Fails at the position of the first token beyond the limit.
+pub fn check_token_limit(input: &str, limit: usize) -> Result<()> {
+    let mut scanner = Scanner {
+        chars: input.chars().peekable(),
+        pos: Pos { line: 1, column: 1 },
+    };
+    let mut count = 0;
+
+    while let Some(c) = scanner.peek() {
+        match c {
+            '\u{feff}' | ',' => {
+                scanner.next();
+                continue;
+            }
+            c if c.is_whitespace() => {
+                scanner.next();
+                continue;
+            }
+            '#' => {
+                while let Some(c) = scanner.next() {
+                    if c == '\n' || c == '\r' {
+                        break;
+                    }
+                }
+                continue;
+            }
+            _ => {}
+        }
+
+        count += 1;
+        if count > limit {
+            return Err(Error::new(
+                format!("Too many tokens, the limit is {}", limit),
+                scanner.pos,
+            ));
+        }
+
+        match scanner.next().unwrap() {
+            '"' => scanner.skip_string(),
+            '.' => {
+                while scanner.peek() == Some('.') {
+                    scanner.next();
+                }
+            }
+            c if c.is_ascii_alphanumeric() || c == '_' || c == '-' => {
+                while let Some(c) = scanner.peek() {
+                    if c.is_ascii_alphanumeric() || c == '_' || c == '.' || c == '+' || c == '-' {
+                        scanner.next();
+                    } else {
+                        break;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+struct Scanner<'a> {
+    chars: Peekable<Chars<'a>>,
+    pos: Pos,
+}
+
+impl<'a> Scanner<'a> {
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        if c == '\n' {
+            self.pos.line += 1;
+            self.pos.column = 1;
+        } else {
+            self.pos.column += 1;
+        }
+        Some(c)
+    }
+
+    /// Skip the rest of a string after its opening quote.
+    fn skip_string(&mut self) {
+        if self.peek() == Some('"') {
+            self.next();
+            if self.peek() != Some('"') {
+                // An empty string.
+                return;
+            }
+            self.next();
+
+            // A block string.
+            let mut quotes = 0;
+            while let Some(c) = self.next() {
+                match c {
+                    '"' => {
+                        quotes += 1;
+                        if quotes == 3 {
+                            return;
+                        }
+                    }
+                    '\\' if quotes == 0 && self.peek() == Some('"') => {
+                        // An escaped triple quote.
+                        self.next();
+                    }
+                    _ => quotes = 0,
+                }
+            }
+            return;
+        }
+
+        while let Some(c) = self.next() {
+            match c {
+                '"' | '\n' | '\r' => return,
+                '\\' => {
+                    self.next();
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_token_limit() {
+        let query = "query($id: ID!) {\n  user(id: $id) { ...UserFields }\n}";
+        assert!(check_token_limit(query, 21).is_ok());
+        assert_eq!(
+            check_token_limit(query, 20).unwrap_err().pos,
+            Pos { line: 3, column: 1 }
+        );
+
+        let query = "# comment\n{ a(s: \"{ } ( )\", b: \"\"\"\n{ \\\"\"\" }\n\"\"\") }";
+        assert!(check_token_limit(query, 11).is_ok());
+        assert!(check_token_limit(query, 10).is_err());
+    }
+}
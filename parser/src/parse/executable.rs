@@ -6,14 +6,12 @@ use super::*;
 ///
 /// Fails if the query is not a valid GraphQL document.
 pub fn parse_query<T: AsRef<str>>(input: T) -> Result<ExecutableDocument> {
-    let mut pc = PositionCalculator::new(input.as_ref());
-    Ok(parse_executable_document(
-        exactly_one(GraphQLParser::parse(
-            Rule::executable_document,
-            input.as_ref(),
-        )?),
-        &mut pc,
-    )?)
+    let input = input.as_ref();
+    let mut pc = PositionCalculator::new(input);
+    GraphQLParser::parse(Rule::executable_document, input)
+        .map_err(Error::from)
+        .and_then(|pairs| parse_executable_document(exactly_one(pairs), &mut pc))
+        .map_err(|err| err.with_source(input))
 }
 
 fn parse_executable_document(
@@ -125,6 +123,7 @@ fn parse_variable_definition(
     let default_value = parse_if_rule(&mut pairs, Rule::default_value, |pair| {
         parse_default_value(pair, pc)
     })?;
+    let directives = parse_opt_directives(&mut pairs, pc)?;
 
     debug_assert_eq!(pairs.next(), None);
 
@@ -133,6 +132,7 @@ fn parse_variable_definition(
             name: variable,
             var_type,
             default_value,
+            directives,
         },
         pos,
     ))
@@ -6,14 +6,12 @@ use super::*;
 ///
 /// Fails if the schema is not a valid GraphQL document.
 pub fn parse_schema<T: AsRef<str>>(input: T) -> Result<ServiceDocument> {
-    let mut pc = PositionCalculator::new(input.as_ref());
-    Ok(parse_service_document(
-        exactly_one(GraphQLParser::parse(
-            Rule::service_document,
-            input.as_ref(),
-        )?),
-        &mut pc,
-    )?)
+    let input = input.as_ref();
+    let mut pc = PositionCalculator::new(input);
+    GraphQLParser::parse(Rule::service_document, input)
+        .map_err(Error::from)
+        .and_then(|pairs| parse_service_document(exactly_one(pairs), &mut pc))
+        .map_err(|err| err.with_source(input))
 }
 
 fn parse_service_document(
@@ -301,6 +299,7 @@ fn parse_directive_definition(
             .collect()
     })?
     .unwrap_or_default();
+    let is_repeatable = next_if_rule(&mut pairs, Rule::repeatable).is_some();
     let locations = {
         let pair = pairs.next().unwrap();
         debug_assert_eq!(pair.as_rule(), Rule::directive_locations);
@@ -343,6 +342,7 @@ fn parse_directive_definition(
             description,
             name,
             arguments,
+            is_repeatable,
             locations,
         },
         pos,
@@ -415,4 +415,22 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_repeatable_directive() {
+        let doc = parse_schema(
+            "directive @tag(name: String!) repeatable on FIELD_DEFINITION\n\
+             directive @key(fields: String!) on OBJECT",
+        )
+        .unwrap();
+        let is_repeatable = doc
+            .definitions
+            .iter()
+            .map(|definition| match definition {
+                TypeSystemDefinition::Directive(directive) => directive.node.is_repeatable,
+                _ => unreachable!(),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(is_repeatable, vec![true, false]);
+    }
 }
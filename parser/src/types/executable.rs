@@ -122,6 +122,8 @@ pub struct VariableDefinition {
     pub var_type: Positioned<Type>,
     /// The optional default value of the variable.
     pub default_value: Option<Positioned<ConstValue>>,
+    /// The variable's directives.
+    pub directives: Vec<Positioned<Directive>>,
 }
 
 impl VariableDefinition {
@@ -183,6 +183,8 @@ pub struct DirectiveDefinition {
     pub name: Positioned<Name>,
     /// The arguments of the directive.
     pub arguments: Vec<Positioned<InputValueDefinition>>,
+    /// Whether the directive can be used more than once at the same location.
+    pub is_repeatable: bool,
     /// The locations the directive applies to.
     pub locations: Vec<Positioned<DirectiveLocation>>,
 }
@@ -1,7 +1,7 @@
-use crate::parser::types::Field;
+use crate::parser::types::{Field, Name, Value as InputValue};
 use crate::registry::Registry;
 use crate::{
-    registry, ContextSelectionSet, FieldResult, InputValueResult, Positioned, Result, Value,
+    registry, ContextSelectionSet, FieldResult, InputValueResult, Pos, Positioned, Result, Value,
 };
 use std::borrow::Cow;
 
@@ -33,10 +33,90 @@ pub trait InputValueType: Type + Sized {
     /// Parse from `Value`. None represents undefined.
     fn parse(value: Option<Value>) -> InputValueResult<Self>;
 
+    /// Parse from `Value`, knowing where it was written in the query.
+    ///
+    /// The default implementation calls `parse`, lists, optional values and input objects pass
+    /// the context of each item on to the type of the item.
+    fn parse_with_context(value: Option<Value>, _ctx: &ParseContext<'_>) -> InputValueResult<Self> {
+        Self::parse(value)
+    }
+
     /// Convert to a `Value` for introspection.
     fn to_value(&self) -> Value;
 }
 
+/// Where an input value came from.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum InputValueSource<'a> {
+    /// The value is written inline in the query.
+    Literal,
+
+    /// The value is, or is part of, the variable with this name.
+    Variable(&'a str),
+}
+
+#[derive(Debug, Copy, Clone)]
+enum Origin<'a> {
+    Literal(&'a InputValue),
+    Variable(&'a Name),
+}
+
+/// The context of an argument that is parsed, see
+/// [`ScalarType::parse_with_context`](trait.ScalarType.html#method.parse_with_context).
+#[derive(Debug, Copy, Clone)]
+pub struct ParseContext<'a> {
+    pos: Pos,
+    origin: Origin<'a>,
+}
+
+impl<'a> ParseContext<'a> {
+    /// Create the context of an argument with the value `value` as written in the query.
+    pub fn new(pos: Pos, value: &'a InputValue) -> Self {
+        Self {
+            pos,
+            origin: match value {
+                InputValue::Variable(name) => Origin::Variable(name),
+                value => Origin::Literal(value),
+            },
+        }
+    }
+
+    /// The position of the argument in the query.
+    pub fn pos(&self) -> Pos {
+        self.pos
+    }
+
+    /// Whether the value is written inline in the query or comes from a variable.
+    pub fn source(&self) -> InputValueSource<'a> {
+        match self.origin {
+            Origin::Literal(_) => InputValueSource::Literal,
+            Origin::Variable(name) => InputValueSource::Variable(name),
+        }
+    }
+
+    /// Returns the context of the item at `index` of a list.
+    pub fn list_item(&self, index: usize) -> Self {
+        match self.origin {
+            Origin::Literal(InputValue::List(items)) => match items.get(index) {
+                Some(item) => Self::new(self.pos, item),
+                None => *self,
+            },
+            _ => *self,
+        }
+    }
+
+    /// Returns the context of the field `name` of an input object.
+    pub fn object_field(&self, name: &str) -> Self {
+        match self.origin {
+            Origin::Literal(InputValue::Object(fields)) => match fields.get(name) {
+                Some(field) => Self::new(self.pos, field),
+                None => *self,
+            },
+            _ => *self,
+        }
+    }
+}
+
 /// Represents a GraphQL output value
 #[async_trait::async_trait]
 pub trait OutputValueType: Type {
@@ -79,6 +159,15 @@ pub trait ScalarType: Sized + Send {
     /// Parse a scalar value, return `Some(Self)` if successful, otherwise return `None`.
     fn parse(value: Value) -> InputValueResult<Self>;
 
+    /// Parse a scalar value, knowing whether it is written inline in the query or comes from a
+    /// variable, and the position of the argument.
+    ///
+    /// This makes it possible to be stricter with literals, for example to reject large inline
+    /// values. The default implementation calls `parse`.
+    fn parse_with_context(value: Value, _ctx: &ParseContext<'_>) -> InputValueResult<Self> {
+        Self::parse(value)
+    }
+
     /// Checks for a valid scalar value.
     ///
     /// Implementing this function can find incorrect input values during the verification phase, which can improve performance.
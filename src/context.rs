@@ -1,11 +1,17 @@
 use crate::base::Type;
-use crate::extensions::Extensions;
+use crate::extensions::ExtensionsLock;
+use crate::live_query::LiveQuery;
 use crate::parser::types::{
-    Directive, ExecutableDocumentData, Field, Name, SelectionSet, Value as InputValue,
+    Directive, ExecutableDocumentData, Field, Name, Selection, SelectionSet, Value as InputValue,
 };
 use crate::schema::SchemaEnv;
-use crate::{FieldResult, InputValueType, Lookahead, Pos, Positioned, QueryError, Result, Value};
+use crate::{
+    CompletionReason, Error, FieldResult, InputValueType, Lookahead, ParseContext, Pos, Positioned,
+    QueryError, RequestExtensions, Result, Value,
+};
 use fnv::FnvHashMap;
+use futures::future::{BoxFuture, Future, FutureExt};
+use once_cell::sync::OnceCell;
 use serde::ser::{SerializeSeq, Serializer};
 use serde::{Deserialize, Serialize};
 use std::any::{Any, TypeId};
@@ -94,6 +100,18 @@ impl Data {
     pub fn insert<D: Any + Send + Sync>(&mut self, data: D) {
         self.0.insert(TypeId::of::<D>(), Box::new(data));
     }
+
+    /// Gets the data of the type, if it was inserted.
+    pub fn get<D: Any + Send + Sync>(&self) -> Option<&D> {
+        self.0
+            .get(&TypeId::of::<D>())
+            .and_then(|data| data.downcast_ref::<D>())
+    }
+
+    /// Insert the data of `other`, replacing the data of the same types.
+    pub(crate) fn merge(&mut self, other: Data) {
+        self.0.extend(other.0);
+    }
 }
 
 impl Debug for Data {
@@ -102,6 +120,16 @@ impl Debug for Data {
     }
 }
 
+/// The data added to a sub-tree of the query by a
+/// [`WithData`](types/struct.WithData.html), and the data added by its ancestors.
+#[derive(Clone, Copy)]
+pub(crate) struct ScopedData<'a> {
+    pub(crate) data: &'a Data,
+    pub(crate) parent: Option<&'a ScopedData<'a>>,
+}
+
+type LazyData = FnvHashMap<TypeId, Arc<OnceCell<Arc<dyn Any + Send + Sync>>>>;
+
 /// Context for `SelectionSet`
 pub type ContextSelectionSet<'a> = ContextBase<'a, &'a Positioned<SelectionSet>>;
 
@@ -236,14 +264,51 @@ pub struct ContextBase<'a, T> {
     pub schema_env: &'a SchemaEnv,
     #[doc(hidden)]
     pub query_env: &'a QueryEnv,
+    pub(crate) scoped_data: Option<&'a ScopedData<'a>>,
 }
 
 #[doc(hidden)]
 pub struct QueryEnvInner {
-    pub extensions: spin::Mutex<Extensions>,
+    pub extensions: ExtensionsLock,
     pub variables: Variables,
     pub document: ExecutableDocumentData,
     pub ctx_data: Arc<Data>,
+    pub request_extensions: RequestExtensions,
+    pub live_query: Option<Arc<LiveQuery>>,
+    pub on_complete: spin::Mutex<Vec<Box<dyn FnOnce(CompletionReason) + Send>>>,
+    pub(crate) spawned: spin::Mutex<Vec<BoxFuture<'static, ()>>>,
+    lazy_data: spin::Mutex<LazyData>,
+}
+
+impl QueryEnvInner {
+    fn var_value(&self, name: &str, pos: Pos) -> Result<Value> {
+        self.document
+            .operation
+            .node
+            .variable_definitions
+            .iter()
+            .find(|def| def.node.name.node == name)
+            .and_then(|def| {
+                self.variables
+                    .0
+                    .get(&def.node.name.node)
+                    .or_else(|| def.node.default_value())
+            })
+            .cloned()
+            .ok_or_else(|| {
+                QueryError::VarNotDefined {
+                    var_name: name.to_owned(),
+                }
+                .into_error(pos)
+            })
+    }
+
+    pub(crate) fn resolve_input_value(&self, value: Positioned<InputValue>) -> Result<Value> {
+        let pos = value.pos;
+        value
+            .node
+            .into_const_with(|name| self.var_value(&name, pos))
+    }
 }
 
 #[doc(hidden)]
@@ -261,16 +326,23 @@ impl Deref for QueryEnv {
 impl QueryEnv {
     #[doc(hidden)]
     pub fn new(
-        extensions: spin::Mutex<Extensions>,
+        extensions: ExtensionsLock,
         variables: Variables,
         document: ExecutableDocumentData,
         ctx_data: Arc<Data>,
+        request_extensions: RequestExtensions,
+        live_query: Option<Arc<LiveQuery>>,
     ) -> QueryEnv {
         QueryEnv(Arc::new(QueryEnvInner {
             extensions,
             variables,
             document,
             ctx_data,
+            request_extensions,
+            live_query,
+            on_complete: Default::default(),
+            spawned: Default::default(),
+            lazy_data: Default::default(),
         }))
     }
 
@@ -289,6 +361,7 @@ impl QueryEnv {
             item,
             schema_env,
             query_env: self,
+            scoped_data: None,
         }
     }
 }
@@ -320,6 +393,7 @@ impl<'a, T> ContextBase<'a, T> {
             inc_resolve_id: self.inc_resolve_id,
             schema_env: self.schema_env,
             query_env: self.query_env,
+            scoped_data: self.scoped_data,
         }
     }
 
@@ -335,6 +409,7 @@ impl<'a, T> ContextBase<'a, T> {
             inc_resolve_id: &self.inc_resolve_id,
             schema_env: self.schema_env,
             query_env: self.query_env,
+            scoped_data: self.scoped_data,
         }
     }
 
@@ -361,7 +436,17 @@ impl<'a, T> ContextBase<'a, T> {
     }
 
     /// Gets the global data defined in the `Context` or `Schema` or `None` if the specified type data does not exist.
+    ///
+    /// The data added by a [`WithData`](types/struct.WithData.html) to the sub-tree of the
+    /// current field overrides them.
     pub fn data_opt<D: Any + Send + Sync>(&self) -> Option<&D> {
+        let mut scoped_data = self.scoped_data;
+        while let Some(scope) = scoped_data {
+            if let Some(data) = scope.data.0.get(&TypeId::of::<D>()) {
+                return data.downcast_ref::<D>();
+            }
+            scoped_data = scope.parent;
+        }
         self.query_env
             .ctx_data
             .0
@@ -370,35 +455,142 @@ impl<'a, T> ContextBase<'a, T> {
             .and_then(|d| d.downcast_ref::<D>())
     }
 
-    fn var_value(&self, name: &str, pos: Pos) -> Result<Value> {
-        self.query_env
-            .document
-            .operation
-            .node
-            .variable_definitions
-            .iter()
-            .find(|def| def.node.name.node == name)
-            .and_then(|def| {
-                self.query_env
-                    .variables
-                    .0
-                    .get(&def.node.name.node)
-                    .or_else(|| def.node.default_value())
-            })
-            .cloned()
-            .ok_or_else(|| {
-                QueryError::VarNotDefined {
-                    var_name: name.to_owned(),
-                }
-                .into_error(pos)
-            })
+    /// Gets the data of type `D` of the request, creating it with `f` the first time it is asked
+    /// for, such as a database transaction that only some fields need.
+    ///
+    /// The data is shared by all the fields of the request, which may resolve concurrently, so
+    /// it is returned in an `Arc` and `f` is called once. It is separate from the data returned
+    /// by [`data`](#method.data).
+    pub fn data_or_insert_with<D, F>(&self, f: F) -> Arc<D>
+    where
+        D: Any + Send + Sync,
+        F: FnOnce() -> D,
+    {
+        let cell = self
+            .query_env
+            .lazy_data
+            .lock()
+            .entry(TypeId::of::<D>())
+            .or_default()
+            .clone();
+        cell.get_or_init(|| Arc::new(f()))
+            .clone()
+            .downcast::<D>()
+            .expect("the data has the type of its key")
     }
 
-    fn resolve_input_value(&self, value: Positioned<InputValue>) -> Result<Value> {
-        let pos = value.pos;
-        value
-            .node
-            .into_const_with(|name| self.var_value(&name, pos))
+    /// Gets the `extensions` of the request, e.g. to attribute metrics to the
+    /// [client](struct.RequestExtensions.html#method.client_name) that sent it.
+    pub fn request_extensions(&self) -> &'a RequestExtensions {
+        &self.query_env.request_extensions
+    }
+
+    /// Re-execute the current live query when `key` is invalidated with
+    /// [`Schema::invalidate`](struct.Schema.html#method.invalidate).
+    ///
+    /// A live query whose resolvers add no keys is re-executed on every invalidation. This does
+    /// nothing if the operation is not a live query.
+    pub fn add_invalidation_key(&self, key: impl Into<String>) {
+        if let Some(live_query) = &self.query_env.live_query {
+            live_query.add_key(key.into());
+        }
+    }
+
+    /// Register a function that is called when the current subscription completes, with the
+    /// reason why it completed, to release the resources used by its stream such as a database
+    /// `LISTEN` handle.
+    ///
+    /// The functions are only called for subscriptions and live queries, once their streams have
+    /// been dropped. The functions added by every execution of a live query are called when it
+    /// completes.
+    pub fn on_complete(&self, f: impl FnOnce(CompletionReason) + Send + 'static) {
+        match &self.query_env.live_query {
+            Some(live_query) => live_query.on_complete(Box::new(f)),
+            None => self.query_env.on_complete.lock().push(Box::new(f)),
+        }
+    }
+
+    /// Run background work of the current request, such as warming a cache or sending a
+    /// notification, without delaying the resolver.
+    ///
+    /// The response of a query or a mutation is only returned once all the work spawned by its
+    /// resolvers has finished, and the work is cancelled if the request is dropped, so none of it
+    /// outlives the request. The work runs on the spawner of the schema, see
+    /// [`SchemaBuilder::spawner`](struct.SchemaBuilder.html#method.spawner), or after the
+    /// resolvers without one. The work spawned by a subscription is cancelled when the
+    /// subscription completes, and only runs with a spawner.
+    pub fn spawn(&self, fut: impl Future<Output = ()> + Send + 'static) {
+        let fut = match &self.schema_env.spawner {
+            Some(spawner) => {
+                let (remote, handle) = fut.remote_handle();
+                spawner(remote.boxed());
+                handle.boxed()
+            }
+            None => fut.boxed(),
+        };
+        self.query_env.spawned.lock().push(fut);
+    }
+
+    /// Runs a blocking or CPU-heavy closure on the thread pool and waits for its result.
+    ///
+    /// The closure receives a [`BlockingContext`](struct.BlockingContext.html), so the data of the
+    /// `Schema` and the current request remains accessible. If the resolver future is dropped
+    /// (for example because the client went away) before the closure starts, it is never run.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use async_graphql::*;
+    ///
+    /// struct Query;
+    ///
+    /// #[Object]
+    /// impl Query {
+    ///     async fn hash(&self, ctx: &Context<'_>, input: String) -> String {
+    ///         ctx.spawn_blocking(move |ctx| {
+    ///             let salt = ctx.data_unchecked::<String>();
+    ///             format!("{}{}", salt, input)
+    ///         })
+    ///         .await
+    ///     }
+    /// }
+    /// ```
+    #[cfg(feature = "unblock")]
+    #[cfg_attr(feature = "nightly", doc(cfg(feature = "unblock")))]
+    pub async fn spawn_blocking<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&BlockingContext) -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let ctx = BlockingContext {
+            schema_env: self.schema_env.clone(),
+            ctx_data: self.query_env.ctx_data.clone(),
+        };
+        blocking::unblock(move || f(&ctx)).await
+    }
+
+    pub(crate) fn resolve_input_value(&self, value: Positioned<InputValue>) -> Result<Value> {
+        self.query_env.resolve_input_value(value)
+    }
+
+    /// Add the path of the current field to an error that does not have one.
+    #[doc(hidden)]
+    pub fn set_error_path(&self, err: Error) -> Error {
+        match err {
+            Error::Query {
+                pos,
+                path: None,
+                err,
+            } => Error::Query {
+                pos,
+                path: self
+                    .path_node
+                    .as_ref()
+                    .and_then(|path| serde_json::to_value(path).ok()),
+                err,
+            },
+            err => err,
+        }
     }
 
     #[doc(hidden)]
@@ -443,9 +635,156 @@ impl<'a, T> ContextBase<'a, T> {
 
         Ok(false)
     }
+
+    fn project_value(
+        &self,
+        selection_set: &SelectionSet,
+        value: &serde_json::Value,
+    ) -> Result<serde_json::Value> {
+        match value {
+            serde_json::Value::Array(items) => items
+                .iter()
+                .map(|item| self.project_value(selection_set, item))
+                .collect(),
+            serde_json::Value::Object(object) => {
+                let mut projected = serde_json::Map::new();
+                self.project_fields(selection_set, object, &mut projected)?;
+                Ok(serde_json::Value::Object(projected))
+            }
+            value => Ok(value.clone()),
+        }
+    }
+
+    fn project_fields(
+        &self,
+        selection_set: &SelectionSet,
+        object: &serde_json::Map<String, serde_json::Value>,
+        projected: &mut serde_json::Map<String, serde_json::Value>,
+    ) -> Result<()> {
+        // A fragment applies if its type condition matches the `__typename` of the object, or if
+        // the object has no `__typename`.
+        let applies = |type_condition: &Name| {
+            object
+                .get("__typename")
+                .and_then(|typename| typename.as_str())
+                .map_or(true, |typename| typename == type_condition.as_str())
+        };
+
+        for selection in &selection_set.items {
+            if self.is_skip(selection.node.directives())? {
+                continue;
+            }
+            match &selection.node {
+                Selection::Field(field) => {
+                    let field = &field.node;
+                    let value = match object.get(field.name.node.as_str()) {
+                        Some(value) if !field.selection_set.node.items.is_empty() => {
+                            self.project_value(&field.selection_set.node, value)?
+                        }
+                        Some(value) => value.clone(),
+                        None => serde_json::Value::Null,
+                    };
+                    match (projected.get_mut(field.response_key().node.as_str()), value) {
+                        // The same field is selected more than once, merge the sub-selections.
+                        (
+                            Some(serde_json::Value::Object(existing)),
+                            serde_json::Value::Object(value),
+                        ) => existing.extend(value),
+                        (_, value) => {
+                            projected.insert(field.response_key().node.to_string(), value);
+                        }
+                    }
+                }
+                Selection::InlineFragment(fragment) => {
+                    let fragment = &fragment.node;
+                    if fragment
+                        .type_condition
+                        .as_ref()
+                        .map_or(true, |condition| applies(&condition.node.on.node))
+                    {
+                        self.project_fields(&fragment.selection_set.node, object, projected)?;
+                    }
+                }
+                Selection::FragmentSpread(spread) => {
+                    if let Some(fragment) = self
+                        .query_env
+                        .document
+                        .fragments
+                        .get(&spread.node.fragment_name.node)
+                    {
+                        if applies(&fragment.node.type_condition.node.on.node) {
+                            self.project_fields(
+                                &fragment.node.selection_set.node,
+                                object,
+                                projected,
+                            )?;
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Context passed to closures executed by [`Context::spawn_blocking`](type.Context.html).
+///
+/// It owns the data of the `Schema` and the current request, so it can be moved to another thread.
+#[cfg(feature = "unblock")]
+#[cfg_attr(feature = "nightly", doc(cfg(feature = "unblock")))]
+pub struct BlockingContext {
+    schema_env: SchemaEnv,
+    ctx_data: Arc<Data>,
+}
+
+#[cfg(feature = "unblock")]
+impl BlockingContext {
+    /// Gets the global data defined in the `Context` or `Schema`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `FieldError` if the specified type data does not exist.
+    pub fn data<D: Any + Send + Sync>(&self) -> FieldResult<&D> {
+        self.data_opt::<D>()
+            .ok_or_else(|| format!("Data `{}` does not exist.", std::any::type_name::<D>()).into())
+    }
+
+    /// Gets the global data defined in the `Context` or `Schema`.
+    ///
+    /// # Panics
+    ///
+    /// It will panic if the specified data type does not exist.
+    pub fn data_unchecked<D: Any + Send + Sync>(&self) -> &D {
+        self.data_opt::<D>()
+            .unwrap_or_else(|| panic!("Data `{}` does not exist.", std::any::type_name::<D>()))
+    }
+
+    /// Gets the global data defined in the `Context` or `Schema` or `None` if the specified type data does not exist.
+    pub fn data_opt<D: Any + Send + Sync>(&self) -> Option<&D> {
+        self.ctx_data
+            .0
+            .get(&TypeId::of::<D>())
+            .or_else(|| self.schema_env.data.0.get(&TypeId::of::<D>()))
+            .and_then(|d| d.downcast_ref::<D>())
+    }
 }
 
 impl<'a> ContextBase<'a, &'a Positioned<SelectionSet>> {
+    pub(crate) fn with_scoped_data<'b>(
+        &'b self,
+        scoped_data: &'b ScopedData<'b>,
+    ) -> ContextBase<'b, &'b Positioned<SelectionSet>> {
+        ContextBase {
+            path_node: self.path_node.clone(),
+            resolve_id: self.resolve_id,
+            inc_resolve_id: self.inc_resolve_id,
+            item: self.item,
+            schema_env: self.schema_env,
+            query_env: self.query_env,
+            scoped_data: Some(scoped_data),
+        }
+    }
+
     #[doc(hidden)]
     pub fn with_index(&'a self, idx: usize) -> ContextBase<'a, &'a Positioned<SelectionSet>> {
         ContextBase {
@@ -458,6 +797,7 @@ impl<'a> ContextBase<'a, &'a Positioned<SelectionSet>> {
             inc_resolve_id: self.inc_resolve_id,
             schema_env: self.schema_env,
             query_env: self.query_env,
+            scoped_data: self.scoped_data,
         }
     }
 }
@@ -469,17 +809,21 @@ impl<'a> ContextBase<'a, &'a Positioned<Field>> {
         name: &str,
         default: Option<fn() -> T>,
     ) -> Result<T> {
-        let value = self.item.node.get_argument(name).cloned();
-        if value.is_none() {
-            if let Some(default) = default {
-                return Ok(default());
+        match self.item.node.get_argument(name) {
+            Some(value) => {
+                let ctx = ParseContext::new(value.pos, &value.node);
+                InputValueType::parse_with_context(
+                    Some(self.resolve_input_value(value.clone())?),
+                    &ctx,
+                )
+                .map_err(|e| e.into_argument_error(value.pos, name, T::qualified_type_name()))
             }
+            None => match default {
+                Some(default) => Ok(default()),
+                None => InputValueType::parse(None)
+                    .map_err(|e| e.into_error(Pos::default(), T::qualified_type_name())),
+            },
         }
-        let (pos, value) = match value {
-            Some(value) => (value.pos, Some(self.resolve_input_value(value)?)),
-            None => (Pos::default(), None),
-        };
-        InputValueType::parse(value).map_err(|e| e.into_error(pos, T::qualified_type_name()))
     }
 
     /// Creates a uniform interface to inspect the forthcoming selections.
@@ -519,6 +863,56 @@ impl<'a> ContextBase<'a, &'a Positioned<Field>> {
     /// }
     /// ```
     pub fn look_ahead(&self) -> Lookahead {
-        Lookahead::new(&self.query_env.document, &self.item.node)
+        Lookahead::new(&self.query_env, &self.item.node)
+    }
+
+    /// Project the selection set of the current field onto an untyped JSON value, such as a
+    /// document from a document database or the response of a remote API.
+    ///
+    /// The result has a field for every selected field, under its alias if it has one, with
+    /// `null` for the fields that are not in `value`. Lists are projected item by item and
+    /// fragments, `@skip` and `@include` are respected. The type condition of a fragment is
+    /// compared to the `__typename` of the object if there is one, and the values are not
+    /// checked against the types of the schema.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use async_graphql::parser::types::Field;
+    /// use async_graphql::*;
+    /// use std::borrow::Cow;
+    ///
+    /// #[derive(SimpleObject)]
+    /// struct User {
+    ///     name: String,
+    ///     email: String,
+    /// }
+    ///
+    /// /// A `User` as it is stored in the database.
+    /// struct UserDocument(serde_json::Value);
+    ///
+    /// impl Type for UserDocument {
+    ///     fn type_name() -> Cow<'static, str> {
+    ///         User::type_name()
+    ///     }
+    ///
+    ///     fn create_type_info(registry: &mut registry::Registry) -> String {
+    ///         User::create_type_info(registry)
+    ///     }
+    /// }
+    ///
+    /// #[async_trait::async_trait]
+    /// impl OutputValueType for UserDocument {
+    ///     async fn resolve(
+    ///         &self,
+    ///         ctx: &ContextSelectionSet<'_>,
+    ///         field: &Positioned<Field>,
+    ///     ) -> Result<serde_json::Value> {
+    ///         ctx.with_field(field).resolve_selection_on(&self.0)
+    ///     }
+    /// }
+    /// ```
+    pub fn resolve_selection_on(&self, value: &serde_json::Value) -> Result<serde_json::Value> {
+        self.project_value(&self.item.node.selection_set.node, value)
     }
 }
@@ -0,0 +1,241 @@
+//! Batch and cache the loads of the resolvers, to avoid the N+1 problem.
+//!
+//! A [`DataLoader`](struct.DataLoader.html) collects the keys that the resolvers of a selection
+//! set load while they are polled together, such as the fields of every item of a list, and loads
+//! them with one call of its [`Loader`](trait.Loader.html). Keys are deduplicated and the values
+//! are cached by the `DataLoader`, so create one per request, in the data of the request or with
+//! [`Context::data_or_insert_with`](../struct.Context.html#method.data_or_insert_with).
+//!
+//! # Examples
+//!
+//! ```rust
+//! use async_graphql::*;
+//! use async_graphql::dataloader::{DataLoader, Loader};
+//! use std::collections::HashMap;
+//! use std::convert::Infallible;
+//!
+//! struct UserNameLoader;
+//!
+//! #[async_trait::async_trait]
+//! impl Loader for UserNameLoader {
+//!     type Key = i32;
+//!     type Value = String;
+//!     type Error = Infallible;
+//!
+//!     async fn load(&self, keys: &[i32]) -> Result<HashMap<i32, String>, Infallible> {
+//!         // SELECT id, name FROM users WHERE id IN (...)
+//!         Ok(keys.iter().map(|id| (*id, format!("user{}", id))).collect())
+//!     }
+//! }
+//!
+//! struct User(i32);
+//!
+//! #[Object]
+//! impl User {
+//!     async fn name(&self, ctx: &Context<'_>) -> FieldResult<Option<String>> {
+//!         let loader = ctx.data_or_insert_with(|| DataLoader::new(UserNameLoader));
+//!         Ok(loader.load_one(self.0).await?)
+//!     }
+//! }
+//!
+//! struct QueryRoot;
+//!
+//! #[Object]
+//! impl QueryRoot {
+//!     async fn users(&self) -> Vec<User> {
+//!         (0..10).map(User).collect()
+//!     }
+//! }
+//! ```
+
+use futures::future::{self, BoxFuture, FutureExt, Shared};
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::hash::Hash;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+/// Loads the values of a batch of keys for a [`DataLoader`](struct.DataLoader.html).
+#[async_trait::async_trait]
+pub trait Loader: Send + Sync + 'static {
+    /// The type of the keys.
+    type Key: Send + Sync + Hash + Eq + Clone + 'static;
+
+    /// The type of the values.
+    type Value: Send + Sync + Clone + 'static;
+
+    /// The type of the errors, which is returned to every load of the batch.
+    type Error: Send + Sync + Clone + 'static;
+
+    /// Load the values of `keys`. The keys that are missing from the result have no value.
+    async fn load(
+        &self,
+        keys: &[Self::Key],
+    ) -> Result<HashMap<Self::Key, Self::Value>, Self::Error>;
+}
+
+type BatchFuture<E> = Shared<BoxFuture<'static, Result<(), E>>>;
+
+/// The keys of a batch that has not been loaded yet.
+type BatchKeys<K> = Arc<spin::Mutex<Vec<K>>>;
+
+struct State<T: Loader> {
+    /// The loaded values, and `None` for the keys that have no value.
+    cache: HashMap<T::Key, Option<T::Value>>,
+    /// The batch that new keys are added to, until it starts loading.
+    open: Option<(BatchKeys<T::Key>, BatchFuture<T::Error>)>,
+    /// The batches of the keys that are being loaded.
+    loading: HashMap<T::Key, BatchFuture<T::Error>>,
+}
+
+/// Batches and caches the loads of a [`Loader`](trait.Loader.html), see the
+/// [module documentation](index.html).
+pub struct DataLoader<T: Loader> {
+    loader: Arc<T>,
+    state: Arc<spin::Mutex<State<T>>>,
+    max_batch_size: usize,
+}
+
+impl<T: Loader> DataLoader<T> {
+    /// Create a `DataLoader` with an empty cache.
+    pub fn new(loader: T) -> Self {
+        Self {
+            loader: Arc::new(loader),
+            state: Arc::new(spin::Mutex::new(State {
+                cache: HashMap::new(),
+                open: None,
+                loading: HashMap::new(),
+            })),
+            max_batch_size: 1000,
+        }
+    }
+
+    /// Set the maximum number of keys that are loaded together, default is 1000.
+    pub fn max_batch_size(mut self, max_batch_size: usize) -> Self {
+        self.max_batch_size = max_batch_size.max(1);
+        self
+    }
+
+    /// Returns the loader.
+    pub fn loader(&self) -> &T {
+        &self.loader
+    }
+
+    /// Load the value of `key`, in a batch with the other keys that are loaded at the same time.
+    pub async fn load_one(&self, key: T::Key) -> Result<Option<T::Value>, T::Error> {
+        let mut values = self.load_many(std::iter::once(key.clone())).await?;
+        Ok(values.remove(&key))
+    }
+
+    /// Load the values of `keys`, in a batch with the other keys that are loaded at the same
+    /// time. The keys that have no value are missing from the result.
+    pub async fn load_many(
+        &self,
+        keys: impl IntoIterator<Item = T::Key>,
+    ) -> Result<HashMap<T::Key, T::Value>, T::Error> {
+        let keys: HashSet<T::Key> = keys.into_iter().collect();
+
+        let batches = {
+            let mut state = self.state.lock();
+            let mut batches = Vec::new();
+            for key in &keys {
+                if state.cache.contains_key(key) {
+                    continue;
+                }
+                if let Some(batch) = state.loading.get(key) {
+                    batches.push(batch.clone());
+                    continue;
+                }
+                let batch = self.add_to_batch(&mut state, key.clone());
+                state.loading.insert(key.clone(), batch.clone());
+                batches.push(batch);
+            }
+            batches
+        };
+        for result in future::join_all(batches).await {
+            result?;
+        }
+
+        let state = self.state.lock();
+        Ok(keys
+            .into_iter()
+            .filter_map(|key| {
+                let value = state.cache.get(&key).cloned().flatten()?;
+                Some((key, value))
+            })
+            .collect())
+    }
+
+    /// Insert the value of `key` into the cache, so that it is not loaded.
+    pub fn feed(&self, key: T::Key, value: T::Value) {
+        self.state.lock().cache.insert(key, Some(value));
+    }
+
+    /// Remove all the values from the cache.
+    pub fn clear(&self) {
+        self.state.lock().cache.clear();
+    }
+
+    fn add_to_batch(&self, state: &mut State<T>, key: T::Key) -> BatchFuture<T::Error> {
+        if let Some((keys, batch)) = &state.open {
+            let mut keys = keys.lock();
+            if keys.len() < self.max_batch_size {
+                keys.push(key);
+                return batch.clone();
+            }
+        }
+
+        let keys = Arc::new(spin::Mutex::new(vec![key]));
+        let batch = {
+            let loader = self.loader.clone();
+            let state = self.state.clone();
+            let keys = keys.clone();
+            async move {
+                // Let the other resolvers that are polled with this one add their keys.
+                YieldNow(false).await;
+
+                let keys = {
+                    let mut state = state.lock();
+                    if matches!(&state.open, Some((open, _)) if Arc::ptr_eq(open, &keys)) {
+                        state.open = None;
+                    }
+                    std::mem::take(&mut *keys.lock())
+                };
+                let result = loader.load(&keys).await;
+
+                let mut state = state.lock();
+                for key in &keys {
+                    state.loading.remove(key);
+                }
+                let mut values = result?;
+                for key in keys {
+                    let value = values.remove(&key);
+                    state.cache.insert(key, value);
+                }
+                Ok(())
+            }
+            .boxed()
+            .shared()
+        };
+        state.open = Some((keys, batch.clone()));
+        batch
+    }
+}
+
+/// Returns `Pending` once, after waking the task so that it is polled again.
+struct YieldNow(bool);
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.0 {
+            Poll::Ready(())
+        } else {
+            self.0 = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use crate::parser::types::Name;
+use crate::registry::MetaInputValue;
+use crate::{FieldResult, Value};
+use indexmap::IndexMap;
+use std::collections::BTreeMap;
+
+/// A directive on variable definitions that transforms the value of the variable before any
+/// resolver sees it, such as `@trim` in `query($name: String! @trim) { ... }`.
+///
+/// On a field, such as `hello(name: " world ") @trim`, it transforms the arguments of the field
+/// that are written in the query. The transformed values are checked against the types of the
+/// arguments that they are passed to.
+///
+/// Register it with
+/// [`SchemaBuilder::variable_directive`](struct.SchemaBuilder.html#method.variable_directive),
+/// which also declares it in the schema so that it is validated and introspectable like the
+/// built-in directives.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+/// use async_graphql::parser::types::Name;
+/// use std::collections::BTreeMap;
+///
+/// struct Trim;
+///
+/// impl VariableDirective for Trim {
+///     fn name(&self) -> &'static str {
+///         "trim"
+///     }
+///
+///     fn transform(&self, _args: &BTreeMap<Name, Value>, value: Value) -> FieldResult<Value> {
+///         match value {
+///             Value::String(s) => Ok(Value::String(s.trim().to_string())),
+///             value => Ok(value),
+///         }
+///     }
+/// }
+///
+/// struct QueryRoot;
+///
+/// #[Object]
+/// impl QueryRoot {
+///     async fn hello(&self, name: String) -> String {
+///         format!("Hello, {}!", name)
+///     }
+/// }
+///
+/// #[async_std::main]
+/// async fn main() {
+///     let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+///         .variable_directive(Trim)
+///         .finish();
+///     let query = r#"query($name: String! = "  world " @trim) { hello(name: $name) }"#;
+///     assert_eq!(
+///         schema.execute(query).await.data,
+///         serde_json::json!({ "hello": "Hello, world!" })
+///     );
+/// }
+/// ```
+pub trait VariableDirective: Send + Sync + 'static {
+    /// The name of the directive, without the preceding `@`.
+    fn name(&self) -> &'static str;
+
+    /// The description of the directive.
+    fn description(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// The arguments of the directive.
+    ///
+    /// Arguments can be of input object types, which must be registered with
+    /// [`SchemaBuilder::register_type`](struct.SchemaBuilder.html#method.register_type).
+    fn args(&self) -> IndexMap<&'static str, MetaInputValue> {
+        Default::default()
+    }
+
+    /// Whether the directive can be used several times on the same variable.
+    fn is_repeatable(&self) -> bool {
+        false
+    }
+
+    /// Transform the value of the variable or argument.
+    ///
+    /// `value` is the whole value of the variable (or its default value) or argument, lists are
+    /// not unwrapped. When there are several directives they are applied from left to right.
+    fn transform(&self, args: &BTreeMap<Name, Value>, value: Value) -> FieldResult<Value>;
+}
@@ -1,5 +1,5 @@
-use crate::{Pos, QueryPathNode, Value};
-use std::fmt::{Debug, Display};
+use crate::{Pos, QueryPathNode, Type, Value};
+use std::fmt::{Debug, Display, Write};
 use thiserror::Error;
 
 /// An error in the format of an input value.
@@ -10,6 +10,37 @@ pub enum InputValueError {
 
     /// The type of input value does not match the expectation. Contains the value that was found.
     ExpectedType(Value),
+
+    /// The value is invalid, see [`InputValueError::invalid`](#method.invalid).
+    Invalid {
+        /// What was expected, such as `an integer from -128 to 127`.
+        expected: String,
+
+        /// The value that was found.
+        actual: Value,
+
+        /// A hint on how to fix the value.
+        hint: Option<String>,
+    },
+
+    /// The error is in an item of a list or a field of an input object.
+    Nested {
+        /// The index of the item or the name of the field.
+        segment: InputValuePathSegment,
+
+        /// The error in the item or field.
+        error: Box<InputValueError>,
+    },
+}
+
+/// An item of a list or a field of an input object in which an input value is invalid.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputValuePathSegment {
+    /// The index of an item of a list.
+    Index(usize),
+
+    /// The name of a field of an input object.
+    Name(String),
 }
 
 impl<T: Display> From<T> for InputValueError {
@@ -19,22 +50,113 @@ impl<T: Display> From<T> for InputValueError {
 }
 
 impl InputValueError {
+    /// Create an error for a value that is not what was expected, so that the message has the
+    /// same format for all input types.
+    ///
+    /// ```rust
+    /// use async_graphql::{InputValueError, Value};
+    ///
+    /// let err = InputValueError::invalid("a color such as `#ff0000`", Value::String("red".into()))
+    ///     .with_hint("Colors are written in hexadecimal.");
+    /// ```
+    pub fn invalid(expected: impl Into<String>, actual: Value) -> Self {
+        InputValueError::Invalid {
+            expected: expected.into(),
+            actual,
+            hint: None,
+        }
+    }
+
+    /// Add a hint to an error created with [`invalid`](#method.invalid).
+    pub fn with_hint(self, hint: impl Into<String>) -> Self {
+        match self {
+            InputValueError::Invalid {
+                expected, actual, ..
+            } => InputValueError::Invalid {
+                expected,
+                actual,
+                hint: Some(hint.into()),
+            },
+            InputValueError::Nested { segment, error } => InputValueError::Nested {
+                segment,
+                error: Box::new(error.with_hint(hint)),
+            },
+            err => err,
+        }
+    }
+
+    /// Mark the error as being in the item `index` of a list of `T`.
+    pub fn with_index<T: Type>(self, index: usize) -> Self {
+        self.nested::<T>(InputValuePathSegment::Index(index))
+    }
+
+    /// Mark the error as being in the field `name` of type `T` of an input object.
+    pub fn with_field<T: Type>(self, name: &str) -> Self {
+        self.nested::<T>(InputValuePathSegment::Name(name.to_string()))
+    }
+
+    fn nested<T: Type>(self, segment: InputValuePathSegment) -> Self {
+        let error = match self {
+            // The expected type is only known here, not where the error is converted.
+            InputValueError::ExpectedType(actual) => {
+                InputValueError::invalid(format!("\"{}\"", T::qualified_type_name()), actual)
+            }
+            err => err,
+        };
+        InputValueError::Nested {
+            segment,
+            error: Box::new(error),
+        }
+    }
+
     /// Convert this error to a regular `Error` type.
     pub fn into_error(self, pos: Pos, expected_type: String) -> Error {
-        match self {
-            InputValueError::Custom(reason) => Error::Query {
-                pos,
-                path: None,
-                err: QueryError::ParseInputValue { reason },
+        self.into_error_with_path(pos, String::new(), expected_type)
+    }
+
+    /// Convert this error of the argument `name` to a regular `Error` type.
+    pub(crate) fn into_argument_error(self, pos: Pos, name: &str, expected_type: String) -> Error {
+        self.into_error_with_path(pos, name.to_string(), expected_type)
+    }
+
+    fn into_error_with_path(self, pos: Pos, mut path: String, expected_type: String) -> Error {
+        let mut err = self;
+        let mut nested = false;
+        while let InputValueError::Nested { segment, error } = err {
+            match segment {
+                InputValuePathSegment::Index(index) => write!(path, "[{}]", index).unwrap(),
+                InputValuePathSegment::Name(name) if path.is_empty() => path = name,
+                InputValuePathSegment::Name(name) => write!(path, ".{}", name).unwrap(),
+            }
+            nested = true;
+            err = *error;
+        }
+
+        let err = match err {
+            InputValueError::Custom(reason) if nested => QueryError::ParseInputValue {
+                reason: format!("{} (at \"{}\")", reason, path),
+            },
+            InputValueError::Custom(reason) => QueryError::ParseInputValue { reason },
+            InputValueError::ExpectedType(value) => QueryError::ExpectedInputType {
+                expect: expected_type,
+                actual: value,
             },
-            InputValueError::ExpectedType(value) => Error::Query {
-                pos,
-                path: None,
-                err: QueryError::ExpectedInputType {
-                    expect: expected_type,
-                    actual: value,
-                },
+            InputValueError::Invalid {
+                expected,
+                actual,
+                hint,
+            } => QueryError::InvalidInputValue {
+                path,
+                expected,
+                actual,
+                hint,
             },
+            InputValueError::Nested { .. } => unreachable!(),
+        };
+        Error::Query {
+            pos,
+            path: None,
+            err,
         }
     }
 }
@@ -182,6 +304,25 @@ pub enum QueryError {
         reason: String,
     },
 
+    /// An input value, or an item or field of it, is invalid.
+    #[error(
+        "Invalid value for \"{path}\": expected {expected}, found {actual}.{}",
+        hint.as_ref().map(|hint| format!(" {}", hint)).unwrap_or_default()
+    )]
+    InvalidInputValue {
+        /// The path of the value, such as `input.items[1]`.
+        path: String,
+
+        /// What was expected.
+        expected: String,
+
+        /// The value that was found.
+        actual: Value,
+
+        /// A hint on how to fix the value.
+        hint: Option<String>,
+    },
+
     /// A field was not found on an object type.
     #[error("Cannot query field \"{field_name}\" on type \"{object}\".")]
     FieldNotFound {
@@ -279,9 +420,67 @@ pub enum QueryError {
     #[error("Too complex")]
     TooComplex,
 
-    /// The query was nested too deep.
-    #[error("Too deep")]
-    TooDeep,
+    /// The selection sets of the query were nested deeper than the limit.
+    #[error("Query is nested too deep, the limit is {limit}")]
+    TooDeep {
+        /// The maximum depth.
+        limit: usize,
+    },
+
+    /// The fragment spreads of the query were nested deeper than the limit.
+    #[error("Fragments are nested too deep, the limit is {limit}")]
+    FragmentTooDeep {
+        /// The maximum fragment depth.
+        limit: usize,
+    },
+
+    /// The `__schema` and `__type` fields of the query were nested deeper than the limit.
+    #[error("Introspection is nested too deep, the limit is {limit}")]
+    IntrospectionTooDeep {
+        /// The maximum introspection depth.
+        limit: usize,
+    },
+
+    /// The `__schema` and `__type` fields of the query selected more fields than the limit.
+    #[error("Introspection selects too many fields, the limit is {limit}")]
+    IntrospectionTooLarge {
+        /// The maximum number of introspection fields.
+        limit: usize,
+    },
+
+    /// The query string was longer than the limit.
+    #[error("Query is too large, the limit is {limit} bytes")]
+    TooLarge {
+        /// The maximum number of bytes.
+        limit: usize,
+    },
+
+    /// The query had more tokens than the limit.
+    #[error("Too many tokens, the limit is {limit}")]
+    TooManyTokens {
+        /// The maximum number of tokens.
+        limit: usize,
+    },
+
+    /// A field requires a preview feature that is not enabled for the request.
+    #[error("Field \"{field_name}\" is a preview of the feature \"{feature}\", which must be enabled with the X-GraphQL-Preview header")]
+    PreviewFeatureRequired {
+        /// Field name
+        field_name: String,
+
+        /// The preview feature.
+        feature: String,
+    },
+
+    /// The redaction policy of the schema returned `null` for a non-null value.
+    #[error("The redaction policy returned null for the non-null field \"{field_name}\" of type \"{ty}\"")]
+    RedactedNonNull {
+        /// Field name
+        field_name: String,
+
+        /// The type of the field.
+        ty: String,
+    },
 
     /// A field handler errored.
     #[error("Failed to resolve field: {err}")]
@@ -300,6 +499,20 @@ pub enum QueryError {
     /// "__typename" must be an existing string.
     #[error("\"__typename\" must be an existing string")]
     TypeNameNotExists,
+
+    /// An idempotency key was sent again with a different request.
+    #[error("Idempotency key \"{key}\" has already been used for a different request")]
+    IdempotencyKeyReused {
+        /// The idempotency key.
+        key: String,
+    },
+
+    /// An idempotency key was sent again before the first request completed.
+    #[error("Idempotency key \"{key}\" is in use by a request that has not completed")]
+    IdempotencyKeyPending {
+        /// The idempotency key.
+        key: String,
+    },
 }
 
 impl QueryError {
@@ -347,10 +560,36 @@ pub enum ParseRequestError {
     #[error("It's not an upload operation")]
     NotUpload,
 
+    /// A part of a multipart request was not in the right order, the "operations" part must be
+    /// first and the "map" part second.
+    #[error("Expected the \"{expected}\" part, found \"{name}\"")]
+    UnexpectedPart {
+        /// The name of the part.
+        name: String,
+        /// The name of the expected part.
+        expected: &'static str,
+    },
+
     /// Files were missing the request.
     #[error("Missing files")]
     MissingFiles,
 
+    /// A part of a multipart request was larger than the maximum file size.
+    #[error("The \"{name}\" part is too large, the limit is {limit} bytes")]
+    PartTooLarge {
+        /// The name of the part.
+        name: String,
+        /// The maximum file size.
+        limit: usize,
+    },
+
+    /// A multipart request had more files than the limit.
+    #[error("Too many files, the limit is {limit}")]
+    TooManyFiles {
+        /// The maximum number of files.
+        limit: usize,
+    },
+
     /// The request's payload is too large, and this server rejected it.
     #[error("Payload too large")]
     PayloadTooLarge,
@@ -364,9 +603,13 @@ pub enum ParseRequestError {
 impl From<multer::Error> for ParseRequestError {
     fn from(err: multer::Error) -> Self {
         match err {
-            multer::Error::FieldSizeExceeded { .. } | multer::Error::StreamSizeExceeded { .. } => {
-                ParseRequestError::PayloadTooLarge
+            multer::Error::FieldSizeExceeded { limit, field_name } => {
+                ParseRequestError::PartTooLarge {
+                    name: field_name.unwrap_or_default(),
+                    limit: limit as usize,
+                }
             }
+            multer::Error::StreamSizeExceeded { .. } => ParseRequestError::PayloadTooLarge,
             _ => ParseRequestError::InvalidMultipart(err),
         }
     }
@@ -380,6 +623,9 @@ pub struct RuleError {
 
     /// A description of this error.
     pub message: String,
+
+    /// Known names that are similar to an unknown name used in the query.
+    pub suggestions: Vec<String>,
 }
 
 /// An error serving a GraphQL query.
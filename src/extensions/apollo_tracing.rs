@@ -1,4 +1,4 @@
-use crate::extensions::{Extension, ResolveInfo};
+use crate::extensions::{Extension, ExtensionContext, ResolveInfo};
 use crate::Variables;
 use chrono::{DateTime, Utc};
 use serde::ser::SerializeMap;
@@ -70,20 +70,26 @@ impl Default for ApolloTracing {
     }
 }
 
+#[async_trait::async_trait]
 impl Extension for ApolloTracing {
     fn name(&self) -> Option<&'static str> {
         Some("tracing")
     }
 
-    fn parse_start(&mut self, _query_source: &str, _variables: &Variables) {
+    async fn parse_start(
+        &mut self,
+        _ctx: &ExtensionContext<'_>,
+        _query_source: &str,
+        _variables: &Variables,
+    ) {
         self.start_time = Utc::now();
     }
 
-    fn execution_end(&mut self) {
+    async fn execution_end(&mut self, _ctx: &ExtensionContext<'_>) {
         self.end_time = Utc::now();
     }
 
-    fn resolve_start(&mut self, info: &ResolveInfo<'_>) {
+    async fn resolve_start(&mut self, _ctx: &ExtensionContext<'_>, info: &ResolveInfo<'_>) {
         self.pending_resolves.insert(
             info.resolve_id.current,
             PendingResolve {
@@ -96,7 +102,7 @@ impl Extension for ApolloTracing {
         );
     }
 
-    fn resolve_end(&mut self, info: &ResolveInfo<'_>) {
+    async fn resolve_end(&mut self, _ctx: &ExtensionContext<'_>, info: &ResolveInfo<'_>) {
         if let Some(pending_resolve) = self.pending_resolves.remove(&info.resolve_id.current) {
             let start_offset = (pending_resolve.start_time - self.start_time)
                 .num_nanoseconds()
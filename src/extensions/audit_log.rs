@@ -0,0 +1,209 @@
+use crate::extensions::{Extension, ExtensionContext, ResolveInfo};
+use crate::parser::types::{Name, OperationType};
+use crate::{Context, Error, Value};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// A record of a mutation field invocation, see [`AuditLog`](struct.AuditLog.html).
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditEntry {
+    /// The name of the operation.
+    pub operation_name: Option<String>,
+
+    /// The name of the mutation field.
+    pub field: String,
+
+    /// The arguments of the field, with the values of variables substituted and the sensitive
+    /// arguments replaced by `"[REDACTED]"`.
+    pub arguments: BTreeMap<Name, Value>,
+
+    /// The identity of the caller.
+    pub caller: Option<String>,
+
+    /// The error if the field failed, formatted with `Display`.
+    pub error: Option<String>,
+}
+
+/// Receives the entries of an [`AuditLog`](struct.AuditLog.html).
+pub trait AuditSink: Send + Sync + 'static {
+    /// Record an entry.
+    fn record(&self, entry: AuditEntry);
+}
+
+impl<F: Fn(AuditEntry) + Send + Sync + 'static> AuditSink for F {
+    fn record(&self, entry: AuditEntry) {
+        self(entry)
+    }
+}
+
+type IdentityFn = dyn Fn(&Context<'_>) -> Option<String> + Send + Sync;
+
+/// Audit log extension
+///
+/// Records every invocation of a mutation field, with its arguments, the caller and whether it
+/// succeeded, to an [`AuditSink`](trait.AuditSink.html).
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+/// use async_graphql::extensions::{AuditEntry, AuditLog};
+///
+/// struct CurrentUser(String);
+///
+/// struct QueryRoot;
+///
+/// #[Object]
+/// impl QueryRoot {
+///     async fn value(&self) -> i32 {
+///         10
+///     }
+/// }
+///
+/// struct MutationRoot;
+///
+/// #[Object]
+/// impl MutationRoot {
+///     async fn change_password(&self, password: String) -> bool {
+///         true
+///     }
+/// }
+///
+/// let audit_log = AuditLog::new(|entry: AuditEntry| println!("{:?}", entry))
+///     .sanitize("password")
+///     .identity(|ctx| ctx.data_opt::<CurrentUser>().map(|user| user.0.clone()));
+/// let schema = Schema::build(QueryRoot, MutationRoot, EmptySubscription)
+///     .extension(move || audit_log.clone())
+///     .finish();
+/// ```
+#[derive(Clone)]
+pub struct AuditLog {
+    sink: Arc<dyn AuditSink>,
+    sanitize: Arc<Vec<String>>,
+    identity: Option<Arc<IdentityFn>>,
+    pending: Option<AuditEntry>,
+}
+
+impl AuditLog {
+    /// Create an audit log that records to `sink`.
+    pub fn new(sink: impl AuditSink) -> Self {
+        Self {
+            sink: Arc::new(sink),
+            sanitize: Default::default(),
+            identity: None,
+            pending: None,
+        }
+    }
+
+    /// Redact the values of the arguments and input object fields with this name.
+    pub fn sanitize(mut self, name: impl Into<String>) -> Self {
+        Arc::make_mut(&mut self.sanitize).push(name.into());
+        self
+    }
+
+    /// Set the function that gets the identity of the caller, usually from the data of the
+    /// request.
+    pub fn identity(
+        mut self,
+        f: impl Fn(&Context<'_>) -> Option<String> + Send + Sync + 'static,
+    ) -> Self {
+        self.identity = Some(Arc::new(f));
+        self
+    }
+
+    fn sanitize_value(&self, value: Value) -> Value {
+        match value {
+            Value::List(items) => Value::List(
+                items
+                    .into_iter()
+                    .map(|item| self.sanitize_value(item))
+                    .collect(),
+            ),
+            Value::Object(fields) => Value::Object(self.sanitize_fields(fields)),
+            Value::Upload(_) => Value::String("[UPLOAD]".to_string()),
+            value => value,
+        }
+    }
+
+    fn sanitize_fields(&self, fields: BTreeMap<Name, Value>) -> BTreeMap<Name, Value> {
+        fields
+            .into_iter()
+            .map(|(name, value)| {
+                let value = if self.sanitize.iter().any(|s| s == name.as_str()) {
+                    Value::String("[REDACTED]".to_string())
+                } else {
+                    self.sanitize_value(value)
+                };
+                (name, value)
+            })
+            .collect()
+    }
+
+    fn finish(&mut self, error: Option<String>) {
+        if let Some(mut entry) = self.pending.take() {
+            entry.error = error;
+            self.sink.record(entry);
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Extension for AuditLog {
+    async fn resolve_start(&mut self, _ctx: &ExtensionContext<'_>, info: &ResolveInfo<'_>) {
+        let ctx = info.context;
+        let operation = &ctx.query_env.document.operation.node;
+        if operation.ty != OperationType::Mutation || info.path_node.parent.is_some() {
+            return;
+        }
+
+        let variables = &ctx.query_env.variables;
+        let arguments = ctx
+            .item
+            .node
+            .arguments
+            .iter()
+            .map(|(name, value)| {
+                let value = value
+                    .node
+                    .clone()
+                    .into_const_with(|name| {
+                        Ok::<_, ()>(variables.0.get(&name).cloned().unwrap_or(Value::Null))
+                    })
+                    .unwrap_or(Value::Null);
+                (name.node.clone(), value)
+            })
+            .collect();
+
+        self.pending = Some(AuditEntry {
+            operation_name: operation.name.as_ref().map(|name| name.node.to_string()),
+            field: ctx.item.node.name.node.to_string(),
+            arguments: self.sanitize_fields(arguments),
+            caller: self.identity.as_ref().and_then(|identity| identity(ctx)),
+            error: None,
+        });
+    }
+
+    async fn resolve_end(&mut self, _ctx: &ExtensionContext<'_>, info: &ResolveInfo<'_>) {
+        if info.path_node.parent.is_none() {
+            self.finish(None);
+        }
+    }
+
+    async fn error(&mut self, _ctx: &ExtensionContext<'_>, err: &Error) {
+        // Mutation fields are resolved one by one, so the pending entry is the one that failed.
+        // An error deeper in the path comes from the payload of a mutation that has already run,
+        // so the entry is recorded as succeeded.
+        let depth = match err {
+            Error::Query {
+                path: Some(serde_json::Value::Array(path)),
+                ..
+            } => path.len(),
+            _ => return,
+        };
+        if depth == 1 {
+            self.finish(Some(err.to_string()));
+        } else {
+            self.finish(None);
+        }
+    }
+}
@@ -1,18 +1,57 @@
-use crate::extensions::{Extension, ResolveInfo};
+use crate::extensions::{Extension, ExtensionContext, ResolveInfo};
 use crate::parser::types::{ExecutableDefinition, ExecutableDocument, OperationType, Selection};
-use crate::{Error, Variables};
+use crate::{Error, RequestExtensions, Value, Variables};
 use itertools::Itertools;
-use log::{error, info, trace};
+use log::{error, info, trace, warn};
 use std::borrow::Cow;
+use std::time::{Duration, Instant};
 use uuid::Uuid;
 
 /// Logger extension
+///
+/// Logs each query, the errors and a summary of each execution with the operation name, the
+/// duration and the number of errors, and warns about the executions that are slower than the
+/// [slow query threshold](#method.slow_query_threshold).
+///
+/// The values of the variables whose names contain `password`, `secret` or `token`, or a name
+/// added with [`redact_variable`](#method.redact_variable), are replaced with `"[REDACTED]"`,
+/// also in nested input objects.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+/// use async_graphql::extensions::Logger;
+/// use std::time::Duration;
+///
+/// struct QueryRoot;
+///
+/// #[Object]
+/// impl QueryRoot {
+///     async fn value(&self) -> i32 {
+///         10
+///     }
+/// }
+///
+/// let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+///     .extension(|| {
+///         Logger::default()
+///             .slow_query_threshold(Duration::from_millis(500))
+///             .redact_variable("creditCard")
+///     })
+///     .finish();
+/// ```
 #[cfg_attr(feature = "nightly", doc(cfg(feature = "log")))]
 pub struct Logger {
     id: Uuid,
     enabled: bool,
     query: String,
     variables: Variables,
+    redacted_variables: Vec<String>,
+    slow_query_threshold: Option<Duration>,
+    start: Instant,
+    operation_name: Option<String>,
+    error_count: usize,
 }
 
 impl Default for Logger {
@@ -22,17 +61,94 @@ impl Default for Logger {
             enabled: true,
             query: String::new(),
             variables: Default::default(),
+            redacted_variables: vec![
+                "password".to_string(),
+                "secret".to_string(),
+                "token".to_string(),
+            ],
+            slow_query_threshold: None,
+            start: Instant::now(),
+            operation_name: None,
+            error_count: 0,
         }
     }
 }
 
+impl Logger {
+    /// Warn about the executions that take longer than `threshold`.
+    pub fn slow_query_threshold(mut self, threshold: Duration) -> Self {
+        self.slow_query_threshold = Some(threshold);
+        self
+    }
+
+    /// Redact the values of the variables whose names contain `name`, ignoring the case.
+    pub fn redact_variable(mut self, name: impl Into<String>) -> Self {
+        self.redacted_variables.push(name.into().to_lowercase());
+        self
+    }
+
+    fn is_redacted(&self, name: &str) -> bool {
+        let name = name.to_lowercase();
+        self.redacted_variables
+            .iter()
+            .any(|redacted| name.contains(redacted.as_str()))
+    }
+
+    fn redact(&self, value: &Value) -> Value {
+        match value {
+            Value::List(values) => {
+                Value::List(values.iter().map(|value| self.redact(value)).collect())
+            }
+            Value::Object(fields) => Value::Object(
+                fields
+                    .iter()
+                    .map(|(name, value)| {
+                        if self.is_redacted(name) {
+                            (name.clone(), Value::String("[REDACTED]".to_string()))
+                        } else {
+                            (name.clone(), self.redact(value))
+                        }
+                    })
+                    .collect(),
+            ),
+            value => value.clone(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
 impl Extension for Logger {
-    fn parse_start(&mut self, query_source: &str, variables: &Variables) {
+    async fn request_start(
+        &mut self,
+        _ctx: &ExtensionContext<'_>,
+        _extensions: &RequestExtensions,
+    ) {
+        self.start = Instant::now();
+    }
+
+    async fn parse_start(
+        &mut self,
+        _ctx: &ExtensionContext<'_>,
+        query_source: &str,
+        variables: &Variables,
+    ) {
         self.query = query_source.replace(char::is_whitespace, "");
-        self.variables = variables.clone();
+        self.variables = Variables(
+            variables
+                .0
+                .iter()
+                .map(|(name, value)| {
+                    if self.is_redacted(name) {
+                        (name.clone(), Value::String("[REDACTED]".to_string()))
+                    } else {
+                        (name.clone(), self.redact(value))
+                    }
+                })
+                .collect(),
+        );
     }
 
-    fn parse_end(&mut self, document: &ExecutableDocument) {
+    async fn parse_end(&mut self, _ctx: &ExtensionContext<'_>, document: &ExecutableDocument) {
         let is_schema = document
             .definitions
             .iter()
@@ -50,21 +166,50 @@ impl Extension for Logger {
         info!(target: "async-graphql", "[Query] id: \"{}\", query: \"{}\", variables: {}", self.id, &self.query, self.variables);
     }
 
-    fn resolve_start(&mut self, info: &ResolveInfo<'_>) {
+    async fn execution_end(&mut self, _ctx: &ExtensionContext<'_>) {
+        if !self.enabled {
+            return;
+        }
+        let duration = self.start.elapsed();
+        let operation_name = self.operation_name.as_deref().unwrap_or("");
+        info!(target: "async-graphql", "[Execution] id: \"{}\", operation: \"{}\", variables: {}, duration: {}ms, errors: {}", self.id, operation_name, self.variables, duration.as_millis(), self.error_count);
+        if let Some(threshold) = self.slow_query_threshold {
+            if duration > threshold {
+                warn!(target: "async-graphql", "[SlowQuery] id: \"{}\", operation: \"{}\", query: \"{}\", variables: {}, duration: {}ms, threshold: {}ms", self.id, operation_name, self.query, self.variables, duration.as_millis(), threshold.as_millis());
+            }
+        }
+    }
+
+    async fn resolve_start(&mut self, _ctx: &ExtensionContext<'_>, info: &ResolveInfo<'_>) {
+        if self.operation_name.is_none() {
+            self.operation_name = info
+                .context
+                .query_env
+                .document
+                .operation
+                .node
+                .name
+                .as_ref()
+                .map(|name| name.node.to_string());
+        }
         if !self.enabled {
             return;
         }
         trace!(target: "async-graphql", "[ResolveStart] id: \"{}\", path: \"{}\"", self.id, info.path_node);
     }
 
-    fn resolve_end(&mut self, info: &ResolveInfo<'_>) {
+    async fn resolve_end(&mut self, _ctx: &ExtensionContext<'_>, info: &ResolveInfo<'_>) {
         if !self.enabled {
             return;
         }
         trace!(target: "async-graphql", "[ResolveEnd] id: \"{}\", path: \"{}\"", self.id, info.path_node);
     }
 
-    fn error(&mut self, err: &Error) {
+    async fn error(&mut self, _ctx: &ExtensionContext<'_>, err: &Error) {
+        self.error_count += match err {
+            Error::Rule { errors } => errors.len(),
+            _ => 1,
+        };
         match err {
             Error::Parse(err) => {
                 error!(target: "async-graphql", "[ParseError] id: \"{}\", pos: [{}:{}], query: \"{}\", variables: {}, {}", self.id, err.pos.line, err.pos.column, self.query, self.variables, err)
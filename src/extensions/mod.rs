@@ -2,32 +2,57 @@
 
 #[cfg(feature = "apollo_tracing")]
 mod apollo_tracing;
+mod audit_log;
 #[cfg(feature = "log")]
 mod logger;
+#[cfg(feature = "opentelemetry")]
+mod opentelemetry;
 #[cfg(feature = "tracing")]
 mod tracing;
 
 use crate::context::{QueryPathNode, ResolveId};
-use crate::{Context, Result, Variables};
+use crate::{Context, Data, RequestExtensions, Result, Variables};
 
 #[cfg(feature = "apollo_tracing")]
 pub use self::apollo_tracing::ApolloTracing;
+pub use self::audit_log::{AuditEntry, AuditLog, AuditSink};
 #[cfg(feature = "log")]
 pub use self::logger::Logger;
+#[cfg(feature = "opentelemetry")]
+pub use self::opentelemetry::OpenTelemetry;
 #[cfg(feature = "tracing")]
 pub use self::tracing::Tracing;
 use crate::parser::types::ExecutableDocument;
 use crate::Error;
 use serde_json::Value;
+use std::any::Any;
 
 pub(crate) type BoxExtension = Box<dyn Extension>;
 
 #[doc(hidden)]
 pub struct Extensions(pub(crate) Vec<BoxExtension>);
 
-/// Parameters for `Extension::resolve_field_start`
+/// The extension instances of a request, in an async mutex because the hooks of the extensions
+/// are awaited while it is locked.
+#[doc(hidden)]
+pub type ExtensionsLock = futures::lock::Mutex<Extensions>;
+
+/// Context passed to the hooks of an extension
+pub struct ExtensionContext<'a> {
+    /// The data of the request.
+    pub data: &'a Data,
+}
+
+impl<'a> ExtensionContext<'a> {
+    /// Gets the data of the request of type `D`, or `None` if it has not been inserted.
+    pub fn data_opt<D: Any + Send + Sync>(&self) -> Option<&'a D> {
+        self.data.get::<D>()
+    }
+}
+
+/// Parameters for `Extension::resolve_start`
 pub struct ResolveInfo<'a> {
-    /// Because resolver is concurrent, `Extension::resolve_field_start` and `Extension::resolve_field_end` are
+    /// Because resolver is concurrent, `Extension::resolve_start` and `Extension::resolve_end` are
     /// not strictly ordered, so each pair is identified by an id.
     pub resolve_id: ResolveId,
 
@@ -45,6 +70,39 @@ pub struct ResolveInfo<'a> {
 }
 
 /// Represents a GraphQL extension
+///
+/// A new instance of each extension is created for every request, so it can keep the state of the
+/// request in its fields. The hooks are async, so they can await other services, such as a metrics
+/// sink or a persisted query store, and receive an [`ExtensionContext`](struct.ExtensionContext.html)
+/// which gives access to the data of the request.
+///
+/// ```no_run
+/// use async_graphql::*;
+/// use async_graphql::extensions::{Extension, ExtensionContext};
+///
+/// struct ClientName(String);
+///
+/// #[derive(Default)]
+/// struct ClientMetrics {
+///     client: Option<String>,
+/// }
+///
+/// #[async_trait::async_trait]
+/// impl Extension for ClientMetrics {
+///     async fn request_start(
+///         &mut self,
+///         ctx: &ExtensionContext<'_>,
+///         _extensions: &RequestExtensions,
+///     ) {
+///         self.client = ctx.data_opt::<ClientName>().map(|name| name.0.clone());
+///     }
+///
+///     async fn execution_end(&mut self, _ctx: &ExtensionContext<'_>) {
+///         // Await a metrics sink with `self.client` here.
+///     }
+/// }
+/// ```
+#[async_trait::async_trait]
 #[allow(unused_variables)]
 pub trait Extension: Sync + Send + 'static {
     /// If this extension needs to output data to query results, you need to specify a name.
@@ -52,32 +110,41 @@ pub trait Extension: Sync + Send + 'static {
         None
     }
 
+    /// Called at the begin of the request, with the `extensions` of the request.
+    async fn request_start(&mut self, ctx: &ExtensionContext<'_>, extensions: &RequestExtensions) {}
+
     /// Called at the begin of the parse.
-    fn parse_start(&mut self, query_source: &str, variables: &Variables) {}
+    async fn parse_start(
+        &mut self,
+        ctx: &ExtensionContext<'_>,
+        query_source: &str,
+        variables: &Variables,
+    ) {
+    }
 
     /// Called at the end of the parse.
-    fn parse_end(&mut self, document: &ExecutableDocument) {}
+    async fn parse_end(&mut self, ctx: &ExtensionContext<'_>, document: &ExecutableDocument) {}
 
     /// Called at the begin of the validation.
-    fn validation_start(&mut self) {}
+    async fn validation_start(&mut self, ctx: &ExtensionContext<'_>) {}
 
     /// Called at the end of the validation.
-    fn validation_end(&mut self) {}
+    async fn validation_end(&mut self, ctx: &ExtensionContext<'_>) {}
 
     /// Called at the begin of the execution.
-    fn execution_start(&mut self) {}
+    async fn execution_start(&mut self, ctx: &ExtensionContext<'_>) {}
 
     /// Called at the end of the execution.
-    fn execution_end(&mut self) {}
+    async fn execution_end(&mut self, ctx: &ExtensionContext<'_>) {}
 
     /// Called at the begin of the resolve field.
-    fn resolve_start(&mut self, info: &ResolveInfo<'_>) {}
+    async fn resolve_start(&mut self, ctx: &ExtensionContext<'_>, info: &ResolveInfo<'_>) {}
 
     /// Called at the end of the resolve field.
-    fn resolve_end(&mut self, info: &ResolveInfo<'_>) {}
+    async fn resolve_end(&mut self, ctx: &ExtensionContext<'_>, info: &ResolveInfo<'_>) {}
 
     /// Called when an error occurs.
-    fn error(&mut self, err: &Error) {}
+    async fn error(&mut self, ctx: &ExtensionContext<'_>, err: &Error) {}
 
     /// Get the results
     fn result(&mut self) -> Option<serde_json::Value> {
@@ -85,56 +152,86 @@ pub trait Extension: Sync + Send + 'static {
     }
 }
 
+#[async_trait::async_trait]
 pub(crate) trait ErrorLogger {
-    fn log_error(self, extensions: &spin::Mutex<Extensions>) -> Self;
+    async fn log_error(self, extensions: &ExtensionsLock, ctx: &ExtensionContext<'_>) -> Self;
 }
 
-impl<T> ErrorLogger for Result<T> {
-    fn log_error(self, extensions: &spin::Mutex<Extensions>) -> Self {
+#[async_trait::async_trait]
+impl<T: Send> ErrorLogger for Result<T> {
+    async fn log_error(self, extensions: &ExtensionsLock, ctx: &ExtensionContext<'_>) -> Self {
         if let Err(err) = &self {
-            extensions.lock().error(err);
+            extensions.lock().await.error(ctx, err).await;
         }
         self
     }
 }
 
+#[async_trait::async_trait]
 impl Extension for Extensions {
-    fn parse_start(&mut self, query_source: &str, variables: &Variables) {
-        self.0
-            .iter_mut()
-            .for_each(|e| e.parse_start(query_source, variables));
+    async fn request_start(&mut self, ctx: &ExtensionContext<'_>, extensions: &RequestExtensions) {
+        for e in &mut self.0 {
+            e.request_start(ctx, extensions).await;
+        }
     }
 
-    fn parse_end(&mut self, document: &ExecutableDocument) {
-        self.0.iter_mut().for_each(|e| e.parse_end(document));
+    async fn parse_start(
+        &mut self,
+        ctx: &ExtensionContext<'_>,
+        query_source: &str,
+        variables: &Variables,
+    ) {
+        for e in &mut self.0 {
+            e.parse_start(ctx, query_source, variables).await;
+        }
     }
 
-    fn validation_start(&mut self) {
-        self.0.iter_mut().for_each(|e| e.validation_start());
+    async fn parse_end(&mut self, ctx: &ExtensionContext<'_>, document: &ExecutableDocument) {
+        for e in &mut self.0 {
+            e.parse_end(ctx, document).await;
+        }
     }
 
-    fn validation_end(&mut self) {
-        self.0.iter_mut().for_each(|e| e.validation_end());
+    async fn validation_start(&mut self, ctx: &ExtensionContext<'_>) {
+        for e in &mut self.0 {
+            e.validation_start(ctx).await;
+        }
+    }
+
+    async fn validation_end(&mut self, ctx: &ExtensionContext<'_>) {
+        for e in &mut self.0 {
+            e.validation_end(ctx).await;
+        }
     }
 
-    fn execution_start(&mut self) {
-        self.0.iter_mut().for_each(|e| e.execution_start());
+    async fn execution_start(&mut self, ctx: &ExtensionContext<'_>) {
+        for e in &mut self.0 {
+            e.execution_start(ctx).await;
+        }
     }
 
-    fn execution_end(&mut self) {
-        self.0.iter_mut().for_each(|e| e.execution_end());
+    async fn execution_end(&mut self, ctx: &ExtensionContext<'_>) {
+        for e in &mut self.0 {
+            e.execution_end(ctx).await;
+        }
     }
 
-    fn resolve_start(&mut self, info: &ResolveInfo<'_>) {
-        self.0.iter_mut().for_each(|e| e.resolve_start(info));
+    async fn resolve_start(&mut self, ctx: &ExtensionContext<'_>, info: &ResolveInfo<'_>) {
+        for e in &mut self.0 {
+            e.resolve_start(ctx, info).await;
+        }
     }
 
-    fn resolve_end(&mut self, resolve_id: &ResolveInfo<'_>) {
-        self.0.iter_mut().for_each(|e| e.resolve_end(resolve_id));
+    async fn resolve_end(&mut self, ctx: &ExtensionContext<'_>, info: &ResolveInfo<'_>) {
+        for e in &mut self.0 {
+            e.resolve_end(ctx, info).await;
+        }
     }
 
-    fn error(&mut self, err: &Error) {
-        self.0.iter_mut().for_each(|e| e.error(err));
+    async fn error(&mut self, ctx: &ExtensionContext<'_>, err: &Error) {
+        for e in &mut self.0 {
+            e.error(ctx, err).await;
+        }
     }
 
     fn result(&mut self) -> Option<Value> {
@@ -0,0 +1,205 @@
+use crate::extensions::{Extension, ExtensionContext, ResolveInfo};
+use crate::parser::types::ExecutableDocument;
+use crate::{Error, RequestExtensions, Variables};
+use opentelemetry::api::trace::{Span, SpanKind, TraceContextExt, Tracer};
+use opentelemetry::api::{Context as OpenTelemetryContext, KeyValue};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// OpenTelemetry extension
+///
+/// Creates a `request` span for each request, with the `parse`, `validation` and `execute` spans
+/// as children and a `field` span for each resolver, which has the `graphql.path`,
+/// `graphql.parentType` and `graphql.returnType` attributes.
+///
+/// The `request` span is a child of the `opentelemetry::api::Context` in the data of the request,
+/// for example one extracted from the headers of an HTTP request with a propagator, or of the
+/// current context if there is none.
+///
+/// The variables of the requests can contain passwords or tokens, so they are only recorded, as
+/// the `graphql.variables` attribute of the `parse` span, with
+/// [`record_variables`](#method.record_variables).
+///
+/// # Examples
+///
+/// ```no_run
+/// use async_graphql::*;
+/// use async_graphql::extensions::OpenTelemetry;
+/// use opentelemetry::api::Provider;
+/// use std::sync::Arc;
+///
+/// struct QueryRoot;
+///
+/// #[Object]
+/// impl QueryRoot {
+///     async fn value(&self) -> i32 {
+///         10
+///     }
+/// }
+///
+/// let tracer = Arc::new(opentelemetry::sdk::Provider::default().get_tracer("graphql"));
+/// let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+///     .extension(move || OpenTelemetry::new(tracer.clone()))
+///     .finish();
+/// ```
+///
+/// # References
+///
+/// <https://crates.io/crates/opentelemetry>
+#[cfg_attr(feature = "nightly", doc(cfg(feature = "opentelemetry")))]
+pub struct OpenTelemetry<T> {
+    tracer: Arc<T>,
+    record_variables: bool,
+    request: Option<OpenTelemetryContext>,
+    parse: Option<OpenTelemetryContext>,
+    validation: Option<OpenTelemetryContext>,
+    execute: Option<OpenTelemetryContext>,
+    fields: BTreeMap<usize, OpenTelemetryContext>,
+}
+
+impl<T: Tracer + Send + Sync> OpenTelemetry<T> {
+    /// Create an extension which creates the spans with `tracer`.
+    pub fn new(tracer: Arc<T>) -> Self {
+        Self {
+            tracer,
+            record_variables: false,
+            request: None,
+            parse: None,
+            validation: None,
+            execute: None,
+            fields: BTreeMap::new(),
+        }
+    }
+
+    /// Record the variables of the requests in the `parse` spans.
+    pub fn record_variables(mut self) -> Self {
+        self.record_variables = true;
+        self
+    }
+
+    fn start_span(
+        &self,
+        name: &str,
+        kind: SpanKind,
+        parent: &OpenTelemetryContext,
+        attributes: Vec<KeyValue>,
+    ) -> OpenTelemetryContext {
+        let span = self.tracer.build_with_context(
+            self.tracer
+                .span_builder(name)
+                .with_kind(kind)
+                .with_attributes(attributes),
+            parent,
+        );
+        parent.with_span(span)
+    }
+
+    fn start_request_child(
+        &self,
+        name: &str,
+        attributes: Vec<KeyValue>,
+    ) -> Option<OpenTelemetryContext> {
+        let request = self.request.as_ref()?;
+        Some(self.start_span(name, SpanKind::Internal, request, attributes))
+    }
+}
+
+fn end_span(cx: Option<OpenTelemetryContext>) {
+    if let Some(cx) = cx {
+        cx.span().end();
+    }
+}
+
+#[async_trait::async_trait]
+impl<T: Tracer + Send + Sync> Extension for OpenTelemetry<T> {
+    async fn request_start(&mut self, ctx: &ExtensionContext<'_>, _extensions: &RequestExtensions) {
+        let parent = ctx
+            .data_opt::<OpenTelemetryContext>()
+            .cloned()
+            .unwrap_or_else(OpenTelemetryContext::current);
+        self.request = Some(self.start_span("request", SpanKind::Server, &parent, Vec::new()));
+    }
+
+    async fn parse_start(
+        &mut self,
+        _ctx: &ExtensionContext<'_>,
+        query_source: &str,
+        variables: &Variables,
+    ) {
+        let mut attributes = vec![KeyValue::new("graphql.source", query_source)];
+        if self.record_variables {
+            attributes.push(KeyValue::new("graphql.variables", variables.to_string()));
+        }
+        self.parse = self.start_request_child("parse", attributes);
+    }
+
+    async fn parse_end(&mut self, _ctx: &ExtensionContext<'_>, _document: &ExecutableDocument) {
+        end_span(self.parse.take());
+    }
+
+    async fn validation_start(&mut self, _ctx: &ExtensionContext<'_>) {
+        self.validation = self.start_request_child("validation", Vec::new());
+    }
+
+    async fn validation_end(&mut self, _ctx: &ExtensionContext<'_>) {
+        end_span(self.validation.take());
+    }
+
+    async fn execution_start(&mut self, _ctx: &ExtensionContext<'_>) {
+        self.execute = self.start_request_child("execute", Vec::new());
+    }
+
+    async fn execution_end(&mut self, _ctx: &ExtensionContext<'_>) {
+        end_span(self.execute.take());
+        end_span(self.request.take());
+    }
+
+    async fn resolve_start(&mut self, _ctx: &ExtensionContext<'_>, info: &ResolveInfo<'_>) {
+        let parent = info
+            .resolve_id
+            .parent
+            .and_then(|id| self.fields.get(&id))
+            .or_else(|| self.execute.as_ref())
+            .or_else(|| self.request.as_ref());
+        if let Some(parent) = parent {
+            let cx = self.start_span(
+                "field",
+                SpanKind::Internal,
+                parent,
+                vec![
+                    KeyValue::new("graphql.path", info.path_node.to_string()),
+                    KeyValue::new("graphql.parentType", info.parent_type),
+                    KeyValue::new("graphql.returnType", info.return_type),
+                ],
+            );
+            self.fields.insert(info.resolve_id.current, cx);
+        }
+    }
+
+    async fn resolve_end(&mut self, _ctx: &ExtensionContext<'_>, info: &ResolveInfo<'_>) {
+        end_span(self.fields.remove(&info.resolve_id.current));
+    }
+
+    async fn error(&mut self, _ctx: &ExtensionContext<'_>, err: &Error) {
+        if let Some(cx) = &self.request {
+            cx.span().add_event(
+                "error".to_string(),
+                vec![KeyValue::new("graphql.error", err.to_string())],
+            );
+        }
+    }
+}
+
+impl<T> Drop for OpenTelemetry<T> {
+    fn drop(&mut self) {
+        // The request ends early when the query is invalid, and the spans of the resolvers are
+        // not ended when they return an error. The children are ended before their parents.
+        for (_, cx) in std::mem::take(&mut self.fields).into_iter().rev() {
+            end_span(Some(cx));
+        }
+        end_span(self.execute.take());
+        end_span(self.validation.take());
+        end_span(self.parse.take());
+        end_span(self.request.take());
+    }
+}
@@ -1,25 +1,66 @@
-use crate::extensions::{Extension, ResolveInfo};
-use crate::Variables;
+use crate::extensions::{Extension, ExtensionContext, ResolveInfo};
+use crate::parser::types::ExecutableDocument;
+use crate::{Error, RequestExtensions, Variables};
 use std::collections::BTreeMap;
-use tracing::{event, span, Id, Level};
+use tracing::{event, span, Level, Span};
 use uuid::Uuid;
 
 /// Tracing extension
 ///
+/// Creates a `graphql` span for each request, with the `parse`, `validation` and `execute` spans
+/// as children and a `field` span with the `path`, `parent_type` and `return_type` of each
+/// resolver, and emits an event for the query and for each error.
+///
+/// # Examples
+///
+/// ```no_run
+/// use async_graphql::*;
+/// use async_graphql::extensions::Tracing;
+///
+/// struct QueryRoot;
+///
+/// #[Object]
+/// impl QueryRoot {
+///     async fn value(&self) -> i32 {
+///         10
+///     }
+/// }
+///
+/// let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+///     .extension(Tracing::default)
+///     .finish();
+/// ```
+///
 /// # References
 ///
 /// <https://crates.io/crates/tracing>
 #[derive(Default)]
 #[cfg_attr(feature = "nightly", doc(cfg(feature = "tracing")))]
 pub struct Tracing {
-    root_id: Option<Id>,
-    fields: BTreeMap<usize, Id>,
+    root: Option<Span>,
+    parse: Option<Span>,
+    validation: Option<Span>,
+    execute: Option<Span>,
+    fields: BTreeMap<usize, Span>,
 }
 
+impl Tracing {
+    fn exit_root(&mut self) {
+        if let Some(id) = self.root.take().as_ref().and_then(Span::id) {
+            tracing::dispatcher::get_default(|d| d.exit(&id));
+        }
+    }
+}
+
+#[async_trait::async_trait]
 impl Extension for Tracing {
     #[allow(clippy::deref_addrof)]
-    fn parse_start(&mut self, query_source: &str, variables: &Variables) {
-        let root_span: tracing::Span = span!(
+    async fn request_start(
+        &mut self,
+        _ctx: &ExtensionContext<'_>,
+        _extensions: &RequestExtensions,
+    ) {
+        let root_span: Span = span!(
             target: "async_graphql::graphql",
             parent:None,
             Level::INFO,
@@ -29,8 +70,20 @@ impl Extension for Tracing {
 
         if let Some(id) = root_span.id() {
             tracing::dispatcher::get_default(|d| d.enter(&id));
-            self.root_id.replace(id);
         }
+        self.root.replace(root_span);
+    }
+
+    #[allow(clippy::deref_addrof)]
+    async fn parse_start(
+        &mut self,
+        _ctx: &ExtensionContext<'_>,
+        query_source: &str,
+        variables: &Variables,
+    ) {
+        self.parse = self.root.as_ref().map(
+            |root| span!(target: "async_graphql::graphql", parent: root, Level::INFO, "parse"),
+        );
 
         event!(
             target: "async_graphql::query",
@@ -40,35 +93,79 @@ impl Extension for Tracing {
         );
     }
 
-    fn execution_end(&mut self) {
-        if let Some(id) = self.root_id.take() {
-            tracing::dispatcher::get_default(|d| d.exit(&id));
-        }
+    async fn parse_end(&mut self, _ctx: &ExtensionContext<'_>, _document: &ExecutableDocument) {
+        self.parse.take();
     }
 
-    fn resolve_start(&mut self, info: &ResolveInfo<'_>) {
+    async fn validation_start(&mut self, _ctx: &ExtensionContext<'_>) {
+        self.validation = self.root.as_ref().map(
+            |root| span!(target: "async_graphql::graphql", parent: root, Level::INFO, "validation"),
+        );
+    }
+
+    async fn validation_end(&mut self, _ctx: &ExtensionContext<'_>) {
+        self.validation.take();
+    }
+
+    async fn execution_start(&mut self, _ctx: &ExtensionContext<'_>) {
+        self.execute = self.root.as_ref().map(
+            |root| span!(target: "async_graphql::graphql", parent: root, Level::INFO, "execute"),
+        );
+    }
+
+    async fn execution_end(&mut self, _ctx: &ExtensionContext<'_>) {
+        self.execute.take();
+        self.exit_root();
+    }
+
+    async fn resolve_start(&mut self, _ctx: &ExtensionContext<'_>, info: &ResolveInfo<'_>) {
         let parent_span = info
             .resolve_id
             .parent
             .and_then(|id| self.fields.get(&id))
-            .or_else(|| self.root_id.as_ref())
-            .cloned();
+            .and_then(Span::id)
+            .or_else(|| self.execute.as_ref().and_then(Span::id))
+            .or_else(|| self.root.as_ref().and_then(Span::id));
         let span = span!(
             target: "async_graphql::field",
             parent: parent_span,
             Level::INFO,
             "field",
             path = %info.path_node,
+            parent_type = %info.parent_type,
+            return_type = %info.return_type,
         );
         if let Some(id) = span.id() {
             tracing::dispatcher::get_default(|d| d.enter(&id));
-            self.fields.insert(info.resolve_id.current, id);
+            self.fields.insert(info.resolve_id.current, span);
         }
     }
 
-    fn resolve_end(&mut self, info: &ResolveInfo<'_>) {
-        if let Some(id) = self.fields.remove(&info.resolve_id.current) {
+    async fn resolve_end(&mut self, _ctx: &ExtensionContext<'_>, info: &ResolveInfo<'_>) {
+        if let Some(id) = self
+            .fields
+            .remove(&info.resolve_id.current)
+            .as_ref()
+            .and_then(Span::id)
+        {
             tracing::dispatcher::get_default(|d| d.exit(&id));
         }
     }
+
+    #[allow(clippy::deref_addrof)]
+    async fn error(&mut self, _ctx: &ExtensionContext<'_>, err: &Error) {
+        event!(
+            target: "async_graphql::graphql",
+            Level::INFO,
+            error = %err,
+            "error"
+        );
+    }
+}
+
+impl Drop for Tracing {
+    fn drop(&mut self) {
+        // The execution does not start when the query is invalid.
+        self.exit_root();
+    }
 }
@@ -1,7 +1,14 @@
 //! Field guards
+//!
+//! Several guards can be given to the `guard` attribute of a field, and they must all pass. They
+//! can also be combined with `and(...)`, `or(...)` and `race(...)`, which correspond to the methods
+//! of [`GuardExt`](trait.GuardExt.html), for example
+//! `#[field(guard(or(RoleGuard(role = "Role::Admin"), and(OwnerGuard(), VerifiedGuard()))))]`.
+//! Post guards can be combined with `and(...)` and `or(...)`.
 
-use crate::{Context, FieldResult};
+use crate::{Context, FieldError, FieldResult};
 use serde::export::PhantomData;
+use std::collections::HashSet;
 
 /// Field guard
 ///
@@ -20,6 +27,16 @@ pub trait GuardExt: Guard + Sized {
     fn and<R: Guard>(self, other: R) -> And<Self, R> {
         And(self, other)
     }
+
+    /// Allow access if either of the guards does, trying them in order.
+    fn or<R: Guard>(self, other: R) -> Or<Self, R> {
+        Or(self, other)
+    }
+
+    /// Allow access if either of the guards does, running them concurrently.
+    fn race<R: Guard>(self, other: R) -> Race<Self, R> {
+        Race(self, other)
+    }
 }
 
 impl<T: Guard> GuardExt for T {}
@@ -35,6 +52,37 @@ impl<A: Guard + Send + Sync, B: Guard + Send + Sync> Guard for And<A, B> {
     }
 }
 
+/// Guard for [`GuardExt::or`](trait.GuardExt.html#method.or).
+///
+/// The second guard is only checked if the first one fails, and its error is returned if it fails
+/// too.
+pub struct Or<A: Guard, B: Guard>(A, B);
+
+#[async_trait::async_trait]
+impl<A: Guard + Send + Sync, B: Guard + Send + Sync> Guard for Or<A, B> {
+    async fn check(&self, ctx: &Context<'_>) -> FieldResult<()> {
+        match self.0.check(ctx).await {
+            Ok(()) => Ok(()),
+            Err(_) => self.1.check(ctx).await,
+        }
+    }
+}
+
+/// Guard for [`GuardExt::race`](trait.GuardExt.html#method.race).
+///
+/// Both guards are checked at the same time, access is allowed as soon as one of them passes. If
+/// both fail, the error of the last one to fail is returned.
+pub struct Race<A: Guard, B: Guard>(A, B);
+
+#[async_trait::async_trait]
+impl<A: Guard + Send + Sync, B: Guard + Send + Sync> Guard for Race<A, B> {
+    async fn check(&self, ctx: &Context<'_>) -> FieldResult<()> {
+        futures::future::select_ok(vec![self.0.check(ctx), self.1.check(ctx)])
+            .await
+            .map(|_| ())
+    }
+}
+
 /// Field post guard
 ///
 /// This is a post-condition for a field that is resolved if `Ok(()` is returned, otherwise an error is returned.
@@ -52,6 +100,11 @@ pub trait PostGuardExt<T: Send + Sync>: PostGuard<T> + Sized {
     fn and<R: PostGuard<T>>(self, other: R) -> PostAnd<T, Self, R> {
         PostAnd(self, other, PhantomData)
     }
+
+    /// Allow the result through if either of the guards does, trying them in order.
+    fn or<R: PostGuard<T>>(self, other: R) -> PostOr<T, Self, R> {
+        PostOr(self, other, PhantomData)
+    }
 }
 
 impl<T: PostGuard<R>, R: Send + Sync> PostGuardExt<R> for T {}
@@ -68,3 +121,85 @@ impl<T: Send + Sync, A: PostGuard<T> + Send + Sync, B: PostGuard<T> + Send + Syn
         self.1.check(ctx, result).await
     }
 }
+
+/// PostGuard for [`PostGuardExt<T>::or`](trait.PostGuardExt.html#method.or).
+///
+/// The second guard is only checked if the first one fails, and its error is returned if it fails
+/// too.
+pub struct PostOr<T: Send + Sync, A: PostGuard<T>, B: PostGuard<T>>(A, B, PhantomData<T>);
+
+#[async_trait::async_trait]
+impl<T: Send + Sync, A: PostGuard<T> + Send + Sync, B: PostGuard<T> + Send + Sync> PostGuard<T>
+    for PostOr<T, A, B>
+{
+    async fn check(&self, ctx: &Context<'_>, result: &T) -> FieldResult<()> {
+        match self.0.check(ctx, result).await {
+            Ok(()) => Ok(()),
+            Err(_) => self.1.check(ctx, result).await,
+        }
+    }
+}
+
+/// Provides the scopes (or roles) granted to the caller, for fields with `requires_scopes`.
+///
+/// Put it into the request (or schema) data as a `Box<dyn ScopeProvider>`, fields that require
+/// scopes are forbidden if there is none.
+pub trait ScopeProvider: Send + Sync + 'static {
+    /// Returns `true` if the caller has been granted `scope`.
+    fn has_scope(&self, scope: &str) -> bool;
+}
+
+impl ScopeProvider for HashSet<String> {
+    fn has_scope(&self, scope: &str) -> bool {
+        self.contains(scope)
+    }
+}
+
+impl ScopeProvider for Vec<String> {
+    fn has_scope(&self, scope: &str) -> bool {
+        self.iter().any(|s| s == scope)
+    }
+}
+
+/// Guard for fields with `requires_scopes`.
+///
+/// If the caller is missing any of the scopes, the error message is `Forbidden` and the
+/// extensions are `{ "code": "FORBIDDEN", "missingScopes": [...] }`.
+pub struct ScopeGuard {
+    scopes: &'static [&'static str],
+}
+
+impl ScopeGuard {
+    /// Create a guard that requires all of `scopes`.
+    pub fn new(scopes: &'static [&'static str]) -> Self {
+        Self { scopes }
+    }
+
+    /// Check whether the caller has been granted all of the required scopes.
+    pub async fn check(&self, ctx: &Context<'_>) -> FieldResult<()> {
+        let provider = ctx.data_opt::<Box<dyn ScopeProvider>>();
+        let missing_scopes = self
+            .scopes
+            .iter()
+            .filter(|scope| !provider.map_or(false, |provider| provider.has_scope(scope)))
+            .collect::<Vec<_>>();
+        if missing_scopes.is_empty() {
+            Ok(())
+        } else {
+            Err(FieldError(
+                "Forbidden".to_string(),
+                Some(serde_json::json!({
+                    "code": "FORBIDDEN",
+                    "missingScopes": missing_scopes,
+                })),
+            ))
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Guard for ScopeGuard {
+    async fn check(&self, ctx: &Context<'_>) -> FieldResult<()> {
+        ScopeGuard::check(self, ctx).await
+    }
+}
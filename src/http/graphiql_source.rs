@@ -1,5 +1,26 @@
+use crate::http::playground_source::nonce_attr;
+
 /// Generate the page for GraphIQL
 pub fn graphiql_source(graphql_endpoint_url: &str, subscription_endpoint: Option<&str>) -> String {
+    let mut config = GraphiQLConfig::new(graphql_endpoint_url);
+    if let Some(subscription_endpoint) = subscription_endpoint {
+        config = config.subscription_endpoint(subscription_endpoint);
+    }
+    graphiql_source_with_config(config)
+}
+
+/// Generate the page for GraphIQL with a [`GraphiQLConfig`](struct.GraphiQLConfig.html)
+///
+/// # Example
+///
+/// ```rust
+/// use async_graphql::http::*;
+///
+/// graphiql_source_with_config(
+///     GraphiQLConfig::new("http://localhost:8000").subscription_endpoint("ws://localhost:8000"),
+/// );
+/// ```
+pub fn graphiql_source_with_config(config: GraphiQLConfig) -> String {
     r#"
     <html>
   <head>
@@ -49,12 +70,44 @@ pub fn graphiql_source(graphql_endpoint_url: &str, subscription_endpoint: Option
   </body>
 </html>
     "#
-    .replace("GRAPHQL_URL", graphql_endpoint_url)
+    .replace("GRAPHQL_URL", config.endpoint)
     .replace(
         "GRAPHQL_SUBSCRIPTION_URL",
-        &match subscription_endpoint {
+        &match config.subscription_endpoint {
             Some(url) => format!("'{}'", url),
             None => "null".to_string(),
         },
     )
+    .replace("<script", &nonce_attr("<script", config.nonce))
+}
+
+/// Config for GraphIQL
+pub struct GraphiQLConfig<'a> {
+    endpoint: &'a str,
+    subscription_endpoint: Option<&'a str>,
+    nonce: Option<&'a str>,
+}
+
+impl<'a> GraphiQLConfig<'a> {
+    /// Create a config for GraphIQL.
+    pub fn new(endpoint: &'a str) -> Self {
+        Self {
+            endpoint,
+            subscription_endpoint: None,
+            nonce: None,
+        }
+    }
+
+    /// Set subscription endpoint, for example: `ws://localhost:8000`.
+    pub fn subscription_endpoint(mut self, endpoint: &'a str) -> Self {
+        self.subscription_endpoint = Some(endpoint);
+        self
+    }
+
+    /// Set the nonce of the scripts of the page, which allows them in a
+    /// `Content-Security-Policy` with `'nonce-<nonce>'`.
+    pub fn nonce(mut self, nonce: &'a str) -> Self {
+        self.nonce = Some(nonce);
+        self
+    }
 }
@@ -0,0 +1,174 @@
+use crate::resolver_utils::ObjectType;
+use crate::{Schema, SubscriptionType};
+use futures::future::BoxFuture;
+use futures::Future;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+type CheckFn = dyn Fn() -> BoxFuture<'static, Result<(), String>> + Send + Sync;
+
+/// Health and readiness checks of a GraphQL server, for liveness and readiness probes such as the
+/// ones of Kubernetes.
+///
+/// The integrations turn the [`HealthStatus`](struct.HealthStatus.html) into a JSON response
+/// with the status `200 OK` when healthy and `503 Service Unavailable` otherwise.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+/// use async_graphql::http::HealthCheck;
+///
+/// struct QueryRoot;
+///
+/// #[Object]
+/// impl QueryRoot {
+///     async fn value(&self) -> i32 {
+///         10
+///     }
+/// }
+///
+/// #[async_std::main]
+/// async fn main() {
+///     let schema = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
+///     let health = HealthCheck::new()
+///         .check("database", || async { Ok(()) })
+///         .max_subscriptions(1000);
+///     assert!(health.readiness(&schema).await.healthy);
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct HealthCheck {
+    checks: Vec<(&'static str, Arc<CheckFn>)>,
+    max_subscriptions: Option<usize>,
+}
+
+/// The result of a single check of a [`HealthStatus`](struct.HealthStatus.html).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HealthCheckResult {
+    /// Whether the check passed.
+    pub healthy: bool,
+
+    /// Details about the check, such as the error of a dependency.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// The result of [`HealthCheck::liveness`](struct.HealthCheck.html#method.liveness) or
+/// [`HealthCheck::readiness`](struct.HealthCheck.html#method.readiness).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HealthStatus {
+    /// Whether all checks passed.
+    pub healthy: bool,
+
+    /// The result of each check, by name.
+    pub checks: BTreeMap<String, HealthCheckResult>,
+}
+
+impl HealthStatus {
+    fn new(checks: BTreeMap<String, HealthCheckResult>) -> Self {
+        Self {
+            healthy: checks.values().all(|check| check.healthy),
+            checks,
+        }
+    }
+}
+
+impl HealthCheck {
+    /// Create a health check without dependencies.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Add a dependency that must respond for the server to be ready, such as a database.
+    ///
+    /// The check returns an error message if the dependency is unavailable.
+    pub fn check<F, Fut>(mut self, name: &'static str, f: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), String>> + Send + 'static,
+    {
+        let check: Arc<CheckFn> =
+            Arc::new(move || Box::pin(f()) as BoxFuture<'static, Result<(), String>>);
+        self.checks.push((name, check));
+        self
+    }
+
+    /// Report the server as not ready when it has more active subscriptions than `max`.
+    pub fn max_subscriptions(mut self, max: usize) -> Self {
+        self.max_subscriptions = Some(max);
+        self
+    }
+
+    /// Check that the server is alive, which is the case as soon as the schema has been built.
+    pub fn liveness<Query, Mutation, Subscription>(
+        &self,
+        schema: &Schema<Query, Mutation, Subscription>,
+    ) -> HealthStatus
+    where
+        Query: ObjectType + Send + Sync + 'static,
+        Mutation: ObjectType + Send + Sync + 'static,
+        Subscription: SubscriptionType + Send + Sync + 'static,
+    {
+        let mut checks = BTreeMap::new();
+        checks.insert("schema".to_string(), schema_check(schema));
+        HealthStatus::new(checks)
+    }
+
+    /// Check that the server is ready to receive requests: the schema has been built, the
+    /// dependencies respond and there are not too many active subscriptions.
+    pub async fn readiness<Query, Mutation, Subscription>(
+        &self,
+        schema: &Schema<Query, Mutation, Subscription>,
+    ) -> HealthStatus
+    where
+        Query: ObjectType + Send + Sync + 'static,
+        Mutation: ObjectType + Send + Sync + 'static,
+        Subscription: SubscriptionType + Send + Sync + 'static,
+    {
+        let mut checks = BTreeMap::new();
+        checks.insert("schema".to_string(), schema_check(schema));
+
+        let results = futures::future::join_all(self.checks.iter().map(|(_, check)| check())).await;
+        for ((name, _), res) in self.checks.iter().zip(results) {
+            checks.insert(
+                name.to_string(),
+                HealthCheckResult {
+                    healthy: res.is_ok(),
+                    message: res.err(),
+                },
+            );
+        }
+
+        if let Some(max_subscriptions) = self.max_subscriptions {
+            let active_subscriptions = schema.active_subscriptions();
+            checks.insert(
+                "subscriptions".to_string(),
+                HealthCheckResult {
+                    healthy: active_subscriptions <= max_subscriptions,
+                    message: Some(format!(
+                        "{} of {} active subscriptions",
+                        active_subscriptions, max_subscriptions
+                    )),
+                },
+            );
+        }
+
+        HealthStatus::new(checks)
+    }
+}
+
+fn schema_check<Query, Mutation, Subscription>(
+    schema: &Schema<Query, Mutation, Subscription>,
+) -> HealthCheckResult
+where
+    Query: ObjectType + Send + Sync + 'static,
+    Mutation: ObjectType + Send + Sync + 'static,
+    Subscription: SubscriptionType + Send + Sync + 'static,
+{
+    HealthCheckResult {
+        healthy: true,
+        message: Some(format!("fingerprint {}", schema.fingerprint())),
+    }
+}
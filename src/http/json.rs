@@ -0,0 +1,20 @@
+//! The JSON parser used for request bodies.
+//!
+//! With the `simd-json` feature, bodies are parsed with [`simd-json`](https://crates.io/crates/simd-json),
+//! which is faster than `serde_json` on large payloads such as requests with many variables.
+//! Errors are converted to `serde_json::Error` either way, so that `ParseRequestError` doesn't
+//! depend on the parser.
+
+use serde::de::DeserializeOwned;
+
+/// Deserialize a value from JSON bytes. The parser may modify the buffer while it parses it.
+#[cfg(not(feature = "simd-json"))]
+pub(crate) fn from_slice<T: DeserializeOwned>(data: &mut [u8]) -> serde_json::Result<T> {
+    serde_json::from_slice(data)
+}
+
+/// Deserialize a value from JSON bytes. The parser may modify the buffer while it parses it.
+#[cfg(feature = "simd-json")]
+pub(crate) fn from_slice<T: DeserializeOwned>(data: &mut [u8]) -> serde_json::Result<T> {
+    simd_json::serde::from_slice(data).map_err(serde::de::Error::custom)
+}
@@ -1,29 +1,40 @@
 //! A helper module that supports HTTP
 
 mod graphiql_source;
+mod health;
+mod json;
 #[cfg(feature = "multipart")]
 mod multipart;
 mod playground_source;
 mod websocket;
 
-pub use graphiql_source::graphiql_source;
+pub use graphiql_source::{graphiql_source, graphiql_source_with_config, GraphiQLConfig};
+pub use health::{HealthCheck, HealthCheckResult, HealthStatus};
 #[cfg(feature = "multipart")]
 pub use multipart::MultipartOptions;
 pub use playground_source::{playground_source, GraphQLPlaygroundConfig};
-pub use websocket::WebSocket;
+pub use websocket::{
+    EventBuffer, MemoryEventBuffer, OutMessage, Protocols, WebSocket, WebSocketEncoding,
+    WebSocketProtocol,
+};
 
 use crate::{BatchRequest, ParseRequestError, Request};
-use futures::io::AsyncRead;
-use futures::AsyncReadExt;
+use bytes::Bytes;
+use futures::{Stream, TryStreamExt};
+use std::error::Error as StdError;
+use std::io::{self, ErrorKind};
 
 #[cfg(feature = "multipart")]
 #[cfg_attr(feature = "nightly", doc(cfg(feature = "multipart")))]
-/// Receive a GraphQL request from a content type and body.
-pub async fn receive_body(
+/// Receive a GraphQL request from a content type and a stream of body chunks.
+pub async fn receive_body<E>(
     content_type: Option<impl AsRef<str>>,
-    body: impl AsyncRead + Send + 'static,
+    body: impl Stream<Item = Result<Bytes, E>> + Send + 'static,
     opts: MultipartOptions,
-) -> Result<Request, ParseRequestError> {
+) -> Result<Request, ParseRequestError>
+where
+    E: Into<Box<dyn StdError + Send + Sync>> + 'static,
+{
     receive_batch_body(content_type, body, opts)
         .await?
         .into_single()
@@ -31,12 +42,15 @@ pub async fn receive_body(
 
 #[cfg(feature = "multipart")]
 #[cfg_attr(feature = "nightly", doc(cfg(feature = "multipart")))]
-/// Receive a GraphQL request from a content type and body.
-pub async fn receive_batch_body(
+/// Receive a GraphQL batch request from a content type and a stream of body chunks.
+pub async fn receive_batch_body<E>(
     content_type: Option<impl AsRef<str>>,
-    body: impl AsyncRead + Send + 'static,
+    body: impl Stream<Item = Result<Bytes, E>> + Send + 'static,
     opts: MultipartOptions,
-) -> Result<BatchRequest, ParseRequestError> {
+) -> Result<BatchRequest, ParseRequestError>
+where
+    E: Into<Box<dyn StdError + Send + Sync>> + 'static,
+{
     if let Some(Ok(boundary)) = content_type.map(multer::parse_boundary) {
         multipart::receive_batch_multipart(body, boundary, opts).await
     } else {
@@ -44,21 +58,31 @@ pub async fn receive_batch_body(
     }
 }
 
-/// Receive a GraphQL request from a body as JSON.
-pub async fn receive_json(
-    body: impl AsyncRead + Send + 'static,
-) -> Result<Request, ParseRequestError> {
+/// Receive a GraphQL request from a stream of body chunks as JSON.
+pub async fn receive_json<E>(
+    body: impl Stream<Item = Result<Bytes, E>> + Send + 'static,
+) -> Result<Request, ParseRequestError>
+where
+    E: Into<Box<dyn StdError + Send + Sync>> + 'static,
+{
     receive_batch_json(body).await?.into_single()
 }
 
-/// Receive a GraphQL batch request from a body as JSON.
-pub async fn receive_batch_json(
-    body: impl AsyncRead + Send + 'static,
-) -> Result<BatchRequest, ParseRequestError> {
+/// Receive a GraphQL batch request from a stream of body chunks as JSON.
+pub async fn receive_batch_json<E>(
+    body: impl Stream<Item = Result<Bytes, E>> + Send + 'static,
+) -> Result<BatchRequest, ParseRequestError>
+where
+    E: Into<Box<dyn StdError + Send + Sync>> + 'static,
+{
     let mut data = Vec::new();
     futures::pin_mut!(body);
-    body.read_to_end(&mut data)
+    while let Some(chunk) = body
+        .try_next()
         .await
-        .map_err(ParseRequestError::Io)?;
-    Ok(serde_json::from_slice::<BatchRequest>(&data).map_err(ParseRequestError::InvalidRequest)?)
+        .map_err(|err| ParseRequestError::Io(io::Error::new(ErrorKind::Other, err)))?
+    {
+        data.extend_from_slice(&chunk);
+    }
+    Ok(json::from_slice::<BatchRequest>(&mut data).map_err(ParseRequestError::InvalidRequest)?)
 }
@@ -1,13 +1,12 @@
+use super::json;
 use crate::{BatchRequest, ParseRequestError};
 use bytes::Bytes;
-use futures::io::AsyncRead;
 use futures::stream::Stream;
-use multer::{Constraints, Multipart, SizeLimit};
-use pin_project_lite::pin_project;
+use multer::{Constraints, Field, Multipart, SizeLimit};
 use std::collections::HashMap;
-use std::io::{self, Seek, SeekFrom, Write};
-use std::pin::Pin;
-use std::task::{Context, Poll};
+use std::error::Error as StdError;
+use std::fs::File;
+use std::io::{Seek, SeekFrom, Write};
 
 /// Options for `receive_multipart`.
 #[derive(Default, Clone)]
@@ -38,18 +37,21 @@ impl MultipartOptions {
     }
 }
 
-pub(super) async fn receive_batch_multipart(
-    body: impl AsyncRead + Send + 'static,
+pub(super) async fn receive_batch_multipart<E>(
+    body: impl Stream<Item = Result<Bytes, E>> + Send + 'static,
     boundary: impl Into<String>,
     opts: MultipartOptions,
-) -> Result<BatchRequest, ParseRequestError> {
+) -> Result<BatchRequest, ParseRequestError>
+where
+    E: Into<Box<dyn StdError + Send + Sync>> + 'static,
+{
     let mut multipart = Multipart::new_with_constraints(
-        ReaderStream::new(body),
+        body,
         boundary,
         Constraints::new().size_limit({
             let mut limit = SizeLimit::new();
             if let (Some(max_file_size), Some(max_num_files)) =
-                (opts.max_file_size, opts.max_file_size)
+                (opts.max_file_size, opts.max_num_files)
             {
                 limit = limit.whole_stream((max_file_size * max_num_files) as u64);
             }
@@ -60,75 +62,59 @@ pub(super) async fn receive_batch_multipart(
         }),
     );
 
-    let mut request = None;
-    let mut map = None;
-    let mut files = Vec::new();
-
+    // The "operations" and "map" parts come before the files, so that every file can be written
+    // to its variables as soon as it arrives.
+    let mut operations = next_part(&mut multipart, "operations")
+        .await?
+        .bytes()
+        .await?
+        .to_vec();
+    let mut request = json::from_slice::<BatchRequest>(&mut operations)
+        .map_err(ParseRequestError::InvalidRequest)?;
+    let mut map = next_part(&mut multipart, "map")
+        .await?
+        .bytes()
+        .await?
+        .to_vec();
+    let mut map = json::from_slice::<HashMap<String, Vec<String>>>(&mut map)
+        .map_err(ParseRequestError::InvalidFilesMap)?;
+
+    let mut num_files = 0;
     while let Some(mut field) = multipart.next_field().await? {
-        match field.name() {
-            Some("operations") => {
-                let request_str = field.text().await?;
-                request = Some(
-                    serde_json::from_str::<BatchRequest>(&request_str)
-                        .map_err(ParseRequestError::InvalidRequest)?,
-                );
-            }
-            Some("map") => {
-                let map_str = field.text().await?;
-                map = Some(
-                    serde_json::from_str::<HashMap<String, Vec<String>>>(&map_str)
-                        .map_err(ParseRequestError::InvalidFilesMap)?,
-                );
+        let var_paths = match field.name().and_then(|name| map.remove(name)) {
+            Some(var_paths) => var_paths,
+            None => {
+                // Skip the parts that are not in the map.
+                while field.chunk().await?.is_some() {}
+                continue;
             }
-            _ => {
-                if let Some(name) = field.name().map(ToString::to_string) {
-                    if let Some(filename) = field.file_name().map(ToString::to_string) {
-                        let content_type = field.content_type().map(|mime| mime.to_string());
-                        let mut file = tempfile::tempfile().map_err(ParseRequestError::Io)?;
-                        while let Some(chunk) = field.chunk().await.unwrap() {
-                            file.write(&chunk).map_err(ParseRequestError::Io)?;
-                        }
-                        file.seek(SeekFrom::Start(0))?;
-                        files.push((name, filename, content_type, file));
-                    }
-                }
+        };
+
+        num_files += 1;
+        if let Some(max_num_files) = opts.max_num_files {
+            if num_files > max_num_files {
+                return Err(ParseRequestError::TooManyFiles {
+                    limit: max_num_files,
+                });
             }
         }
-    }
-
-    let mut request: BatchRequest = request.ok_or(ParseRequestError::MissingOperatorsPart)?;
-    let map = map.as_mut().ok_or(ParseRequestError::MissingMapPart)?;
 
-    for (name, filename, content_type, file) in files {
-        if let Some(var_paths) = map.remove(&name) {
-            for var_path in var_paths {
-                match &mut request {
-                    BatchRequest::Single(request) => {
-                        request.set_upload(
-                            &var_path,
-                            filename.clone(),
-                            content_type.clone(),
-                            file.try_clone().unwrap(),
-                        );
-                    }
-                    BatchRequest::Batch(requests) => {
-                        let mut s = var_path.splitn(2, '.');
-                        let idx = s.next().and_then(|idx| idx.parse::<usize>().ok());
-                        let path = s.next();
-
-                        if let (Some(idx), Some(path)) = (idx, path) {
-                            if let Some(request) = requests.get_mut(idx) {
-                                request.set_upload(
-                                    path,
-                                    filename.clone(),
-                                    content_type.clone(),
-                                    file.try_clone().unwrap(),
-                                );
-                            }
-                        }
-                    }
-                }
-            }
+        let filename = field.file_name().unwrap_or_default().to_string();
+        let content_type = field.content_type().map(ToString::to_string);
+        let mut file = tempfile::tempfile()?;
+        while let Some(chunk) = field.chunk().await? {
+            file.write_all(&chunk)?;
+        }
+        file.seek(SeekFrom::Start(0))?;
+
+        for var_path in var_paths {
+            set_upload(
+                &mut request,
+                &var_path,
+                filename.clone(),
+                content_type.clone(),
+                file.try_clone()?,
+            );
         }
     }
 
@@ -139,34 +125,43 @@ pub(super) async fn receive_batch_multipart(
     Ok(request)
 }
 
-pin_project! {
-    pub(crate) struct ReaderStream<T> {
-        buf: [u8; 2048],
-        #[pin]
-        reader: T,
+/// Returns the next part, which must be the `name` part.
+async fn next_part(
+    multipart: &mut Multipart,
+    name: &'static str,
+) -> Result<Field, ParseRequestError> {
+    match multipart.next_field().await? {
+        Some(field) if field.name() == Some(name) => Ok(field),
+        Some(field) => Err(ParseRequestError::UnexpectedPart {
+            name: field.name().unwrap_or_default().to_string(),
+            expected: name,
+        }),
+        None if name == "operations" => Err(ParseRequestError::MissingOperatorsPart),
+        None => Err(ParseRequestError::MissingMapPart),
     }
 }
 
-impl<T> ReaderStream<T> {
-    pub(crate) fn new(reader: T) -> Self {
-        Self {
-            buf: [0; 2048],
-            reader,
+fn set_upload(
+    request: &mut BatchRequest,
+    var_path: &str,
+    filename: String,
+    content_type: Option<String>,
+    file: File,
+) {
+    match request {
+        BatchRequest::Single(request) => {
+            request.set_upload(var_path, filename, content_type, file);
+        }
+        BatchRequest::Batch(requests) => {
+            let mut s = var_path.splitn(2, '.');
+            let idx = s.next().and_then(|idx| idx.parse::<usize>().ok());
+            let path = s.next();
+
+            if let (Some(idx), Some(path)) = (idx, path) {
+                if let Some(request) = requests.get_mut(idx) {
+                    request.set_upload(path, filename, content_type, file);
+                }
+            }
         }
-    }
-}
-
-impl<T: AsyncRead> Stream for ReaderStream<T> {
-    type Item = io::Result<Bytes>;
-
-    fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
-        let this = self.project();
-
-        Poll::Ready(
-            match futures::ready!(this.reader.poll_read(cx, this.buf)?) {
-                0 => None,
-                size => Some(Ok(Bytes::copy_from_slice(&this.buf[..size]))),
-            },
-        )
     }
 }
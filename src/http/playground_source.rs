@@ -554,6 +554,15 @@ pub fn playground_source(config: GraphQLPlaygroundConfig) -> String {
             Ok(str) => str,
             _ => "{}".to_string()
         })
+        .replace("<script", &nonce_attr("<script", config.nonce))
+        .replace("<style", &nonce_attr("<style", config.nonce))
+}
+
+pub(crate) fn nonce_attr(tag: &str, nonce: Option<&str>) -> String {
+    match nonce {
+        Some(nonce) => format!("{} nonce=\"{}\"", tag, nonce),
+        None => tag.to_string(),
+    }
 }
 
 /// Config for GraphQL Playground
@@ -563,6 +572,8 @@ pub struct GraphQLPlaygroundConfig<'a> {
     endpoint: &'a str,
     subscription_endpoint: Option<&'a str>,
     headers: Option<HashMap<&'a str, &'a str>>,
+    #[serde(skip)]
+    nonce: Option<&'a str>,
 }
 
 impl<'a> GraphQLPlaygroundConfig<'a> {
@@ -572,6 +583,7 @@ impl<'a> GraphQLPlaygroundConfig<'a> {
             endpoint,
             subscription_endpoint: None,
             headers: Default::default(),
+            nonce: None,
         }
     }
 
@@ -581,6 +593,13 @@ impl<'a> GraphQLPlaygroundConfig<'a> {
         self
     }
 
+    /// Set the nonce of the scripts and styles of the page, which allows them in a
+    /// `Content-Security-Policy` with `'nonce-<nonce>'`.
+    pub fn nonce(mut self, nonce: &'a str) -> Self {
+        self.nonce = Some(nonce);
+        self
+    }
+
     /// Set HTTP header for per query.
     pub fn with_header(mut self, name: &'a str, value: &'a str) -> Self {
         if let Some(headers) = &mut self.headers {
@@ -1,24 +1,794 @@
 //! WebSocket transport for subscription
 
 use crate::resolver_utils::ObjectType;
-use crate::{Data, FieldResult, Request, Response, Schema, SubscriptionType};
-use futures::Stream;
+use crate::subscription::CompletionHandle;
+use crate::{
+    CompletionReason, Data, FieldError, FieldResult, Request, Response, Schema, SubscriptionType,
+};
+use futures::future::BoxFuture;
+use futures::{FutureExt, Stream};
 use pin_project_lite::pin_project;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
 use std::pin::Pin;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+/// Stores the events sent to the subscriptions of websocket connections, so that they can be
+/// replayed when a client reconnects, see
+/// [`WebSocket::event_buffer`](struct.WebSocket.html#method.event_buffer).
+///
+/// The connections are identified by random ids that are only sent to their own client, so
+/// they can't be guessed by other clients to replay their events.
+pub trait EventBuffer: Send + Sync + 'static {
+    /// Store an event of a subscription and return its id, which must be greater than the ids of
+    /// all the events stored before.
+    fn push(&self, connection_id: &str, subscription_id: &str, payload: serde_json::Value) -> u64;
+
+    /// Returns `true` if events of the connection are still stored.
+    fn contains_connection(&self, connection_id: &str) -> bool;
+
+    /// Returns the stored events of a subscription with an id greater than `event_id`, in order.
+    fn events_after(
+        &self,
+        connection_id: &str,
+        subscription_id: &str,
+        event_id: u64,
+    ) -> Vec<(u64, serde_json::Value)>;
+
+    /// Remove the events of a subscription that ended, either because its stream ended or
+    /// because the client stopped it.
+    fn remove_subscription(&self, connection_id: &str, subscription_id: &str);
+
+    /// Remove the events of a connection that the client closed.
+    fn remove_connection(&self, connection_id: &str);
+}
+
+impl<T: EventBuffer> EventBuffer for Arc<T> {
+    fn push(&self, connection_id: &str, subscription_id: &str, payload: serde_json::Value) -> u64 {
+        T::push(self, connection_id, subscription_id, payload)
+    }
+
+    fn contains_connection(&self, connection_id: &str) -> bool {
+        T::contains_connection(self, connection_id)
+    }
+
+    fn events_after(
+        &self,
+        connection_id: &str,
+        subscription_id: &str,
+        event_id: u64,
+    ) -> Vec<(u64, serde_json::Value)> {
+        T::events_after(self, connection_id, subscription_id, event_id)
+    }
+
+    fn remove_subscription(&self, connection_id: &str, subscription_id: &str) {
+        T::remove_subscription(self, connection_id, subscription_id)
+    }
+
+    fn remove_connection(&self, connection_id: &str) {
+        T::remove_connection(self, connection_id)
+    }
+}
+
+/// An in-memory [`EventBuffer`](trait.EventBuffer.html) that keeps the last events of every
+/// subscription.
+///
+/// The events are removed when their subscription or connection is closed, and the events of
+/// connections that were lost are removed after a [time to live](#method.ttl), five minutes by
+/// default.
+///
+/// Share it between the connections of a server by wrapping it in an `Arc`.
+pub struct MemoryEventBuffer {
+    capacity: usize,
+    ttl: Duration,
+    inner: Mutex<MemoryEventBufferInner>,
+}
+
+type SubscriptionEvents = VecDeque<(u64, Instant, serde_json::Value)>;
+
+struct MemoryEventBufferInner {
+    last_event_id: u64,
+    last_eviction: Instant,
+    connections: HashMap<String, HashMap<String, SubscriptionEvents>>,
+}
+
+impl MemoryEventBuffer {
+    /// Create a buffer that keeps the last `capacity` events of every subscription.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            ttl: Duration::from_secs(5 * 60),
+            inner: Mutex::new(MemoryEventBufferInner {
+                last_event_id: 0,
+                last_eviction: Instant::now(),
+                connections: HashMap::new(),
+            }),
+        }
+    }
+
+    /// Set how long the events are kept.
+    pub fn ttl(self, ttl: Duration) -> Self {
+        Self { ttl, ..self }
+    }
+}
+
+impl MemoryEventBufferInner {
+    /// Remove the events older than `ttl`, at most once a second.
+    fn evict(&mut self, ttl: Duration) {
+        let now = Instant::now();
+        if now.duration_since(self.last_eviction) < Duration::from_secs(1) {
+            return;
+        }
+        self.last_eviction = now;
+        self.connections.retain(|_, subscriptions| {
+            subscriptions.retain(|_, events| {
+                while let Some((_, time, _)) = events.front() {
+                    if now.duration_since(*time) < ttl {
+                        break;
+                    }
+                    events.pop_front();
+                }
+                !events.is_empty()
+            });
+            !subscriptions.is_empty()
+        });
+    }
+}
+
+impl EventBuffer for MemoryEventBuffer {
+    fn push(&self, connection_id: &str, subscription_id: &str, payload: serde_json::Value) -> u64 {
+        let mut inner = self.inner.lock().unwrap();
+        inner.evict(self.ttl);
+        inner.last_event_id += 1;
+        let event_id = inner.last_event_id;
+        let events = inner
+            .connections
+            .entry(connection_id.to_string())
+            .or_default()
+            .entry(subscription_id.to_string())
+            .or_default();
+        events.push_back((event_id, Instant::now(), payload));
+        if events.len() > self.capacity {
+            events.pop_front();
+        }
+        event_id
+    }
+
+    fn contains_connection(&self, connection_id: &str) -> bool {
+        self.inner
+            .lock()
+            .unwrap()
+            .connections
+            .contains_key(connection_id)
+    }
+
+    fn events_after(
+        &self,
+        connection_id: &str,
+        subscription_id: &str,
+        event_id: u64,
+    ) -> Vec<(u64, serde_json::Value)> {
+        self.inner
+            .lock()
+            .unwrap()
+            .connections
+            .get(connection_id)
+            .and_then(|subscriptions| subscriptions.get(subscription_id))
+            .map(|events| {
+                events
+                    .iter()
+                    .filter(|(id, _, _)| *id > event_id)
+                    .map(|(id, _, payload)| (*id, payload.clone()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn remove_subscription(&self, connection_id: &str, subscription_id: &str) {
+        let mut inner = self.inner.lock().unwrap();
+        if let Some(subscriptions) = inner.connections.get_mut(connection_id) {
+            subscriptions.remove(subscription_id);
+            if subscriptions.is_empty() {
+                inner.connections.remove(connection_id);
+            }
+        }
+    }
+
+    fn remove_connection(&self, connection_id: &str) {
+        self.inner.lock().unwrap().connections.remove(connection_id);
+    }
+}
+
+type ResponseStream = Pin<Box<dyn Stream<Item = Response> + Send>>;
+
+type AsyncDataInitializer =
+    Box<dyn FnOnce(serde_json::Value) -> BoxFuture<'static, FieldResult<Data>> + Send>;
+
+/// A message to send to the client of a websocket connection.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OutMessage {
+    /// A text message.
+    Text(String),
+
+    /// A binary message.
+    Binary(Vec<u8>),
+
+    /// Close the connection.
+    Close,
+
+    /// Close the connection with a close frame with `code` and `reason`, used by the
+    /// `graphql-transport-ws` protocol to tell the client why the connection is closed.
+    CloseWithCode {
+        /// The close code.
+        code: u16,
+
+        /// The reason of the close.
+        reason: String,
+    },
+}
+
+/// The GraphQL over websocket protocols, negotiated with the `Sec-WebSocket-Protocol` header of
+/// the upgrade request.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Protocols {
+    /// The legacy `graphql-ws` subprotocol of
+    /// [subscriptions-transport-ws](https://github.com/apollographql/subscriptions-transport-ws/blob/master/PROTOCOL.md).
+    SubscriptionsTransportWS,
+
+    /// The `graphql-transport-ws` subprotocol of
+    /// [graphql-ws](https://github.com/enisdenjo/graphql-ws/blob/master/PROTOCOL.md).
+    GraphQLWS,
+}
+
+impl Default for Protocols {
+    fn default() -> Self {
+        Protocols::SubscriptionsTransportWS
+    }
+}
+
+impl Protocols {
+    /// Returns the protocol and the encoding of the first supported subprotocol in `protocols`,
+    /// the value of the `Sec-WebSocket-Protocol` header of the upgrade request.
+    ///
+    /// The binary encodings are asked for with a suffix, such as `graphql-transport-ws+msgpack`.
+    pub fn negotiate(protocols: &str) -> Option<(Self, WebSocketEncoding)> {
+        protocols.split(',').find_map(parse_subprotocol)
+    }
+
+    /// Returns the subprotocol of the protocol with `encoding`, the value of the
+    /// `Sec-WebSocket-Protocol` header of the response.
+    pub fn sec_websocket_protocol(&self, encoding: WebSocketEncoding) -> String {
+        let protocol = match self {
+            Protocols::SubscriptionsTransportWS => "graphql-ws",
+            Protocols::GraphQLWS => "graphql-transport-ws",
+        };
+        format!("{}{}", protocol, encoding.suffix())
+    }
+}
+
+fn parse_subprotocol(subprotocol: &str) -> Option<(Protocols, WebSocketEncoding)> {
+    let subprotocol = subprotocol.trim();
+    let (protocol, suffix) = match subprotocol.find('+') {
+        Some(idx) => subprotocol.split_at(idx),
+        None => (subprotocol, ""),
+    };
+    let protocol = match protocol {
+        "graphql-ws" => Protocols::SubscriptionsTransportWS,
+        "graphql-transport-ws" => Protocols::GraphQLWS,
+        _ => return None,
+    };
+    let encoding = match suffix {
+        "" => WebSocketEncoding::Json,
+        #[cfg(feature = "msgpack")]
+        "+msgpack" => WebSocketEncoding::MessagePack,
+        #[cfg(feature = "cbor")]
+        "+cbor" => WebSocketEncoding::Cbor,
+        _ => return None,
+    };
+    Some((protocol, encoding))
+}
+
+/// The encoding of the messages of a websocket connection, negotiated with the
+/// `Sec-WebSocket-Protocol` header of the upgrade request.
+///
+/// The messages are JSON text messages by default. Bandwidth-sensitive clients can ask for the
+/// binary encodings, which are enabled by the `msgpack` and `cbor` features.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum WebSocketEncoding {
+    /// JSON text messages, the `graphql-ws` subprotocol.
+    Json,
+
+    /// MessagePack binary messages, the `graphql-ws+msgpack` subprotocol.
+    #[cfg(feature = "msgpack")]
+    #[cfg_attr(feature = "nightly", doc(cfg(feature = "msgpack")))]
+    MessagePack,
+
+    /// CBOR binary messages, the `graphql-ws+cbor` subprotocol.
+    #[cfg(feature = "cbor")]
+    #[cfg_attr(feature = "nightly", doc(cfg(feature = "cbor")))]
+    Cbor,
+}
+
+impl Default for WebSocketEncoding {
+    fn default() -> Self {
+        WebSocketEncoding::Json
+    }
+}
+
+impl WebSocketEncoding {
+    /// Returns the encoding of the first supported `graphql-ws` subprotocol in `protocols`, the
+    /// value of the `Sec-WebSocket-Protocol` header of the upgrade request. Use
+    /// [`Protocols::negotiate`](enum.Protocols.html#method.negotiate) to also support the
+    /// `graphql-transport-ws` protocol.
+    pub fn negotiate(protocols: &str) -> Option<Self> {
+        protocols
+            .split(',')
+            .filter_map(parse_subprotocol)
+            .find(|(protocol, _)| *protocol == Protocols::SubscriptionsTransportWS)
+            .map(|(_, encoding)| encoding)
+    }
+
+    /// Returns the `graphql-ws` subprotocol of the encoding, the value of the
+    /// `Sec-WebSocket-Protocol` header of the response.
+    pub fn protocol(&self) -> &'static str {
+        match self {
+            WebSocketEncoding::Json => "graphql-ws",
+            #[cfg(feature = "msgpack")]
+            WebSocketEncoding::MessagePack => "graphql-ws+msgpack",
+            #[cfg(feature = "cbor")]
+            WebSocketEncoding::Cbor => "graphql-ws+cbor",
+        }
+    }
+
+    fn suffix(&self) -> &'static str {
+        match self {
+            WebSocketEncoding::Json => "",
+            #[cfg(feature = "msgpack")]
+            WebSocketEncoding::MessagePack => "+msgpack",
+            #[cfg(feature = "cbor")]
+            WebSocketEncoding::Cbor => "+cbor",
+        }
+    }
+
+    fn decode<'a>(&self, message: &'a [u8]) -> Result<ClientMessage<'a>, String> {
+        match self {
+            WebSocketEncoding::Json => serde_json::from_slice(message).map_err(|e| e.to_string()),
+            #[cfg(feature = "msgpack")]
+            WebSocketEncoding::MessagePack => {
+                rmp_serde::from_read_ref(message).map_err(|e| e.to_string())
+            }
+            #[cfg(feature = "cbor")]
+            WebSocketEncoding::Cbor => serde_cbor::from_slice(message).map_err(|e| e.to_string()),
+        }
+    }
+
+    fn encode(&self, message: &ServerMessage) -> OutMessage {
+        match self {
+            WebSocketEncoding::Json => OutMessage::Text(serde_json::to_string(message).unwrap()),
+            #[cfg(feature = "msgpack")]
+            WebSocketEncoding::MessagePack => {
+                OutMessage::Binary(rmp_serde::to_vec_named(message).unwrap())
+            }
+            #[cfg(feature = "cbor")]
+            WebSocketEncoding::Cbor => OutMessage::Binary(serde_cbor::to_vec(message).unwrap()),
+        }
+    }
+}
+
+/// The GraphQL over websocket protocol of a connection, without the connection itself.
+///
+/// The messages of the client are passed to [`on_message`](#method.on_message), which returns the
+/// replies, and the messages of the subscriptions are produced by polling it as a stream. The
+/// stream is pending while there is nothing to send and never ends.
+///
+/// This lets integrations and tests drive the protocol without a socket,
+/// [`WebSocket`](struct.WebSocket.html) adapts it to a stream of messages. It speaks the legacy
+/// `graphql-ws` protocol unless another one is set with [`protocol`](#method.protocol).
+pub struct WebSocketProtocol<F, Query, Mutation, Subscription> {
+    data_initializer: Option<F>,
+    async_data_initializer: Option<AsyncDataInitializer>,
+    init_future: Option<BoxFuture<'static, FieldResult<Data>>>,
+    deferred: VecDeque<Vec<u8>>,
+    replies: VecDeque<OutMessage>,
+    data: Arc<Data>,
+    schema: Schema<Query, Mutation, Subscription>,
+    streams: HashMap<String, (CompletionHandle, ResponseStream)>,
+    event_buffer: Option<Arc<dyn EventBuffer>>,
+    encoding: WebSocketEncoding,
+    protocol: Protocols,
+    initialized: bool,
+    connection_id: String,
+    last_event_id: Option<u64>,
+}
+
+// The subscription streams are boxed, so nothing is pinned structurally.
+impl<F, Query, Mutation, Subscription> Unpin
+    for WebSocketProtocol<F, Query, Mutation, Subscription>
+{
+}
+
+impl<Query, Mutation, Subscription>
+    WebSocketProtocol<fn(serde_json::Value) -> FieldResult<Data>, Query, Mutation, Subscription>
+{
+    /// Create the protocol of a new connection.
+    #[must_use]
+    pub fn new(schema: Schema<Query, Mutation, Subscription>) -> Self {
+        Self::with_data(schema, None)
+    }
+}
+
+impl<F, Query, Mutation, Subscription> WebSocketProtocol<F, Query, Mutation, Subscription> {
+    /// Create the protocol of a new connection with a data initialization function, see
+    /// [`WebSocket::with_data`](struct.WebSocket.html#method.with_data).
+    #[must_use]
+    pub fn with_data(
+        schema: Schema<Query, Mutation, Subscription>,
+        data_initializer: Option<F>,
+    ) -> Self {
+        Self {
+            data_initializer,
+            async_data_initializer: None,
+            init_future: None,
+            deferred: VecDeque::new(),
+            replies: VecDeque::new(),
+            data: Arc::default(),
+            schema,
+            streams: HashMap::new(),
+            event_buffer: None,
+            encoding: WebSocketEncoding::Json,
+            protocol: Protocols::SubscriptionsTransportWS,
+            initialized: false,
+            connection_id: Uuid::new_v4().to_string(),
+            last_event_id: None,
+        }
+    }
+
+    /// Set an asynchronous data initialization function, see
+    /// [`WebSocket::async_initializer`](struct.WebSocket.html#method.async_initializer).
+    #[must_use]
+    pub fn async_initializer<G, Fut>(mut self, initializer: G) -> Self
+    where
+        G: FnOnce(serde_json::Value) -> Fut + Send + 'static,
+        Fut: Future<Output = FieldResult<Data>> + Send + 'static,
+    {
+        self.async_data_initializer = Some(Box::new(move |payload| initializer(payload).boxed()));
+        self
+    }
+
+    /// Store the events sent to subscriptions in `event_buffer`, see
+    /// [`WebSocket::event_buffer`](struct.WebSocket.html#method.event_buffer).
+    #[must_use]
+    pub fn event_buffer(mut self, event_buffer: impl EventBuffer) -> Self {
+        self.event_buffer = Some(Arc::new(event_buffer));
+        self
+    }
+
+    /// Set the data of the connection before it is initialized, see
+    /// [`WebSocket::connection_data`](struct.WebSocket.html#method.connection_data).
+    #[must_use]
+    pub fn connection_data(mut self, data: Data) -> Self {
+        self.data = Arc::new(data);
+        self
+    }
+
+    /// Set the encoding of the messages, usually negotiated with
+    /// [`WebSocketEncoding::negotiate`](enum.WebSocketEncoding.html#method.negotiate). The
+    /// messages are binary messages with the binary encodings.
+    #[must_use]
+    pub fn encoding(mut self, encoding: WebSocketEncoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Set the GraphQL over websocket protocol, usually negotiated with
+    /// [`Protocols::negotiate`](enum.Protocols.html#method.negotiate).
+    #[must_use]
+    pub fn protocol(mut self, protocol: Protocols) -> Self {
+        self.protocol = protocol;
+        self
+    }
+}
+
+impl<F, Query, Mutation, Subscription> WebSocketProtocol<F, Query, Mutation, Subscription>
+where
+    F: FnOnce(serde_json::Value) -> FieldResult<Data>,
+    Query: ObjectType + Send + Sync + 'static,
+    Mutation: ObjectType + Send + Sync + 'static,
+    Subscription: SubscriptionType + Send + Sync + 'static,
+{
+    /// Handle a message from the client, returning the messages to send in reply.
+    ///
+    /// While an [asynchronous data initialization function](#method.async_initializer) runs, the
+    /// messages are handled after it returns, and the replies are produced by the stream.
+    pub fn on_message(&mut self, message: &[u8]) -> Vec<OutMessage> {
+        if self.init_future.is_some() {
+            self.deferred.push_back(message.to_vec());
+            return Vec::new();
+        }
+
+        let message = match self.encoding.decode(message) {
+            Ok(message) => message,
+            Err(e) => return vec![self.protocol_error(e)],
+        };
+
+        match (self.protocol, message) {
+            (protocol, ClientMessage::ConnectionInit { payload }) => {
+                if protocol == Protocols::GraphQLWS && self.initialized {
+                    return vec![close(4429, "Too many initialisation requests")];
+                }
+                if let Err(e) = self.init(payload) {
+                    return self.init_error(e);
+                }
+                if self.init_future.is_some() {
+                    // Acknowledged when the asynchronous initialization function returns.
+                    return Vec::new();
+                }
+                self.initialized = true;
+                vec![self.connection_ack()]
+            }
+            (Protocols::SubscriptionsTransportWS, ClientMessage::Start { id, payload }) => {
+                if !self.initialized {
+                    return vec![close(4401, "Unauthorized")];
+                }
+                self.start(id, payload)
+            }
+            (Protocols::SubscriptionsTransportWS, ClientMessage::Stop { id }) => {
+                match self.streams.remove(id) {
+                    Some((completion, _)) => {
+                        completion.set(CompletionReason::ClientComplete);
+                        self.remove_events(id);
+                        vec![self.encoding.encode(&ServerMessage::Complete { id })]
+                    }
+                    None => Vec::new(),
+                }
+            }
+            (Protocols::SubscriptionsTransportWS, ClientMessage::ConnectionTerminate) => {
+                if let Some(event_buffer) = &self.event_buffer {
+                    event_buffer.remove_connection(&self.connection_id);
+                }
+                vec![OutMessage::Close]
+            }
+            (Protocols::GraphQLWS, ClientMessage::Subscribe { id, payload }) => {
+                if !self.initialized {
+                    return vec![close(4401, "Unauthorized")];
+                }
+                if self.streams.contains_key(&id) {
+                    return vec![close(4409, format!("Subscriber for {} already exists", id))];
+                }
+                self.start(id, payload)
+            }
+            (Protocols::GraphQLWS, ClientMessage::Complete { id }) => {
+                // The client does not expect a reply, unlike to the `stop` message.
+                if let Some((completion, _)) = self.streams.remove(id) {
+                    completion.set(CompletionReason::ClientComplete);
+                    self.remove_events(id);
+                }
+                Vec::new()
+            }
+            (Protocols::GraphQLWS, ClientMessage::Ping) => {
+                vec![self.encoding.encode(&ServerMessage::Pong)]
+            }
+            (Protocols::GraphQLWS, ClientMessage::Pong) => Vec::new(),
+            (_, _) => vec![self.protocol_error("Unexpected message type".to_string())],
+        }
+    }
+
+    /// Call the data initialization function with the payload of the `connection_init`
+    /// message.
+    fn init(&mut self, payload: Option<serde_json::Value>) -> FieldResult<()> {
+        if let Some(event_buffer) = self.event_buffer.as_ref() {
+            // Only the client that was given the id of a connection can resume it.
+            let resumed = payload.as_ref().and_then(|payload| {
+                let connection_id = payload.get("connectionId")?.as_str()?;
+                let last_event_id = payload.get("lastEventId")?.as_u64()?;
+                if event_buffer.contains_connection(connection_id) {
+                    Some((connection_id.to_string(), last_event_id))
+                } else {
+                    None
+                }
+            });
+            if let Some((connection_id, last_event_id)) = resumed {
+                self.connection_id = connection_id;
+                self.last_event_id = Some(last_event_id);
+            }
+        }
+        // The initializers also run without a payload, so that they can reject the connection.
+        let payload = payload.unwrap_or_default();
+        if let Some(async_data_initializer) = self.async_data_initializer.take() {
+            self.init_future = Some(async_data_initializer(payload.clone()));
+        }
+        if let Some(data_initializer) = self.data_initializer.take() {
+            let data = data_initializer(payload)?;
+            self.merge_data(data);
+        }
+        Ok(())
+    }
+
+    /// The `connection_ack` message, with the id to resume the connection if the events are
+    /// buffered.
+    fn connection_ack(&self) -> OutMessage {
+        let payload = self
+            .event_buffer
+            .as_ref()
+            .map(|_| serde_json::json!({ "connectionId": self.connection_id }));
+        self.encoding
+            .encode(&ServerMessage::ConnectionAck { payload })
+    }
+
+    /// Remove the buffered events of a subscription that ended.
+    fn remove_events(&self, subscription_id: &str) {
+        if let Some(event_buffer) = &self.event_buffer {
+            event_buffer.remove_subscription(&self.connection_id, subscription_id);
+        }
+    }
+
+    fn merge_data(&mut self, data: Data) {
+        // No subscription can have started before the connection is initialized.
+        match Arc::get_mut(&mut self.data) {
+            Some(connection_data) => connection_data.merge(data),
+            None => self.data = Arc::new(data),
+        }
+    }
+
+    /// The replies to a failed initialization, which close the connection. The messages received
+    /// while the initialization ran are dropped.
+    fn init_error(&mut self, e: FieldError) -> Vec<OutMessage> {
+        self.init_future = None;
+        self.deferred.clear();
+        match self.protocol {
+            Protocols::SubscriptionsTransportWS => vec![
+                self.encoding.encode(&ServerMessage::ConnectionError {
+                    payload: ConnectionError {
+                        message: e.0,
+                        extensions: e.1,
+                    },
+                }),
+                OutMessage::Close,
+            ],
+            Protocols::GraphQLWS => vec![close(4403, "Forbidden")],
+        }
+    }
+
+    /// Start a subscription, returning the events it missed since the last connection.
+    fn start(&mut self, id: String, request: Request) -> Vec<OutMessage> {
+        let mut replies = Vec::new();
+        if let (Some(event_buffer), Some(last_event_id)) =
+            (self.event_buffer.as_ref(), self.last_event_id)
+        {
+            for (event_id, payload) in
+                event_buffer.events_after(&self.connection_id, &id, last_event_id)
+            {
+                replies.push(
+                    self.encoding
+                        .encode(&self.protocol.buffered_data(&id, payload, event_id)),
+                );
+            }
+        }
+        let completion = CompletionHandle::default();
+        let stream = self.schema.execute_stream_with_ctx_data(
+            request,
+            Arc::clone(&self.data),
+            completion.clone(),
+        );
+        self.streams.insert(id, (completion, Box::pin(stream)));
+        replies
+    }
+
+    /// The reply to an invalid message, which closes the connection in the
+    /// `graphql-transport-ws` protocol.
+    fn protocol_error(&self, message: String) -> OutMessage {
+        match self.protocol {
+            Protocols::SubscriptionsTransportWS => {
+                self.encoding.encode(&ServerMessage::ConnectionError {
+                    payload: ConnectionError {
+                        message,
+                        extensions: None,
+                    },
+                })
+            }
+            Protocols::GraphQLWS => close(4400, message),
+        }
+    }
+}
+
+impl<F, Query, Mutation, Subscription> Stream
+    for WebSocketProtocol<F, Query, Mutation, Subscription>
+where
+    F: FnOnce(serde_json::Value) -> FieldResult<Data>,
+    Query: ObjectType + Send + Sync + 'static,
+    Mutation: ObjectType + Send + Sync + 'static,
+    Subscription: SubscriptionType + Send + Sync + 'static,
+{
+    type Item = OutMessage;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        if let Some(init_future) = &mut this.init_future {
+            let result = match init_future.as_mut().poll(cx) {
+                Poll::Ready(result) => result,
+                Poll::Pending => return Poll::Pending,
+            };
+            this.init_future = None;
+            match result {
+                Ok(data) => {
+                    this.merge_data(data);
+                    this.initialized = true;
+                    let reply = this.connection_ack();
+                    this.replies.push_back(reply);
+                }
+                Err(e) => {
+                    let replies = this.init_error(e);
+                    this.replies.extend(replies);
+                }
+            }
+            while let Some(message) = this.deferred.pop_front() {
+                let replies = this.on_message(&message);
+                this.replies.extend(replies);
+            }
+        }
+        if let Some(reply) = this.replies.pop_front() {
+            return Poll::Ready(Some(reply));
+        }
+
+        for (id, (_, stream)) in &mut this.streams {
+            match Pin::new(stream).poll_next(cx) {
+                Poll::Ready(Some(payload))
+                    if this.protocol == Protocols::GraphQLWS && payload.is_request_error() =>
+                {
+                    // The operation did not start, so the subscription ends with its errors
+                    // without a `complete` message.
+                    let id = id.clone();
+                    this.streams.remove(&id);
+                    let mut payload = serde_json::to_value(&payload).unwrap();
+                    return Poll::Ready(Some(this.encoding.encode(&ServerMessage::Error {
+                        id: &id,
+                        payload: payload["errors"].take(),
+                    })));
+                }
+                Poll::Ready(Some(payload)) => {
+                    let message = match &this.event_buffer {
+                        Some(event_buffer) => {
+                            // Serialize the payload once, for both the buffer and the message.
+                            let payload = serde_json::to_value(&payload).unwrap();
+                            let event_id =
+                                event_buffer.push(&this.connection_id, id, payload.clone());
+                            this.protocol.buffered_data(id, payload, event_id)
+                        }
+                        None => this.protocol.data(id, payload),
+                    };
+                    return Poll::Ready(Some(this.encoding.encode(&message)));
+                }
+                Poll::Ready(None) => {
+                    let id = id.clone();
+                    this.streams.remove(&id);
+                    this.remove_events(&id);
+                    return Poll::Ready(Some(
+                        this.encoding.encode(&ServerMessage::Complete { id: &id }),
+                    ));
+                }
+                Poll::Pending => {}
+            }
+        }
+
+        Poll::Pending
+    }
+}
 
 pin_project! {
     /// A GraphQL connection over websocket.
     ///
-    /// [Reference](https://github.com/apollographql/subscriptions-transport-ws/blob/master/PROTOCOL.md).
+    /// This adapts a [`WebSocketProtocol`](struct.WebSocketProtocol.html) to a stream of the
+    /// messages of the client, and is a stream of the messages to send to it, which ends when the
+    /// connection must be closed.
     pub struct WebSocket<S, F, Query, Mutation, Subscription> {
-        data_initializer: Option<F>,
-        data: Arc<Data>,
-        schema: Schema<Query, Mutation, Subscription>,
-        streams: HashMap<String, Pin<Box<dyn Stream<Item = Response> + Send>>>,
+        protocol: WebSocketProtocol<F, Query, Mutation, Subscription>,
+        pending: VecDeque<OutMessage>,
+        closed: bool,
         #[pin]
         stream: S,
     }
@@ -30,13 +800,7 @@ impl<S, Query, Mutation, Subscription>
     /// Create a new websocket.
     #[must_use]
     pub fn new(schema: Schema<Query, Mutation, Subscription>, stream: S) -> Self {
-        Self {
-            data_initializer: None,
-            data: Arc::default(),
-            schema,
-            streams: HashMap::new(),
-            stream,
-        }
+        Self::with_data(schema, stream, None)
     }
 }
 
@@ -44,25 +808,97 @@ impl<S, F, Query, Mutation, Subscription> WebSocket<S, F, Query, Mutation, Subsc
     /// Create a new websocket with a data initialization function.
     ///
     /// This function, if present, will be called with the data sent by the client in the
-    /// [`GQL_CONNECTION_INIT` message](https://github.com/apollographql/subscriptions-transport-ws/blob/master/PROTOCOL.md#gql_connection_init).
-    /// From that point on the returned data will be accessible to all requests.
+    /// [`GQL_CONNECTION_INIT` message](https://github.com/apollographql/subscriptions-transport-ws/blob/master/PROTOCOL.md#gql_connection_init),
+    /// or `null` if it has no payload. From that point on the returned data will be accessible to
+    /// all requests. If it returns an error, the connection is closed.
     #[must_use]
     pub fn with_data(
         schema: Schema<Query, Mutation, Subscription>,
         stream: S,
         data_initializer: Option<F>,
+    ) -> Self {
+        Self::from_protocol(
+            WebSocketProtocol::with_data(schema, data_initializer),
+            stream,
+        )
+    }
+
+    /// Create a new websocket that runs `protocol`.
+    #[must_use]
+    pub fn from_protocol(
+        protocol: WebSocketProtocol<F, Query, Mutation, Subscription>,
+        stream: S,
     ) -> Self {
         Self {
-            data_initializer,
-            data: Arc::default(),
-            schema,
-            streams: HashMap::new(),
+            protocol,
+            pending: VecDeque::new(),
+            closed: false,
             stream,
         }
     }
+
+    /// Set an asynchronous data initialization function, which is called like the one of
+    /// [`with_data`](#method.with_data) and can be combined with it, for example to validate a
+    /// token with a remote service.
+    ///
+    /// The connection is acknowledged when it returns, and the messages received meanwhile are
+    /// handled afterwards. If it returns an error, the error is sent in a `connection_error`
+    /// message and the connection is closed, or it is closed with the `4403` code in the
+    /// `graphql-transport-ws` protocol.
+    #[must_use]
+    pub fn async_initializer<G, Fut>(mut self, initializer: G) -> Self
+    where
+        G: FnOnce(serde_json::Value) -> Fut + Send + 'static,
+        Fut: Future<Output = FieldResult<Data>> + Send + 'static,
+    {
+        self.protocol = self.protocol.async_initializer(initializer);
+        self
+    }
+
+    /// Number the events sent to subscriptions and store them in `event_buffer`, so that they
+    /// can be replayed after a reconnection.
+    ///
+    /// The `connection_ack` message then has a random `connectionId` in its payload, and every
+    /// `data` message has an `eventId`. When the client reconnects, it sends the connection id
+    /// and the last event id it received as `connectionId` and `lastEventId` in the payload of
+    /// the `connection_init` message, and the missed events of each subscription are sent again
+    /// when it is restarted with the same id.
+    #[must_use]
+    pub fn event_buffer(mut self, event_buffer: impl EventBuffer) -> Self {
+        self.protocol = self.protocol.event_buffer(event_buffer);
+        self
+    }
+
+    /// Set the data of the connection before it is initialized, usually from the HTTP upgrade
+    /// request, such as the user of a token in its URL for browser clients that cannot set the
+    /// headers of a websocket.
+    ///
+    /// The data returned by the data initialization function is added to it, replacing the
+    /// data of the same types.
+    #[must_use]
+    pub fn connection_data(mut self, data: Data) -> Self {
+        self.protocol = self.protocol.connection_data(data);
+        self
+    }
+
+    /// Set the encoding of the messages, see
+    /// [`WebSocketProtocol::encoding`](struct.WebSocketProtocol.html#method.encoding).
+    #[must_use]
+    pub fn encoding(mut self, encoding: WebSocketEncoding) -> Self {
+        self.protocol = self.protocol.encoding(encoding);
+        self
+    }
+
+    /// Set the GraphQL over websocket protocol, see
+    /// [`WebSocketProtocol::protocol`](struct.WebSocketProtocol.html#method.protocol).
+    #[must_use]
+    pub fn protocol(mut self, protocol: Protocols) -> Self {
+        self.protocol = self.protocol.protocol(protocol);
+        self
+    }
 }
 
-impl<S, F, Query, Mutation, Subscription> Stream for WebSocket<S, F, Query, Mutation, Subscription>
+impl<S, F, Query, Mutation, Subscription> WebSocket<S, F, Query, Mutation, Subscription>
 where
     S: Stream,
     S::Item: AsRef<[u8]>,
@@ -71,110 +907,115 @@ where
     Mutation: ObjectType + Send + Sync + 'static,
     Subscription: SubscriptionType + Send + Sync + 'static,
 {
-    type Item = String;
+    /// Returns the messages to send as text and binary messages, for connections with a binary
+    /// [encoding](#method.encoding). The stream ends when the connection must be closed.
+    pub fn into_messages(self) -> impl Stream<Item = OutMessage> {
+        let mut websocket = Box::pin(self);
+        futures::stream::poll_fn(move |cx| websocket.as_mut().poll_message(cx))
+    }
 
-    fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
-        let this = self.project();
-
-        match this.stream.poll_next(cx) {
-            Poll::Ready(message) => {
-                let message = match message {
-                    Some(message) => message,
-                    None => return Poll::Ready(None),
-                };
-
-                let message: ClientMessage = match serde_json::from_slice(message.as_ref()) {
-                    Ok(message) => message,
-                    Err(e) => {
-                        return Poll::Ready(Some(
-                            serde_json::to_string(&ServerMessage::ConnectionError {
-                                payload: ConnectionError {
-                                    message: e.to_string(),
-                                    extensions: None,
-                                },
-                            })
-                            .unwrap(),
-                        ))
-                    }
-                };
-
-                match message {
-                    ClientMessage::ConnectionInit { payload } => {
-                        if let Some(payload) = payload {
-                            if let Some(data_initializer) = this.data_initializer.take() {
-                                *this.data = Arc::new(match data_initializer(payload) {
-                                    Ok(data) => data,
-                                    Err(e) => {
-                                        return Poll::Ready(Some(
-                                            serde_json::to_string(
-                                                &ServerMessage::ConnectionError {
-                                                    payload: ConnectionError {
-                                                        message: e.0,
-                                                        extensions: e.1,
-                                                    },
-                                                },
-                                            )
-                                            .unwrap(),
-                                        ))
-                                    }
-                                });
-                            }
-                        }
-                        return Poll::Ready(Some(
-                            serde_json::to_string(&ServerMessage::ConnectionAck).unwrap(),
-                        ));
-                    }
-                    ClientMessage::Start {
-                        id,
-                        payload: request,
-                    } => {
-                        this.streams.insert(
-                            id,
-                            Box::pin(
-                                this.schema
-                                    .execute_stream_with_ctx_data(request, Arc::clone(this.data)),
-                            ),
-                        );
-                    }
-                    ClientMessage::Stop { id } => {
-                        if this.streams.remove(id).is_some() {
-                            return Poll::Ready(Some(
-                                serde_json::to_string(&ServerMessage::Complete { id }).unwrap(),
-                            ));
-                        }
-                    }
-                    ClientMessage::ConnectionTerminate => return Poll::Ready(None),
-                }
-            }
-            Poll::Pending => {}
+    fn poll_message(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<OutMessage>> {
+        let mut this = self.project();
+        if *this.closed {
+            return Poll::Ready(None);
         }
 
-        for (id, stream) in &mut *this.streams {
-            match Pin::new(stream).poll_next(cx) {
-                Poll::Ready(Some(payload)) => {
-                    return Poll::Ready(Some(
-                        serde_json::to_string(&ServerMessage::Data {
-                            id,
-                            payload: Box::new(payload),
-                        })
-                        .unwrap(),
-                    ));
+        // Keep reading until there is a reply or the transport is pending, so that the transport
+        // wakes this task up again.
+        loop {
+            match this.pending.pop_front() {
+                Some(OutMessage::Close) => return Poll::Ready(None),
+                Some(message @ OutMessage::CloseWithCode { .. }) => {
+                    // Send the close frame, then end the stream.
+                    *this.closed = true;
+                    return Poll::Ready(Some(message));
                 }
-                Poll::Ready(None) => {
-                    let id = id.clone();
-                    this.streams.remove(&id);
-                    return Poll::Ready(Some(
-                        serde_json::to_string(&ServerMessage::Complete { id: &id }).unwrap(),
-                    ));
+                Some(message) => return Poll::Ready(Some(message)),
+                None => {}
+            }
+
+            match this.stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(message)) => {
+                    let replies = this.protocol.on_message(message.as_ref());
+                    this.pending.extend(replies);
                 }
-                Poll::Pending => {}
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => break,
             }
         }
 
-        Poll::Pending
+        match Pin::new(this.protocol).poll_next(cx) {
+            Poll::Ready(Some(OutMessage::Close)) | Poll::Ready(None) => Poll::Ready(None),
+            Poll::Ready(Some(message @ OutMessage::CloseWithCode { .. })) => {
+                *this.closed = true;
+                Poll::Ready(Some(message))
+            }
+            Poll::Ready(Some(message)) => Poll::Ready(Some(message)),
+            Poll::Pending => Poll::Pending,
+        }
     }
 }
 
+/// The text messages to send. The stream ends at the first binary message, use
+/// [`into_messages`](struct.WebSocket.html#method.into_messages) for binary encodings.
+impl<S, F, Query, Mutation, Subscription> Stream for WebSocket<S, F, Query, Mutation, Subscription>
+where
+    S: Stream,
+    S::Item: AsRef<[u8]>,
+    F: FnOnce(serde_json::Value) -> FieldResult<Data>,
+    Query: ObjectType + Send + Sync + 'static,
+    Mutation: ObjectType + Send + Sync + 'static,
+    Subscription: SubscriptionType + Send + Sync + 'static,
+{
+    type Item = String;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        match self.poll_message(cx) {
+            Poll::Ready(Some(OutMessage::Text(message))) => Poll::Ready(Some(message)),
+            Poll::Ready(_) => Poll::Ready(None),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+fn close(code: u16, reason: impl Into<String>) -> OutMessage {
+    OutMessage::CloseWithCode {
+        code,
+        reason: reason.into(),
+    }
+}
+
+impl Protocols {
+    fn data(self, id: &str, payload: Response) -> ServerMessage {
+        match self {
+            Protocols::SubscriptionsTransportWS => ServerMessage::Data {
+                id,
+                payload: Box::new(payload),
+            },
+            Protocols::GraphQLWS => ServerMessage::Next {
+                id,
+                payload: Box::new(payload),
+            },
+        }
+    }
+
+    fn buffered_data(self, id: &str, payload: serde_json::Value, event_id: u64) -> ServerMessage {
+        match self {
+            Protocols::SubscriptionsTransportWS => ServerMessage::BufferedData {
+                id,
+                payload,
+                event_id,
+            },
+            Protocols::GraphQLWS => ServerMessage::BufferedNext {
+                id,
+                payload,
+                event_id,
+            },
+        }
+    }
+}
+
+/// The messages of both protocols, the messages of the other protocol are rejected.
 #[derive(Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 enum ClientMessage<'a> {
@@ -182,20 +1023,54 @@ enum ClientMessage<'a> {
     Start { id: String, payload: Request },
     Stop { id: &'a str },
     ConnectionTerminate,
+    Subscribe { id: String, payload: Request },
+    Complete { id: &'a str },
+    Ping,
+    Pong,
 }
 
 #[derive(Serialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 enum ServerMessage<'a> {
-    ConnectionError { payload: ConnectionError },
-    ConnectionAck,
-    Data { id: &'a str, payload: Box<Response> },
-    // Not used by this library, as it's not necessary to send
-    // Error {
-    //     id: &'a str,
-    //     payload: serde_json::Value,
-    // },
-    Complete { id: &'a str },
+    ConnectionError {
+        payload: ConnectionError,
+    },
+    ConnectionAck {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        payload: Option<serde_json::Value>,
+    },
+    Data {
+        id: &'a str,
+        payload: Box<Response>,
+    },
+    #[serde(rename = "data")]
+    BufferedData {
+        id: &'a str,
+        payload: serde_json::Value,
+        #[serde(rename = "eventId")]
+        event_id: u64,
+    },
+    Next {
+        id: &'a str,
+        payload: Box<Response>,
+    },
+    #[serde(rename = "next")]
+    BufferedNext {
+        id: &'a str,
+        payload: serde_json::Value,
+        #[serde(rename = "eventId")]
+        event_id: u64,
+    },
+    // Only sent in the graphql-transport-ws protocol, the legacy protocol sends the errors
+    // in the payload of a data message.
+    Error {
+        id: &'a str,
+        payload: serde_json::Value,
+    },
+    Complete {
+        id: &'a str,
+    },
+    Pong,
     // Not used by this library
     // #[serde(rename = "ka")]
     // KeepAlive
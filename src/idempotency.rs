@@ -0,0 +1,234 @@
+use crate::parser::types::{ExecutableDocumentData, Selection, SelectionSet};
+use crate::registry::Registry;
+use crate::stream_guard::SpawnFn;
+use crate::Request;
+use fnv::FnvHasher;
+use futures::FutureExt;
+use std::collections::HashMap;
+use std::hash::Hasher;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// A key sent by the client to make a mutation idempotent, usually taken from the
+/// `Idempotency-Key` HTTP header and inserted into the data of the request.
+///
+/// When the schema has an [`IdempotencyStore`](trait.IdempotencyStore.html), a mutation that
+/// only selects fields marked with `#[field(idempotent)]` and is repeated with the same key
+/// returns the response of the first execution instead of being executed again. Reusing a key
+/// for a different request, or while the first request is still executing, is an error.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct IdempotencyKey(pub String);
+
+/// A response stored for an [`IdempotencyKey`](struct.IdempotencyKey.html).
+#[derive(Debug, Clone, PartialEq)]
+pub struct IdempotentResponse {
+    /// The hash of the query, operation name and variables of the request.
+    pub request_hash: u64,
+
+    /// Data of the response.
+    pub data: serde_json::Value,
+
+    /// Extensions of the response.
+    pub extensions: Option<serde_json::Value>,
+}
+
+/// The state of an [`IdempotencyKey`](struct.IdempotencyKey.html), returned by
+/// [`IdempotencyStore::reserve`](trait.IdempotencyStore.html#tymethod.reserve).
+#[derive(Debug, Clone, PartialEq)]
+pub enum IdempotencyReservation {
+    /// The key was not in use and is now reserved for the request.
+    Reserved,
+
+    /// The key is reserved for a request that has not completed yet.
+    Pending {
+        /// The hash of the request that reserved the key.
+        request_hash: u64,
+    },
+
+    /// The response stored for the key.
+    Stored(IdempotentResponse),
+}
+
+/// Stores the responses of idempotent mutations, see
+/// [`SchemaBuilder::idempotency_store`](struct.SchemaBuilder.html#method.idempotency_store).
+///
+/// A key is reserved before the mutation is executed, so that a concurrent request with the same
+/// key is rejected instead of being executed twice. Only successful responses are stored, the
+/// reservation of a failed mutation, or of a request that is dropped before it completes, is
+/// released so that it can be retried with the same key.
+#[async_trait::async_trait]
+pub trait IdempotencyStore: Send + Sync + 'static {
+    /// Reserve the key for the request for the duration of `ttl` if it is not in use, otherwise
+    /// return its current state. This must be atomic.
+    async fn reserve(&self, key: &str, request_hash: u64, ttl: Duration) -> IdempotencyReservation;
+
+    /// Store the response for a reserved key for the duration of `ttl`.
+    async fn set(&self, key: &str, response: IdempotentResponse, ttl: Duration);
+
+    /// Release a reserved key without storing a response.
+    async fn release(&self, key: &str);
+}
+
+enum MemoryEntry {
+    Pending(u64),
+    Stored(IdempotentResponse),
+}
+
+/// An in-memory [`IdempotencyStore`](trait.IdempotencyStore.html), for a single server.
+///
+/// Expired responses are removed when the store is accessed.
+#[derive(Default)]
+pub struct MemoryIdempotencyStore(Mutex<HashMap<String, (Instant, MemoryEntry)>>);
+
+#[async_trait::async_trait]
+impl IdempotencyStore for MemoryIdempotencyStore {
+    async fn reserve(&self, key: &str, request_hash: u64, ttl: Duration) -> IdempotencyReservation {
+        let mut entries = self.0.lock().unwrap();
+        let now = Instant::now();
+        entries.retain(|_, (expires_at, _)| *expires_at > now);
+        match entries.get(key) {
+            Some((_, MemoryEntry::Pending(request_hash))) => IdempotencyReservation::Pending {
+                request_hash: *request_hash,
+            },
+            Some((_, MemoryEntry::Stored(response))) => {
+                IdempotencyReservation::Stored(response.clone())
+            }
+            None => {
+                entries.insert(
+                    key.to_string(),
+                    (now + ttl, MemoryEntry::Pending(request_hash)),
+                );
+                IdempotencyReservation::Reserved
+            }
+        }
+    }
+
+    async fn set(&self, key: &str, response: IdempotentResponse, ttl: Duration) {
+        self.0.lock().unwrap().insert(
+            key.to_string(),
+            (Instant::now() + ttl, MemoryEntry::Stored(response)),
+        );
+    }
+
+    async fn release(&self, key: &str) {
+        let mut entries = self.0.lock().unwrap();
+        if let Some((_, MemoryEntry::Pending(_))) = entries.get(key) {
+            entries.remove(key);
+        }
+    }
+}
+
+/// The reservation of a key, which is released when it is dropped before a response is stored,
+/// e.g. because the client disconnected or a resolver panicked, so that the mutation can be
+/// retried with the same key.
+///
+/// Like the cleanup of a [`StreamGuard`](struct.StreamGuard.html), the release is spawned with
+/// the spawner of the schema, or run to completion on a new thread without one.
+pub(crate) struct ReservationGuard {
+    store: Arc<dyn IdempotencyStore>,
+    key: Option<String>,
+    spawner: Option<Arc<SpawnFn>>,
+}
+
+impl ReservationGuard {
+    pub(crate) fn new(
+        store: Arc<dyn IdempotencyStore>,
+        key: String,
+        spawner: Option<Arc<SpawnFn>>,
+    ) -> Self {
+        Self {
+            store,
+            key: Some(key),
+            spawner,
+        }
+    }
+
+    pub(crate) async fn set(mut self, response: IdempotentResponse, ttl: Duration) {
+        if let Some(key) = &self.key {
+            self.store.set(key, response, ttl).await;
+        }
+        self.key = None;
+    }
+
+    pub(crate) async fn release(mut self) {
+        if let Some(key) = &self.key {
+            self.store.release(key).await;
+        }
+        self.key = None;
+    }
+}
+
+impl Drop for ReservationGuard {
+    fn drop(&mut self) {
+        if let Some(key) = self.key.take() {
+            let store = self.store.clone();
+            let release = async move { store.release(&key).await }.boxed();
+            match &self.spawner {
+                Some(spawner) => spawner(release),
+                None => {
+                    std::thread::spawn(move || futures::executor::block_on(release));
+                }
+            }
+        }
+    }
+}
+
+pub(crate) fn request_hash(request: &Request) -> u64 {
+    let mut hasher = FnvHasher::default();
+    hasher.write(request.query.as_bytes());
+    hasher.write_u8(0);
+    hasher.write(request.operation_name.as_deref().unwrap_or("").as_bytes());
+    hasher.write_u8(0);
+    hasher.write(request.variables.to_string().as_bytes());
+    hasher.finish()
+}
+
+/// Returns true if every field selected on the mutation root is marked as idempotent.
+pub(crate) fn is_idempotent_mutation(
+    registry: &Registry,
+    document: &ExecutableDocumentData,
+) -> bool {
+    let mutation_type = match registry
+        .mutation_type
+        .as_ref()
+        .and_then(|name| registry.types.get(name))
+    {
+        Some(ty) => ty,
+        None => return false,
+    };
+
+    fn check(
+        selection_set: &SelectionSet,
+        document: &ExecutableDocumentData,
+        is_idempotent: &impl Fn(&str) -> bool,
+    ) -> bool {
+        selection_set
+            .items
+            .iter()
+            .all(|selection| match &selection.node {
+                Selection::Field(field) => is_idempotent(&field.node.name.node),
+                Selection::FragmentSpread(spread) => document
+                    .fragments
+                    .get(&spread.node.fragment_name.node)
+                    .map(|fragment| {
+                        check(&fragment.node.selection_set.node, document, is_idempotent)
+                    })
+                    .unwrap_or(false),
+                Selection::InlineFragment(fragment) => {
+                    check(&fragment.node.selection_set.node, document, is_idempotent)
+                }
+            })
+    }
+
+    check(
+        &document.operation.node.selection_set.node,
+        document,
+        &|name| {
+            name == "__typename"
+                || mutation_type
+                    .field_by_name(name)
+                    .map(|field| field.idempotent)
+                    .unwrap_or(false)
+        },
+    )
+}
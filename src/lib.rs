@@ -44,6 +44,7 @@
 //! * Subscriptions (WebSocket transport)
 //! * Custom extensions
 //! * Apollo Tracing extension
+//! * OpenTelemetry extension
 //! * Limit query complexity/depth
 //! * Error Extensions
 //! * Apollo Federation
@@ -70,6 +71,7 @@
 //! * [GraphQL Multipart Request](https://github.com/jaydenseric/graphql-multipart-request-spec)
 //! * [GraphQL Cursor Connections Specification](https://facebook.github.io/relay/graphql/connections.htm)
 //! * [GraphQL over WebSocket Protocol](https://github.com/apollographql/subscriptions-transport-ws/blob/master/PROTOCOL.md)
+//! * [GraphQL over WebSocket Protocol (graphql-ws)](https://github.com/enisdenjo/graphql-ws/blob/master/PROTOCOL.md)
 //! * [Apollo Tracing](https://github.com/apollographql/apollo-tracing)
 //! * [Apollo Federation](https://www.apollographql.com/docs/apollo-server/federation/introduction)
 //!
@@ -101,11 +103,23 @@
 //! - `log`: Enable the [logger extension](extensions/struct.Logger.html).
 //! - `tracing`: Enable the [tracing extension](extensions/struct.Tracing.html).
 //! - `multipart`: Support [sending files over HTTP multipart](http/fn.receive_body.html).
-//! - `unblock`: Support [asynchronous reader for Upload](types/struct.Upload.html)
+//! - `unblock`: Support [asynchronous reader for Upload](types/struct.Upload.html) and [running blocking code from resolvers](struct.ContextBase.html#method.spawn_blocking).
 //! - `bson`: Integrate with the [`bson` crate](https://crates.io/crates/bson).
 //! - `chrono`: Integrate with the [`chrono` crate](https://crates.io/crates/chrono).
 //! - `chrono-tz`: Integrate with the [`chrono-tz` crate](https://crates.io/crates/chrono-tz).
 //! - `url`: Integrate with the [`url` crate](https://crates.io/crates/url).
+//! - `ulid`: Integrate with the [`ulid` crate](https://crates.io/crates/ulid).
+//! - `duration`: ISO-8601 duration scalars for `std::time::Duration` (`Duration`) and, with `chrono`, `chrono::Duration` (`SignedDuration`).
+//!
+//! The following features are not activated by default:
+//!
+//! - `rest`: Support [resolving fields from REST APIs](rest/index.html).
+//! - `msgpack`: Support [MessagePack websocket messages](http/enum.WebSocketEncoding.html).
+//! - `cbor`: Support [CBOR websocket messages](http/enum.WebSocketEncoding.html).
+//! - `simd-json`: Parse [HTTP request bodies](http/fn.receive_body.html), including their variables, with [`simd-json`](https://crates.io/crates/simd-json) instead of `serde_json`.
+//! - `opentelemetry`: Enable the [OpenTelemetry extension](extensions/struct.OpenTelemetry.html).
+//! - `sql`: Support [building SQL column lists from the selection set](struct.Lookahead.html#method.columns).
+//! - `isocodes`: Country ([ISO 3166](https://crates.io/crates/isocountry)), currency ([ISO 4217](https://crates.io/crates/iso_currency)) and language ([BCP 47](https://crates.io/crates/language-tags)) code scalars.
 
 #![warn(missing_docs)]
 #![allow(clippy::trivially_copy_pass_by_ref)]
@@ -115,19 +129,34 @@
 
 mod base;
 mod context;
+mod directive;
 mod error;
+mod idempotency;
+mod live_query;
 mod look_ahead;
 mod model;
+mod prepared;
+mod preview;
+mod query_cache;
+mod redaction;
 mod request;
 mod response;
 mod schema;
 mod serialize_resp;
+mod stream_guard;
 mod subscription;
+mod transaction;
 mod validation;
 
+pub mod dataloader;
 pub mod extensions;
 pub mod guard;
 pub mod http;
+pub mod persisted_operations;
+#[cfg(feature = "rest")]
+#[cfg_attr(feature = "nightly", doc(cfg(feature = "rest")))]
+pub mod rest;
+pub mod schema_registry;
 pub mod types;
 pub mod validators;
 
@@ -148,23 +177,40 @@ pub use indexmap;
 #[doc(hidden)]
 pub use serde_json;
 #[doc(hidden)]
+pub use stream_guard::StreamGuard;
+#[doc(hidden)]
 pub use subscription::SubscriptionType;
 
 pub use async_graphql_parser as parser;
-pub use base::{InputValueType, OutputValueType, ScalarType, Type};
+pub use base::{InputValueSource, InputValueType, OutputValueType, ParseContext, ScalarType, Type};
+#[cfg(feature = "unblock")]
+pub use context::BlockingContext;
 pub use context::{
     Context, ContextBase, Data, QueryEnv, QueryPathNode, QueryPathSegment, Variables,
 };
+pub use directive::VariableDirective;
 pub use error::{
-    Error, ErrorExtensions, FieldError, FieldResult, InputValueError, InputValueResult,
-    ParseRequestError, QueryError, ResultExt, RuleError,
+    Error, ErrorExtensions, FieldError, FieldResult, InputValueError, InputValuePathSegment,
+    InputValueResult, ParseRequestError, QueryError, ResultExt, RuleError,
+};
+pub use idempotency::{
+    IdempotencyKey, IdempotencyReservation, IdempotencyStore, IdempotentResponse,
+    MemoryIdempotencyStore,
 };
-pub use look_ahead::Lookahead;
+pub use look_ahead::{Lookahead, SelectionField};
 pub use parser::types::{ConstValue as Value, Number};
-pub use registry::CacheControl;
-pub use request::{BatchRequest, Request};
-pub use response::{BatchResponse, Response};
-pub use schema::{Schema, SchemaBuilder, SchemaEnv};
+pub use prepared::PreparedOperation;
+pub use preview::{PreviewFeatures, PREVIEW_HEADER};
+pub use redaction::RedactionPolicy;
+pub use registry::{CacheControl, UnknownInputFields};
+pub use request::{BatchRequest, PersistedQuery, Request, RequestExtensions};
+pub use response::{BatchResponse, ErrorKind, RateLimit, Response};
+pub use schema::{
+    Schema, SchemaBuilder, SchemaEnv, SchemaTypes, DEFAULT_INTROSPECTION_DEPTH,
+    DEFAULT_INTROSPECTION_SIZE, INTROSPECTION_QUERY,
+};
+pub use subscription::CompletionReason;
+pub use transaction::TransactionHook;
 pub use validation::ValidationMode;
 
 #[doc(no_inline)]
@@ -186,10 +232,11 @@ pub type Result<T> = std::result::Result<T, Error>;
 ///
 /// | Attribute     | description               | Type     | Optional |
 /// |---------------|---------------------------|----------|----------|
-/// | name          | Object name               | string   | Y        |
+/// | name          | Object name, which can contain type parameters such as `Edge_{T}`, see [Generic objects](#generic-objects) | string   | Y        |
 /// | desc          | Object description        | string   | Y        |
 /// | cache_control | Object cache control      | [`CacheControl`](struct.CacheControl.html) | Y        |
 /// | extends       | Add fields to an entity that's defined in another service | bool | Y |
+/// | desc_file     | TOML file with field descriptions, relative to `Cargo.toml`, see [`SimpleObject`](derive.SimpleObject.html) | string | Y |
 ///
 /// # Field parameters
 ///
@@ -199,10 +246,15 @@ pub type Result<T> = std::result::Result<T, Error>;
 /// | desc          | Field description         | string   | Y        |
 /// | deprecation   | Field deprecation reason  | string   | Y        |
 /// | cache_control | Field cache control       | [`CacheControl`](struct.CacheControl.html) | Y        |
+/// | variant       | When implemented on an enum, only resolve the field for these variants and return `null` for the others | string ("Variant1,Variant2") | Y |
 /// | external      | Mark a field as owned by another service. This allows service A to use fields from service B while also knowing at runtime the types of that field. | bool | Y |
 /// | provides      | Annotate the expected returned fieldset from a field on a base type that is guaranteed to be selectable by the gateway. | string | Y |
 /// | requires      | Annotate the required input fieldset from a base type for a resolver. It is used to develop a query plan where the required fields may not be needed by the client, but the service may need additional information from other services. | string | Y |
-/// | guard         | Field of guard            | [`Guard`](guard/trait.Guard.html) | Y        |
+/// | guard         | Field of guard, guards can be [combined](guard/index.html) with `and(...)`, `or(...)` and `race(...)` | [`Guard`](guard/trait.Guard.html) | Y        |
+/// | requires_scopes | Scopes the caller must have been granted by the [`ScopeProvider`](guard/trait.ScopeProvider.html) in the data, otherwise a `FORBIDDEN` error is returned | string ("read:user write:user") | Y |
+/// | idempotent    | Mark a mutation field as safe to retry, which adds the `@idempotent` directive to the SDL and allows [idempotency keys](struct.IdempotencyKey.html) for it | bool | Y |
+/// | requires_feature_header | Mark the field as a preview of a feature, which is only valid in requests that enable the feature in their [`PreviewFeatures`](struct.PreviewFeatures.html), and annotate it with `@preview(feature: "...")` in the SDL | string | Y |
+/// | complexity    | The cost of the field in the [complexity](struct.SchemaBuilder.html#method.limit_complexity) of a query, added to the complexity of its selection set, or an expression of its arguments and `child_complexity: usize` that returns the complexity of the field and its selection set | usize or string ("first as usize * child_complexity") | Y |
 /// | feature       | It's like a `#[cfg(feature = "foo")]` attribute but instead of not compiling this field it will just return a proper `FieldError` to tell you this feature is not enabled | string ("feature1,feature2") | Y |
 ///
 /// # Field argument parameters
@@ -215,6 +267,7 @@ pub type Result<T> = std::result::Result<T, Error>;
 /// | default      | Argument default value                   | literal     | Y        |
 /// | default_with | Expression to generate default value     | code string | Y        |
 /// | validator    | Input value validator                    | [`InputValueValidator`](validators/trait.InputValueValidator.html) | Y        |
+/// | inject       | Path of a `fn(&Context<'_>) -> FieldResult<T>` that provides the value, such as the id of the current user, instead of an argument in the schema | code string | Y        |
 ///
 /// # Valid field return types
 ///
@@ -288,6 +341,59 @@ pub type Result<T> = std::result::Result<T, Error>;
 ///     }));
 /// });
 /// ```
+///
+/// # Generic objects
+///
+/// An object can be generic if its name contains its type parameters between braces, which are
+/// replaced by the names of the types that they are instantiated with, so that each instantiation
+/// is a different type of the schema. The nullability and the lists of these types are part of
+/// their names, e.g. `Edge<i32>` is `IntEdge`, `Edge<Option<i32>>` is `NullableIntEdge` and
+/// `Edge<Vec<i32>>` is `ListOfIntEdge`.
+///
+/// ```rust
+/// use async_graphql::*;
+///
+/// struct Edge<T> {
+///     cursor: String,
+///     node: T,
+/// }
+///
+/// #[Object(name = "{T}Edge")]
+/// impl<T: OutputValueType + Send + Sync> Edge<T> {
+///     async fn cursor(&self) -> &str {
+///         &self.cursor
+///     }
+///
+///     async fn node(&self) -> &T {
+///         &self.node
+///     }
+/// }
+///
+/// struct QueryRoot;
+///
+/// #[Object]
+/// impl QueryRoot {
+///     async fn int_edge(&self) -> Edge<i32> {
+///         Edge { cursor: "a".to_string(), node: 10 }
+///     }
+///
+///     async fn string_edge(&self) -> Edge<String> {
+///         Edge { cursor: "b".to_string(), node: "hello".to_string() }
+///     }
+/// }
+///
+/// async_std::task::block_on(async move {
+///     let schema = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
+///     let res = schema.execute(r#"{
+///         intEdge { __typename node }
+///         stringEdge { __typename node }
+///     }"#).await.into_result().unwrap().data;
+///     assert_eq!(res, serde_json::json!({
+///         "intEdge": { "__typename": "IntEdge", "node": 10 },
+///         "stringEdge": { "__typename": "StringEdge", "node": "hello" }
+///     }));
+/// });
+/// ```
 pub use async_graphql_derive::Object;
 
 /// Define a GraphQL object with fields
@@ -300,25 +406,47 @@ pub use async_graphql_derive::Object;
 ///
 /// | Attribute     | description               | Type     | Optional |
 /// |---------------|---------------------------|----------|----------|
-/// | name          | Object name               | string   | Y        |
+/// | name          | Object name, which can contain type parameters such as `Edge_{T}`, see [`Object`](attr.Object.html#generic-objects) | string   | Y        |
 /// | desc          | Object description        | string   | Y        |
 /// | cache_control | Object cache control      | [`CacheControl`](struct.CacheControl.html) | Y        |
+/// | from          | Generate a `From` implementation that converts the given type into this object | string | Y |
+/// | desc_file     | TOML file with field descriptions, relative to `Cargo.toml` | string | Y |
+/// | complex       | Add the fields of the [`ComplexObject`](attr.ComplexObject.html) impl block of the type | bool | Y |
 ///
 /// # Field parameters
 ///
 /// | Attribute     | description               | Type     | Optional |
 /// |---------------|---------------------------|----------|----------|
+/// | skip          | Do not expose the field in the object | bool | Y |
 /// | name          | Field name                | string   | Y        |
 /// | desc          | Field description         | string   | Y        |
 /// | deprecation   | Field deprecation reason  | string   | Y        |
 /// | owned         | Field resolver return a ownedship value  | bool   | Y        |
+/// | from_field    | Name of the field of the `from` type to convert from, defaults to the same name | string | Y |
+/// | from_with     | Function used to convert the value of the `from` type, defaults to `Into::into` | string | Y |
 /// | cache_control | Field cache control       | [`CacheControl`](struct.CacheControl.html) | Y        |
 /// | external      | Mark a field as owned by another service. This allows service A to use fields from service B while also knowing at runtime the types of that field. | bool | Y |
 /// | provides      | Annotate the expected returned fieldset from a field on a base type that is guaranteed to be selectable by the gateway. | string | Y |
 /// | requires      | Annotate the required input fieldset from a base type for a resolver. It is used to develop a query plan where the required fields may not be needed by the client, but the service may need additional information from other services. | string | Y |
-/// | guard         | Field of guard            | [`Guard`](guard/trait.Guard.html) | Y        |
+/// | guard         | Field of guard, guards can be [combined](guard/index.html) with `and(...)`, `or(...)` and `race(...)` | [`Guard`](guard/trait.Guard.html) | Y        |
+/// | requires_scopes | Scopes the caller must have been granted by the [`ScopeProvider`](guard/trait.ScopeProvider.html) in the data, otherwise a `FORBIDDEN` error is returned | string ("read:user write:user") | Y |
+/// | idempotent    | Mark a mutation field as safe to retry, which adds the `@idempotent` directive to the SDL and allows [idempotency keys](struct.IdempotencyKey.html) for it | bool | Y |
+/// | requires_feature_header | Mark the field as a preview of a feature, which is only valid in requests that enable the feature in their [`PreviewFeatures`](struct.PreviewFeatures.html), and annotate it with `@preview(feature: "...")` in the SDL | string | Y |
+/// | complexity    | The cost of the field in the [complexity](struct.SchemaBuilder.html#method.limit_complexity) of a query, added to the complexity of its selection set, or an expression of `child_complexity: usize` that returns the complexity of the field and its selection set | usize or string ("2 * child_complexity") | Y |
 /// | feature       | It's like a `#[cfg(feature = "foo")]` attribute but instead of not compiling this field it will just return a proper `FieldError` to tell you this feature is not enabled | string ("feature1,feature2") | Y |
 ///
+/// # Description files
+///
+/// Field descriptions can be maintained separately from the code with `desc_file`. The file
+/// contains one table per GraphQL type, keyed by GraphQL field name, and is only used for fields
+/// that don't have a `desc` attribute or a doc comment. A key in the table of the type that is not
+/// one of its fields is a compile error:
+///
+/// ```toml
+/// [QueryRoot]
+/// value = "The current value"
+/// ```
+///
 /// # Examples
 ///
 /// ```rust
@@ -339,6 +467,44 @@ pub use async_graphql_derive::Object;
 /// ```
 pub use async_graphql_derive::SimpleObject;
 
+/// Define resolver-based fields of a `SimpleObject`.
+///
+/// The `SimpleObject` must have the `complex` attribute. The methods of the impl block are fields
+/// of the same GraphQL type, and take the same field and argument parameters as
+/// [`Object`](attr.Object.html), except for entities. Building a schema with a field that is
+/// defined by both the struct and the impl block panics.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+///
+/// #[derive(SimpleObject)]
+/// #[graphql(complex)]
+/// struct MyObj {
+///     a: i32,
+///     #[field(skip)]
+///     b: i32,
+/// }
+///
+/// #[ComplexObject]
+/// impl MyObj {
+///     async fn sum(&self, #[arg(default = 0)] c: i32) -> i32 {
+///         self.a + self.b + c
+///     }
+/// }
+///
+/// async_std::task::block_on(async move {
+///     let schema = Schema::new(MyObj { a: 10, b: 20 }, EmptyMutation, EmptySubscription);
+///     let res = schema.execute("{ a sum(c: 5) }").await.into_result().unwrap().data;
+///     assert_eq!(res, serde_json::json!({
+///         "a": 10,
+///         "sum": 35,
+///     }));
+/// });
+/// ```
+pub use async_graphql_derive::ComplexObject;
+
 /// Define a GraphQL enum
 ///
 /// *[See also the Book](https://async-graphql.github.io/async-graphql/en/define_enum.html).*
@@ -405,6 +571,8 @@ pub use async_graphql_derive::Enum;
 /// |-------------|---------------------------|----------|----------|
 /// | name        | Object name               | string   | Y        |
 /// | desc        | Object description        | string   | Y        |
+/// | deny_unknown_fields | Reject values with fields that are not defined by the object, overriding [`SchemaBuilder::unknown_input_fields`](struct.SchemaBuilder.html#method.unknown_input_fields) | bool | Y |
+/// | ignore_unknown_fields | Ignore the fields of values that are not defined by the object, overriding [`SchemaBuilder::unknown_input_fields`](struct.SchemaBuilder.html#method.unknown_input_fields) | bool | Y |
 ///
 /// # Field parameters
 ///
@@ -413,10 +581,10 @@ pub use async_graphql_derive::Enum;
 /// | name         | Field name                               | string   | Y        |
 /// | desc         | Field description                        | string   | Y        |
 /// | default      | Use `Default::default` for default value | none        | Y        |
-/// | default      | Argument default value                   | literal     | Y        |
+/// | default      | Field default value                      | literal     | Y        |
 /// | default_with | Expression to generate default value     | code string | Y        |
 /// | validator    | Input value validator                    | [`InputValueValidator`](validators/trait.InputValueValidator.html) | Y        |
-/// | flatten      | Similar to serde (flatten)               | boolean | Y |
+/// | flatten      | Similar to serde (flatten), cannot be combined with `name`, `default` or `validator` | boolean | Y |
 ///
 /// # Examples
 ///
@@ -452,6 +620,66 @@ pub use async_graphql_derive::Enum;
 /// ```
 pub use async_graphql_derive::InputObject;
 
+/// Define a GraphQL oneof input object
+///
+/// A oneof input object is an input object, marked with the `@oneOf` directive, of which exactly
+/// one field must be provided and not be null. Each variant of the enum is a field of the object,
+/// whose type is the type of the variant.
+///
+/// # Macro parameters
+///
+/// | Attribute   | description               | Type     | Optional |
+/// |-------------|---------------------------|----------|----------|
+/// | name        | Object name               | string   | Y        |
+/// | desc        | Object description        | string   | Y        |
+/// | deny_unknown_fields | Reject values with fields that are not defined by the object, overriding [`SchemaBuilder::unknown_input_fields`](struct.SchemaBuilder.html#method.unknown_input_fields) | bool | Y |
+/// | ignore_unknown_fields | Ignore the fields of values that are not defined by the object, overriding [`SchemaBuilder::unknown_input_fields`](struct.SchemaBuilder.html#method.unknown_input_fields) | bool | Y |
+///
+/// # Item parameters
+///
+/// | Attribute    | description                              | Type     | Optional |
+/// |--------------|------------------------------------------|----------|----------|
+/// | name         | Field name                               | string   | Y        |
+/// | desc         | Field description                        | string   | Y        |
+/// | validator    | Input value validator                    | [`InputValueValidator`](validators/trait.InputValueValidator.html) | Y        |
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+///
+/// #[derive(OneofObject)]
+/// enum UserBy {
+///     Id(ID),
+///     Email(String),
+/// }
+///
+/// struct QueryRoot;
+///
+/// #[Object]
+/// impl QueryRoot {
+///     async fn user(&self, by: UserBy) -> String {
+///         match by {
+///             UserBy::Id(id) => format!("id {}", *id),
+///             UserBy::Email(email) => format!("email {}", email),
+///         }
+///     }
+/// }
+///
+/// async_std::task::block_on(async move {
+///     let schema = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
+///     let res = schema.execute(r#"
+///     {
+///         user1: user(by: { id: "1" })
+///         user2: user(by: { email: "a@example.com" })
+///     }"#).await.into_result().unwrap().data;
+///     assert_eq!(res, serde_json::json!({ "user1": "id 1", "user2": "email a@example.com" }));
+///
+///     assert!(schema.execute(r#"{ user(by: { id: "1", email: "a@example.com" }) }"#).await.is_err());
+/// });
+/// ```
+pub use async_graphql_derive::OneofObject;
+
 /// Define a GraphQL interface
 ///
 /// *[See also the Book](https://async-graphql.github.io/async-graphql/en/define_interface.html).*
@@ -462,6 +690,7 @@ pub use async_graphql_derive::InputObject;
 /// |-------------|---------------------------|----------|----------|
 /// | name        | Object name               | string   | Y        |
 /// | desc        | Object description        | string   | Y        |
+/// | implements  | An interface that this interface implements, can be repeated. Its implementers must also be variants of that interface, see [`Schema::check_interfaces`](struct.Schema.html#method.check_interfaces) | string | Y |
 ///
 /// # Field parameters
 ///
@@ -504,6 +733,11 @@ pub use async_graphql_derive::InputObject;
 /// The type, name, and parameter fields of the interface must exactly match the type of the
 /// implementation interface, but FieldResult can be omitted.
 ///
+/// The implementations may return a non-null type for a nullable field, or an implementation of
+/// an interface or union, and may have additional optional arguments.
+/// [`Schema::check_interfaces`](struct.Schema.html#method.check_interfaces) lists every type that
+/// doesn't have compatible fields with its interfaces.
+///
 /// ```rust
 /// use async_graphql::*;
 ///
@@ -591,6 +825,12 @@ pub use async_graphql_derive::Interface;
 /// | name        | Object name               | string   | Y        |
 /// | desc        | Object description        | string   | Y        |
 ///
+/// # Item parameters
+///
+/// | Attribute   | description                                               | Type     | Optional |
+/// |-------------|-----------------------------------------------------------|----------|----------|
+/// | flatten     | Merge the possible types of a nested union into this union | bool     | Y        |
+///
 /// # Define a union
 ///
 /// Define TypeA, TypeB, ... as MyUnion
@@ -669,7 +909,10 @@ pub use async_graphql_derive::Union;
 /// | name        | Field name                | string   | Y        |
 /// | desc        | Field description         | string   | Y        |
 /// | deprecation | Field deprecation reason  | string   | Y        |
-/// | guard         | Field of guard            | [`Guard`](guard/trait.Guard.html) | Y        |
+/// | guard         | Field of guard, guards can be [combined](guard/index.html) with `and(...)`, `or(...)` and `race(...)` | [`Guard`](guard/trait.Guard.html) | Y        |
+/// | requires_scopes | Scopes the caller must have been granted by the [`ScopeProvider`](guard/trait.ScopeProvider.html) in the data, otherwise a `FORBIDDEN` error is returned | string ("read:user write:user") | Y |
+/// | shared        | Resolve the source stream once for all the subscriptions of this field with the same arguments and send its items to each of them. The stream must be `'static` and its items `Clone` | bool | Y |
+/// | cleanup       | Function called with the `Context` after the stream is created, which returns a future that releases its resources when the stream is dropped, see [`StreamGuard`](struct.StreamGuard.html) | string | Y |
 /// | feature       | It's like a `#[cfg(feature = "foo")]` attribute but instead of not compiling this field it will just return a proper `FieldError` to tell you this feature is not enabled | string ("feature1,feature2") | Y |
 ///
 /// # Field argument parameters
@@ -682,6 +925,7 @@ pub use async_graphql_derive::Union;
 /// | default      | Argument default value                   | literal     | Y        |
 /// | default_with | Expression to generate default value     | code string | Y        |
 /// | validator    | Input value validator                    | [`InputValueValidator`](validators/trait.InputValueValidator.html) | Y        |
+/// | inject       | Path of a `fn(&Context<'_>) -> FieldResult<T>` that provides the value instead of an argument in the schema | code string | Y        |
 ///
 /// # Examples
 ///
@@ -709,6 +953,7 @@ pub use async_graphql_derive::Subscription;
 /// |-------------|---------------------------|----------|----------|
 /// | name        | Scalar name               | string   | Y        |
 /// | desc        | Scalar description        | string   | Y        |
+/// | specified_by_url | URL of the scalar specification, exposed as `specifiedByUrl` and `@specifiedBy` | string | Y |
 ///
 pub use async_graphql_derive::Scalar;
 
@@ -716,6 +961,10 @@ pub use async_graphql_derive::Scalar;
 ///
 /// *[See also the Book](https://async-graphql.github.io/async-graphql/en/merging_objects.html).*
 ///
+/// The fields of the merged objects must have distinct names, a field that is defined by two of
+/// them is reported by
+/// [`SchemaBuilder::try_finish`](struct.SchemaBuilder.html#method.try_finish).
+///
 /// # Macro parameters
 ///
 /// | Attribute     | description               | Type     | Optional |
@@ -754,6 +1003,9 @@ pub use async_graphql_derive::MergedObject;
 
 /// Define a merged subscription with multiple subscription types.
 ///
+/// Each subscription field is routed to the merged type that defines it, which can itself be a
+/// merged subscription. As with merged objects, the field names must be distinct.
+///
 /// *[See also the Book](https://async-graphql.github.io/async-graphql/en/merging_objects.html).*
 ///
 /// # Macro parameters
@@ -0,0 +1,88 @@
+use crate::subscription::CompletionHandle;
+use crate::CompletionReason;
+use futures::channel::mpsc;
+use std::collections::HashSet;
+use std::sync::{Arc, Weak};
+
+/// A live query that is waiting for invalidations.
+#[doc(hidden)]
+pub struct LiveQuery {
+    /// The invalidation keys added by the resolvers during the last execution. Any invalidation
+    /// re-executes the query if there are none.
+    keys: spin::Mutex<HashSet<String>>,
+    sender: mpsc::UnboundedSender<()>,
+    /// The completion callbacks added by the resolvers during all the executions.
+    on_complete: spin::Mutex<Vec<Box<dyn FnOnce(CompletionReason) + Send>>>,
+}
+
+impl LiveQuery {
+    pub(crate) fn add_key(&self, key: String) {
+        self.keys.lock().insert(key);
+    }
+
+    pub(crate) fn clear_keys(&self) {
+        self.keys.lock().clear();
+    }
+
+    pub(crate) fn on_complete(&self, f: Box<dyn FnOnce(CompletionReason) + Send>) {
+        self.on_complete.lock().push(f);
+    }
+
+    fn is_invalidated_by(&self, key: &str) -> bool {
+        let keys = self.keys.lock();
+        keys.is_empty() || keys.contains(key)
+    }
+}
+
+/// The live queries of a schema.
+#[derive(Default)]
+pub(crate) struct LiveQueries(spin::Mutex<Vec<Weak<LiveQuery>>>);
+
+impl LiveQueries {
+    /// Register a live query, the receiver is notified when it must be re-executed.
+    pub(crate) fn register(&self) -> (Arc<LiveQuery>, mpsc::UnboundedReceiver<()>) {
+        let (sender, receiver) = mpsc::unbounded();
+        let live_query = Arc::new(LiveQuery {
+            keys: Default::default(),
+            sender,
+            on_complete: Default::default(),
+        });
+        self.0.lock().push(Arc::downgrade(&live_query));
+        (live_query, receiver)
+    }
+
+    pub(crate) fn invalidate(&self, key: &str) {
+        let mut live_queries = self.0.lock();
+        live_queries.retain(|live_query| match live_query.upgrade() {
+            Some(live_query) => {
+                if live_query.is_invalidated_by(key) {
+                    let _ = live_query.sender.unbounded_send(());
+                }
+                true
+            }
+            None => false,
+        });
+    }
+}
+
+/// Calls the completion callbacks of a live query when it is dropped.
+pub(crate) struct LiveQueryGuard {
+    live_query: Arc<LiveQuery>,
+    handle: CompletionHandle,
+}
+
+impl LiveQueryGuard {
+    pub(crate) fn new(live_query: Arc<LiveQuery>, handle: CompletionHandle) -> Self {
+        Self { live_query, handle }
+    }
+}
+
+impl Drop for LiveQueryGuard {
+    fn drop(&mut self) {
+        let reason = self.handle.reason();
+        let callbacks = std::mem::take(&mut *self.live_query.on_complete.lock());
+        for callback in callbacks {
+            callback(reason);
+        }
+    }
+}
@@ -1,15 +1,17 @@
+use crate::context::QueryEnvInner;
 use crate::parser::types::{ExecutableDocumentData, Field, Selection, SelectionSet};
+use crate::{InputValueType, Result, Value};
 
 /// A selection performed by a query.
 pub struct Lookahead<'a> {
-    document: &'a ExecutableDocumentData,
+    query_env: &'a QueryEnvInner,
     field: Option<&'a Field>,
 }
 
 impl<'a> Lookahead<'a> {
-    pub(crate) fn new(document: &'a ExecutableDocumentData, field: &'a Field) -> Self {
+    pub(crate) fn new(query_env: &'a QueryEnvInner, field: &'a Field) -> Self {
         Self {
-            document,
+            query_env,
             field: Some(field),
         }
     }
@@ -21,10 +23,10 @@ impl<'a> Lookahead<'a> {
     /// represents `{ b }`.
     pub fn field(&self, name: &str) -> Self {
         Self {
-            document: self.document,
+            query_env: self.query_env,
             field: self
                 .field
-                .and_then(|field| find(self.document, &field.selection_set.node, name)),
+                .and_then(|field| find(&self.query_env.document, &field.selection_set.node, name)),
         }
     }
 
@@ -33,6 +35,191 @@ impl<'a> Lookahead<'a> {
     pub fn exists(&self) -> bool {
         self.field.is_some()
     }
+
+    /// Get the argument `name` of the field, with the variables substituted, or `None` if the
+    /// field does not exist or the argument is not given.
+    ///
+    /// For example, calling `.field("items").argument::<i32>("first")` on
+    /// `query($n: Int) { items(first: $n) { id } }` will return the value of `$n`.
+    pub fn argument<T: InputValueType>(&self, name: &str) -> Result<Option<T>> {
+        match self.field {
+            Some(field) => parse_argument(self.query_env, field, name),
+            None => Ok(None),
+        }
+    }
+
+    /// Get the subfields of the selection set, including the fields selected through fragments
+    /// and inline fragments, in the order they are selected.
+    pub fn selection_fields(&self) -> Vec<SelectionField<'a>> {
+        let mut fields = Vec::new();
+        if let Some(field) = self.field {
+            collect_fields(self.query_env, &field.selection_set.node, &mut fields);
+        }
+        fields
+    }
+
+    /// Get the names of the selected leaf fields (fields without a selection set), suitable for
+    /// building the column list of a dynamic `SELECT` statement.
+    ///
+    /// Fields selected through fragments and inline fragments are included regardless of their
+    /// type condition, aliases are ignored and `__typename` is skipped. `renames` maps field names
+    /// to column names, fields that are not in `renames` are used as-is. Every column appears
+    /// only once, in the order it is first selected.
+    ///
+    /// For example, calling `.columns(&[("userName", "user_name")])` on
+    /// `{ id name: userName ... on User { id email } friends { id } }` will return
+    /// `["id", "user_name", "email"]`.
+    #[cfg(feature = "sql")]
+    #[cfg_attr(feature = "nightly", doc(cfg(feature = "sql")))]
+    pub fn columns(&self, renames: &[(&str, &str)]) -> Vec<String> {
+        let mut columns = Vec::new();
+        if let Some(field) = self.field {
+            collect_columns(
+                &self.query_env.document,
+                &field.selection_set.node,
+                renames,
+                &mut columns,
+            );
+        }
+        columns
+    }
+}
+
+/// A field selected by a query, see
+/// [`Lookahead::selection_fields`](struct.Lookahead.html#method.selection_fields).
+#[derive(Copy, Clone)]
+pub struct SelectionField<'a> {
+    query_env: &'a QueryEnvInner,
+    field: &'a Field,
+}
+
+impl<'a> SelectionField<'a> {
+    /// The name of the field.
+    pub fn name(&self) -> &'a str {
+        self.field.name.node.as_str()
+    }
+
+    /// The alias of the field, if it has one.
+    pub fn alias(&self) -> Option<&'a str> {
+        self.field.alias.as_ref().map(|alias| alias.node.as_str())
+    }
+
+    /// Get the arguments of the field with the variables substituted, in the order they are
+    /// written. Arguments that are not given are not included.
+    pub fn arguments(&self) -> Result<Vec<(&'a str, Value)>> {
+        self.field
+            .arguments
+            .iter()
+            .map(|(name, value)| {
+                Ok((
+                    name.node.as_str(),
+                    self.query_env.resolve_input_value(value.clone())?,
+                ))
+            })
+            .collect()
+    }
+
+    /// Get the argument `name` of the field, with the variables substituted, or `None` if it is
+    /// not given.
+    pub fn argument<T: InputValueType>(&self, name: &str) -> Result<Option<T>> {
+        parse_argument(self.query_env, self.field, name)
+    }
+
+    /// Get the subfields of the selection set of the field, see
+    /// [`Lookahead::selection_fields`](struct.Lookahead.html#method.selection_fields).
+    pub fn selection_fields(&self) -> Vec<SelectionField<'a>> {
+        let mut fields = Vec::new();
+        collect_fields(self.query_env, &self.field.selection_set.node, &mut fields);
+        fields
+    }
+}
+
+fn parse_argument<T: InputValueType>(
+    query_env: &QueryEnvInner,
+    field: &Field,
+    name: &str,
+) -> Result<Option<T>> {
+    match field.get_argument(name) {
+        Some(value) => {
+            let pos = value.pos;
+            T::parse(Some(query_env.resolve_input_value(value.clone())?))
+                .map(Some)
+                .map_err(|err| err.into_argument_error(pos, name, T::qualified_type_name()))
+        }
+        None => Ok(None),
+    }
+}
+
+fn collect_fields<'a>(
+    query_env: &'a QueryEnvInner,
+    selection_set: &'a SelectionSet,
+    fields: &mut Vec<SelectionField<'a>>,
+) {
+    for item in &selection_set.items {
+        match &item.node {
+            Selection::Field(field) => fields.push(SelectionField {
+                query_env,
+                field: &field.node,
+            }),
+            Selection::InlineFragment(fragment) => {
+                collect_fields(query_env, &fragment.node.selection_set.node, fields);
+            }
+            Selection::FragmentSpread(spread) => {
+                if let Some(fragment) = query_env
+                    .document
+                    .fragments
+                    .get(&spread.node.fragment_name.node)
+                {
+                    collect_fields(query_env, &fragment.node.selection_set.node, fields);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "sql")]
+fn collect_columns(
+    document: &ExecutableDocumentData,
+    selection_set: &SelectionSet,
+    renames: &[(&str, &str)],
+    columns: &mut Vec<String>,
+) {
+    for item in &selection_set.items {
+        match &item.node {
+            Selection::Field(field) => {
+                let name = field.node.name.node.as_str();
+                if name == "__typename" || !field.node.selection_set.node.items.is_empty() {
+                    continue;
+                }
+                let column = renames
+                    .iter()
+                    .find(|(field_name, _)| *field_name == name)
+                    .map(|(_, column)| *column)
+                    .unwrap_or(name);
+                if !columns.iter().any(|c| c == column) {
+                    columns.push(column.to_string());
+                }
+            }
+            Selection::InlineFragment(fragment) => {
+                collect_columns(
+                    document,
+                    &fragment.node.selection_set.node,
+                    renames,
+                    columns,
+                );
+            }
+            Selection::FragmentSpread(spread) => {
+                if let Some(fragment) = document.fragments.get(&spread.node.fragment_name.node) {
+                    collect_columns(
+                        document,
+                        &fragment.node.selection_set.node,
+                        renames,
+                        columns,
+                    );
+                }
+            }
+        }
+    }
 }
 
 fn find<'a>(
@@ -213,4 +400,156 @@ mod tests {
             .await
             .is_err());
     }
+
+    #[cfg(feature = "sql")]
+    #[async_std::test]
+    async fn test_look_ahead_columns() {
+        #[derive(SimpleObject)]
+        #[graphql(internal)]
+        struct User {
+            id: i32,
+            user_name: String,
+            email: String,
+            friends: Vec<User>,
+        }
+
+        #[derive(Interface)]
+        #[graphql(internal, field(name = "id", type = "&i32"))]
+        enum Node {
+            User(User),
+        }
+
+        struct Query;
+
+        #[Object(internal)]
+        impl Query {
+            async fn node(&self, ctx: &Context<'_>, columns: Vec<String>) -> Node {
+                assert_eq!(
+                    ctx.look_ahead().columns(&[("userName", "user_name")]),
+                    columns
+                );
+                User {
+                    id: 1,
+                    user_name: String::new(),
+                    email: String::new(),
+                    friends: Vec::new(),
+                }
+                .into()
+            }
+        }
+
+        let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+            .register_type::<User>()
+            .finish();
+
+        assert!(!schema
+            .execute(
+                r#"{
+            node(columns: ["id", "user_name", "email"]) {
+                __typename
+                id
+                ... on User {
+                    name: userName
+                    friends { email }
+                    ... A
+                }
+            }
+        }
+
+        fragment A on User {
+            email
+            id
+        }"#,
+            )
+            .await
+            .is_err());
+    }
+
+    #[async_std::test]
+    async fn test_selection_field_arguments() {
+        struct Obj(Vec<String>);
+
+        #[Object(internal)]
+        impl Obj {
+            async fn summary(&self) -> Vec<String> {
+                self.0.clone()
+            }
+
+            async fn items(&self, first: Option<i32>) -> i32 {
+                first.unwrap_or_default()
+            }
+
+            async fn child(&self) -> Obj {
+                Obj(Vec::new())
+            }
+        }
+
+        struct Query;
+
+        #[Object(internal)]
+        impl Query {
+            async fn obj(&self, ctx: &Context<'_>) -> FieldResult<Obj> {
+                let mut summary = Vec::new();
+                for field in ctx.look_ahead().selection_fields() {
+                    let arguments = field.arguments()?;
+                    summary.push(format!(
+                        "{}{}({}) {}",
+                        field
+                            .alias()
+                            .map(|alias| format!("{}: ", alias))
+                            .unwrap_or_default(),
+                        field.name(),
+                        arguments
+                            .iter()
+                            .map(|(name, value)| format!("{}: {}", name, value))
+                            .collect::<Vec<_>>()
+                            .join(", "),
+                        field.selection_fields().len(),
+                    ));
+                }
+                let first = ctx
+                    .look_ahead()
+                    .field("child")
+                    .field("items")
+                    .argument::<i32>("first")?;
+                summary.push(format!("child.items.first = {:?}", first));
+                Ok(Obj(summary))
+            }
+        }
+
+        let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+        let query = r#"query($n: Int) {
+            obj {
+                summary
+                a: items(first: $n)
+                ... F
+                child { items(first: 3) }
+            }
+        }
+        fragment F on Obj { items }"#;
+        let res = schema
+            .execute(
+                Request::new(query).variables(Variables::from_json(serde_json::json!({
+                    "n": 5
+                }))),
+            )
+            .await;
+        assert_eq!(
+            res.data,
+            serde_json::json!({
+                "obj": {
+                    "summary": [
+                        "summary() 0",
+                        "a: items(first: 5) 0",
+                        "items() 0",
+                        "child() 1",
+                        "child.items.first = Some(3)",
+                    ],
+                    "a": 5,
+                    "items": 0,
+                    "child": { "items": 3 },
+                },
+            })
+        );
+    }
 }
@@ -9,6 +9,11 @@ pub struct __Schema<'a> {
 /// A GraphQL Schema defines the capabilities of a GraphQL server. It exposes all available types and directives on the server, as well as the entry points for query, mutation, and subscription operations.
 #[Object(internal)]
 impl<'a> __Schema<'a> {
+    /// The description of the schema.
+    async fn description(&self) -> Option<&str> {
+        self.registry.description.as_deref()
+    }
+
     /// A list of all types supported by this server.
     async fn types(&self) -> Vec<__Type<'a>> {
         let mut types = self
@@ -114,19 +114,20 @@ impl<'a> __Type<'a> {
     }
 
     async fn interfaces(&self) -> Option<Vec<__Type<'a>>> {
-        if let TypeDetail::Named(registry::MetaType::Object { name, .. }) = &self.detail {
-            Some(
-                self.registry
-                    .implements
-                    .get(name)
-                    .unwrap_or(&Default::default())
-                    .iter()
-                    .map(|ty| __Type::new(self.registry, ty))
-                    .collect(),
-            )
-        } else {
-            None
-        }
+        let name = match &self.detail {
+            TypeDetail::Named(registry::MetaType::Object { name, .. })
+            | TypeDetail::Named(registry::MetaType::Interface { name, .. }) => name,
+            _ => return None,
+        };
+        Some(
+            self.registry
+                .implements
+                .get(name)
+                .unwrap_or(&Default::default())
+                .iter()
+                .map(|ty| __Type::new(self.registry, ty))
+                .collect(),
+        )
     }
 
     async fn possible_types(&self) -> Option<Vec<__Type<'a>>> {
@@ -200,4 +201,23 @@ impl<'a> __Type<'a> {
             None
         }
     }
+
+    async fn specified_by_url(&self) -> Option<&'static str> {
+        if let TypeDetail::Named(registry::MetaType::Scalar {
+            specified_by_url, ..
+        }) = &self.detail
+        {
+            *specified_by_url
+        } else {
+            None
+        }
+    }
+
+    async fn is_one_of(&self) -> Option<bool> {
+        if let TypeDetail::Named(registry::MetaType::InputObject { oneof, .. }) = &self.detail {
+            Some(*oneof)
+        } else {
+            None
+        }
+    }
 }
@@ -0,0 +1,189 @@
+//! Export the operations of a client as a manifest of persisted operations.
+//!
+//! [`Schema::export_operations`](../struct.Schema.html#method.export_operations) reads the
+//! `.graphql` documents of a client, validates them against the schema and hashes them, so that a
+//! build script can generate the list of operations that the server allows.
+//!
+//! ```ignore
+//! // build.rs
+//! let manifest = schema.export_operations("client/src/graphql").unwrap();
+//! std::fs::write("operations.json", serde_json::to_string_pretty(&manifest)?)?;
+//! ```
+//!
+//! The server can then prepare each operation once with its id, so that the locations of errors
+//! refer to the operation rather than to a query that the client never sent:
+//!
+//! ```ignore
+//! let operation = schema.prepare(operation.body.as_str()).await?.with_id(&operation.id);
+//! let response = schema.execute_prepared_with(&operation, request).await;
+//! ```
+
+use crate::parser::parse_query;
+use crate::parser::types::{ExecutableDefinition, ExecutableDocument};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// A manifest of persisted operations, in the format of the
+/// [Apollo persisted query manifest](https://www.apollographql.com/docs/graphos/operations/persisted-queries).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OperationManifest {
+    /// Always `apollo-persisted-query-manifest`.
+    pub format: String,
+
+    /// The version of the format, always `1`.
+    pub version: u32,
+
+    /// The operations, sorted by the path of their document.
+    pub operations: Vec<ManifestOperation>,
+}
+
+impl Default for OperationManifest {
+    fn default() -> Self {
+        Self {
+            format: "apollo-persisted-query-manifest".to_string(),
+            version: 1,
+            operations: Vec::new(),
+        }
+    }
+}
+
+impl OperationManifest {
+    /// Returns the operation with the hash `id`.
+    pub fn get(&self, id: &str) -> Option<&ManifestOperation> {
+        self.operations.iter().find(|operation| operation.id == id)
+    }
+}
+
+/// An operation of an [`OperationManifest`](struct.OperationManifest.html).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ManifestOperation {
+    /// The SHA-256 hash of the body as a hex string, the same as the `sha256Hash` of
+    /// [automatic persisted queries](../struct.PersistedQuery.html).
+    pub id: String,
+
+    /// The name of the operation, if it has one.
+    pub name: Option<String>,
+
+    /// The type of the operation: `query`, `mutation` or `subscription`.
+    #[serde(rename = "type")]
+    pub ty: String,
+
+    /// The document, exactly as it is in the file.
+    pub body: String,
+}
+
+/// An error exporting the operation of a document.
+#[derive(Debug, thiserror::Error)]
+pub enum ExportError {
+    /// The document could not be read.
+    #[error("Failed to read {}: {error}", path.display())]
+    Io {
+        /// The path of the document.
+        path: PathBuf,
+
+        /// The IO error.
+        error: io::Error,
+    },
+
+    /// The document does not contain exactly one operation.
+    #[error("{} contains {count} operations, expected exactly one", path.display())]
+    OperationCount {
+        /// The path of the document.
+        path: PathBuf,
+
+        /// The number of operations in the document.
+        count: usize,
+    },
+
+    /// The document could not be parsed or is invalid against the schema.
+    #[error("Invalid operation in {}: {error}", path.display())]
+    Invalid {
+        /// The path of the document.
+        path: PathBuf,
+
+        /// The parse or validation error.
+        error: crate::Error,
+    },
+}
+
+/// Returns the SHA-256 hash of `body` as a hex string.
+fn operation_id(body: &str) -> String {
+    format!("{:x}", Sha256::digest(body.as_bytes()))
+}
+
+/// Returns the `.graphql` files in `dir` and its subdirectories, sorted by their path.
+pub(crate) fn find_documents(dir: &Path) -> Result<Vec<PathBuf>, ExportError> {
+    let mut documents = Vec::new();
+    let mut dirs = vec![dir.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        let entries = fs::read_dir(&dir).map_err(|error| ExportError::Io {
+            path: dir.clone(),
+            error,
+        })?;
+        for entry in entries {
+            let path = entry
+                .map_err(|error| ExportError::Io {
+                    path: dir.clone(),
+                    error,
+                })?
+                .path();
+            if path.is_dir() {
+                dirs.push(path);
+            } else if path.extension().map_or(false, |ext| ext == "graphql") {
+                documents.push(path);
+            }
+        }
+    }
+    documents.sort();
+    Ok(documents)
+}
+
+/// Parse the document at `path`, call `validate` with it and return its operation.
+pub(crate) fn export_document(
+    path: PathBuf,
+    validate: impl FnOnce(&ExecutableDocument) -> crate::Result<()>,
+) -> Result<ManifestOperation, ExportError> {
+    let body = fs::read_to_string(&path).map_err(|error| ExportError::Io {
+        path: path.clone(),
+        error,
+    })?;
+    let document = match parse_query(&body) {
+        Ok(document) => document,
+        Err(err) => {
+            return Err(ExportError::Invalid {
+                path,
+                error: err.into(),
+            })
+        }
+    };
+
+    let operations: Vec<_> = document
+        .definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            ExecutableDefinition::Operation(operation) => Some(&operation.node),
+            ExecutableDefinition::Fragment(_) => None,
+        })
+        .collect();
+    if operations.len() != 1 {
+        return Err(ExportError::OperationCount {
+            path,
+            count: operations.len(),
+        });
+    }
+    let operation = operations[0];
+
+    if let Err(error) = validate(&document) {
+        return Err(ExportError::Invalid { path, error });
+    }
+
+    Ok(ManifestOperation {
+        id: operation_id(&body),
+        name: operation.name.as_ref().map(|name| name.node.to_string()),
+        ty: operation.ty.to_string(),
+        body,
+    })
+}
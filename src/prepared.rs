@@ -0,0 +1,135 @@
+use crate::extensions::ExtensionsLock;
+use crate::parser::types::{ExecutableDocumentData, OperationType, Selection, SelectionSet};
+use crate::{CacheControl, Request};
+use std::collections::HashSet;
+
+/// A request that has been parsed and validated, but not executed yet, see
+/// [`Schema::prepare`](struct.Schema.html#method.prepare).
+///
+/// It describes the operation that will be executed, so that integrations can route it, such as
+/// mutations to a primary region, and is executed with
+/// [`Schema::execute_prepared`](struct.Schema.html#method.execute_prepared) without parsing the
+/// query again, or with
+/// [`Schema::execute_prepared_with`](struct.Schema.html#method.execute_prepared_with) for every
+/// request of a known operation.
+pub struct PreparedOperation {
+    pub(crate) request: Request,
+    pub(crate) document: ExecutableDocumentData,
+    pub(crate) cache_control: CacheControl,
+    pub(crate) complexity: usize,
+    pub(crate) extensions: ExtensionsLock,
+    pub(crate) idempotency_key: Option<(String, u64)>,
+    pub(crate) id: Option<String>,
+}
+
+impl PreparedOperation {
+    /// Returns the type of the operation.
+    pub fn operation_type(&self) -> OperationType {
+        self.document.operation.node.ty
+    }
+
+    /// Returns the name of the operation, if it is named.
+    pub fn operation_name(&self) -> Option<&str> {
+        self.document
+            .operation
+            .node
+            .name
+            .as_ref()
+            .map(|name| name.node.as_str())
+    }
+
+    /// Returns the names of the variables defined by the operation, in order.
+    pub fn variables(&self) -> impl Iterator<Item = &str> {
+        self.document
+            .operation
+            .node
+            .variable_definitions
+            .iter()
+            .map(|variable| variable.node.name.node.as_str())
+    }
+
+    /// Returns the names of the fields of the root type that the operation selects, directly or
+    /// in fragments, in order and without duplicates.
+    pub fn root_fields(&self) -> Vec<&str> {
+        let mut fields = Vec::new();
+        self.collect_fields(
+            &self.document.operation.node.selection_set.node,
+            &mut fields,
+            &mut HashSet::new(),
+        );
+        fields
+    }
+
+    /// Returns the cache control of the result of the operation.
+    pub fn cache_control(&self) -> &CacheControl {
+        &self.cache_control
+    }
+
+    /// Returns the complexity of the operation with the variables of its request, its cost for
+    /// the [complexity limit](struct.SchemaBuilder.html#method.limit_complexity).
+    pub fn complexity(&self) -> usize {
+        self.complexity
+    }
+
+    /// Returns the request of the operation.
+    pub fn request(&self) -> &Request {
+        &self.request
+    }
+
+    /// Set the id of the stored document of the operation, such as the
+    /// [id of a persisted operation](persisted_operations/struct.ManifestOperation.html#structfield.id).
+    ///
+    /// The locations of the errors of its responses refer to the stored document, which the
+    /// client never sent, so they are moved to the `persistedOperation` extension of each error,
+    /// next to this id, e.g.
+    /// `"extensions": { "persistedOperation": { "id": "...", "locations": [...] } }`.
+    #[must_use]
+    pub fn with_id(self, id: impl Into<String>) -> Self {
+        Self {
+            id: Some(id.into()),
+            ..self
+        }
+    }
+
+    /// Returns the id of the stored document of the operation, see
+    /// [`with_id`](#method.with_id).
+    pub fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    fn collect_fields<'a>(
+        &'a self,
+        selection_set: &'a SelectionSet,
+        fields: &mut Vec<&'a str>,
+        visited_fragments: &mut HashSet<&'a str>,
+    ) {
+        for selection in &selection_set.items {
+            match &selection.node {
+                Selection::Field(field) => {
+                    let name = field.node.name.node.as_str();
+                    if !fields.contains(&name) {
+                        fields.push(name);
+                    }
+                }
+                Selection::InlineFragment(fragment) => self.collect_fields(
+                    &fragment.node.selection_set.node,
+                    fields,
+                    visited_fragments,
+                ),
+                Selection::FragmentSpread(spread) => {
+                    let name = spread.node.fragment_name.node.as_str();
+                    if !visited_fragments.insert(name) {
+                        continue;
+                    }
+                    if let Some(fragment) = self.document.fragments.get(name) {
+                        self.collect_fields(
+                            &fragment.node.selection_set.node,
+                            fields,
+                            visited_fragments,
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,131 @@
+use crate::parser::types::{ExecutableDocumentData, OperationType, Selection, SelectionSet};
+use crate::registry::{MetaType, Registry};
+use crate::{Error, QueryError, Result};
+use std::collections::HashSet;
+
+/// The name of the HTTP header that lists the preview features enabled for a request, separated
+/// by commas.
+pub const PREVIEW_HEADER: &str = "X-GraphQL-Preview";
+
+/// The preview features that a request opts in to, usually parsed from the
+/// [`X-GraphQL-Preview`](constant.PREVIEW_HEADER.html) header and inserted into the data of the
+/// request.
+///
+/// A field marked with `#[field(requires_feature_header = "feature")]` is only valid in requests
+/// that enable its feature, and is annotated with `@preview(feature: "feature")` in the SDL.
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct PreviewFeatures(pub HashSet<String>);
+
+impl PreviewFeatures {
+    /// Parse the comma-separated features of a header value.
+    pub fn from_header(value: &str) -> Self {
+        Self(
+            value
+                .split(',')
+                .map(str::trim)
+                .filter(|feature| !feature.is_empty())
+                .map(ToString::to_string)
+                .collect(),
+        )
+    }
+
+    /// Returns `true` if `feature` is enabled.
+    pub fn is_enabled(&self, feature: &str) -> bool {
+        self.0.contains(feature)
+    }
+}
+
+/// Returns an error for the first field of `document` that requires a preview feature that is not
+/// enabled.
+pub(crate) fn check_preview_features(
+    registry: &Registry,
+    document: &ExecutableDocumentData,
+    previews: Option<&PreviewFeatures>,
+) -> Result<()> {
+    fn check<'a>(
+        registry: &'a Registry,
+        document: &'a ExecutableDocumentData,
+        previews: Option<&PreviewFeatures>,
+        ty: Option<&'a MetaType>,
+        selection_set: &'a SelectionSet,
+        visited: &mut HashSet<&'a str>,
+    ) -> Result<()> {
+        for selection in &selection_set.items {
+            match &selection.node {
+                Selection::Field(field) => {
+                    let meta_field = ty.and_then(|ty| ty.field_by_name(&field.node.name.node));
+                    if let Some(feature) = meta_field.and_then(|field| field.requires_feature) {
+                        if !previews.map_or(false, |previews| previews.is_enabled(feature)) {
+                            return Err(Error::Query {
+                                pos: field.pos,
+                                path: None,
+                                err: QueryError::PreviewFeatureRequired {
+                                    field_name: field.node.name.node.to_string(),
+                                    feature: feature.to_string(),
+                                },
+                            });
+                        }
+                    }
+                    let child_ty = meta_field
+                        .and_then(|meta_field| registry.concrete_type_by_name(&meta_field.ty));
+                    check(
+                        registry,
+                        document,
+                        previews,
+                        child_ty,
+                        &field.node.selection_set.node,
+                        visited,
+                    )?;
+                }
+                Selection::InlineFragment(fragment) => {
+                    let ty = match &fragment.node.type_condition {
+                        Some(cond) => registry.types.get(cond.node.on.node.as_str()),
+                        None => ty,
+                    };
+                    check(
+                        registry,
+                        document,
+                        previews,
+                        ty,
+                        &fragment.node.selection_set.node,
+                        visited,
+                    )?;
+                }
+                Selection::FragmentSpread(spread) => {
+                    let name = spread.node.fragment_name.node.as_str();
+                    if !visited.insert(name) {
+                        continue;
+                    }
+                    if let Some(fragment) = document.fragments.get(&spread.node.fragment_name.node)
+                    {
+                        check(
+                            registry,
+                            document,
+                            previews,
+                            registry
+                                .types
+                                .get(fragment.node.type_condition.node.on.node.as_str()),
+                            &fragment.node.selection_set.node,
+                            visited,
+                        )?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    let root = match document.operation.node.ty {
+        OperationType::Query => Some(&registry.query_type),
+        OperationType::Mutation => registry.mutation_type.as_ref(),
+        OperationType::Subscription => registry.subscription_type.as_ref(),
+    };
+    check(
+        registry,
+        document,
+        previews,
+        root.and_then(|root| registry.types.get(root)),
+        &document.operation.node.selection_set.node,
+        &mut HashSet::new(),
+    )
+}
@@ -0,0 +1,143 @@
+use crate::parser::types::ExecutableDocumentData;
+use crate::CacheControl;
+use fnv::FnvHasher;
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hasher;
+use std::sync::{Arc, Mutex};
+
+/// A query that has been parsed and validated.
+///
+/// Its limits, such as the complexity, depend on the variables of each request, so they are not
+/// cached.
+pub(crate) struct CachedQuery {
+    query: String,
+    operation_name: Option<String>,
+    pub(crate) document: ExecutableDocumentData,
+    pub(crate) cache_control: CacheControl,
+}
+
+impl CachedQuery {
+    fn matches(&self, query: &str, operation_name: Option<&str>) -> bool {
+        self.query == query && self.operation_name.as_deref() == operation_name
+    }
+}
+
+struct Entry {
+    cached: Arc<CachedQuery>,
+    /// The generation of the last use of the entry.
+    generation: u64,
+}
+
+#[derive(Default)]
+struct Entries {
+    map: HashMap<u64, Entry>,
+    /// The keys of the entries in the order in which they were used, with the generation of
+    /// each use. A use is stale when its entry was used again, or removed, since.
+    uses: VecDeque<(u64, u64)>,
+    generation: u64,
+}
+
+impl Entries {
+    fn is_current(&self, key: u64, generation: u64) -> bool {
+        self.map
+            .get(&key)
+            .map_or(false, |entry| entry.generation == generation)
+    }
+
+    /// Drop the stale uses once they outnumber the entries, so that every use is dropped once.
+    fn compact(&mut self) {
+        if self.uses.len() > self.map.len().saturating_mul(2) {
+            let map = &self.map;
+            self.uses.retain(|(key, generation)| {
+                map.get(key)
+                    .map_or(false, |entry| entry.generation == *generation)
+            });
+        }
+    }
+
+    fn evict_least_recently_used(&mut self) {
+        while let Some((key, generation)) = self.uses.pop_front() {
+            if self.is_current(key, generation) {
+                self.map.remove(&key);
+                return;
+            }
+        }
+    }
+}
+
+/// The least recently used cache of parsed and validated queries, see
+/// [`SchemaBuilder::query_cache`](struct.SchemaBuilder.html#method.query_cache).
+///
+/// Every use of an entry is appended to a queue with a new generation, and the entries whose
+/// current generation is at the front of the queue are evicted first, so lookups and insertions
+/// take constant time.
+pub(crate) struct QueryCache {
+    capacity: usize,
+    entries: Mutex<Entries>,
+}
+
+impl QueryCache {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Default::default(),
+        }
+    }
+
+    pub(crate) fn get(
+        &self,
+        query: &str,
+        operation_name: Option<&str>,
+    ) -> Option<Arc<CachedQuery>> {
+        let key = cache_key(query, operation_name);
+        let mut entries = self.entries.lock().unwrap();
+        let entries = &mut *entries;
+        let entry = entries.map.get_mut(&key)?;
+        // Another query with the same hash is left in the cache.
+        if !entry.cached.matches(query, operation_name) {
+            return None;
+        }
+        entries.generation += 1;
+        entry.generation = entries.generation;
+        entries.uses.push_back((key, entries.generation));
+        let cached = entry.cached.clone();
+        entries.compact();
+        Some(cached)
+    }
+
+    pub(crate) fn insert(
+        &self,
+        query: &str,
+        operation_name: Option<&str>,
+        document: ExecutableDocumentData,
+        cache_control: CacheControl,
+    ) {
+        if self.capacity == 0 {
+            return;
+        }
+        let key = cache_key(query, operation_name);
+        let cached = Arc::new(CachedQuery {
+            query: query.to_string(),
+            operation_name: operation_name.map(ToString::to_string),
+            document,
+            cache_control,
+        });
+        let mut entries = self.entries.lock().unwrap();
+        if !entries.map.contains_key(&key) && entries.map.len() >= self.capacity {
+            entries.evict_least_recently_used();
+        }
+        entries.generation += 1;
+        let generation = entries.generation;
+        entries.map.insert(key, Entry { cached, generation });
+        entries.uses.push_back((key, generation));
+        entries.compact();
+    }
+}
+
+fn cache_key(query: &str, operation_name: Option<&str>) -> u64 {
+    let mut hasher = FnvHasher::default();
+    hasher.write(query.as_bytes());
+    hasher.write_u8(0);
+    hasher.write(operation_name.unwrap_or("").as_bytes());
+    hasher.finish()
+}
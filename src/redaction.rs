@@ -0,0 +1,66 @@
+use crate::Context;
+
+/// A hook that can replace or remove the resolved values of fields depending on the field and
+/// the caller, such as masking personal data for users that are not administrators.
+///
+/// Register it with
+/// [`SchemaBuilder::redaction_policy`](struct.SchemaBuilder.html#method.redaction_policy).
+/// It is called with the resolved value of every field of every object, including the objects
+/// inside lists and the items of subscriptions. The context gives access to the field
+/// (`ctx.item`), its path in the response (`ctx.path_node`) and the data of the request.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+///
+/// struct IsAdmin(bool);
+///
+/// struct MaskEmails;
+///
+/// impl RedactionPolicy for MaskEmails {
+///     fn redact(&self, ctx: &Context<'_>, value: serde_json::Value) -> serde_json::Value {
+///         if ctx.item.node.name.node == "email" && !ctx.data_unchecked::<IsAdmin>().0 {
+///             serde_json::Value::Null
+///         } else {
+///             value
+///         }
+///     }
+/// }
+///
+/// #[derive(SimpleObject)]
+/// struct User {
+///     name: String,
+///     email: Option<String>,
+/// }
+///
+/// struct QueryRoot;
+///
+/// #[Object]
+/// impl QueryRoot {
+///     async fn users(&self) -> Vec<User> {
+///         vec![User { name: "alice".to_string(), email: Some("alice@example.com".to_string()) }]
+///     }
+/// }
+///
+/// #[async_std::main]
+/// async fn main() {
+///     let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+///         .redaction_policy(MaskEmails)
+///         .finish();
+///     let query = "{ users { name email } }";
+///     assert_eq!(
+///         schema.execute(Request::new(query).data(IsAdmin(false))).await.data,
+///         serde_json::json!({ "users": [{ "name": "alice", "email": null }] })
+///     );
+/// }
+/// ```
+pub trait RedactionPolicy: Send + Sync + 'static {
+    /// Returns the value to put in the response instead of the resolved `value`.
+    ///
+    /// For fields of list types `value` is the whole list. Returning `null` for a non-null field,
+    /// or a list with `null` items for a list of non-null items, is an error that fails the
+    /// parent of the field, like the error of a resolver, so the policy should only nullify
+    /// nullable fields.
+    fn redact(&self, ctx: &Context<'_>, value: serde_json::Value) -> serde_json::Value;
+}
@@ -1,5 +1,10 @@
 /// Cache control values
 ///
+/// The `cache_control` of an object applies to each of its fields, and a field can override the
+/// `max_age` or the scope with its own `cache_control`. The cache control of a response is the
+/// intersection of the fields it selects: the smallest `max_age` of the fields that set one, and
+/// private if any field is private. `__typename` has the cache control of its object.
+///
 /// # Examples
 ///
 /// ```rust
@@ -18,6 +23,11 @@
 ///     async fn value2(&self) -> i32 {
 ///         0
 ///     }
+///
+///     #[field(cache_control(max_age = 120))]
+///     async fn value3(&self) -> i32 {
+///         0
+///     }
 /// }
 ///
 /// #[async_std::main]
@@ -26,6 +36,8 @@
 ///     assert_eq!(schema.execute("{ value1 }").await.into_result().unwrap().cache_control, CacheControl { public: true, max_age: 30 });
 ///     assert_eq!(schema.execute("{ value2 }").await.into_result().unwrap().cache_control, CacheControl { public: false, max_age: 60 });
 ///     assert_eq!(schema.execute("{ value1 value2 }").await.into_result().unwrap().cache_control, CacheControl { public: false, max_age: 30 });
+///     assert_eq!(schema.execute("{ value3 }").await.into_result().unwrap().cache_control, CacheControl { public: true, max_age: 120 });
+///     assert_eq!(schema.execute("{ value2 value3 }").await.into_result().unwrap().cache_control, CacheControl { public: false, max_age: 60 });
 /// }
 /// ```
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
@@ -0,0 +1,66 @@
+use crate::parser::types::{Field, VariableDefinition};
+use crate::{InputValueType, Pos, Positioned, QueryError, Result, Value, Variables};
+
+/// Computes the complexity of a field from its arguments and the complexity of its selection set.
+pub type ComputeComplexityFn = fn(&ComplexityContext<'_>, usize) -> Result<usize>;
+
+/// The cost of a field in the complexity of a query, set with `#[field(complexity = ...)]`.
+#[derive(Clone, Copy)]
+pub enum ComplexityType {
+    /// The cost of the field, which is added to the complexity of its selection set.
+    Const(usize),
+    /// Computes the complexity of the field, including its selection set.
+    Fn(ComputeComplexityFn),
+}
+
+/// The field whose complexity is computed by a [`ComputeComplexityFn`], used to parse its
+/// arguments.
+pub struct ComplexityContext<'a> {
+    pub(crate) variables: Option<&'a Variables>,
+    pub(crate) variable_definitions: &'a [Positioned<VariableDefinition>],
+    pub(crate) field: &'a Positioned<Field>,
+}
+
+impl<'a> ComplexityContext<'a> {
+    fn var_value(&self, name: &str, pos: Pos) -> Result<Value> {
+        self.variable_definitions
+            .iter()
+            .find(|def| def.node.name.node == name)
+            .and_then(|def| {
+                self.variables
+                    .and_then(|variables| variables.0.get(&def.node.name.node))
+                    .or_else(|| def.node.default_value())
+            })
+            .cloned()
+            .ok_or_else(|| {
+                QueryError::VarNotDefined {
+                    var_name: name.to_owned(),
+                }
+                .into_error(pos)
+            })
+    }
+
+    /// Parses the argument `name` of the field, like the resolver of the field.
+    pub fn param_value<T: InputValueType>(
+        &self,
+        name: &str,
+        default: Option<fn() -> T>,
+    ) -> Result<T> {
+        match self.field.node.get_argument(name) {
+            Some(value) => {
+                let pos = value.pos;
+                let value = value
+                    .node
+                    .clone()
+                    .into_const_with(|var| self.var_value(&var, pos))?;
+                InputValueType::parse(Some(value))
+                    .map_err(|e| e.into_argument_error(pos, name, T::qualified_type_name()))
+            }
+            None => match default {
+                Some(default) => Ok(default()),
+                None => InputValueType::parse(None)
+                    .map_err(|e| e.into_error(self.field.pos, T::qualified_type_name())),
+            },
+        }
+    }
+}
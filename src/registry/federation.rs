@@ -1,4 +1,4 @@
-use crate::registry::{MetaField, MetaInputValue, MetaType, Registry};
+use crate::registry::{MetaField, MetaInputValue, MetaType, MetaTypeName, Registry};
 use crate::{Any, Type};
 use indexmap::IndexMap;
 use itertools::Itertools;
@@ -7,6 +7,23 @@ use std::fmt::Write;
 impl Registry {
     pub fn create_federation_sdl(&self) -> String {
         let mut sdl = String::new();
+        if let Some(description) = &self.description {
+            writeln!(
+                sdl,
+                "\"\"\"\n{}\n\"\"\"",
+                description.replace("\"\"\"", "\\\"\"\"")
+            )
+            .ok();
+            writeln!(sdl, "schema {{").ok();
+            writeln!(sdl, "\tquery: {}", self.query_type).ok();
+            if let Some(mutation_type) = &self.mutation_type {
+                writeln!(sdl, "\tmutation: {}", mutation_type).ok();
+            }
+            if let Some(subscription_type) = &self.subscription_type {
+                writeln!(sdl, "\tsubscription: {}", subscription_type).ok();
+            }
+            writeln!(sdl, "}}").ok();
+        }
         for ty in self.types.values() {
             if ty.name().starts_with("__") {
                 continue;
@@ -42,6 +59,9 @@ impl Registry {
                             external: false,
                             requires: None,
                             provides: None,
+                            idempotent: false,
+                            compute_complexity: None,
+                            requires_feature: None,
                         },
                     );
                     fields
@@ -68,6 +88,9 @@ impl Registry {
                     external: false,
                     requires: None,
                     provides: None,
+                    idempotent: false,
+                    compute_complexity: None,
+                    requires_feature: None,
                 },
             );
 
@@ -96,6 +119,9 @@ impl Registry {
                     external: false,
                     requires: None,
                     provides: None,
+                    idempotent: false,
+                    compute_complexity: None,
+                    requires_feature: None,
                 },
             );
         }
@@ -133,16 +159,44 @@ impl Registry {
             if let Some(provides) = field.provides {
                 write!(sdl, " @provides(fields: \"{}\")", provides).ok();
             }
+            if field.idempotent {
+                write!(sdl, " @idempotent").ok();
+            }
+            if let Some(feature) = field.requires_feature {
+                write!(sdl, " @preview(feature: \"{}\")", feature).ok();
+            }
             writeln!(sdl).ok();
         }
     }
 
+    fn create_federation_implements(&self, sdl: &mut String, name: &str) {
+        if let Some(implements) = self.implements.get(name) {
+            if !implements.is_empty() {
+                write!(
+                    sdl,
+                    "implements {} ",
+                    implements.iter().sorted().join(" & ")
+                )
+                .ok();
+            }
+        }
+    }
+
     fn create_federation_type(&self, ty: &MetaType, sdl: &mut String) {
         match ty {
-            MetaType::Scalar { name, .. } => {
+            MetaType::Scalar {
+                name,
+                specified_by_url,
+                ..
+            } => {
                 const SYSTEM_SCALARS: &[&str] = &["Int", "Float", "String", "Boolean", "ID", "Any"];
                 if !SYSTEM_SCALARS.contains(&name.as_str()) {
-                    writeln!(sdl, "scalar {}", name).ok();
+                    match specified_by_url {
+                        Some(url) => {
+                            writeln!(sdl, "scalar {} @specifiedBy(url: \"{}\")", name, url).ok()
+                        }
+                        None => writeln!(sdl, "scalar {}", name).ok(),
+                    };
                 }
             }
             MetaType::Object {
@@ -152,7 +206,11 @@ impl Registry {
                 keys,
                 ..
             } => {
-                if name == &self.query_type && fields.len() == 4 {
+                if name == &self.query_type
+                    && fields.keys().all(|name| {
+                        name.starts_with("__") || matches!(name.as_str(), "_service" | "_entities")
+                    })
+                {
                     // Is empty query root, only __schema, __type, _service, _entities fields
                     return;
                 }
@@ -161,15 +219,13 @@ impl Registry {
                         return;
                     }
                 }
-                if *extends {
+                let is_root = name == &self.query_type
+                    || self.mutation_type.as_ref().map_or(false, |ty| ty == name);
+                if *extends || (is_root && self.extend_root_types) {
                     write!(sdl, "extend ").ok();
                 }
                 write!(sdl, "type {} ", name).ok();
-                if let Some(implements) = self.implements.get(name) {
-                    if !implements.is_empty() {
-                        write!(sdl, "implements {}", implements.iter().join(" & ")).ok();
-                    }
-                }
+                self.create_federation_implements(sdl, name);
                 if let Some(keys) = keys {
                     for key in keys {
                         write!(sdl, "@key(fields: \"{}\") ", key).ok();
@@ -190,6 +246,7 @@ impl Registry {
                     write!(sdl, "extend ").ok();
                 }
                 write!(sdl, "interface {} ", name).ok();
+                self.create_federation_implements(sdl, name);
                 if let Some(keys) = keys {
                     for key in keys {
                         write!(sdl, "@key(fields: \"{}\") ", key).ok();
@@ -210,9 +267,15 @@ impl Registry {
                 writeln!(sdl, "}}").ok();
             }
             MetaType::InputObject {
-                name, input_fields, ..
+                name,
+                input_fields,
+                oneof,
+                ..
             } => {
                 write!(sdl, "input {} ", name).ok();
+                if *oneof {
+                    write!(sdl, "@oneOf ").ok();
+                }
                 writeln!(sdl, "{{").ok();
                 for field in input_fields.values() {
                     writeln!(sdl, "{}", federation_input_value(&field)).ok();
@@ -236,6 +299,150 @@ impl Registry {
     }
 }
 
+impl Registry {
+    /// Check that the federation metadata of the types refers to fields that exist, and that the
+    /// fields in `requires` and `provides` are marked as external, returning a message for each
+    /// mismatch.
+    ///
+    /// Only the registry is checked, not how the resolvers use the fields.
+    pub fn check_federation(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        for ty in self.types.values() {
+            let fields = match ty.fields() {
+                Some(fields) => fields,
+                None => continue,
+            };
+
+            let keys = match ty {
+                MetaType::Object { keys, .. } | MetaType::Interface { keys, .. } => keys.as_ref(),
+                _ => None,
+            };
+            for key in keys.into_iter().flatten() {
+                let context = format!("Key \"{}\" of type \"{}\"", key, ty.name());
+                self.check_field_set(ty, key, false, &context, &mut errors);
+            }
+
+            for field in fields.values() {
+                if let Some(requires) = field.requires {
+                    let context = format!("Requires of field \"{}.{}\"", ty.name(), field.name);
+                    self.check_field_set(ty, requires, true, &context, &mut errors);
+                }
+                if let Some(provides) = field.provides {
+                    let context = format!("Provides of field \"{}.{}\"", ty.name(), field.name);
+                    match self
+                        .types
+                        .get(MetaTypeName::concrete_typename(&field.ty))
+                        .filter(|ty| ty.fields().is_some())
+                    {
+                        Some(field_ty) => {
+                            self.check_field_set(field_ty, provides, true, &context, &mut errors)
+                        }
+                        None => errors
+                            .push(format!("{}: type \"{}\" has no fields.", context, field.ty)),
+                    }
+                }
+            }
+        }
+        errors
+    }
+
+    fn check_field_set(
+        &self,
+        ty: &MetaType,
+        field_set: &str,
+        external: bool,
+        context: &str,
+        errors: &mut Vec<String>,
+    ) {
+        let field_set = field_set.replace('{', " { ").replace('}', " } ");
+        let mut tokens = field_set.split_whitespace().peekable();
+        match parse_field_set(&mut tokens) {
+            Some(items) if tokens.next().is_none() => {
+                self.check_field_set_items(ty, &items, external, context, errors)
+            }
+            _ => errors.push(format!("{}: invalid field set.", context)),
+        }
+    }
+
+    fn check_field_set_items(
+        &self,
+        ty: &MetaType,
+        items: &[FieldSetItem<'_>],
+        external: bool,
+        context: &str,
+        errors: &mut Vec<String>,
+    ) {
+        for item in items {
+            let field = match ty.field_by_name(item.name) {
+                Some(field) => field,
+                None => {
+                    errors.push(format!(
+                        "{}: type \"{}\" has no field \"{}\".",
+                        context,
+                        ty.name(),
+                        item.name
+                    ));
+                    continue;
+                }
+            };
+            if external && !field.external {
+                errors.push(format!(
+                    "{}: field \"{}.{}\" is not marked as external.",
+                    context,
+                    ty.name(),
+                    item.name
+                ));
+            }
+            if !item.children.is_empty() {
+                match self
+                    .types
+                    .get(MetaTypeName::concrete_typename(&field.ty))
+                    .filter(|ty| ty.fields().is_some())
+                {
+                    Some(field_ty) => {
+                        self.check_field_set_items(field_ty, &item.children, false, context, errors)
+                    }
+                    None => errors.push(format!(
+                        "{}: field \"{}.{}\" has no subfields.",
+                        context,
+                        ty.name(),
+                        item.name
+                    )),
+                }
+            }
+        }
+    }
+}
+
+struct FieldSetItem<'a> {
+    name: &'a str,
+    children: Vec<FieldSetItem<'a>>,
+}
+
+fn parse_field_set<'a>(
+    tokens: &mut std::iter::Peekable<impl Iterator<Item = &'a str>>,
+) -> Option<Vec<FieldSetItem<'a>>> {
+    let mut items = Vec::new();
+    loop {
+        match tokens.peek() {
+            None | Some(&"}") => return Some(items),
+            Some(&"{") => return None,
+            Some(_) => {
+                let name = tokens.next().unwrap();
+                let mut children = Vec::new();
+                if tokens.peek() == Some(&"{") {
+                    tokens.next();
+                    children = parse_field_set(tokens)?;
+                    if tokens.next() != Some("}") {
+                        return None;
+                    }
+                }
+                items.push(FieldSetItem { name, children });
+            }
+        }
+    }
+}
+
 fn federation_input_value(input_value: &MetaInputValue) -> String {
     if let Some(default_value) = &input_value.default_value {
         format!(
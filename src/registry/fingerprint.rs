@@ -0,0 +1,192 @@
+use crate::registry::{MetaField, MetaInputValue, MetaType, Registry};
+use fnv::FnvHasher;
+use itertools::Itertools;
+use std::fmt::Write;
+use std::hash::Hasher;
+
+impl Registry {
+    /// Returns a hash of the type system as a hex string.
+    ///
+    /// The hash only depends on the names, types, arguments, default values, deprecations and
+    /// descriptions of the schema, types and directives, and not on the order in which they were
+    /// registered, so it is the same for every build of the same schema.
+    pub fn fingerprint(&self) -> String {
+        let mut s = String::new();
+
+        writeln!(
+            s,
+            "schema {:?} {} {:?} {:?}",
+            self.description, self.query_type, self.mutation_type, self.subscription_type
+        )
+        .ok();
+
+        for ty in self.types.values().sorted_by(|a, b| a.name().cmp(b.name())) {
+            match ty {
+                MetaType::Scalar {
+                    name,
+                    description,
+                    specified_by_url,
+                    ..
+                } => {
+                    writeln!(
+                        s,
+                        "scalar {} {:?} {:?}",
+                        name, description, specified_by_url
+                    )
+                    .ok();
+                }
+                MetaType::Object {
+                    name,
+                    description,
+                    fields,
+                    extends,
+                    keys,
+                    ..
+                } => {
+                    writeln!(
+                        s,
+                        "type {} {:?} {} {:?} implements {}",
+                        name,
+                        description,
+                        extends,
+                        keys,
+                        self.implements
+                            .get(name)
+                            .map(|implements| implements.iter().sorted().join(" & "))
+                            .unwrap_or_default()
+                    )
+                    .ok();
+                    fingerprint_fields(&mut s, fields.values());
+                }
+                MetaType::Interface {
+                    name,
+                    description,
+                    fields,
+                    possible_types,
+                    extends,
+                    keys,
+                } => {
+                    writeln!(
+                        s,
+                        "interface {} {:?} {} {:?} implements {} = {}",
+                        name,
+                        description,
+                        extends,
+                        keys,
+                        self.implements
+                            .get(name)
+                            .map(|implements| implements.iter().sorted().join(" & "))
+                            .unwrap_or_default(),
+                        possible_types.iter().sorted().join(" | ")
+                    )
+                    .ok();
+                    fingerprint_fields(&mut s, fields.values());
+                }
+                MetaType::Union {
+                    name,
+                    description,
+                    possible_types,
+                } => {
+                    writeln!(
+                        s,
+                        "union {} {:?} = {}",
+                        name,
+                        description,
+                        possible_types.iter().sorted().join(" | ")
+                    )
+                    .ok();
+                }
+                MetaType::Enum {
+                    name,
+                    description,
+                    enum_values,
+                } => {
+                    writeln!(s, "enum {} {:?}", name, description).ok();
+                    for value in enum_values.values() {
+                        writeln!(
+                            s,
+                            "\t{} {:?} {:?}",
+                            value.name, value.description, value.deprecation
+                        )
+                        .ok();
+                    }
+                }
+                MetaType::InputObject {
+                    name,
+                    description,
+                    input_fields,
+                    oneof,
+                    ..
+                } => {
+                    writeln!(
+                        s,
+                        "input {} {:?}{}",
+                        name,
+                        description,
+                        if *oneof { " @oneOf" } else { "" }
+                    )
+                    .ok();
+                    fingerprint_input_values(&mut s, input_fields.values());
+                }
+            }
+        }
+
+        for directive in self.directives.values().sorted_by_key(|d| d.name) {
+            writeln!(
+                s,
+                "directive {} {:?} {:?}{}",
+                directive.name,
+                directive.description,
+                directive.locations,
+                if directive.is_repeatable {
+                    " repeatable"
+                } else {
+                    ""
+                }
+            )
+            .ok();
+            fingerprint_input_values(&mut s, directive.args.values());
+        }
+
+        let mut hasher = FnvHasher::default();
+        hasher.write(s.as_bytes());
+        format!("{:016x}", hasher.finish())
+    }
+}
+
+fn fingerprint_fields<'a>(s: &mut String, fields: impl Iterator<Item = &'a MetaField>) {
+    for field in fields {
+        writeln!(
+            s,
+            "\t{}: {} {:?} {:?} {} {:?} {:?} {} {:?}",
+            field.name,
+            field.ty,
+            field.description,
+            field.deprecation,
+            field.external,
+            field.requires,
+            field.provides,
+            field.idempotent,
+            field.requires_feature
+        )
+        .ok();
+        for arg in field.args.values() {
+            write!(s, "\t").ok();
+            fingerprint_input_values(s, std::iter::once(arg));
+        }
+    }
+}
+
+fn fingerprint_input_values<'a>(
+    s: &mut String,
+    input_values: impl Iterator<Item = &'a MetaInputValue>,
+) {
+    for input_value in input_values {
+        writeln!(
+            s,
+            "\t{}: {} = {:?} {:?}",
+            input_value.name, input_value.ty, input_value.default_value, input_value.description
+        )
+        .ok();
+    }
+}
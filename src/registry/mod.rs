@@ -1,15 +1,22 @@
 mod cache_control;
+mod complexity;
 mod federation;
+mod fingerprint;
+mod rust_client;
+mod typescript;
 
 use crate::parser::types::{BaseType as ParsedBaseType, Type as ParsedType};
 use crate::validators::InputValueValidator;
 use crate::{model, Value};
 use indexmap::map::IndexMap;
 use indexmap::set::IndexSet;
+use itertools::Itertools;
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 pub use cache_control::CacheControl;
+pub use complexity::{ComplexityContext, ComplexityType, ComputeComplexityFn};
+pub use model::__DirectiveLocation;
 
 fn strip_brackets(type_name: &str) -> Option<&str> {
     if let Some(rest) = type_name.strip_prefix('[') {
@@ -83,6 +90,39 @@ impl<'a> MetaTypeName<'a> {
     }
 }
 
+/// Returns the name that replaces a type parameter in the name of a generic type, for the
+/// qualified type name `ty` of the type that it is instantiated with.
+///
+/// The nullability and the lists of `ty` are part of the name, so that every instantiation has a
+/// different valid name, e.g. `Int!` is `Int`, `Int` is `NullableInt`, `[Int!]!` is `ListOfInt`
+/// and `[Int]` is `NullableListOfNullableInt`.
+#[doc(hidden)]
+pub fn type_param_name(ty: &str) -> String {
+    match MetaTypeName::create(ty) {
+        MetaTypeName::NonNull(ty) => match MetaTypeName::create(ty) {
+            MetaTypeName::List(ty) => format!("ListOf{}", type_param_name(ty)),
+            _ => ty.to_string(),
+        },
+        MetaTypeName::List(ty) => format!("NullableListOf{}", type_param_name(ty)),
+        MetaTypeName::Named(ty) => format!("Nullable{}", ty),
+    }
+}
+
+/// How the fields of input object values that are not defined by their type are handled.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum UnknownInputFields {
+    /// Reject the value, listing the unknown fields and their closest matches.
+    Deny,
+    /// Ignore the unknown fields.
+    Ignore,
+}
+
+impl Default for UnknownInputFields {
+    fn default() -> Self {
+        UnknownInputFields::Deny
+    }
+}
+
 #[derive(Clone)]
 pub struct MetaInputValue {
     pub name: &'static str,
@@ -103,6 +143,9 @@ pub struct MetaField {
     pub external: bool,
     pub requires: Option<&'static str>,
     pub provides: Option<&'static str>,
+    pub idempotent: bool,
+    pub compute_complexity: Option<ComplexityType>,
+    pub requires_feature: Option<&'static str>,
 }
 
 #[derive(Clone)]
@@ -112,11 +155,13 @@ pub struct MetaEnumValue {
     pub deprecation: Option<&'static str>,
 }
 
+#[derive(Clone)]
 pub enum MetaType {
     Scalar {
         name: String,
         description: Option<&'static str>,
         is_valid: fn(value: &Value) -> bool,
+        specified_by_url: Option<&'static str>,
     },
     Object {
         name: String,
@@ -148,6 +193,8 @@ pub enum MetaType {
         name: String,
         description: Option<&'static str>,
         input_fields: IndexMap<String, MetaInputValue>,
+        unknown_fields: Option<UnknownInputFields>,
+        oneof: bool,
     },
 }
 
@@ -245,20 +292,30 @@ impl MetaType {
     }
 }
 
+#[derive(Clone)]
 pub struct MetaDirective {
     pub name: &'static str,
     pub description: Option<&'static str>,
     pub locations: Vec<model::__DirectiveLocation>,
     pub args: IndexMap<&'static str, MetaInputValue>,
+    pub is_repeatable: bool,
 }
 
+#[derive(Clone)]
 pub struct Registry {
+    pub description: Option<String>,
+    pub extend_root_types: bool,
     pub types: HashMap<String, MetaType>,
     pub directives: HashMap<String, MetaDirective>,
     pub implements: HashMap<String, HashSet<String>>,
+    pub unknown_input_fields: UnknownInputFields,
     pub query_type: String,
     pub mutation_type: Option<String>,
     pub subscription_type: Option<String>,
+    /// The mistakes in the definitions of the types, such as a field that is defined by more than
+    /// one merged object, see
+    /// [`SchemaBuilder::try_finish`](../struct.SchemaBuilder.html#method.try_finish).
+    pub errors: Vec<String>,
 }
 
 impl Registry {
@@ -304,6 +361,135 @@ impl Registry {
             });
     }
 
+    /// Check that the types implement interfaces, that their fields are compatible with the fields
+    /// of their interfaces and that they also implement the interfaces that their interfaces
+    /// implement, returning a message for each mismatch.
+    pub fn check_interfaces(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        for (name, interfaces) in self.implements.iter().sorted_by_key(|(name, _)| *name) {
+            let fields = match self.types.get(name).and_then(MetaType::fields) {
+                Some(fields) => fields,
+                None => continue,
+            };
+            for interface in interfaces.iter().sorted() {
+                if interface == name {
+                    errors.push(format!("Type \"{}\" cannot implement itself.", name));
+                    continue;
+                }
+                let interface_fields = match self.types.get(interface) {
+                    Some(MetaType::Interface { fields, .. }) => fields,
+                    _ => {
+                        errors.push(format!(
+                            "Type \"{}\" implements \"{}\", which is not an interface.",
+                            name, interface
+                        ));
+                        continue;
+                    }
+                };
+                self.check_implementation_fields(
+                    name,
+                    fields,
+                    interface,
+                    interface_fields,
+                    &mut errors,
+                );
+                for transitive in self
+                    .implements
+                    .get(interface)
+                    .into_iter()
+                    .flatten()
+                    .sorted()
+                {
+                    if transitive != name && !interfaces.contains(transitive) {
+                        errors.push(format!(
+                            "Type \"{}\" implements \"{}\", so it must also implement \"{}\".",
+                            name, interface, transitive
+                        ));
+                    }
+                }
+            }
+        }
+        errors
+    }
+
+    fn check_implementation_fields(
+        &self,
+        name: &str,
+        fields: &IndexMap<String, MetaField>,
+        interface: &str,
+        interface_fields: &IndexMap<String, MetaField>,
+        errors: &mut Vec<String>,
+    ) {
+        for (field_name, interface_field) in interface_fields {
+            let field = match fields.get(field_name) {
+                Some(field) => field,
+                None => {
+                    errors.push(format!(
+                        "Type \"{}\" has no field \"{}\" of the interface \"{}\".",
+                        name, field_name, interface
+                    ));
+                    continue;
+                }
+            };
+            if !self.is_output_subtype(&field.ty, &interface_field.ty) {
+                errors.push(format!(
+                    "Field \"{}.{}\" has the type \"{}\", which is not compatible with the type \"{}\" of \"{}.{}\".",
+                    name, field_name, field.ty, interface_field.ty, interface, field_name
+                ));
+            }
+            for (arg_name, interface_arg) in &interface_field.args {
+                match field.args.get(arg_name) {
+                    Some(arg) if arg.ty != interface_arg.ty => errors.push(format!(
+                        "Argument \"{}\" of \"{}.{}\" has the type \"{}\", but it has the type \"{}\" in \"{}.{}\".",
+                        arg_name, name, field_name, arg.ty, interface_arg.ty, interface, field_name
+                    )),
+                    Some(_) => {}
+                    None => errors.push(format!(
+                        "Field \"{}.{}\" has no argument \"{}\" of \"{}.{}\".",
+                        name, field_name, arg_name, interface, field_name
+                    )),
+                }
+            }
+            for (arg_name, arg) in &field.args {
+                if !interface_field.args.contains_key(arg_name)
+                    && MetaTypeName::create(&arg.ty).is_non_null()
+                    && arg.default_value.is_none()
+                {
+                    errors.push(format!(
+                        "Argument \"{}\" of \"{}.{}\" must be optional, since it is not an argument of \"{}.{}\".",
+                        arg_name, name, field_name, interface, field_name
+                    ));
+                }
+            }
+        }
+    }
+
+    /// Whether a field of the type `sub` can implement a field of the type `sup`.
+    fn is_output_subtype(&self, sub: &str, sup: &str) -> bool {
+        match (MetaTypeName::create(sub), MetaTypeName::create(sup)) {
+            (MetaTypeName::NonNull(sub), MetaTypeName::NonNull(sup)) => {
+                self.is_output_subtype(sub, sup)
+            }
+            (MetaTypeName::NonNull(sub), _) => self.is_output_subtype(sub, sup),
+            (_, MetaTypeName::NonNull(_)) => false,
+            (MetaTypeName::List(sub), MetaTypeName::List(sup)) => self.is_output_subtype(sub, sup),
+            (MetaTypeName::Named(sub), MetaTypeName::Named(sup)) => {
+                sub == sup
+                    || self
+                        .types
+                        .get(sup)
+                        .map(|ty| ty.is_abstract() && ty.is_possible_type(sub))
+                        .unwrap_or_default()
+                    || self
+                        .implements
+                        .get(sub)
+                        .map(|interfaces| interfaces.contains(sup))
+                        .unwrap_or_default()
+            }
+            _ => false,
+        }
+    }
+
     pub fn add_keys(&mut self, ty: &str, keys: &str) {
         let all_keys = match self.types.get_mut(ty) {
             Some(MetaType::Object { keys: all_keys, .. }) => all_keys,
@@ -0,0 +1,370 @@
+use crate::parser::types::{
+    ExecutableDefinition, ExecutableDocument, FragmentDefinition, OperationDefinition,
+    OperationType, Selection, SelectionSet,
+};
+use crate::registry::{MetaInputValue, MetaType, MetaTypeName, Registry};
+use indexmap::IndexMap;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// The fields selected with the same response key, and whether they are only selected in a
+/// fragment on another type, so that they may be missing from the response.
+struct SelectedField<'a> {
+    name: &'a str,
+    selection_sets: Vec<&'a SelectionSet>,
+    optional: bool,
+}
+
+impl Registry {
+    /// `documents` are the source and the parsed document of the operations, which must have
+    /// been validated against the registry.
+    pub fn create_rust_client(&self, documents: &[(&str, &ExecutableDocument)]) -> String {
+        let mut code = String::from("// Generated from the schema, do not edit.\n\n");
+
+        let mut types: Vec<&MetaType> = self
+            .types
+            .values()
+            .filter(|ty| !ty.name().starts_with("__"))
+            .collect();
+        types.sort_by(|a, b| a.name().cmp(b.name()));
+        for ty in types {
+            match ty {
+                MetaType::Enum {
+                    name, enum_values, ..
+                } => {
+                    code += "#[derive(Debug, Copy, Clone, Eq, PartialEq, serde::Serialize, serde::Deserialize)]\n";
+                    writeln!(code, "pub enum {} {{", name).ok();
+                    for value in enum_values.keys() {
+                        writeln!(code, "    #[serde(rename = \"{}\")]", value).ok();
+                        writeln!(code, "    {},", pascal_case(value)).ok();
+                    }
+                    code += "}\n\n";
+                }
+                MetaType::InputObject {
+                    name, input_fields, ..
+                } => {
+                    code += "#[derive(Debug, Clone, PartialEq, serde::Serialize)]\n";
+                    writeln!(code, "pub struct {} {{", name).ok();
+                    self.write_input_values(&mut code, input_fields.values());
+                    code += "}\n\n";
+                }
+                _ => {}
+            }
+        }
+
+        for (source, document) in documents {
+            let fragments: HashMap<&str, &FragmentDefinition> = document
+                .definitions
+                .iter()
+                .filter_map(|definition| match definition {
+                    ExecutableDefinition::Fragment(fragment) => {
+                        Some((fragment.node.name.node.as_str(), &fragment.node))
+                    }
+                    ExecutableDefinition::Operation(_) => None,
+                })
+                .collect();
+            for definition in &document.definitions {
+                if let ExecutableDefinition::Operation(operation) = definition {
+                    self.write_operation(&mut code, source, &operation.node, &fragments);
+                }
+            }
+        }
+        code
+    }
+
+    fn write_operation(
+        &self,
+        code: &mut String,
+        source: &str,
+        operation: &OperationDefinition,
+        fragments: &HashMap<&str, &FragmentDefinition>,
+    ) {
+        let name = match &operation.name {
+            Some(name) => name.node.to_string(),
+            None => pascal_case(&operation.ty.to_string()),
+        };
+        let root_type = match operation.ty {
+            OperationType::Query => Some(self.query_type.as_str()),
+            OperationType::Mutation => self.mutation_type.as_deref(),
+            OperationType::Subscription => self.subscription_type.as_deref(),
+        }
+        .unwrap_or_default();
+        let query_const = format!("{}_QUERY", snake_case(&name).to_uppercase());
+
+        writeln!(code, "pub const {}: &str = {:?};\n", query_const, source).ok();
+
+        code.push_str("#[derive(Debug, Clone, PartialEq, serde::Serialize)]\n");
+        writeln!(code, "pub struct {}Variables {{", name).ok();
+        for variable in &operation.variable_definitions {
+            let variable = &variable.node;
+            let ty = variable.var_type.node.to_string();
+            write_rename(code, &variable.name.node);
+            if !MetaTypeName::create(&ty).is_non_null() {
+                code.push_str("    #[serde(skip_serializing_if = \"Option::is_none\")]\n");
+            }
+            writeln!(
+                code,
+                "    pub {}: {},",
+                field_ident(&variable.name.node),
+                self.input_type(&ty)
+            )
+            .ok();
+        }
+        code.push_str("}\n\n");
+
+        self.write_selection_struct(
+            code,
+            &format!("{}Data", name),
+            root_type,
+            &[&operation.selection_set.node],
+            fragments,
+        );
+
+        writeln!(
+            code,
+            "/// Returns the body of a `{}` request.\n\
+             pub fn {}(variables: &{}Variables) -> serde_json::Value {{\n    \
+             serde_json::json!({{\n        \
+             \"query\": {},\n        \
+             \"operationName\": {:?},\n        \
+             \"variables\": variables,\n    \
+             }})\n\
+             }}\n",
+            name,
+            field_ident(&name),
+            name,
+            query_const,
+            name
+        )
+        .ok();
+    }
+
+    fn write_selection_struct(
+        &self,
+        code: &mut String,
+        struct_name: &str,
+        type_name: &str,
+        selection_sets: &[&SelectionSet],
+        fragments: &HashMap<&str, &FragmentDefinition>,
+    ) {
+        let mut fields = IndexMap::new();
+        for selection_set in selection_sets {
+            collect_fields(&mut fields, type_name, selection_set, fragments, false);
+        }
+
+        let mut nested = Vec::new();
+        let mut body = String::new();
+        for (key, field) in &fields {
+            let ty = self.types.get(type_name).and_then(|ty| match ty {
+                // The fields of fragments on the possible types of a union are not known here.
+                MetaType::Union { .. } => None,
+                ty => ty.field_by_name(field.name).map(|field| field.ty.as_str()),
+            });
+            let rust_type = match (field.name, ty) {
+                ("__typename", _) => "String".to_string(),
+                (_, Some(ty)) => {
+                    let concrete = MetaTypeName::concrete_typename(ty);
+                    let leaf = match self.types.get(concrete) {
+                        Some(MetaType::Object { .. })
+                        | Some(MetaType::Interface { .. })
+                        | Some(MetaType::Union { .. }) => {
+                            let nested_name = format!("{}{}", struct_name, pascal_case(key));
+                            nested.push((nested_name.clone(), concrete, field));
+                            nested_name
+                        }
+                        _ => leaf_type(self, concrete),
+                    };
+                    output_type(ty, &leaf)
+                }
+                (_, None) => "serde_json::Value".to_string(),
+            };
+
+            write_rename(&mut body, key);
+            if field.optional && !rust_type.starts_with("Option<") {
+                body.push_str("    #[serde(default)]\n");
+                writeln!(body, "    pub {}: Option<{}>,", field_ident(key), rust_type).ok();
+            } else {
+                writeln!(body, "    pub {}: {},", field_ident(key), rust_type).ok();
+            }
+        }
+
+        code.push_str("#[derive(Debug, Clone, PartialEq, serde::Deserialize)]\n");
+        writeln!(code, "pub struct {} {{", struct_name).ok();
+        code.push_str(&body);
+        code.push_str("}\n\n");
+
+        for (nested_name, concrete, field) in nested {
+            self.write_selection_struct(
+                code,
+                &nested_name,
+                concrete,
+                &field.selection_sets,
+                fragments,
+            );
+        }
+    }
+
+    fn write_input_values<'a>(
+        &self,
+        code: &mut String,
+        values: impl Iterator<Item = &'a MetaInputValue>,
+    ) {
+        for value in values {
+            write_rename(code, value.name);
+            if !MetaTypeName::create(&value.ty).is_non_null() {
+                code.push_str("    #[serde(skip_serializing_if = \"Option::is_none\")]\n");
+            }
+            writeln!(
+                code,
+                "    pub {}: {},",
+                field_ident(value.name),
+                self.input_type(&value.ty)
+            )
+            .ok();
+        }
+    }
+
+    fn input_type(&self, ty: &str) -> String {
+        output_type(ty, &leaf_type(self, MetaTypeName::concrete_typename(ty)))
+    }
+}
+
+fn collect_fields<'a>(
+    fields: &mut IndexMap<&'a str, SelectedField<'a>>,
+    type_name: &str,
+    selection_set: &'a SelectionSet,
+    fragments: &HashMap<&str, &'a FragmentDefinition>,
+    optional: bool,
+) {
+    for selection in &selection_set.items {
+        let has_conditions = selection
+            .node
+            .directives()
+            .iter()
+            .any(|directive| matches!(directive.node.name.node.as_str(), "skip" | "include"));
+        match &selection.node {
+            Selection::Field(field) => {
+                let field = &field.node;
+                let selected = fields
+                    .entry(field.response_key().node.as_str())
+                    .or_insert_with(|| SelectedField {
+                        name: field.name.node.as_str(),
+                        selection_sets: Vec::new(),
+                        optional: true,
+                    });
+                selected.selection_sets.push(&field.selection_set.node);
+                selected.optional &= optional || has_conditions;
+            }
+            Selection::InlineFragment(fragment) => {
+                let fragment = &fragment.node;
+                let on_other_type = fragment
+                    .type_condition
+                    .as_ref()
+                    .map_or(false, |condition| condition.node.on.node != type_name);
+                collect_fields(
+                    fields,
+                    type_name,
+                    &fragment.selection_set.node,
+                    fragments,
+                    optional || has_conditions || on_other_type,
+                );
+            }
+            Selection::FragmentSpread(spread) => {
+                if let Some(fragment) = fragments.get(spread.node.fragment_name.node.as_str()) {
+                    let on_other_type = fragment.type_condition.node.on.node != type_name;
+                    collect_fields(
+                        fields,
+                        type_name,
+                        &fragment.selection_set.node,
+                        fragments,
+                        optional || has_conditions || on_other_type,
+                    );
+                }
+            }
+        }
+    }
+}
+
+fn leaf_type(registry: &Registry, name: &str) -> String {
+    match name {
+        "Int" => "i32".to_string(),
+        "Float" => "f64".to_string(),
+        "String" | "ID" => "String".to_string(),
+        "Boolean" => "bool".to_string(),
+        _ => match registry.types.get(name) {
+            Some(MetaType::Enum { .. }) | Some(MetaType::InputObject { .. }) => name.to_string(),
+            _ => "serde_json::Value".to_string(),
+        },
+    }
+}
+
+/// Returns the Rust type of the GraphQL type `ty`, whose named type is `leaf` in Rust.
+fn output_type(ty: &str, leaf: &str) -> String {
+    match MetaTypeName::create(ty) {
+        MetaTypeName::NonNull(ty) => match MetaTypeName::create(ty) {
+            MetaTypeName::List(item) => format!("Vec<{}>", output_type(item, leaf)),
+            _ => leaf.to_string(),
+        },
+        MetaTypeName::List(item) => format!("Option<Vec<{}>>", output_type(item, leaf)),
+        MetaTypeName::Named(_) => format!("Option<{}>", leaf),
+    }
+}
+
+fn write_rename(code: &mut String, name: &str) {
+    if field_ident(name).trim_start_matches("r#") != name {
+        writeln!(code, "    #[serde(rename = \"{}\")]", name).ok();
+    }
+}
+
+fn field_ident(name: &str) -> String {
+    const KEYWORDS: &[&str] = &[
+        "as", "async", "await", "box", "break", "const", "continue", "crate", "dyn", "else",
+        "enum", "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod",
+        "move", "mut", "pub", "ref", "return", "static", "struct", "trait", "true", "type",
+        "unsafe", "use", "where", "while",
+    ];
+    let ident = snake_case(name);
+    if KEYWORDS.contains(&ident.as_str()) {
+        format!("r#{}", ident)
+    } else {
+        ident
+    }
+}
+
+fn snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    let mut prev_lower = false;
+    for c in name.chars() {
+        if c.is_ascii_uppercase() {
+            if prev_lower {
+                snake.push('_');
+            }
+            snake.push(c.to_ascii_lowercase());
+            prev_lower = false;
+        } else {
+            snake.push(c);
+            prev_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+        }
+    }
+    snake
+}
+
+fn pascal_case(name: &str) -> String {
+    let mut pascal = String::new();
+    let mut upper = true;
+    // Names in SCREAMING_SNAKE_CASE are converted word by word.
+    let screaming = !name.chars().any(|c| c.is_ascii_lowercase());
+    for c in name.chars() {
+        if c == '_' {
+            upper = true;
+        } else if upper {
+            pascal.push(c.to_ascii_uppercase());
+            upper = false;
+        } else if screaming {
+            pascal.push(c.to_ascii_lowercase());
+        } else {
+            pascal.push(c);
+        }
+    }
+    pascal
+}
@@ -0,0 +1,201 @@
+use crate::registry::{MetaField, MetaInputValue, MetaType, MetaTypeName, Registry};
+use itertools::Itertools;
+use std::fmt::Write;
+
+impl Registry {
+    pub fn create_typescript_definitions(&self) -> String {
+        let mut types: Vec<&MetaType> = self
+            .types
+            .values()
+            .filter(|ty| {
+                const FEDERATION_TYPES: &[&str] = &["_Any", "_Entity", "_Service"];
+                !ty.name().starts_with("__") && !FEDERATION_TYPES.contains(&ty.name())
+            })
+            .collect();
+        types.sort_by(|a, b| a.name().cmp(b.name()));
+
+        let mut ts = String::new();
+        for ty in types {
+            match ty {
+                MetaType::Scalar {
+                    name, description, ..
+                } => {
+                    if scalar_type(name).is_none() {
+                        write_description(&mut ts, "", *description);
+                        writeln!(ts, "export type {} = unknown;", name).ok();
+                        ts.push('\n');
+                    }
+                }
+                MetaType::Object {
+                    name,
+                    description,
+                    fields,
+                    ..
+                } => {
+                    write_description(&mut ts, "", *description);
+                    writeln!(ts, "export interface {} {{", name).ok();
+                    writeln!(ts, "  __typename?: \"{}\";", name).ok();
+                    write_fields(&mut ts, fields.values());
+                    ts.push_str("}\n\n");
+                    write_args(&mut ts, name, fields.values());
+                }
+                MetaType::Interface {
+                    name,
+                    description,
+                    fields,
+                    possible_types,
+                    ..
+                } => {
+                    write_description(&mut ts, "", *description);
+                    writeln!(ts, "export interface {} {{", name).ok();
+                    writeln!(
+                        ts,
+                        "  __typename?: {};",
+                        typename_union(possible_types.iter())
+                    )
+                    .ok();
+                    write_fields(&mut ts, fields.values());
+                    ts.push_str("}\n\n");
+                    write_args(&mut ts, name, fields.values());
+                }
+                MetaType::Union {
+                    name,
+                    description,
+                    possible_types,
+                } => {
+                    write_description(&mut ts, "", *description);
+                    writeln!(
+                        ts,
+                        "export type {} = {};",
+                        name,
+                        if possible_types.is_empty() {
+                            "never".to_string()
+                        } else {
+                            possible_types.iter().join(" | ")
+                        }
+                    )
+                    .ok();
+                    ts.push('\n');
+                }
+                MetaType::Enum {
+                    name,
+                    description,
+                    enum_values,
+                } => {
+                    write_description(&mut ts, "", *description);
+                    writeln!(
+                        ts,
+                        "export type {} = {};",
+                        name,
+                        typename_union(enum_values.keys())
+                    )
+                    .ok();
+                    ts.push('\n');
+                }
+                MetaType::InputObject {
+                    name,
+                    description,
+                    input_fields,
+                    ..
+                } => {
+                    write_description(&mut ts, "", *description);
+                    writeln!(ts, "export interface {} {{", name).ok();
+                    write_input_values(&mut ts, input_fields.values());
+                    ts.push_str("}\n\n");
+                }
+            }
+        }
+        ts
+    }
+}
+
+fn scalar_type(name: &str) -> Option<&'static str> {
+    match name {
+        "Int" | "Float" => Some("number"),
+        "String" | "ID" => Some("string"),
+        "Boolean" => Some("boolean"),
+        _ => None,
+    }
+}
+
+/// Returns the TypeScript type of a non-null GraphQL type.
+fn value_type(ty: &str) -> String {
+    match MetaTypeName::create(ty) {
+        MetaTypeName::NonNull(ty) => value_type(ty),
+        MetaTypeName::List(ty) => {
+            let item = if MetaTypeName::create(ty).is_non_null() {
+                value_type(ty)
+            } else {
+                format!("{} | null", value_type(ty))
+            };
+            if item.contains(' ') {
+                format!("Array<{}>", item)
+            } else {
+                format!("{}[]", item)
+            }
+        }
+        MetaTypeName::Named(name) => scalar_type(name).unwrap_or(name).to_string(),
+    }
+}
+
+/// Returns a union of string literals, or `never` if there are none.
+fn typename_union(names: impl Iterator<Item = impl AsRef<str>>) -> String {
+    let union = names
+        .map(|name| format!("\"{}\"", name.as_ref()))
+        .collect::<Vec<_>>()
+        .join(" | ");
+    if union.is_empty() {
+        "never".to_string()
+    } else {
+        union
+    }
+}
+
+fn write_description(ts: &mut String, indent: &str, description: Option<&str>) {
+    if let Some(description) = description {
+        writeln!(ts, "{}/** {} */", indent, description.replace("*/", "*\\/")).ok();
+    }
+}
+
+/// Introspection and federation fields are not part of the types of the schema.
+fn is_hidden(field: &MetaField) -> bool {
+    field.name.starts_with("__") || field.name == "_service" || field.name == "_entities"
+}
+
+fn write_fields<'a>(ts: &mut String, fields: impl Iterator<Item = &'a MetaField>) {
+    for field in fields.filter(|field| !is_hidden(field)) {
+        write_description(ts, "  ", field.description);
+        if MetaTypeName::create(&field.ty).is_non_null() {
+            writeln!(ts, "  {}: {};", field.name, value_type(&field.ty)).ok();
+        } else {
+            writeln!(ts, "  {}?: {} | null;", field.name, value_type(&field.ty)).ok();
+        }
+    }
+}
+
+fn write_input_values<'a>(ts: &mut String, values: impl Iterator<Item = &'a MetaInputValue>) {
+    for value in values {
+        write_description(ts, "  ", value.description);
+        if !MetaTypeName::create(&value.ty).is_non_null() {
+            writeln!(ts, "  {}?: {} | null;", value.name, value_type(&value.ty)).ok();
+        } else if value.default_value.is_some() {
+            writeln!(ts, "  {}?: {};", value.name, value_type(&value.ty)).ok();
+        } else {
+            writeln!(ts, "  {}: {};", value.name, value_type(&value.ty)).ok();
+        }
+    }
+}
+
+/// Write an interface for the arguments of each field with arguments, named like
+/// `QueryUserArgs`.
+fn write_args<'a>(ts: &mut String, type_name: &str, fields: impl Iterator<Item = &'a MetaField>) {
+    for field in fields.filter(|field| !field.args.is_empty() && !is_hidden(field)) {
+        let mut field_name = field.name.clone();
+        if let Some(first) = field_name.get_mut(..1) {
+            first.make_ascii_uppercase();
+        }
+        writeln!(ts, "export interface {}{}Args {{", type_name, field_name).ok();
+        write_input_values(ts, field.args.values());
+        ts.push_str("}\n\n");
+    }
+}
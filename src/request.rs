@@ -1,7 +1,8 @@
 use crate::parser::types::UploadValue;
 use crate::{Data, ParseRequestError, Value, Variables};
-use serde::{Deserialize, Deserializer};
+use serde::{Deserialize, Deserializer, Serialize};
 use std::any::Any;
+use std::collections::BTreeMap;
 use std::fs::File;
 
 /// GraphQL request.
@@ -19,6 +20,10 @@ pub struct Request {
     /// The variables of the request.
     #[serde(default)]
     pub variables: Variables,
+    /// The extensions of the request, which can be accessed through
+    /// `Context::request_extensions`.
+    #[serde(default)]
+    pub extensions: RequestExtensions,
     /// The data of the request that can be accessed through `Context::data`.
     ///
     /// **This data is only valid for this request**
@@ -33,6 +38,7 @@ impl Request {
             query: query.into(),
             operation_name: None,
             variables: Variables::default(),
+            extensions: RequestExtensions::default(),
             data: Data::default(),
         }
     }
@@ -50,12 +56,28 @@ impl Request {
         Self { variables, ..self }
     }
 
+    /// Set an extension of the request.
+    pub fn extension(mut self, name: impl Into<String>, value: serde_json::Value) -> Self {
+        self.extensions.0.insert(name.into(), value);
+        self
+    }
+
     /// Insert some data for this request.
     pub fn data<D: Any + Send + Sync>(mut self, data: D) -> Self {
         self.data.insert(data);
         self
     }
 
+    /// Enable or disable introspection for this request, overriding
+    /// [`SchemaBuilder::disable_introspection`](struct.SchemaBuilder.html#method.disable_introspection).
+    ///
+    /// This allows privileged callers, such as internal tooling that fetches the schema, to use
+    /// introspection when it is disabled for everyone else.
+    pub fn introspection(mut self, enabled: bool) -> Self {
+        self.data.insert(IntrospectionOverride(enabled));
+        self
+    }
+
     /// Set a variable to an upload value.
     ///
     /// `var_path` is a dot-separated path to the item that begins with `variables`, for example
@@ -87,6 +109,60 @@ impl<T: Into<String>> From<T> for Request {
     }
 }
 
+/// Whether introspection is enabled for a request, set with `Request::introspection`.
+pub(crate) struct IntrospectionOverride(pub(crate) bool);
+
+/// The `extensions` of a request, which hold the information about a request that is not part of
+/// the query, such as the persisted query or the client that sent it.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "Option<BTreeMap<String, serde_json::Value>>")]
+pub struct RequestExtensions(pub BTreeMap<String, serde_json::Value>);
+
+impl From<Option<BTreeMap<String, serde_json::Value>>> for RequestExtensions {
+    fn from(extensions: Option<BTreeMap<String, serde_json::Value>>) -> Self {
+        Self(extensions.unwrap_or_default())
+    }
+}
+
+impl RequestExtensions {
+    /// Returns the extension `name`.
+    pub fn get(&self, name: &str) -> Option<&serde_json::Value> {
+        self.0.get(name)
+    }
+
+    /// Returns the `persistedQuery` extension of
+    /// [automatic persisted queries](https://www.apollographql.com/docs/apollo-server/performance/apq/).
+    pub fn persisted_query(&self) -> Option<PersistedQuery> {
+        serde_json::from_value(self.get("persistedQuery")?.clone()).ok()
+    }
+
+    /// Returns the name of the client that sent the request, which Apollo clients send in
+    /// `clientInfo.clientName`.
+    pub fn client_name(&self) -> Option<&str> {
+        self.client_info("clientName")
+    }
+
+    /// Returns the version of the client that sent the request, which Apollo clients send in
+    /// `clientInfo.clientVersion`.
+    pub fn client_version(&self) -> Option<&str> {
+        self.client_info("clientVersion")
+    }
+
+    fn client_info(&self, name: &str) -> Option<&str> {
+        self.get("clientInfo")?.get(name)?.as_str()
+    }
+}
+
+/// The `persistedQuery` extension of a request.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PersistedQuery {
+    /// The version of the protocol.
+    pub version: i32,
+    /// The SHA-256 hash of the query, in hex.
+    pub sha256_hash: String,
+}
+
 /// Batch support for GraphQL requests, which is either a single query, or an array of queries
 ///
 /// **Reference:** <https://www.apollographql.com/blog/batching-client-graphql-queries-a685f5bcd41b/>
@@ -102,7 +178,9 @@ pub enum BatchRequest {
 }
 
 impl BatchRequest {
-    pub(crate) fn into_single(self) -> Result<Request, ParseRequestError> {
+    /// Returns the request if it is a single request, or an error if it is a batch, for servers
+    /// that do not support batches.
+    pub fn into_single(self) -> Result<Request, ParseRequestError> {
         match self {
             Self::Single(req) => Ok(req),
             Self::Batch(_) => Err(ParseRequestError::UnsupportedBatch),
@@ -165,6 +243,42 @@ mod tests {
         assert_eq!(request.query, "{ a b c }");
     }
 
+    #[test]
+    fn test_request_with_extensions() {
+        let request: Request = serde_json::from_value(json! ({
+            "query": "{ a b c }",
+            "extensions": {
+                "persistedQuery": {
+                    "version": 1,
+                    "sha256Hash": "ecf4edb46db40b5132295c0291d62fb65d6759a9eedfa4d5d612dd5ec54a6b38"
+                },
+                "clientInfo": {
+                    "clientName": "web",
+                    "clientVersion": "1.2.0"
+                }
+            }
+        }))
+        .unwrap();
+        assert_eq!(
+            request.extensions.persisted_query(),
+            Some(PersistedQuery {
+                version: 1,
+                sha256_hash: "ecf4edb46db40b5132295c0291d62fb65d6759a9eedfa4d5d612dd5ec54a6b38"
+                    .to_string(),
+            })
+        );
+        assert_eq!(request.extensions.client_name(), Some("web"));
+        assert_eq!(request.extensions.client_version(), Some("1.2.0"));
+
+        let request: Request = serde_json::from_value(json! ({
+            "query": "{ a b c }",
+            "extensions": null
+        }))
+        .unwrap();
+        assert_eq!(request.extensions, RequestExtensions::default());
+        assert_eq!(request.extensions.client_name(), None);
+    }
+
     #[test]
     fn test_request_with_variables() {
         let request: Request = serde_json::from_value(json! ({
@@ -0,0 +1,80 @@
+use crate::registry::MetaTypeName;
+use crate::{Context, Error, FieldError, FieldResult, OutputValueType, QueryError, Result};
+
+/// Convert the error of a resolver or a guard into an `Error`, attaching the position and path of
+/// the current field.
+#[doc(hidden)]
+pub fn field_error(ctx: &Context<'_>, err: FieldError) -> Error {
+    err.into_error_with_path(ctx.item.pos, ctx.path_node.as_ref())
+}
+
+/// Convert the result of a resolver or a guard into a `Result` with [`field_error`].
+#[doc(hidden)]
+pub fn field_result<T>(ctx: &Context<'_>, res: FieldResult<T>) -> Result<T> {
+    res.map_err(|err| field_error(ctx, err))
+}
+
+/// Resolve the result of a resolver, this is the whole body that the derive macros generate for
+/// a field without arguments or guards.
+#[doc(hidden)]
+pub async fn resolve_field_result<T: OutputValueType + Sync>(
+    ctx: &Context<'_>,
+    res: FieldResult<T>,
+) -> Result<serde_json::Value> {
+    let value = field_result(ctx, res)?;
+    resolve_field_value(ctx, &value).await
+}
+
+/// Resolve the value returned by a resolver with the selection set of the current field, and
+/// apply the redaction policy of the schema to the result.
+#[doc(hidden)]
+pub async fn resolve_field_value<T: OutputValueType + Sync>(
+    ctx: &Context<'_>,
+    value: &T,
+) -> Result<serde_json::Value> {
+    let ctx_obj = ctx.with_selection_set(&ctx.item.node.selection_set);
+    let value = OutputValueType::resolve(value, &ctx_obj, ctx.item).await?;
+    redact_field_value(ctx, value, T::qualified_type_name)
+}
+
+/// Apply the redaction policy to the value of a field of the type returned by `ty`.
+fn redact_field_value(
+    ctx: &Context<'_>,
+    value: serde_json::Value,
+    ty: fn() -> String,
+) -> Result<serde_json::Value> {
+    match &ctx.schema_env.redaction_policy {
+        Some(policy) => {
+            let value = policy.redact(ctx, value);
+            let ty = ty();
+            if has_null_for_non_null(&ty, &value) {
+                // Like the error of a resolver, this fails the parent of the field.
+                return Err(Error::Query {
+                    pos: ctx.item.pos,
+                    path: ctx
+                        .path_node
+                        .as_ref()
+                        .and_then(|path| serde_json::to_value(path).ok()),
+                    err: QueryError::RedactedNonNull {
+                        field_name: ctx.item.node.name.node.to_string(),
+                        ty,
+                    },
+                });
+            }
+            Ok(value)
+        }
+        None => Ok(value),
+    }
+}
+
+/// Whether `value` is `null` where the type `ty` is non-null, or has such a list item.
+fn has_null_for_non_null(ty: &str, value: &serde_json::Value) -> bool {
+    match (MetaTypeName::create(ty), value) {
+        (MetaTypeName::NonNull(_), serde_json::Value::Null) => true,
+        (MetaTypeName::NonNull(ty), value) => has_null_for_non_null(ty, value),
+        (MetaTypeName::List(ty), serde_json::Value::Array(items)) => {
+            items.iter().any(|item| has_null_for_non_null(ty, item))
+        }
+        _ => false,
+    }
+}
@@ -1,7 +1,11 @@
 //! Utilities for implementing `OutputValueType::resolve`.
 
 mod r#enum;
+mod field;
 mod object;
+mod shared_stream;
 
+pub use field::*;
 pub use object::*;
 pub use r#enum::*;
+pub use shared_stream::*;
@@ -1,8 +1,9 @@
-use crate::extensions::{ErrorLogger, Extension, ResolveInfo};
+use crate::extensions::{ErrorLogger, Extension, ExtensionContext, ResolveInfo};
 use crate::parser::types::Selection;
-use crate::registry::MetaType;
+use crate::registry::{MetaField, MetaType, Registry};
 use crate::{Context, ContextSelectionSet, Error, OutputValueType, QueryError, Result, Value};
 use futures::TryFutureExt;
+use indexmap::IndexMap;
 use std::future::Future;
 use std::pin::Pin;
 
@@ -49,6 +50,19 @@ impl<T: ObjectType + Send + Sync> ObjectType for &T {
     }
 }
 
+/// The fields of a `SimpleObject` that are defined by its `#[ComplexObject]` impl block.
+///
+/// This trait is implemented by the `ComplexObject` macro, and used by the `SimpleObject` derive
+/// of the same type when it has the `complex` attribute.
+#[async_trait::async_trait]
+pub trait ComplexObject {
+    /// Register the types of the fields and return their definitions.
+    fn fields(registry: &mut Registry) -> IndexMap<String, MetaField>;
+
+    /// Resolves a field value and outputs it as a json value `serde_json::Value`.
+    async fn resolve_field(&self, ctx: &Context<'_>) -> Result<serde_json::Value>;
+}
+
 // TODO: reduce code duplication between the two below functions?
 
 /// Resolve an object by executing each of the fields concurrently.
@@ -180,7 +194,10 @@ impl<'a> Fields<'a> {
                                     None => {
                                         return Err(Error::Query {
                                             pos: field.pos,
-                                            path: None,
+                                            path: ctx_field
+                                                .path_node
+                                                .as_ref()
+                                                .and_then(|path| serde_json::to_value(path).ok()),
                                             err: QueryError::FieldNotFound {
                                                 field_name: field
                                                     .node
@@ -195,23 +212,32 @@ impl<'a> Fields<'a> {
                                 },
                             };
 
+                            let extension_ctx = ExtensionContext {
+                                data: &ctx_field.query_env.ctx_data,
+                            };
                             ctx_field
                                 .query_env
                                 .extensions
                                 .lock()
-                                .resolve_start(&resolve_info);
+                                .await
+                                .resolve_start(&extension_ctx, &resolve_info)
+                                .await;
 
                             let res = root
                                 .resolve_field(&ctx_field)
                                 .map_ok(move |value| (field_name, value))
                                 .await
-                                .log_error(&ctx_field.query_env.extensions)?;
+                                .map_err(|err| ctx_field.set_error_path(err))
+                                .log_error(&ctx_field.query_env.extensions, &extension_ctx)
+                                .await?;
 
                             ctx_field
                                 .query_env
                                 .extensions
                                 .lock()
-                                .resolve_end(&resolve_info);
+                                .await
+                                .resolve_end(&extension_ctx, &resolve_info)
+                                .await;
                             Ok(res)
                         }
                     }));
@@ -260,6 +286,12 @@ impl<'a> Fields<'a> {
                                 .implements
                                 .get(&*introspection_type_name)
                                 .map_or(false, |interfaces| interfaces.contains(condition))
+                            || ctx
+                                .schema_env
+                                .registry
+                                .types
+                                .get(condition)
+                                .map_or(false, |ty| ty.is_possible_type(&introspection_type_name))
                     });
                     if applies_concrete_object {
                         // The fragment applies to the concrete object type.
@@ -0,0 +1,160 @@
+use crate::{Context, Result};
+use futures::task::{Context as TaskContext, Poll, Waker};
+use futures::{Future, Stream};
+use std::any::Any;
+use std::collections::{HashMap, VecDeque};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, Weak};
+
+type AnySource = dyn Any + Send + Sync;
+
+/// The source streams of the `#[field(shared)]` subscription fields of a schema, by field name
+/// and arguments.
+#[doc(hidden)]
+#[derive(Default)]
+pub struct SharedStreams(Mutex<HashMap<String, Weak<AnySource>>>);
+
+struct Source<T> {
+    stream: Pin<Box<dyn Stream<Item = T> + Send>>,
+    queues: HashMap<usize, VecDeque<T>>,
+    wakers: HashMap<usize, Waker>,
+    next_id: usize,
+    finished: bool,
+}
+
+impl<T> Source<T> {
+    fn wake_others(&mut self, id: usize) {
+        for (_, waker) in self.wakers.drain().filter(|(waker_id, _)| *waker_id != id) {
+            waker.wake();
+        }
+    }
+}
+
+/// A subscriber of a shared source stream.
+///
+/// The source is polled by whichever subscriber needs the next item, and every item is cloned
+/// into the queues of the other subscribers. It is dropped with the last subscriber.
+#[doc(hidden)]
+pub struct SharedStream<T> {
+    source: Arc<Mutex<Source<T>>>,
+    id: usize,
+}
+
+impl<T> SharedStream<T> {
+    fn subscribe(source: Arc<Mutex<Source<T>>>) -> Self {
+        let id = {
+            let mut source = source.lock().unwrap();
+            let id = source.next_id;
+            source.next_id += 1;
+            source.queues.insert(id, VecDeque::new());
+            id
+        };
+        Self { source, id }
+    }
+}
+
+impl<T: Clone> Stream for SharedStream<T> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<Option<Self::Item>> {
+        let id = self.id;
+        let mut source = self.source.lock().unwrap();
+
+        if let Some(item) = source.queues.get_mut(&id).and_then(VecDeque::pop_front) {
+            return Poll::Ready(Some(item));
+        }
+        if source.finished {
+            return Poll::Ready(None);
+        }
+
+        match source.stream.as_mut().poll_next(cx) {
+            Poll::Ready(Some(item)) => {
+                for (_, queue) in source
+                    .queues
+                    .iter_mut()
+                    .filter(|(queue_id, _)| **queue_id != id)
+                {
+                    queue.push_back(item.clone());
+                }
+                source.wake_others(id);
+                Poll::Ready(Some(item))
+            }
+            Poll::Ready(None) => {
+                source.finished = true;
+                source.wake_others(id);
+                Poll::Ready(None)
+            }
+            Poll::Pending => {
+                source.wakers.insert(id, cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<T> Drop for SharedStream<T> {
+    fn drop(&mut self) {
+        if let Ok(mut source) = self.source.lock() {
+            source.queues.remove(&self.id);
+            source.wakers.remove(&self.id);
+            // The source may have registered the waker of this subscriber, so another one must
+            // poll it again.
+            source.wake_others(self.id);
+        }
+    }
+}
+
+/// Subscribe to the source stream of the current field that is shared by the subscriptions with
+/// the same field name and arguments, creating it with `create` if there is none.
+#[doc(hidden)]
+pub async fn shared_field_stream<T, S, F, Fut>(
+    ctx: &Context<'_>,
+    create: F,
+) -> Result<SharedStream<T>>
+where
+    T: Clone + Send + 'static,
+    S: Stream<Item = T> + Send + 'static,
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<S>>,
+{
+    let mut arguments = Vec::with_capacity(ctx.item.node.arguments.len());
+    for (name, value) in &ctx.item.node.arguments {
+        arguments.push(format!(
+            "{}: {}",
+            name.node,
+            ctx.resolve_input_value(value.clone())?
+        ));
+    }
+    arguments.sort();
+    let key = format!("{}({})", ctx.item.node.name.node, arguments.join(", "));
+
+    let shared_streams = &ctx.schema_env.shared_streams;
+    let existing = |streams: &HashMap<String, Weak<AnySource>>| {
+        streams
+            .get(&key)
+            .and_then(Weak::upgrade)
+            .and_then(|source| source.downcast::<Mutex<Source<T>>>().ok())
+    };
+
+    let source = existing(&shared_streams.0.lock().unwrap());
+    if let Some(source) = source {
+        return Ok(SharedStream::subscribe(source));
+    }
+
+    let stream = create().await?;
+    let mut streams = shared_streams.0.lock().unwrap();
+    if let Some(source) = existing(&streams) {
+        // Another subscription created the source while this one was being created.
+        return Ok(SharedStream::subscribe(source));
+    }
+    let source = Arc::new(Mutex::new(Source {
+        stream: Box::pin(stream),
+        queues: HashMap::new(),
+        wakers: HashMap::new(),
+        next_id: 0,
+        finished: false,
+    }));
+    streams.retain(|_, source| source.strong_count() > 0);
+    streams.insert(key, Arc::downgrade(&source) as Weak<AnySource>);
+    Ok(SharedStream::subscribe(source))
+}
@@ -1,6 +1,35 @@
 use crate::{CacheControl, Error, Result};
 use serde::Serialize;
 
+/// Whether the error of a [`Response`](struct.Response.html) happened before or during the
+/// execution of the operation, so that HTTP integrations can choose the status code as described
+/// by the GraphQL over HTTP specification.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ErrorKind {
+    /// The request was not executed, for example because it failed to parse or validate, the
+    /// operation was not found or it is too complex. Integrations respond with
+    /// `400 Bad Request`.
+    Request,
+
+    /// The operation was executed and a field failed. Integrations respond with `200 OK`.
+    Field,
+}
+
+/// The state of the rate limit of a client, which integrations send as the `x-ratelimit-limit`,
+/// `x-ratelimit-remaining` and `x-ratelimit-reset` headers, see
+/// [`Response::rate_limit`](struct.Response.html#method.rate_limit).
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct RateLimit {
+    /// The maximum cost that the client can spend in a window.
+    pub limit: u64,
+
+    /// The cost that the client can still spend in the current window.
+    pub remaining: u64,
+
+    /// The time at which the current window resets, in seconds since the Unix epoch.
+    pub reset: u64,
+}
+
 /// Query response
 #[derive(Debug, Default)]
 pub struct Response {
@@ -13,8 +42,23 @@ pub struct Response {
     /// Cache control value
     pub cache_control: CacheControl,
 
+    pub(crate) schema_fingerprint: Option<String>,
+
+    /// Cost of the query, if it is exposed
+    pub query_cost: Option<usize>,
+
+    /// Rate limit of the client
+    pub rate_limit: Option<RateLimit>,
+
     /// Error
     pub error: Option<Error>,
+
+    /// The kind of the error, `None` if there is no error.
+    pub error_kind: Option<ErrorKind>,
+
+    /// The id of the stored document that the locations of the error refer to, see
+    /// [`PreparedOperation::with_id`](struct.PreparedOperation.html#method.with_id).
+    pub operation_id: Option<String>,
 }
 
 impl Response {
@@ -27,21 +71,27 @@ impl Response {
         }
     }
 
-    /// Create a response from the error.
+    /// Create a response from an error of the request, which was not executed.
     #[must_use]
     pub fn from_error(error: impl Into<Error>) -> Self {
         Self {
             error: Some(error.into()),
+            error_kind: Some(ErrorKind::Request),
             ..Default::default()
         }
     }
 
-    /// Create a response from the result of the data and an error.
+    /// Create a response from the result of executing the operation, whose error is a
+    /// [field error](enum.ErrorKind.html#variant.Field).
     #[must_use]
     pub fn from_result(result: Result<serde_json::Value>) -> Self {
         match result {
             Ok(data) => Self::new(data),
-            Err(e) => Self::from_error(e),
+            Err(e) => Self {
+                error: Some(e),
+                error_kind: Some(ErrorKind::Field),
+                ..Default::default()
+            },
         }
     }
 
@@ -60,6 +110,67 @@ impl Response {
         }
     }
 
+    /// Set the id of the stored document that the locations of the error refer to, see
+    /// [`PreparedOperation::with_id`](struct.PreparedOperation.html#method.with_id).
+    #[must_use]
+    pub fn operation_id(self, operation_id: Option<String>) -> Self {
+        Self {
+            operation_id,
+            ..self
+        }
+    }
+
+    /// Set the fingerprint of the schema that produced the response, which is also added to the
+    /// extensions as `schemaFingerprint`.
+    #[must_use]
+    pub fn schema_fingerprint(mut self, fingerprint: String) -> Self {
+        let mut extensions = match self.extensions.take() {
+            Some(serde_json::Value::Object(extensions)) => extensions,
+            _ => serde_json::Map::new(),
+        };
+        extensions.insert(
+            "schemaFingerprint".to_string(),
+            serde_json::Value::String(fingerprint.clone()),
+        );
+        Self {
+            extensions: Some(extensions.into()),
+            schema_fingerprint: Some(fingerprint),
+            ..self
+        }
+    }
+
+    /// Returns the fingerprint of the schema that produced the response, if it is exposed, see
+    /// [`SchemaBuilder::expose_fingerprint`](struct.SchemaBuilder.html#method.expose_fingerprint).
+    pub fn fingerprint(&self) -> Option<&str> {
+        self.schema_fingerprint.as_deref()
+    }
+
+    /// Set the cost of the query, see
+    /// [`SchemaBuilder::expose_query_cost`](struct.SchemaBuilder.html#method.expose_query_cost).
+    #[must_use]
+    pub fn query_cost(self, cost: usize) -> Self {
+        Self {
+            query_cost: Some(cost),
+            ..self
+        }
+    }
+
+    /// Set the rate limit of the client, usually after charging the
+    /// [cost of the query](#structfield.query_cost) to it, so that it can throttle itself.
+    #[must_use]
+    pub fn rate_limit(self, rate_limit: RateLimit) -> Self {
+        Self {
+            rate_limit: Some(rate_limit),
+            ..self
+        }
+    }
+
+    /// Returns the `x-graphql-cost` and `x-ratelimit-*` headers of the cost of the query and the
+    /// rate limit of the client, which are set by HTTP integrations.
+    pub fn cost_headers(&self) -> Vec<(&'static str, String)> {
+        cost_headers(self.query_cost, self.rate_limit.as_ref())
+    }
+
     /// Returns `true` if the response is ok.
     #[inline]
     pub fn is_ok(&self) -> bool {
@@ -72,6 +183,13 @@ impl Response {
         self.error.is_some()
     }
 
+    /// Returns `true` if the request was not executed because of its error, in which case HTTP
+    /// integrations respond with `400 Bad Request`.
+    #[inline]
+    pub fn is_request_error(&self) -> bool {
+        self.error_kind == Some(ErrorKind::Request)
+    }
+
     /// Extract the error from the response. Only if the `error` field is `None` will this return
     /// `Ok`.
     #[inline]
@@ -112,6 +230,24 @@ impl BatchResponse {
         }
     }
 
+    /// Returns the `x-graphql-cost` and `x-ratelimit-*` headers of the responses, with the total
+    /// cost of the queries and the rate limit of the last response that has one.
+    pub fn cost_headers(&self) -> Vec<(&'static str, String)> {
+        match self {
+            BatchResponse::Single(resp) => resp.cost_headers(),
+            BatchResponse::Batch(resp) => {
+                let cost = resp
+                    .iter()
+                    .filter_map(|item| item.query_cost)
+                    .fold(None, |acc: Option<usize>, cost| {
+                        Some(acc.unwrap_or_default() + cost)
+                    });
+                let rate_limit = resp.iter().rev().find_map(|item| item.rate_limit.as_ref());
+                cost_headers(cost, rate_limit)
+            }
+        }
+    }
+
     /// Returns `true` if all responses are ok.
     pub fn is_ok(&self) -> bool {
         match self {
@@ -121,6 +257,22 @@ impl BatchResponse {
     }
 }
 
+fn cost_headers(
+    cost: Option<usize>,
+    rate_limit: Option<&RateLimit>,
+) -> Vec<(&'static str, String)> {
+    let mut headers = Vec::new();
+    if let Some(cost) = cost {
+        headers.push(("x-graphql-cost", cost.to_string()));
+    }
+    if let Some(rate_limit) = rate_limit {
+        headers.push(("x-ratelimit-limit", rate_limit.limit.to_string()));
+        headers.push(("x-ratelimit-remaining", rate_limit.remaining.to_string()));
+        headers.push(("x-ratelimit-reset", rate_limit.reset.to_string()));
+    }
+    headers
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -142,4 +294,32 @@ mod tests {
             r#"[{"data":true},{"data":"1"}]"#
         );
     }
+
+    #[test]
+    fn test_batch_response_cost_headers() {
+        let rate_limit = RateLimit {
+            limit: 100,
+            remaining: 90,
+            reset: 1600000000,
+        };
+        let resp = BatchResponse::Batch(vec![
+            Response::new(serde_json::Value::Null).query_cost(3),
+            Response::new(serde_json::Value::Null).query_cost(7),
+            Response::new(serde_json::Value::Null).rate_limit(rate_limit),
+        ]);
+        assert_eq!(
+            resp.cost_headers(),
+            vec![
+                ("x-graphql-cost", "10".to_string()),
+                ("x-ratelimit-limit", "100".to_string()),
+                ("x-ratelimit-remaining", "90".to_string()),
+                ("x-ratelimit-reset", "1600000000".to_string()),
+            ]
+        );
+        assert!(
+            BatchResponse::Single(Response::new(serde_json::Value::Null))
+                .cost_headers()
+                .is_empty()
+        );
+    }
 }
@@ -0,0 +1,280 @@
+//! Resolve fields from REST APIs.
+//!
+//! A [`RestDataSource`](struct.RestDataSource.html) wraps the base URL of a REST API and a
+//! [`RestClient`](trait.RestClient.html) that sends the requests with the HTTP client of the
+//! application. Each field declares its endpoint with a URL template that is filled in from the
+//! arguments of the field, and a JSON pointer to the part of the response that it returns.
+//!
+//! ```ignore
+//! #[Object]
+//! impl Query {
+//!     async fn user(&self, ctx: &Context<'_>, id: ID) -> FieldResult<Option<User>> {
+//!         ctx.data_unchecked::<RestDataSource>()
+//!             .get("/users/{id}")
+//!             .pointer("/data")
+//!             .fetch(ctx)
+//!             .await
+//!     }
+//!
+//!     async fn users(
+//!         &self,
+//!         ctx: &Context<'_>,
+//!         #[arg(default = 10)] limit: i32,
+//!     ) -> FieldResult<Vec<User>> {
+//!         ctx.data_unchecked::<RestDataSource>()
+//!             .get("/users?limit={limit}")
+//!             .arg("limit", &limit)
+//!             .fetch(ctx)
+//!             .await
+//!     }
+//! }
+//! ```
+
+use crate::{Context, FieldError, FieldResult, InputValueType, Value};
+use serde::de::DeserializeOwned;
+use std::any::Any;
+use std::fmt::Write;
+use std::sync::Arc;
+
+/// An HTTP request to a REST API.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RestRequest {
+    /// The method of the request, such as `GET`.
+    pub method: String,
+
+    /// The URL of the request.
+    pub url: String,
+
+    /// The headers of the request.
+    pub headers: Vec<(String, String)>,
+}
+
+/// The response of a REST API.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RestResponse {
+    /// The HTTP status code.
+    pub status: u16,
+
+    /// The JSON body of the response, `null` if it is empty.
+    pub body: serde_json::Value,
+}
+
+/// Sends the requests of a [`RestDataSource`](struct.RestDataSource.html), usually with the HTTP
+/// client of the application.
+#[async_trait::async_trait]
+pub trait RestClient: Send + Sync {
+    /// Send `request` and return the response, or an error if it could not be sent or the body
+    /// is not JSON.
+    async fn send(&self, request: RestRequest) -> Result<RestResponse, String>;
+}
+
+type HeaderFn = Box<dyn Fn(&Context<'_>) -> Option<String> + Send + Sync>;
+type ErrorFn = Box<dyn Fn(&RestResponse) -> FieldError + Send + Sync>;
+
+/// A REST API that fields are resolved from, usually added to the data of the schema.
+pub struct RestDataSource {
+    base_url: String,
+    client: Arc<dyn RestClient>,
+    headers: Vec<(String, HeaderFn)>,
+    map_error: ErrorFn,
+}
+
+impl RestDataSource {
+    /// Create a data source for the API at `base_url`.
+    pub fn new(base_url: impl Into<String>, client: impl RestClient + 'static) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            client: Arc::new(client),
+            headers: Vec::new(),
+            map_error: Box::new(default_error),
+        }
+    }
+
+    /// Add the header `name` with `value` to every request.
+    pub fn header(self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        let value = value.into();
+        self.header_with(name, move |_| Some(value.clone()))
+    }
+
+    /// Add the header `name` to every request, with a value from the data `D` of the request or
+    /// the schema. The header is omitted if there is no `D`.
+    ///
+    /// For example, `.header_from_data("Authorization", |token: &Token| format!("Bearer {}",
+    /// token.0))` forwards the token of the user.
+    pub fn header_from_data<D: Any + Send + Sync>(
+        self,
+        name: impl Into<String>,
+        f: impl Fn(&D) -> String + Send + Sync + 'static,
+    ) -> Self {
+        self.header_with(name, move |ctx| ctx.data_opt::<D>().map(&f))
+    }
+
+    fn header_with(
+        mut self,
+        name: impl Into<String>,
+        f: impl Fn(&Context<'_>) -> Option<String> + Send + Sync + 'static,
+    ) -> Self {
+        self.headers.push((name.into(), Box::new(f)));
+        self
+    }
+
+    /// Set the error of fields whose request failed with a status that is not `2xx` or `404`.
+    ///
+    /// By default the message names the status, which is also in the extensions of the error.
+    /// The body of the response is not exposed to the client, it is logged with the `log`
+    /// feature.
+    pub fn map_error(
+        mut self,
+        f: impl Fn(&RestResponse) -> FieldError + Send + Sync + 'static,
+    ) -> Self {
+        self.map_error = Box::new(f);
+        self
+    }
+
+    /// An endpoint requested with `GET`, see [`endpoint`](#method.endpoint).
+    pub fn get(&self, path: &str) -> RestEndpoint<'_> {
+        self.endpoint("GET", path)
+    }
+
+    /// An endpoint of the API.
+    ///
+    /// `path` is appended to the base URL. It can contain the arguments of the field in braces,
+    /// such as `/users/{id}`, which are replaced by their values.
+    pub fn endpoint(&self, method: &str, path: &str) -> RestEndpoint<'_> {
+        RestEndpoint {
+            source: self,
+            method: method.to_string(),
+            path: path.to_string(),
+            args: Vec::new(),
+            pointer: None,
+        }
+    }
+}
+
+fn default_error(response: &RestResponse) -> FieldError {
+    #[cfg(feature = "log")]
+    log::warn!(
+        target: "async-graphql",
+        "[RestDataSource] The request failed with status {}: {}",
+        response.status,
+        response.body
+    );
+    FieldError(
+        format!("The request failed with status {}", response.status),
+        Some(serde_json::json!({ "status": response.status })),
+    )
+}
+
+/// An endpoint of a [`RestDataSource`](struct.RestDataSource.html).
+pub struct RestEndpoint<'a> {
+    source: &'a RestDataSource,
+    method: String,
+    path: String,
+    args: Vec<(String, Value)>,
+    pointer: Option<String>,
+}
+
+impl<'a> RestEndpoint<'a> {
+    /// Only return the part of the response at the
+    /// [JSON pointer](https://tools.ietf.org/html/rfc6901) `pointer`, such as `/data/user`.
+    pub fn pointer(self, pointer: impl Into<String>) -> Self {
+        Self {
+            pointer: Some(pointer.into()),
+            ..self
+        }
+    }
+
+    /// Set the value of the argument `name` of the URL, instead of taking it from the query.
+    ///
+    /// The URL template only sees the arguments written in the query, so arguments with a default
+    /// value must be passed with their resolved value.
+    pub fn arg(mut self, name: impl Into<String>, value: &impl InputValueType) -> Self {
+        self.args.push((name.into(), value.to_value()));
+        self
+    }
+
+    /// Returns the request for the field of `ctx`.
+    pub fn request(&self, ctx: &Context<'_>) -> FieldResult<RestRequest> {
+        let mut url = self.source.base_url.clone();
+        let mut rest = self.path.as_str();
+        while let Some(start) = rest.find('{') {
+            let end = rest[start..]
+                .find('}')
+                .map(|end| start + end)
+                .ok_or_else(|| format!("Unclosed argument in \"{}\"", self.path))?;
+            url += &rest[..start];
+            let name = &rest[start + 1..end];
+            let value = match self.args.iter().find(|(arg, _)| arg == name) {
+                Some((_, value)) => value.clone(),
+                None => match ctx.item.node.get_argument(name) {
+                    Some(value) => ctx.resolve_input_value(value.clone())?,
+                    None => Value::Null,
+                },
+            };
+            match value {
+                Value::String(s) => percent_encode(&mut url, &s),
+                Value::Enum(s) => percent_encode(&mut url, &s),
+                Value::Number(n) => write!(url, "{}", n).unwrap(),
+                Value::Boolean(b) => write!(url, "{}", b).unwrap(),
+                Value::Null => {
+                    return Err(format!("The argument \"{}\" of the URL is missing", name).into())
+                }
+                _ => {
+                    return Err(format!(
+                        "The argument \"{}\" of the URL must be a string, number or boolean",
+                        name
+                    )
+                    .into())
+                }
+            }
+            rest = &rest[end + 1..];
+        }
+        url += rest;
+
+        Ok(RestRequest {
+            method: self.method.clone(),
+            url,
+            headers: self
+                .source
+                .headers
+                .iter()
+                .filter_map(|(name, f)| f(ctx).map(|value| (name.clone(), value)))
+                .collect(),
+        })
+    }
+
+    /// Send the request for the field of `ctx` and deserialize the response.
+    ///
+    /// A `404` response is deserialized as `null`, so that a field that returns an `Option`
+    /// resolves to `None`. So is a missing value at the pointer.
+    pub async fn fetch<T: DeserializeOwned>(&self, ctx: &Context<'_>) -> FieldResult<T> {
+        let response = self.source.client.send(self.request(ctx)?).await?;
+        if response.status == 404 {
+            return Ok(serde_json::from_value(serde_json::Value::Null)?);
+        }
+        if !(200..300).contains(&response.status) {
+            return Err((self.source.map_error)(&response));
+        }
+        let body = match &self.pointer {
+            Some(pointer) => response
+                .body
+                .pointer(pointer)
+                .cloned()
+                .unwrap_or(serde_json::Value::Null),
+            None => response.body,
+        };
+        Ok(serde_json::from_value(body)?)
+    }
+}
+
+/// Append `s` to `url` as a path segment.
+fn percent_encode(url: &mut String, s: &str) {
+    for b in s.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                url.push(b as char)
+            }
+            b => write!(url, "%{:02X}", b).unwrap(),
+        }
+    }
+}
@@ -1,25 +1,81 @@
 use crate::context::{Data, ResolveId};
-use crate::extensions::{BoxExtension, ErrorLogger, Extension, Extensions};
+use crate::extensions::{
+    BoxExtension, ErrorLogger, Extension, ExtensionContext, Extensions, ExtensionsLock,
+};
+use crate::idempotency::{is_idempotent_mutation, request_hash, ReservationGuard};
+use crate::live_query::{LiveQueries, LiveQuery, LiveQueryGuard};
 use crate::model::__DirectiveLocation;
-use crate::parser::parse_query;
 use crate::parser::types::OperationType;
-use crate::registry::{MetaDirective, MetaInputValue, Registry};
-use crate::resolver_utils::{resolve_object, resolve_object_serial, ObjectType};
-use crate::subscription::collect_subscription_streams;
+use crate::parser::{check_token_limit, parse_query};
+use crate::persisted_operations::{self, ExportError, OperationManifest};
+use crate::preview::{check_preview_features, PreviewFeatures};
+use crate::query_cache::QueryCache;
+use crate::registry::{MetaDirective, MetaInputValue, MetaType, Registry, UnknownInputFields};
+use crate::resolver_utils::{resolve_object, resolve_object_serial, ObjectType, SharedStreams};
+use crate::schema_registry::{self, RegistryError, RegistryTransport, SchemaRegistry};
+use crate::stream_guard::SpawnFn;
+use crate::subscription::{
+    collect_subscription_streams, CompletionGuard, CompletionHandle, CompletionReason, Shutdown,
+};
 use crate::types::QueryRoot;
-use crate::validation::{check_rules, CheckResult, ValidationMode};
+use crate::validation::{self, check_rules, check_variables, CheckResult, ValidationMode};
 use crate::{
-    BatchRequest, BatchResponse, CacheControl, ContextBase, Error, Pos, QueryEnv, QueryError,
-    Request, Response, Result, SubscriptionType, Type, Variables, ID,
+    BatchRequest, BatchResponse, CacheControl, ContextBase, Error, IdempotencyKey,
+    IdempotencyReservation, IdempotencyStore, IdempotentResponse, Pos, Positioned,
+    PreparedOperation, QueryEnv, QueryError, RedactionPolicy, Request, RequestExtensions, Response,
+    Result, SubscriptionType, TransactionHook, Type, Value, VariableDirective, Variables, ID,
 };
-use async_graphql_parser::types::ExecutableDocumentData;
+use async_graphql_parser::types::{
+    Directive, ExecutableDefinition, ExecutableDocument, ExecutableDocumentData, Field,
+    OperationDefinition, Selection, SelectionSet,
+};
+use futures::future::{self, BoxFuture, Either};
 use futures::stream::{self, Stream, StreamExt};
 use indexmap::map::IndexMap;
 use itertools::Itertools;
-use std::any::Any;
+use std::any::{Any, TypeId};
+use std::collections::{BTreeMap, HashMap};
+use std::marker::PhantomData;
 use std::ops::Deref;
-use std::sync::atomic::AtomicUsize;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
+
+/// The standard introspection query sent by GraphiQL and GraphQL Playground, which is what
+/// [`Schema::introspect`](struct.Schema.html#method.introspect) executes.
+pub const INTROSPECTION_QUERY: &str = include_str!("introspection_query.graphql");
+
+/// The default maximum depth of the `__schema` and `__type` fields of a query, which leaves room
+/// for twice the `ofType` levels of the [introspection query](constant.INTROSPECTION_QUERY.html).
+pub const DEFAULT_INTROSPECTION_DEPTH: usize = 20;
+
+/// The default maximum number of fields that the `__schema` and `__type` fields of a query can
+/// select, about five times the size of the
+/// [introspection query](constant.INTROSPECTION_QUERY.html).
+pub const DEFAULT_INTROSPECTION_SIZE: usize = 1000;
+
+/// The type information of the root types of a schema, which can be created once with
+/// [`Schema::types`](struct.Schema.html#method.types) and shared by the schemas built with
+/// [`Schema::build_with_types`](struct.Schema.html#method.build_with_types).
+///
+/// Creating it walks `Type::create_type_info` of every type reachable from the root types, which
+/// is expensive for large schemas that are built repeatedly, e.g. once per test. The schemas built
+/// from it share it without copying it, unless their builder changes it, e.g. with
+/// [`register_type`](struct.SchemaBuilder.html#method.register_type) or with federation.
+pub struct SchemaTypes<Query, Mutation, Subscription> {
+    registry: Arc<Registry>,
+    _marker: PhantomData<fn() -> (Query, Mutation, Subscription)>,
+}
+
+impl<Query, Mutation, Subscription> Clone for SchemaTypes<Query, Mutation, Subscription> {
+    fn clone(&self) -> Self {
+        Self {
+            registry: self.registry.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
 
 /// Schema builder
 pub struct SchemaBuilder<Query, Mutation, Subscription> {
@@ -27,12 +83,27 @@ pub struct SchemaBuilder<Query, Mutation, Subscription> {
     query: QueryRoot<Query>,
     mutation: Mutation,
     subscription: Subscription,
-    registry: Registry,
+    registry: Arc<Registry>,
     data: Data,
     complexity: Option<usize>,
     depth: Option<usize>,
+    fragment_depth: Option<usize>,
+    introspection_depth: usize,
+    introspection_size: usize,
+    query_size: Option<usize>,
+    tokens: Option<usize>,
     extensions: Vec<Box<dyn Fn() -> BoxExtension + Send + Sync>>,
     enable_federation: bool,
+    disable_federation: bool,
+    expose_fingerprint: bool,
+    expose_query_cost: bool,
+    parse_error_details: bool,
+    variable_directives: HashMap<&'static str, Box<dyn VariableDirective>>,
+    redaction_policy: Option<Box<dyn RedactionPolicy>>,
+    idempotency: Option<(Arc<dyn IdempotencyStore>, Duration)>,
+    transaction_hook: Option<Box<dyn TransactionHook>>,
+    query_cache: Option<QueryCache>,
+    spawner: Option<Arc<SpawnFn>>,
 }
 
 impl<Query: ObjectType, Mutation: ObjectType, Subscription: SubscriptionType>
@@ -42,7 +113,14 @@ impl<Query: ObjectType, Mutation: ObjectType, Subscription: SubscriptionType>
     ///
     /// You can use this function to register schema types that are not directly referenced.
     pub fn register_type<T: Type>(mut self) -> Self {
-        T::create_type_info(&mut self.registry);
+        T::create_type_info(Arc::make_mut(&mut self.registry));
+        self
+    }
+
+    /// Set the description of the schema, which is returned by the `description` field of
+    /// `__schema` and added to the SDL.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        Arc::make_mut(&mut self.registry).description = Some(description.into());
         self
     }
 
@@ -53,6 +131,10 @@ impl<Query: ObjectType, Mutation: ObjectType, Subscription: SubscriptionType>
     }
 
     /// Set the maximum complexity a query can have. By default there is no limit.
+    ///
+    /// Every field costs 1 plus the complexity of its selection set, which fields can change with
+    /// `#[field(complexity = ...)]`, and fragments are counted wherever they are spread. Queries
+    /// that are too complex are rejected before they are executed.
     pub fn limit_complexity(mut self, complexity: usize) -> Self {
         self.complexity = Some(complexity);
         self
@@ -64,6 +146,44 @@ impl<Query: ObjectType, Mutation: ObjectType, Subscription: SubscriptionType>
         self
     }
 
+    /// Set the maximum number of nested fragment spreads a query can have, e.g. an operation that
+    /// spreads a fragment which spreads another fragment has a fragment depth of 2. By default
+    /// there is no limit.
+    pub fn limit_fragment_depth(mut self, depth: usize) -> Self {
+        self.fragment_depth = Some(depth);
+        self
+    }
+
+    /// Set the maximum depth of the `__schema` and `__type` fields of a query, which is checked
+    /// independently of [`limit_depth`](#method.limit_depth) to reject deeply nested `ofType`
+    /// chains. The default is
+    /// [`DEFAULT_INTROSPECTION_DEPTH`](constant.DEFAULT_INTROSPECTION_DEPTH.html).
+    pub fn limit_introspection_depth(mut self, depth: usize) -> Self {
+        self.introspection_depth = depth;
+        self
+    }
+
+    /// Set the maximum number of fields that the `__schema` and `__type` fields of a query can
+    /// select, counting fragments wherever they are spread. The default is
+    /// [`DEFAULT_INTROSPECTION_SIZE`](constant.DEFAULT_INTROSPECTION_SIZE.html).
+    pub fn limit_introspection_size(mut self, size: usize) -> Self {
+        self.introspection_size = size;
+        self
+    }
+
+    /// Set the maximum length of a query string in bytes. By default there is no limit.
+    pub fn limit_query_size(mut self, bytes: usize) -> Self {
+        self.query_size = Some(bytes);
+        self
+    }
+
+    /// Set the maximum number of tokens a query can have, which is checked before the query is
+    /// parsed. By default there is no limit.
+    pub fn limit_tokens(mut self, tokens: usize) -> Self {
+        self.tokens = Some(tokens);
+        self
+    }
+
     /// Add an extension to the schema.
     pub fn extension<F: Fn() -> E + Send + Sync + 'static, E: Extension>(
         mut self,
@@ -92,13 +212,151 @@ impl<Query: ObjectType, Mutation: ObjectType, Subscription: SubscriptionType>
         self
     }
 
+    /// Disable federation even if the Query has entity definitions, so that the `_service` and
+    /// `_entities` fields are not added to it, to run a subgraph as a standalone service.
+    pub fn disable_federation(mut self) -> Self {
+        self.disable_federation = true;
+        self
+    }
+
+    /// Emit the Query and Mutation roots as `extend type` in the federation SDL, for subgraphs
+    /// whose gateway defines the root types in another service.
+    pub fn extend_root_types(mut self) -> Self {
+        Arc::make_mut(&mut self.registry).extend_root_types = true;
+        self
+    }
+
+    /// Declare a directive that queries can use, such as `@persisted` on operations or client hints
+    /// on variable definitions.
+    ///
+    /// The executor ignores these directives, but they are validated like the built-in ones and
+    /// can be read from the document by [extensions](extensions/trait.Extension.html#method.parse_end).
+    pub fn directive(mut self, directive: MetaDirective) -> Self {
+        Arc::make_mut(&mut self.registry).add_directive(directive);
+        self
+    }
+
+    /// Set how the fields of input object values that are not defined by their type are handled,
+    /// for the input objects that don't have the `deny_unknown_fields` or `ignore_unknown_fields`
+    /// attribute. They are denied by default.
+    pub fn unknown_input_fields(mut self, unknown_input_fields: UnknownInputFields) -> Self {
+        Arc::make_mut(&mut self.registry).unknown_input_fields = unknown_input_fields;
+        self
+    }
+
+    /// Add a directive that transforms the values of variables and arguments, see
+    /// [`VariableDirective`](trait.VariableDirective.html).
+    pub fn variable_directive(mut self, directive: impl VariableDirective) -> Self {
+        Arc::make_mut(&mut self.registry).add_directive(MetaDirective {
+            name: directive.name(),
+            description: directive.description(),
+            locations: vec![
+                __DirectiveLocation::VARIABLE_DEFINITION,
+                __DirectiveLocation::FIELD,
+            ],
+            args: directive.args(),
+            is_repeatable: directive.is_repeatable(),
+        });
+        self.variable_directives
+            .insert(directive.name(), Box::new(directive));
+        self
+    }
+
+    /// Set the policy that redacts resolved values, see
+    /// [`RedactionPolicy`](trait.RedactionPolicy.html).
+    pub fn redaction_policy(mut self, policy: impl RedactionPolicy) -> Self {
+        self.redaction_policy = Some(Box::new(policy));
+        self
+    }
+
+    /// Set the function that spawns the cleanups of the [`StreamGuard`](struct.StreamGuard.html)s,
+    /// and the release of the idempotency keys of dropped requests, on the runtime of the server,
+    /// such as `async_std::task::spawn` or `tokio::spawn`.
+    pub fn spawner(mut self, f: impl Fn(BoxFuture<'static, ()>) + Send + Sync + 'static) -> Self {
+        self.spawner = Some(Arc::new(f));
+        self
+    }
+
+    /// Make the mutations of idempotent fields with an [`IdempotencyKey`](struct.IdempotencyKey.html)
+    /// in the data of the request idempotent, by storing their responses in `store` for the
+    /// duration of `ttl`.
+    pub fn idempotency_store(mut self, store: impl IdempotencyStore, ttl: Duration) -> Self {
+        self.idempotency = Some((Arc::new(store), ttl));
+        self
+    }
+
+    /// Run every mutation in a transaction, which is committed only if no field of the mutation
+    /// root returned an error, see [`TransactionHook`](trait.TransactionHook.html).
+    pub fn transaction_hook(mut self, hook: impl TransactionHook) -> Self {
+        self.transaction_hook = Some(Box::new(hook));
+        self
+    }
+
+    /// Cache up to `capacity` parsed and validated queries, so that repeated queries skip the
+    /// parser and the validator. The least recently used queries are evicted first.
+    ///
+    /// Only the arguments that use variables are checked again for a cached query. The parse and
+    /// validation hooks of the extensions are still called, and `parse_end` gets the cached
+    /// document, which only has the selected operation and its fragments.
+    pub fn query_cache(mut self, capacity: usize) -> Self {
+        self.query_cache = Some(QueryCache::new(capacity));
+        self
+    }
+
+    /// Add the [fingerprint](struct.Schema.html#method.fingerprint) of the schema to every
+    /// response, as the `schemaFingerprint` extension and, in the integrations, the
+    /// `x-schema-fingerprint` header.
+    pub fn expose_fingerprint(mut self) -> Self {
+        self.expose_fingerprint = true;
+        self
+    }
+
+    /// Add the cost of the query, its [complexity](#method.limit_complexity), to every response
+    /// that executed an operation, which the integrations send as the `x-graphql-cost` header so
+    /// that clients can throttle themselves.
+    pub fn expose_query_cost(mut self) -> Self {
+        self.expose_query_cost = true;
+        self
+    }
+
+    /// Add the offending token, the expected tokens and the line of the query with a caret under
+    /// the position to the extensions of parse errors, as `token`, `expected` and `snippet`.
+    pub fn parse_error_details(mut self) -> Self {
+        self.parse_error_details = true;
+        self
+    }
+
+    /// Build the schema, or return the mistakes in the definitions of its types, such as a field
+    /// that is defined by more than one merged object.
+    pub fn try_finish(
+        self,
+    ) -> std::result::Result<Schema<Query, Mutation, Subscription>, Vec<String>> {
+        if self.registry.errors.is_empty() {
+            Ok(self.finish())
+        } else {
+            Err(self.registry.errors.clone())
+        }
+    }
+
     /// Build schema.
+    ///
+    /// The types are not checked, e.g. only the first definition of a field that is defined by
+    /// more than one merged object is kept. Use [`try_finish`](#method.try_finish) to reject them.
     pub fn finish(mut self) -> Schema<Query, Mutation, Subscription> {
         // federation
-        if self.enable_federation || self.registry.has_entities() {
-            self.registry.create_federation_types();
+        if !self.disable_federation && (self.enable_federation || self.registry.has_entities()) {
+            Arc::make_mut(&mut self.registry).create_federation_types();
+            self.query.federation = true;
         }
 
+        let fingerprint = self.registry.fingerprint();
+        let has_preview_fields = self.registry.types.values().any(|ty| match ty {
+            MetaType::Object { fields, .. } | MetaType::Interface { fields, .. } => fields
+                .values()
+                .any(|field| field.requires_feature.is_some()),
+            _ => false,
+        });
+
         Schema(Arc::new(SchemaInner {
             validation_mode: self.validation_mode,
             query: self.query,
@@ -106,19 +364,58 @@ impl<Query: ObjectType, Mutation: ObjectType, Subscription: SubscriptionType>
             subscription: self.subscription,
             complexity: self.complexity,
             depth: self.depth,
+            fragment_depth: self.fragment_depth,
+            introspection_depth: self.introspection_depth,
+            introspection_size: self.introspection_size,
+            query_size: self.query_size,
+            tokens: self.tokens,
             extensions: self.extensions,
+            fingerprint,
+            expose_fingerprint: self.expose_fingerprint,
+            expose_query_cost: self.expose_query_cost,
+            parse_error_details: self.parse_error_details,
+            variable_directives: self.variable_directives,
+            idempotency: self.idempotency,
+            transaction_hook: self.transaction_hook,
+            query_cache: self.query_cache,
+            has_preview_fields,
+            active_subscriptions: Default::default(),
+            live_queries: Default::default(),
+            shutdown: Default::default(),
             env: SchemaEnv(Arc::new(SchemaEnvInner {
                 registry: self.registry,
                 data: self.data,
+                redaction_policy: self.redaction_policy,
+                shared_streams: Default::default(),
+                spawner: self.spawner,
             })),
         }))
     }
 }
 
+/// Counts a running subscription until it is dropped.
+struct ActiveSubscription<'a>(&'a AtomicUsize);
+
+impl<'a> ActiveSubscription<'a> {
+    fn new(count: &'a AtomicUsize) -> Self {
+        count.fetch_add(1, Ordering::Relaxed);
+        Self(count)
+    }
+}
+
+impl<'a> Drop for ActiveSubscription<'a> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
 #[doc(hidden)]
 pub struct SchemaEnvInner {
-    pub registry: Registry,
+    pub registry: Arc<Registry>,
     pub data: Data,
+    pub redaction_policy: Option<Box<dyn RedactionPolicy>>,
+    pub shared_streams: SharedStreams,
+    pub spawner: Option<Arc<SpawnFn>>,
 }
 
 #[doc(hidden)]
@@ -141,7 +438,24 @@ pub struct SchemaInner<Query, Mutation, Subscription> {
     pub(crate) subscription: Subscription,
     pub(crate) complexity: Option<usize>,
     pub(crate) depth: Option<usize>,
+    pub(crate) fragment_depth: Option<usize>,
+    pub(crate) introspection_depth: usize,
+    pub(crate) introspection_size: usize,
+    pub(crate) query_size: Option<usize>,
+    pub(crate) tokens: Option<usize>,
     pub(crate) extensions: Vec<Box<dyn Fn() -> BoxExtension + Send + Sync>>,
+    pub(crate) fingerprint: String,
+    pub(crate) expose_fingerprint: bool,
+    pub(crate) expose_query_cost: bool,
+    pub(crate) parse_error_details: bool,
+    pub(crate) variable_directives: HashMap<&'static str, Box<dyn VariableDirective>>,
+    pub(crate) idempotency: Option<(Arc<dyn IdempotencyStore>, Duration)>,
+    pub(crate) transaction_hook: Option<Box<dyn TransactionHook>>,
+    pub(crate) query_cache: Option<QueryCache>,
+    pub(crate) has_preview_fields: bool,
+    pub(crate) active_subscriptions: AtomicUsize,
+    pub(crate) live_queries: LiveQueries,
+    pub(crate) shutdown: Shutdown,
     pub(crate) env: SchemaEnv,
 }
 
@@ -185,20 +499,15 @@ where
     Mutation: ObjectType + Send + Sync + 'static,
     Subscription: SubscriptionType + Send + Sync + 'static,
 {
-    /// Create a schema builder
-    ///
-    /// The root object for the query and Mutation needs to be specified.
-    /// If there is no mutation, you can use `EmptyMutation`.
-    /// If there is no subscription, you can use `EmptySubscription`.
-    pub fn build(
-        query: Query,
-        mutation: Mutation,
-        subscription: Subscription,
-    ) -> SchemaBuilder<Query, Mutation, Subscription> {
+    fn create_registry() -> Registry {
         let mut registry = Registry {
+            description: None,
+            extend_root_types: false,
             types: Default::default(),
             directives: Default::default(),
             implements: Default::default(),
+            unknown_input_fields: Default::default(),
+            errors: Default::default(),
             query_type: Query::type_name().to_string(),
             mutation_type: if Mutation::is_empty() {
                 None
@@ -230,7 +539,8 @@ where
                     validator: None,
                 });
                 args
-            }
+            },
+            is_repeatable: false,
         });
 
         registry.add_directive(MetaDirective {
@@ -251,7 +561,50 @@ where
                     validator: None,
                 });
                 args
-            }
+            },
+            is_repeatable: false,
+        });
+
+        registry.add_directive(MetaDirective {
+            name: "idempotent",
+            description: Some("Marks a mutation field as safe to retry, executing it several times has the same effect as executing it once."),
+            locations: vec![__DirectiveLocation::FIELD_DEFINITION],
+            args: Default::default(),
+            is_repeatable: false,
+        });
+
+        registry.add_directive(MetaDirective {
+            name: "live",
+            description: Some("Directs the executor to send the result of the query again whenever the data it selected is invalidated."),
+            locations: vec![__DirectiveLocation::QUERY],
+            args: Default::default(),
+            is_repeatable: false,
+        });
+
+        registry.add_directive(MetaDirective {
+            name: "specifiedBy",
+            description: Some("Provides a scalar specification URL for specifying the behavior of custom scalar types."),
+            locations: vec![__DirectiveLocation::SCALAR],
+            args: {
+                let mut args = IndexMap::new();
+                args.insert("url", MetaInputValue {
+                    name: "url",
+                    description: Some("The URL that specifies the behaviour of this scalar."),
+                    ty: "String!".to_string(),
+                    default_value: None,
+                    validator: None,
+                });
+                args
+            },
+            is_repeatable: false,
+        });
+
+        registry.add_directive(MetaDirective {
+            name: "oneOf",
+            description: Some("Indicates that exactly one field of an input object must be provided, and that it must not be null."),
+            locations: vec![__DirectiveLocation::INPUT_OBJECT],
+            args: Default::default(),
+            is_repeatable: false,
         });
 
         registry.add_directive(MetaDirective {
@@ -259,6 +612,7 @@ where
             description: Some("Directs the executor to query only when the field exists."),
             locations: vec![__DirectiveLocation::FIELD],
             args: Default::default(),
+            is_repeatable: false,
         });
 
         // register scalars
@@ -276,20 +630,89 @@ where
             Subscription::create_type_info(&mut registry);
         }
 
+        registry
+    }
+
+    /// Create a schema builder
+    ///
+    /// The root object for the query and Mutation needs to be specified.
+    /// If there is no mutation, you can use `EmptyMutation`.
+    /// If there is no subscription, you can use `EmptySubscription`.
+    pub fn build(
+        query: Query,
+        mutation: Mutation,
+        subscription: Subscription,
+    ) -> SchemaBuilder<Query, Mutation, Subscription> {
+        Self::build_with_types(&Self::types(), query, mutation, subscription)
+    }
+
+    /// Create the type information of the root types, to build several schemas with
+    /// [`build_with_types`](#method.build_with_types) without creating it for each of them.
+    pub fn types() -> SchemaTypes<Query, Mutation, Subscription> {
+        SchemaTypes {
+            registry: Arc::new(Self::create_registry()),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Create a schema builder with the type information created by [`types`](#method.types).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use async_graphql::*;
+    ///
+    /// struct QueryRoot;
+    ///
+    /// #[Object]
+    /// impl QueryRoot {
+    ///     async fn value(&self) -> i32 {
+    ///         10
+    ///     }
+    /// }
+    ///
+    /// let types = Schema::<QueryRoot, EmptyMutation, EmptySubscription>::types();
+    /// for _ in 0..10 {
+    ///     let schema =
+    ///         Schema::build_with_types(&types, QueryRoot, EmptyMutation, EmptySubscription).finish();
+    /// }
+    /// ```
+    pub fn build_with_types(
+        types: &SchemaTypes<Query, Mutation, Subscription>,
+        query: Query,
+        mutation: Mutation,
+        subscription: Subscription,
+    ) -> SchemaBuilder<Query, Mutation, Subscription> {
         SchemaBuilder {
             validation_mode: ValidationMode::Strict,
             query: QueryRoot {
                 inner: query,
                 disable_introspection: false,
+                federation: false,
             },
             mutation,
             subscription,
-            registry,
+            registry: types.registry.clone(),
             data: Default::default(),
             complexity: None,
             depth: None,
+            fragment_depth: None,
+            introspection_depth: DEFAULT_INTROSPECTION_DEPTH,
+            introspection_size: DEFAULT_INTROSPECTION_SIZE,
+            query_size: None,
+            tokens: None,
             extensions: Default::default(),
             enable_federation: false,
+            disable_federation: false,
+            expose_fingerprint: false,
+            expose_query_cost: false,
+            parse_error_details: false,
+            variable_directives: Default::default(),
+            redaction_policy: None,
+            idempotency: None,
+            transaction_hook: None,
+            query_cache: None,
+            spawner: None,
         }
     }
 
@@ -302,59 +725,215 @@ where
         Self::build(query, mutation, subscription).finish()
     }
 
-    fn prepare_request(
-        &self,
-        request: &Request,
-    ) -> Result<(
-        ExecutableDocumentData,
-        CacheControl,
-        spin::Mutex<Extensions>,
-    )> {
-        // create extension instances
-        let extensions = spin::Mutex::new(Extensions(
+    /// Validate a parsed query document against this schema without executing it.
+    ///
+    /// This runs the same validation rules and complexity/depth limits as `Schema::execute`, which
+    /// makes it useful for checking queries ahead of time or benchmarking the validation stage.
+    pub fn validate(&self, document: &ExecutableDocument, variables: &Variables) -> Result<()> {
+        check_rules(
+            &self.env.registry,
+            document,
+            Some(variables),
+            self.validation_mode,
+        )?;
+        self.check_limits(document, variables)?;
+        Ok(())
+    }
+
+    /// Check the complexity, depth and introspection limits of a validated document with the
+    /// variables that it is executed with, returning its complexity.
+    fn check_limits(&self, document: &ExecutableDocument, variables: &Variables) -> Result<usize> {
+        let check_result = validation::check_limits(&self.env.registry, document, variables)?;
+
+        if let Some(limit_complexity) = self.complexity {
+            if check_result.complexity > limit_complexity {
+                return Err(QueryError::TooComplex.into_error(Pos::default()));
+            }
+        }
+
+        if let Some(limit_depth) = self.depth {
+            if check_result.depth > limit_depth {
+                return Err(too_deep(limit_depth, check_result.deepest_field.clone()));
+            }
+        }
+
+        if let Some(limit) = self.fragment_depth {
+            if check_result.fragment_depth > limit {
+                return Err(QueryError::FragmentTooDeep { limit }.into_error(Pos::default()));
+            }
+        }
+
+        if check_result.introspection_depth > self.introspection_depth {
+            return Err(QueryError::IntrospectionTooDeep {
+                limit: self.introspection_depth,
+            }
+            .into_error(Pos::default()));
+        }
+
+        if check_result.introspection_size > self.introspection_size {
+            return Err(QueryError::IntrospectionTooLarge {
+                limit: self.introspection_size,
+            }
+            .into_error(Pos::default()));
+        }
+
+        Ok(check_result.complexity)
+    }
+
+    fn create_extensions(&self) -> ExtensionsLock {
+        ExtensionsLock::new(Extensions(
             self.0
                 .extensions
                 .iter()
                 .map(|factory| factory())
                 .collect_vec(),
-        ));
+        ))
+    }
+
+    /// Parse and validate the request. `ctx_data` is the data of the context when it is not
+    /// in the data of the request, as for `execute_stream`.
+    async fn prepare_request(
+        &self,
+        request: &mut Request,
+        ctx_data: Option<&Data>,
+    ) -> Result<(ExecutableDocumentData, CacheControl, usize, ExtensionsLock)> {
+        // create extension instances
+        let extensions = self.create_extensions();
+        let extension_ctx = ExtensionContext {
+            data: &request.data,
+        };
+
+        extensions
+            .lock()
+            .await
+            .request_start(&extension_ctx, &request.extensions)
+            .await;
+
+        if let Some(cached) = self
+            .query_cache
+            .as_ref()
+            .and_then(|cache| cache.get(&request.query, request.operation_name.as_deref()))
+        {
+            let document = executable_document(&cached.document);
+            extensions
+                .lock()
+                .await
+                .parse_start(&extension_ctx, &request.query, &request.variables)
+                .await;
+            extensions
+                .lock()
+                .await
+                .parse_end(&extension_ctx, &document)
+                .await;
+            extensions
+                .lock()
+                .await
+                .validation_start(&extension_ctx)
+                .await;
+            let (document, complexity) = self
+                .check_document(
+                    document,
+                    &mut request.variables,
+                    &extensions,
+                    &extension_ctx,
+                )
+                .await?;
+            extensions.lock().await.validation_end(&extension_ctx).await;
+            self.check_preview_features(&document, &request.data, ctx_data)
+                .log_error(&extensions, &extension_ctx)
+                .await?;
+            return Ok((document, cached.cache_control, complexity, extensions));
+        }
 
         extensions
             .lock()
-            .parse_start(&request.query, &request.variables);
+            .await
+            .parse_start(&extension_ctx, &request.query, &request.variables)
+            .await;
+
+        // check limits before parsing
+        if let Some(limit) = self.query_size {
+            if request.query.len() > limit {
+                return Err(QueryError::TooLarge { limit }.into_error(Pos::default()))
+                    .log_error(&extensions, &extension_ctx)
+                    .await;
+            }
+        }
+
+        if let Some(limit) = self.tokens {
+            check_token_limit(&request.query, limit)
+                .map_err(|err| QueryError::TooManyTokens { limit }.into_error(err.pos))
+                .log_error(&extensions, &extension_ctx)
+                .await?;
+        }
+
         let document = parse_query(&request.query)
-            .map_err(Into::<Error>::into)
-            .log_error(&extensions)?;
-        extensions.lock().parse_end(&document);
+            .map_err(|mut err| {
+                if !self.parse_error_details {
+                    err.token = None;
+                    err.expected.clear();
+                    err.source_line = None;
+                }
+                Error::Parse(err)
+            })
+            .log_error(&extensions, &extension_ctx)
+            .await?;
+        extensions
+            .lock()
+            .await
+            .parse_end(&extension_ctx, &document)
+            .await;
+
+        // Reject operations on roots that are not configured before validating them.
+        if let Some(operation) = selected_operation(&document, request.operation_name.as_deref()) {
+            let err = match operation.node.ty {
+                OperationType::Mutation if Mutation::is_empty() => {
+                    Some(QueryError::NotConfiguredMutations)
+                }
+                OperationType::Subscription if Subscription::is_empty() => {
+                    Some(QueryError::NotConfiguredSubscriptions)
+                }
+                _ => None,
+            };
+            if let Some(err) = err {
+                return Err(err.into_error(operation.pos))
+                    .log_error(&extensions, &extension_ctx)
+                    .await;
+            }
+        }
 
         // check rules
-        extensions.lock().validation_start();
-        let CheckResult {
-            cache_control,
-            complexity,
-            depth,
-        } = check_rules(
+        extensions
+            .lock()
+            .await
+            .validation_start(&extension_ctx)
+            .await;
+        // With variable directives, the variables are checked after they are transformed.
+        let variables = if self.variable_directives.is_empty() {
+            Some(&request.variables)
+        } else {
+            None
+        };
+        let CheckResult { cache_control } = check_rules(
             &self.env.registry,
             &document,
-            Some(&request.variables),
+            variables,
             self.validation_mode,
         )
-        .log_error(&extensions)?;
-        extensions.lock().validation_end();
-
-        // check limit
-        if let Some(limit_complexity) = self.complexity {
-            if complexity > limit_complexity {
-                return Err(QueryError::TooComplex.into_error(Pos::default()))
-                    .log_error(&extensions);
-            }
-        }
+        .log_error(&extensions, &extension_ctx)
+        .await?;
+        extensions.lock().await.validation_end(&extension_ctx).await;
 
-        if let Some(limit_depth) = self.depth {
-            if depth > limit_depth {
-                return Err(QueryError::TooDeep.into_error(Pos::default())).log_error(&extensions);
-            }
-        }
+        // check limits
+        let complexity = if self.variable_directives.is_empty() {
+            Some(
+                self.check_limits(&document, &request.variables)
+                    .log_error(&extensions, &extension_ctx)
+                    .await?,
+            )
+        } else {
+            None
+        };
 
         let document = match document.into_data(request.operation_name.as_deref()) {
             Some(document) => document,
@@ -374,24 +953,196 @@ where
                         err: QueryError::MissingOperation,
                     }
                 };
-                extensions.lock().error(&err);
+                extensions.lock().await.error(&extension_ctx, &err).await;
                 return Err(err);
             }
         };
 
-        Ok((document, cache_control, extensions))
+        if let Some(cache) = &self.query_cache {
+            cache.insert(
+                &request.query,
+                request.operation_name.as_deref(),
+                document.clone(),
+                cache_control,
+            );
+        }
+
+        let (document, complexity) = match complexity {
+            Some(complexity) => (document, complexity),
+            // With variable directives, the limits are checked with the transformed variables.
+            None => {
+                self.check_document(
+                    executable_document(&document),
+                    &mut request.variables,
+                    &extensions,
+                    &extension_ctx,
+                )
+                .await?
+            }
+        };
+        self.check_preview_features(&document, &request.data, ctx_data)
+            .log_error(&extensions, &extension_ctx)
+            .await?;
+
+        Ok((document, cache_control, complexity, extensions))
+    }
+
+    /// Check the preview features of the document against the `PreviewFeatures` of the request,
+    /// looked up like the data of a resolver: in the data of the request, then in the data of the
+    /// context, then in the data of the schema.
+    fn check_preview_features(
+        &self,
+        document: &ExecutableDocumentData,
+        request_data: &Data,
+        ctx_data: Option<&Data>,
+    ) -> Result<()> {
+        if !self.has_preview_fields {
+            return Ok(());
+        }
+        let previews = request_data
+            .get::<PreviewFeatures>()
+            .or_else(|| ctx_data.and_then(|data| data.get::<PreviewFeatures>()))
+            .or_else(|| self.env.data.get::<PreviewFeatures>());
+        check_preview_features(&self.env.registry, document, previews)
+    }
+
+    /// Transform the variables and the argument literals of the fields with the variable
+    /// directives of the document, before they are checked.
+    fn apply_variable_directives(
+        &self,
+        document: &mut ExecutableDocument,
+        variables: &mut Variables,
+    ) -> Result<()> {
+        if self.variable_directives.is_empty() {
+            return Ok(());
+        }
+
+        for definition in &mut document.definitions {
+            let selection_set = match definition {
+                ExecutableDefinition::Operation(operation) => {
+                    for variable_definition in &operation.node.variable_definitions {
+                        let name = &variable_definition.node.name.node;
+                        for directive in &variable_definition.node.directives {
+                            let value = match variables
+                                .0
+                                .get(name)
+                                .or_else(|| variable_definition.node.default_value())
+                            {
+                                Some(value) => value.clone(),
+                                None => continue,
+                            };
+                            if let Some(value) =
+                                self.transform_value(directive, variables, value)?
+                            {
+                                variables.0.insert(name.clone(), value);
+                            }
+                        }
+                    }
+                    &mut operation.node.selection_set.node
+                }
+                ExecutableDefinition::Fragment(fragment) => &mut fragment.node.selection_set.node,
+            };
+            self.apply_argument_directives(selection_set, variables)?;
+        }
+        Ok(())
+    }
+
+    fn apply_argument_directives(
+        &self,
+        selection_set: &mut SelectionSet,
+        variables: &Variables,
+    ) -> Result<()> {
+        for selection in &mut selection_set.items {
+            match &mut selection.node {
+                Selection::Field(field) => {
+                    let Field {
+                        arguments,
+                        directives,
+                        selection_set,
+                        ..
+                    } = &mut field.node;
+                    for directive in directives.iter() {
+                        for (_, value) in arguments.iter_mut() {
+                            // Arguments with variables are transformed with their variables.
+                            if let Some(current) = value.node.clone().into_const() {
+                                if let Some(transformed) =
+                                    self.transform_value(directive, variables, current)?
+                                {
+                                    value.node = transformed.into_value();
+                                }
+                            }
+                        }
+                    }
+                    self.apply_argument_directives(&mut selection_set.node, variables)?;
+                }
+                Selection::InlineFragment(fragment) => {
+                    self.apply_argument_directives(
+                        &mut fragment.node.selection_set.node,
+                        variables,
+                    )?;
+                }
+                Selection::FragmentSpread(_) => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Transform `value` with `directive`, returns `None` if it is not a variable directive.
+    fn transform_value(
+        &self,
+        directive: &Positioned<Directive>,
+        variables: &Variables,
+        value: Value,
+    ) -> Result<Option<Value>> {
+        let variable_directive = match self
+            .variable_directives
+            .get(directive.node.name.node.as_str())
+        {
+            Some(variable_directive) => variable_directive,
+            None => return Ok(None),
+        };
+        let args = directive
+            .node
+            .arguments
+            .iter()
+            .map(|(name, value)| {
+                let value = value.node.clone().into_const_with(|var_name| {
+                    variables.0.get(&var_name).cloned().ok_or_else(|| {
+                        QueryError::VarNotDefined {
+                            var_name: var_name.to_string(),
+                        }
+                        .into_error(value.pos)
+                    })
+                })?;
+                Ok((name.node.clone(), value))
+            })
+            .collect::<Result<BTreeMap<_, _>>>()?;
+        variable_directive
+            .transform(&args, value)
+            .map(Some)
+            .map_err(|err| err.into_error(directive.pos))
     }
 
     async fn execute_once(
         &self,
         document: ExecutableDocumentData,
-        extensions: spin::Mutex<Extensions>,
+        extensions: ExtensionsLock,
         variables: Variables,
-        ctx_data: Data,
+        ctx_data: Arc<Data>,
+        request_extensions: RequestExtensions,
+        live_query: Option<Arc<LiveQuery>>,
+        transaction_hook: Option<&dyn TransactionHook>,
     ) -> Response {
         // execute
         let inc_resolve_id = AtomicUsize::default();
-        let env = QueryEnv::new(extensions, variables, document, Arc::new(ctx_data));
+        let env = QueryEnv::new(
+            extensions,
+            variables,
+            document,
+            ctx_data,
+            request_extensions,
+            live_query,
+        );
         let ctx = ContextBase {
             path_node: None,
             resolve_id: ResolveId::root(),
@@ -399,9 +1150,17 @@ where
             item: &env.document.operation.node.selection_set,
             schema_env: &self.env,
             query_env: &env,
+            scoped_data: None,
         };
 
-        env.extensions.lock().execution_start();
+        let extension_ctx = ExtensionContext {
+            data: &env.ctx_data,
+        };
+        env.extensions
+            .lock()
+            .await
+            .execution_start(&extension_ctx)
+            .await;
 
         let data = match &env.document.operation.node.ty {
             OperationType::Query => resolve_object(&ctx, &self.query).await,
@@ -416,22 +1175,466 @@ where
             }
         };
 
-        env.extensions.lock().execution_end();
-        let extensions = env.extensions.lock().result();
+        let spawned = std::mem::take(&mut *env.spawned.lock());
+        future::join_all(spawned).await;
+
+        // The transaction that was begun by `execute_in_transaction`.
+        let data = match (transaction_hook, data) {
+            (Some(hook), Ok(data)) => {
+                hook.commit(&env.ctx_data)
+                    .await
+                    .map(|()| data)
+                    .map_err(|err| err.into_error(Pos::default()))
+                    .log_error(&env.extensions, &extension_ctx)
+                    .await
+            }
+            (Some(hook), Err(err)) => {
+                hook.rollback(&env.ctx_data).await;
+                Err(err)
+            }
+            (None, data) => data,
+        };
+
+        let extensions = {
+            let mut extensions = env.extensions.lock().await;
+            extensions.execution_end(&extension_ctx).await;
+            extensions.result()
+        };
 
         Response::from_result(data).extensions(extensions)
     }
 
+    /// Check the federation metadata of the schema: the fields in the `key` of entities and the
+    /// `requires` and `provides` of fields must exist, and the fields in `requires` and `provides`
+    /// must be marked as `external`.
+    ///
+    /// The gateway only reports these mistakes when it plans a query, so call this at startup or
+    /// in a test to catch them early.
+    ///
+    /// This is a static check of the annotations against the types of the schema. It doesn't
+    /// check the behavior of the resolvers, e.g. that a field with `requires` reads the fields it
+    /// requires, or that a field is missing a `requires` for an external field that it reads.
+    pub fn check_federation(&self) -> std::result::Result<(), Vec<String>> {
+        let errors = self.env.registry.check_federation();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Check the interfaces of the schema: the types that implement an interface must have its
+    /// fields with compatible types and arguments, and must also implement the interfaces that it implements, e.g. a type that
+    /// implements `interface Image implements Resource & Node` must implement `Image`, `Resource`
+    /// and `Node`.
+    ///
+    /// Call this at startup or in a test to catch these mistakes early.
+    pub fn check_interfaces(&self) -> std::result::Result<(), Vec<String>> {
+        let errors = self.env.registry.check_interfaces();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Returns the number of subscriptions that are currently running.
+    pub fn active_subscriptions(&self) -> usize {
+        self.active_subscriptions.load(Ordering::Relaxed)
+    }
+
+    /// Complete the running subscriptions and live queries, with
+    /// [`CompletionReason::ServerShutdown`](enum.CompletionReason.html#variant.ServerShutdown), and
+    /// the ones that are started afterwards.
+    pub fn shutdown(&self) {
+        self.shutdown.shutdown();
+    }
+
+    /// Re-execute the live queries that depend on `key`, see
+    /// [`Context::add_invalidation_key`](struct.Context.html#method.add_invalidation_key).
+    ///
+    /// A query is live when it has the `@live` directive, such as `query @live { todos { id } }`.
+    /// It is executed with [`Schema::execute_stream`](#method.execute_stream) or the websocket
+    /// transport like a subscription, and the result is sent again each time it changes.
+    pub fn invalidate(&self, key: &str) {
+        self.live_queries.invalidate(key);
+    }
+
+    /// Returns a hash of the type system as a hex string, which changes whenever a type, field,
+    /// argument or directive is added, removed or changed.
+    ///
+    /// Clients and gateways can compare it to detect schema changes, for example to invalidate
+    /// cached persisted queries. See also
+    /// [`SchemaBuilder::expose_fingerprint`](struct.SchemaBuilder.html#method.expose_fingerprint).
+    pub fn fingerprint(&self) -> &str {
+        &self.fingerprint
+    }
+
+    /// Returns `true` if the subscription root is not
+    /// [`EmptySubscription`](struct.EmptySubscription.html), so that integrations only mount
+    /// subscription routes, such as websockets, when there are subscriptions.
+    pub fn has_subscriptions(&self) -> bool {
+        !Subscription::is_empty()
+    }
+
+    /// Returns the SDL of the schema, in the format of the `_service` field of Apollo Federation.
+    pub fn sdl(&self) -> String {
+        self.env.registry.create_federation_sdl()
+    }
+
+    /// Returns TypeScript definitions of the types of the schema, for clients that do not
+    /// generate them from the schema with a separate tool.
+    ///
+    /// Objects, interfaces and input objects become interfaces, enums and unions become union
+    /// types, and custom scalars become `unknown`. Nullable fields are optional. For every field
+    /// with arguments there is an interface of the arguments named like `QueryUserArgs`.
+    pub fn typescript_definitions(&self) -> String {
+        self.env.registry.create_typescript_definitions()
+    }
+
+    /// Returns Rust code for calling the operations of `documents` on this schema, usually
+    /// written to a file by the build script of another service.
+    ///
+    /// The documents are validated against the schema first. For every operation the code
+    /// contains the document as a constant, a `{Name}Variables` struct, a `{Name}Data` struct
+    /// for the response and a function returning the JSON body of the request. The enums and
+    /// input objects of the schema are generated as well. The code depends on `serde` and
+    /// `serde_json`.
+    pub fn rust_client(&self, documents: &[&str]) -> Result<String> {
+        let parsed = documents
+            .iter()
+            .map(|source| {
+                let document = parse_query(source)?;
+                self.validate(&document, &Variables::default())?;
+                Ok(document)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let documents: Vec<_> = documents.iter().copied().zip(parsed.iter()).collect();
+        Ok(self.env.registry.create_rust_client(&documents))
+    }
+
+    /// Publish the SDL of the schema to a schema registry, usually when the server starts.
+    pub async fn publish(
+        &self,
+        registry: &impl SchemaRegistry,
+        transport: &impl RegistryTransport,
+    ) -> std::result::Result<(), RegistryError> {
+        schema_registry::publish(&self.env.registry, registry, transport).await
+    }
+
+    /// Validate the `.graphql` documents in `dir` and its subdirectories against the schema and
+    /// return them as a manifest of persisted operations, usually in a build script.
+    ///
+    /// Every document must contain exactly one operation. The errors of all invalid documents are
+    /// returned, so that they can be fixed at once.
+    pub fn export_operations(
+        &self,
+        dir: impl AsRef<Path>,
+    ) -> std::result::Result<OperationManifest, Vec<ExportError>> {
+        let documents =
+            persisted_operations::find_documents(dir.as_ref()).map_err(|err| vec![err])?;
+        let mut manifest = OperationManifest::default();
+        let mut errors = Vec::new();
+        for path in documents {
+            match persisted_operations::export_document(path, |document| {
+                self.validate(document, &Variables::default())
+            }) {
+                Ok(operation) => manifest.operations.push(operation),
+                Err(err) => errors.push(err),
+            }
+        }
+        if errors.is_empty() {
+            Ok(manifest)
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Parse and validate a request without executing it, returning the operation that will be
+    /// executed.
+    ///
+    /// Integrations can inspect its type, name, variables and root fields to route it, then
+    /// execute it with [`execute_prepared`](#method.execute_prepared) without parsing the query
+    /// again.
+    pub async fn prepare(&self, request: impl Into<Request>) -> Result<PreparedOperation> {
+        let mut request = request.into();
+        let idempotency_key = self.idempotency_key(&request);
+        let (document, cache_control, complexity, extensions) =
+            self.prepare_request(&mut request, None).await?;
+        Ok(PreparedOperation {
+            request,
+            document,
+            cache_control,
+            complexity,
+            extensions,
+            idempotency_key,
+            id: None,
+        })
+    }
+
     /// Execute an GraphQL query.
     pub async fn execute(&self, request: impl Into<Request>) -> Response {
-        let request = request.into();
-        match self.prepare_request(&request) {
-            Ok((document, cache_control, extensions)) => self
-                .execute_once(document, extensions, request.variables, request.data)
+        match self.prepare(request).await {
+            Ok(operation) => self.execute_prepared(operation).await,
+            Err(e) => self.with_fingerprint(Response::from_error(e)),
+        }
+    }
+
+    /// Execute an operation returned by [`prepare`](#method.prepare).
+    pub async fn execute_prepared(&self, operation: PreparedOperation) -> Response {
+        let PreparedOperation {
+            request,
+            document,
+            cache_control,
+            complexity,
+            extensions,
+            idempotency_key,
+            id,
+        } = operation;
+        self.execute_document(
+            document,
+            cache_control,
+            complexity,
+            extensions,
+            request,
+            idempotency_key,
+        )
+        .await
+        .operation_id(id)
+    }
+
+    /// Execute an operation returned by [`prepare`](#method.prepare) with the variables, data
+    /// and extensions of another request, without parsing and validating the query again. The
+    /// query and the operation name of `request` are ignored.
+    ///
+    /// This lets servers prepare their known operations, such as persisted queries, once and
+    /// reuse them for every request. Only the arguments that use variables are checked again,
+    /// against the new variables, along with the complexity and depth limits, since the
+    /// complexity of a field can depend on its arguments.
+    pub async fn execute_prepared_with(
+        &self,
+        operation: &PreparedOperation,
+        request: impl Into<Request>,
+    ) -> Response {
+        let mut request = request.into();
+        request.query = operation.request.query.clone();
+        request.operation_name = operation.request.operation_name.clone();
+        let idempotency_key = self.idempotency_key(&request);
+
+        let extensions = self.create_extensions();
+        let extension_ctx = ExtensionContext {
+            data: &request.data,
+        };
+        extensions
+            .lock()
+            .await
+            .request_start(&extension_ctx, &request.extensions)
+            .await;
+        let (document, complexity) = match self
+            .check_document(
+                executable_document(&operation.document),
+                &mut request.variables,
+                &extensions,
+                &extension_ctx,
+            )
+            .await
+        {
+            Ok(document) => document,
+            Err(e) => {
+                return self
+                    .with_fingerprint(Response::from_error(e))
+                    .operation_id(operation.id.clone())
+            }
+        };
+        if let Err(e) = self
+            .check_preview_features(&document, &request.data, None)
+            .log_error(&extensions, &extension_ctx)
+            .await
+        {
+            return self
+                .with_fingerprint(Response::from_error(e))
+                .operation_id(operation.id.clone());
+        }
+        self.execute_document(
+            document,
+            operation.cache_control,
+            complexity,
+            extensions,
+            request,
+            idempotency_key,
+        )
+        .await
+        .operation_id(operation.id.clone())
+    }
+
+    fn idempotency_key(&self, request: &Request) -> Option<(String, u64)> {
+        self.idempotency
+            .as_ref()
+            .and_then(|_| request.data.0.get(&TypeId::of::<IdempotencyKey>()))
+            .and_then(|key| key.downcast_ref::<IdempotencyKey>())
+            .map(|key| (key.0.clone(), request_hash(request)))
+    }
+
+    /// Check a document that has already been validated against new variables, returning it to
+    /// execute with the variable directives applied, and its complexity.
+    ///
+    /// The limits are checked with the transformed variables, because the complexity of a field
+    /// can depend on its arguments.
+    async fn check_document(
+        &self,
+        mut document: ExecutableDocument,
+        variables: &mut Variables,
+        extensions: &ExtensionsLock,
+        extension_ctx: &ExtensionContext<'_>,
+    ) -> Result<(ExecutableDocumentData, usize)> {
+        self.apply_variable_directives(&mut document, variables)
+            .log_error(extensions, extension_ctx)
+            .await?;
+        check_variables(
+            &self.env.registry,
+            &document,
+            variables,
+            self.validation_mode,
+        )
+        .log_error(extensions, extension_ctx)
+        .await?;
+        let complexity = self
+            .check_limits(&document, variables)
+            .log_error(extensions, extension_ctx)
+            .await?;
+        let document = document
+            .into_data(None)
+            .expect("a validated document has an operation");
+        Ok((document, complexity))
+    }
+
+    async fn execute_document(
+        &self,
+        document: ExecutableDocumentData,
+        cache_control: CacheControl,
+        complexity: usize,
+        extensions: ExtensionsLock,
+        request: Request,
+        idempotency_key: Option<(String, u64)>,
+    ) -> Response {
+        let is_idempotent = document.operation.node.ty == OperationType::Mutation
+            && is_idempotent_mutation(&self.env.registry, &document);
+        let response = match (&self.idempotency, idempotency_key) {
+            (Some((store, ttl)), Some((key, request_hash))) if is_idempotent => {
+                match store.reserve(&key, request_hash, *ttl).await {
+                    IdempotencyReservation::Stored(stored)
+                        if stored.request_hash == request_hash =>
+                    {
+                        Response::new(stored.data).extensions(stored.extensions)
+                    }
+                    IdempotencyReservation::Pending {
+                        request_hash: pending_hash,
+                    } if pending_hash == request_hash => Response::from_error(
+                        QueryError::IdempotencyKeyPending { key }.into_error(Pos::default()),
+                    ),
+                    IdempotencyReservation::Stored(_) | IdempotencyReservation::Pending { .. } => {
+                        Response::from_error(
+                            QueryError::IdempotencyKeyReused { key }.into_error(Pos::default()),
+                        )
+                    }
+                    IdempotencyReservation::Reserved => {
+                        // Releases the key if the request is dropped or a resolver panics.
+                        let reservation =
+                            ReservationGuard::new(store.clone(), key, self.env.spawner.clone());
+                        let response = self
+                            .execute_in_transaction(document, extensions, request)
+                            .await;
+                        if response.is_err() {
+                            reservation.release().await;
+                        } else {
+                            let stored = IdempotentResponse {
+                                request_hash,
+                                data: response.data.clone(),
+                                extensions: response.extensions.clone(),
+                            };
+                            reservation.set(stored, *ttl).await;
+                        }
+                        response
+                    }
+                }
+            }
+            _ => self
+                .execute_in_transaction(document, extensions, request)
                 .await
                 .cache_control(cache_control),
-            Err(e) => Response::from_error(e),
+        };
+        let response = if self.expose_query_cost {
+            response.query_cost(complexity)
+        } else {
+            response
+        };
+        self.with_fingerprint(response)
+    }
+
+    async fn execute_in_transaction(
+        &self,
+        document: ExecutableDocumentData,
+        extensions: ExtensionsLock,
+        mut request: Request,
+    ) -> Response {
+        let hook = match &self.transaction_hook {
+            Some(hook) if document.operation.node.ty == OperationType::Mutation => hook,
+            _ => {
+                return self
+                    .execute_once(
+                        document,
+                        extensions,
+                        request.variables,
+                        Arc::new(request.data),
+                        request.extensions,
+                        None,
+                        None,
+                    )
+                    .await
+            }
+        };
+
+        // Like the errors of the resolvers, the errors of the transaction are execution errors.
+        if let Err(err) = hook.begin(&mut request.data).await {
+            let extension_ctx = ExtensionContext {
+                data: &request.data,
+            };
+            let err = Err::<serde_json::Value, _>(err.into_error(Pos::default()))
+                .log_error(&extensions, &extension_ctx)
+                .await;
+            let extensions = extensions.lock().await.result();
+            return Response::from_result(err).extensions(extensions);
         }
+        self.execute_once(
+            document,
+            extensions,
+            request.variables,
+            Arc::new(request.data),
+            request.extensions,
+            None,
+            Some(hook.as_ref()),
+        )
+        .await
+    }
+
+    fn with_fingerprint(&self, response: Response) -> Response {
+        if self.expose_fingerprint {
+            response.schema_fingerprint(self.fingerprint.clone())
+        } else {
+            response
+        }
+    }
+
+    /// Execute the standard [introspection query](constant.INTROSPECTION_QUERY.html) and return
+    /// its result, e.g. to export the schema at startup or to snapshot it in tests.
+    ///
+    /// Returns an error if introspection has been disabled with
+    /// [`SchemaBuilder::disable_introspection`](struct.SchemaBuilder.html#method.disable_introspection).
+    pub async fn introspect(&self) -> Result<serde_json::Value> {
+        self.execute(INTROSPECTION_QUERY)
+            .await
+            .into_result()
+            .map(|response| response.data)
     }
 
     /// Execute an GraphQL batch query.
@@ -451,12 +1654,13 @@ where
         &self,
         request: impl Into<Request> + Send,
         ctx_data: Arc<Data>,
+        completion: CompletionHandle,
     ) -> impl Stream<Item = Response> + Send {
         let schema = self.clone();
 
         async_stream::stream! {
-            let request = request.into();
-            let (document, cache_control, extensions) = match schema.prepare_request(&request) {
+            let mut request = request.into();
+            let (document, cache_control, _, extensions) = match schema.prepare_request(&mut request, Some(&ctx_data)).await {
                 Ok(res) => res,
                 Err(err) => {
                     yield Response::from(err);
@@ -464,9 +1668,70 @@ where
                 }
             };
 
+            let is_live = document.operation.node.ty == OperationType::Query
+                && document
+                    .operation
+                    .node
+                    .directives
+                    .iter()
+                    .any(|directive| directive.node.name.node == "live");
+            if is_live {
+                let (live_query, mut invalidations) = schema.live_queries.register();
+                let _completion_guard = LiveQueryGuard::new(live_query.clone(), completion.clone());
+                let _active_subscription = ActiveSubscription::new(&schema.active_subscriptions);
+                let mut shutdown = schema.shutdown.subscribe();
+                let mut extensions = Some(extensions);
+                let mut last_data = None;
+                loop {
+                    live_query.clear_keys();
+                    let response = schema
+                        .execute_once(
+                            document.clone(),
+                            extensions.take().unwrap_or_else(|| schema.create_extensions()),
+                            request.variables.clone(),
+                            ctx_data.clone(),
+                            request.extensions.clone(),
+                            Some(live_query.clone()),
+                            None,
+                        )
+                        .await;
+                    let is_err = response.is_err();
+                    if is_err || last_data.as_ref() != Some(&response.data) {
+                        last_data = Some(response.data.clone());
+                        yield response;
+                    }
+                    if is_err {
+                        completion.set(CompletionReason::Error);
+                        break;
+                    }
+                    match future::select(invalidations.next(), &mut shutdown).await {
+                        Either::Left((Some(()), _)) => {}
+                        Either::Left((None, _)) => {
+                            completion.set(CompletionReason::Finished);
+                            break;
+                        }
+                        Either::Right(_) => {
+                            completion.set(CompletionReason::ServerShutdown);
+                            break;
+                        }
+                    }
+                    // Several invalidations while executing only need one re-execution.
+                    while let Ok(Some(())) = invalidations.try_next() {}
+                }
+                return;
+            }
+
             if document.operation.node.ty != OperationType::Subscription {
                 yield schema
-                    .execute_once(document, extensions, request.variables, request.data)
+                    .execute_once(
+                        document,
+                        extensions,
+                        request.variables,
+                        Arc::new(request.data),
+                        request.extensions,
+                        None,
+                        None,
+                    )
                     .await
                     .cache_control(cache_control);
                 return;
@@ -478,6 +1743,8 @@ where
                 request.variables,
                 document,
                 ctx_data,
+                request.extensions,
+                None,
             );
 
             let ctx = env.create_context(
@@ -489,18 +1756,35 @@ where
 
             // TODO: Invoke extensions
 
+            // Declared before the streams, so that it is dropped after them.
+            let _completion_guard = CompletionGuard::new(env.clone(), completion.clone());
             let mut streams = Vec::new();
             if let Err(e) = collect_subscription_streams(&ctx, &schema.subscription, &mut streams) {
+                completion.set(CompletionReason::Error);
                 yield Response::from(e);
                 return;
             }
 
+            let _active_subscription = ActiveSubscription::new(&schema.active_subscriptions);
             let mut stream = stream::select_all(streams);
-            while let Some(data) = stream.next().await {
+            let mut shutdown = schema.shutdown.subscribe();
+            loop {
+                let data = match future::select(stream.next(), &mut shutdown).await {
+                    Either::Left((Some(data), _)) => data,
+                    Either::Left((None, _)) => {
+                        completion.set(CompletionReason::Finished);
+                        break;
+                    }
+                    Either::Right(_) => {
+                        completion.set(CompletionReason::ServerShutdown);
+                        break;
+                    }
+                };
                 let is_err = data.is_err();
-                let extensions = env.extensions.lock().result();
+                let extensions = env.extensions.lock().await.result();
                 yield Response::from_result(data).extensions(extensions);
                 if is_err {
+                    completion.set(CompletionReason::Error);
                     break;
                 }
             }
@@ -514,6 +1798,53 @@ where
     ) -> impl Stream<Item = Response> + Send {
         let mut request = request.into();
         let ctx_data = std::mem::take(&mut request.data);
-        self.execute_stream_with_ctx_data(request, Arc::new(ctx_data))
+        self.execute_stream_with_ctx_data(request, Arc::new(ctx_data), Default::default())
+    }
+}
+
+/// The error of a query that is nested deeper than `limit`, at its deepest field.
+fn too_deep(limit: usize, deepest_field: Option<(Pos, Vec<String>)>) -> Error {
+    let (pos, path) = deepest_field.unwrap_or_default();
+    Error::Query {
+        pos,
+        path: Some(serde_json::json!(path)),
+        err: QueryError::TooDeep { limit },
     }
 }
+
+/// Returns a document with the operation and the fragments of `document`.
+fn executable_document(document: &ExecutableDocumentData) -> ExecutableDocument {
+    ExecutableDocument {
+        definitions: std::iter::once(ExecutableDefinition::Operation(document.operation.clone()))
+            .chain(
+                document
+                    .fragments
+                    .values()
+                    .cloned()
+                    .map(ExecutableDefinition::Fragment),
+            )
+            .collect(),
+    }
+}
+
+/// Returns the operation of `document` that will be executed, like
+/// `ExecutableDocument::into_data` but without consuming the document.
+fn selected_operation<'a>(
+    document: &'a ExecutableDocument,
+    operation_name: Option<&str>,
+) -> Option<&'a Positioned<OperationDefinition>> {
+    document
+        .definitions
+        .iter()
+        .find_map(|definition| match definition {
+            ExecutableDefinition::Operation(operation)
+                if operation_name.map_or(true, |required_name| {
+                    operation.node.name.as_ref().map(|name| name.node.as_str())
+                        == Some(required_name)
+                }) =>
+            {
+                Some(operation)
+            }
+            _ => None,
+        })
+}
@@ -0,0 +1,387 @@
+//! Publish the schema to a schema registry.
+//!
+//! A [`SchemaRegistry`](trait.SchemaRegistry.html) builds the request that publishes the SDL of
+//! a schema to a registry, and a [`RegistryTransport`](trait.RegistryTransport.html) sends it
+//! with the HTTP client of the application, so that deploy pipelines can publish the schema when
+//! the server starts without a separate CLI.
+//!
+//! ```ignore
+//! use async_graphql::schema_registry::ApolloStudio;
+//!
+//! let registry = ApolloStudio::new(std::env::var("APOLLO_KEY")?, "my-graph@production")
+//!     .subgraph("products", "https://products.example.com/graphql");
+//! schema.publish(&registry, &transport).await?;
+//! ```
+
+use crate::registry::Registry;
+use serde_json::{json, Value};
+
+/// An HTTP `POST` request with a JSON body to a schema registry.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RegistryRequest {
+    /// The URL of the request.
+    pub url: String,
+
+    /// The headers of the request, e.g. for authentication.
+    pub headers: Vec<(String, String)>,
+
+    /// The JSON body of the request.
+    pub body: Value,
+}
+
+/// An error publishing a schema.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum RegistryError {
+    /// The request could not be sent, or the response was not JSON.
+    #[error("Failed to send the schema to the registry: {0}")]
+    Transport(String),
+
+    /// The registry rejected the schema.
+    #[error("The registry rejected the schema: {0}")]
+    Rejected(String),
+}
+
+/// Sends the requests to schema registries, usually with the HTTP client of the application.
+#[async_trait::async_trait]
+pub trait RegistryTransport: Send + Sync {
+    /// Send `request` and return the JSON body of the response.
+    async fn send(&self, request: RegistryRequest) -> Result<Value, RegistryError>;
+}
+
+/// A schema registry that a schema can be published to.
+pub trait SchemaRegistry: Send + Sync {
+    /// Returns the request that publishes `sdl`.
+    fn publish_request(&self, sdl: &str) -> RegistryRequest;
+
+    /// Check the response of the registry to the publish request.
+    fn check_response(&self, response: &Value) -> Result<(), RegistryError>;
+}
+
+/// Publish the SDL of `registry` to a schema registry.
+pub(crate) async fn publish(
+    registry: &Registry,
+    schema_registry: &impl SchemaRegistry,
+    transport: &impl RegistryTransport,
+) -> Result<(), RegistryError> {
+    let request = schema_registry.publish_request(&registry.create_federation_sdl());
+    let response = transport.send(request).await?;
+    schema_registry.check_response(&response)
+}
+
+/// Returns the messages of the `errors` of a GraphQL response, if there are any.
+fn graphql_errors(response: &Value) -> Option<String> {
+    let errors = response.get("errors")?.as_array()?;
+    if errors.is_empty() {
+        return None;
+    }
+    Some(
+        errors
+            .iter()
+            .map(|error| error.get("message").and_then(Value::as_str).unwrap_or(""))
+            .collect::<Vec<_>>()
+            .join(", "),
+    )
+}
+
+const APOLLO_PUBLISH_SUBGRAPH: &str = r#"
+mutation PublishSubgraph(
+  $graphId: ID!, $variant: String!, $name: String!, $url: String, $sdl: String!
+) {
+  graph(id: $graphId) {
+    publishSubgraph(
+      graphVariant: $variant, name: $name, url: $url, revision: "",
+      activePartialSchema: { sdl: $sdl }
+    ) {
+      errors { message }
+    }
+  }
+}"#;
+
+const APOLLO_UPLOAD_SCHEMA: &str = r#"
+mutation UploadSchema($graphId: ID!, $variant: String!, $sdl: String!) {
+  graph(id: $graphId) {
+    uploadSchema(schemaDocument: $sdl, tag: $variant) { success message }
+  }
+}"#;
+
+/// [Apollo Studio](https://www.apollographql.com/docs/studio/), authenticated with a graph API
+/// key.
+///
+/// The schema is published as a monolithic schema, or as a subgraph of a federated graph with
+/// [`subgraph`](#method.subgraph).
+pub struct ApolloStudio {
+    endpoint: String,
+    api_key: String,
+    graph_ref: String,
+    subgraph: Option<(String, String)>,
+}
+
+impl ApolloStudio {
+    /// Publish to the variant of a graph, e.g. `my-graph@production`. The variant defaults to
+    /// `current`.
+    pub fn new(api_key: impl Into<String>, graph_ref: impl Into<String>) -> Self {
+        Self {
+            endpoint: "https://api.apollographql.com/api/graphql".to_string(),
+            api_key: api_key.into(),
+            graph_ref: graph_ref.into(),
+            subgraph: None,
+        }
+    }
+
+    /// Publish as the subgraph `name` that is served at `url`.
+    pub fn subgraph(self, name: impl Into<String>, url: impl Into<String>) -> Self {
+        Self {
+            subgraph: Some((name.into(), url.into())),
+            ..self
+        }
+    }
+
+    /// Set the URL of the Platform API.
+    pub fn endpoint(self, endpoint: impl Into<String>) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            ..self
+        }
+    }
+}
+
+impl SchemaRegistry for ApolloStudio {
+    fn publish_request(&self, sdl: &str) -> RegistryRequest {
+        let mut graph_ref = self.graph_ref.splitn(2, '@');
+        let graph_id = graph_ref.next().unwrap_or_default();
+        let variant = graph_ref.next().unwrap_or("current");
+
+        let body = match &self.subgraph {
+            Some((name, url)) => json!({
+                "query": APOLLO_PUBLISH_SUBGRAPH,
+                "variables": {
+                    "graphId": graph_id,
+                    "variant": variant,
+                    "name": name,
+                    "url": url,
+                    "sdl": sdl,
+                },
+            }),
+            None => json!({
+                "query": APOLLO_UPLOAD_SCHEMA,
+                "variables": {
+                    "graphId": graph_id,
+                    "variant": variant,
+                    "sdl": sdl,
+                },
+            }),
+        };
+
+        RegistryRequest {
+            url: self.endpoint.clone(),
+            headers: vec![
+                ("x-api-key".to_string(), self.api_key.clone()),
+                (
+                    "apollographql-client-name".to_string(),
+                    "async-graphql".to_string(),
+                ),
+            ],
+            body,
+        }
+    }
+
+    fn check_response(&self, response: &Value) -> Result<(), RegistryError> {
+        if let Some(errors) = graphql_errors(response) {
+            return Err(RegistryError::Rejected(errors));
+        }
+
+        let graph = &response["data"]["graph"];
+        if graph.is_null() {
+            return Err(RegistryError::Rejected(format!(
+                "Graph \"{}\" not found",
+                self.graph_ref
+            )));
+        }
+        match &self.subgraph {
+            Some(_) => match graphql_errors(&graph["publishSubgraph"]) {
+                Some(errors) => Err(RegistryError::Rejected(errors)),
+                None => Ok(()),
+            },
+            None => {
+                let result = &graph["uploadSchema"];
+                if result["success"].as_bool() == Some(true) {
+                    Ok(())
+                } else {
+                    Err(RegistryError::Rejected(
+                        result["message"].as_str().unwrap_or_default().to_string(),
+                    ))
+                }
+            }
+        }
+    }
+}
+
+const HIVE_SCHEMA_PUBLISH: &str = r#"
+mutation SchemaPublish($input: SchemaPublishInput!) {
+  schemaPublish(input: $input) {
+    __typename
+    ... on SchemaPublishMissingServiceError { message }
+    ... on SchemaPublishMissingUrlError { message }
+  }
+}"#;
+
+/// [GraphQL Hive](https://graphql-hive.com), authenticated with a registry access token.
+pub struct Hive {
+    endpoint: String,
+    token: String,
+    author: String,
+    commit: String,
+    service: Option<(String, String)>,
+}
+
+impl Hive {
+    /// Publish with the author and commit that are shown in the history of the schema.
+    pub fn new(
+        token: impl Into<String>,
+        author: impl Into<String>,
+        commit: impl Into<String>,
+    ) -> Self {
+        Self {
+            endpoint: "https://app.graphql-hive.com/graphql".to_string(),
+            token: token.into(),
+            author: author.into(),
+            commit: commit.into(),
+            service: None,
+        }
+    }
+
+    /// Publish as the service `name` of a distributed schema that is served at `url`.
+    pub fn service(self, name: impl Into<String>, url: impl Into<String>) -> Self {
+        Self {
+            service: Some((name.into(), url.into())),
+            ..self
+        }
+    }
+
+    /// Set the URL of the registry, for self-hosted instances.
+    pub fn endpoint(self, endpoint: impl Into<String>) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            ..self
+        }
+    }
+}
+
+impl SchemaRegistry for Hive {
+    fn publish_request(&self, sdl: &str) -> RegistryRequest {
+        let mut input = json!({
+            "sdl": sdl,
+            "author": self.author,
+            "commit": self.commit,
+        });
+        if let Some((name, url)) = &self.service {
+            input["service"] = json!(name);
+            input["url"] = json!(url);
+        }
+
+        RegistryRequest {
+            url: self.endpoint.clone(),
+            headers: vec![(
+                "Authorization".to_string(),
+                format!("Bearer {}", self.token),
+            )],
+            body: json!({
+                "query": HIVE_SCHEMA_PUBLISH,
+                "variables": { "input": input },
+            }),
+        }
+    }
+
+    fn check_response(&self, response: &Value) -> Result<(), RegistryError> {
+        if let Some(errors) = graphql_errors(response) {
+            return Err(RegistryError::Rejected(errors));
+        }
+
+        let result = &response["data"]["schemaPublish"];
+        match result["__typename"].as_str() {
+            Some("SchemaPublishSuccess") => Ok(()),
+            Some(typename) => Err(RegistryError::Rejected(
+                result["message"].as_str().unwrap_or(typename).to_string(),
+            )),
+            None => Err(RegistryError::Rejected(
+                "Missing the result of schemaPublish".to_string(),
+            )),
+        }
+    }
+}
+
+/// [WunderGraph Cosmo](https://wundergraph.com/cosmo), authenticated with an API key.
+pub struct WunderGraph {
+    endpoint: String,
+    api_key: String,
+    subgraph: String,
+    namespace: String,
+}
+
+impl WunderGraph {
+    /// Publish as the subgraph `name`.
+    pub fn new(api_key: impl Into<String>, subgraph: impl Into<String>) -> Self {
+        Self {
+            endpoint: "https://cosmo-cp.wundergraph.com".to_string(),
+            api_key: api_key.into(),
+            subgraph: subgraph.into(),
+            namespace: "default".to_string(),
+        }
+    }
+
+    /// Set the namespace of the subgraph, which defaults to `default`.
+    pub fn namespace(self, namespace: impl Into<String>) -> Self {
+        Self {
+            namespace: namespace.into(),
+            ..self
+        }
+    }
+
+    /// Set the URL of the control plane, for self-hosted instances.
+    pub fn endpoint(self, endpoint: impl Into<String>) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            ..self
+        }
+    }
+}
+
+impl SchemaRegistry for WunderGraph {
+    fn publish_request(&self, sdl: &str) -> RegistryRequest {
+        RegistryRequest {
+            url: format!(
+                "{}/wg.cosmo.platform.v1.PlatformService/PublishFederatedSubgraph",
+                self.endpoint.trim_end_matches('/')
+            ),
+            headers: vec![
+                (
+                    "Authorization".to_string(),
+                    format!("Bearer {}", self.api_key),
+                ),
+                ("Connect-Protocol-Version".to_string(), "1".to_string()),
+            ],
+            body: json!({
+                "name": self.subgraph,
+                "namespace": self.namespace,
+                "schema": sdl,
+            }),
+        }
+    }
+
+    fn check_response(&self, response: &Value) -> Result<(), RegistryError> {
+        // An error of the Connect protocol, e.g. an invalid API key.
+        if let Some(code) = response["code"].as_str() {
+            return Err(RegistryError::Rejected(
+                response["message"].as_str().unwrap_or(code).to_string(),
+            ));
+        }
+
+        let status = &response["response"];
+        match status["code"].as_str() {
+            None | Some("OK") => Ok(()),
+            Some(code) => Err(RegistryError::Rejected(
+                status["details"].as_str().unwrap_or(code).to_string(),
+            )),
+        }
+    }
+}
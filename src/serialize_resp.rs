@@ -1,6 +1,6 @@
 use crate::{Error, QueryError, Response};
 use itertools::Itertools;
-use serde::ser::{SerializeSeq, SerializeStruct};
+use serde::ser::{Error as _, SerializeSeq, SerializeStruct};
 use serde::{Serialize, Serializer};
 
 impl Serialize for Response {
@@ -17,13 +17,44 @@ impl Serialize for Response {
             }
             Some(err) => {
                 let mut s = serializer.serialize_struct("Response", 1)?;
-                s.serialize_field("errors", err)?;
+                match &self.operation_id {
+                    Some(id) => s.serialize_field(
+                        "errors",
+                        &persisted_errors(err, id).map_err(S::Error::custom)?,
+                    )?,
+                    None => s.serialize_field("errors", err)?,
+                }
                 s.end()
             }
         }
     }
 }
 
+/// Serialize `err` with its locations moved to the `persistedOperation` extension, since they
+/// refer to the stored document `id` rather than to a query sent by the client.
+fn persisted_errors(err: &Error, id: &str) -> serde_json::Result<serde_json::Value> {
+    let mut errors = serde_json::to_value(err)?;
+    if let serde_json::Value::Array(errors) = &mut errors {
+        for error in errors {
+            if let serde_json::Value::Object(error) = error {
+                let locations = error
+                    .remove("locations")
+                    .unwrap_or_else(|| serde_json::Value::Array(Vec::new()));
+                let extensions = error
+                    .entry("extensions")
+                    .or_insert_with(|| serde_json::Map::new().into());
+                if let serde_json::Value::Object(extensions) = extensions {
+                    extensions.insert(
+                        "persistedOperation".to_string(),
+                        serde_json::json!({ "id": id, "locations": locations }),
+                    );
+                }
+            }
+        }
+    }
+    Ok(errors)
+}
+
 impl Serialize for Error {
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
     where
@@ -32,10 +63,32 @@ impl Serialize for Error {
         match self {
             Error::Parse(err) => {
                 let mut seq = serializer.serialize_seq(Some(1))?;
-                seq.serialize_element(&serde_json::json! ({
-                    "message": err.message,
-                    "locations": [{"line": err.pos.line, "column": err.pos.column}]
-                }))?;
+                let mut map = serde_json::Map::new();
+                map.insert("message".to_string(), err.message.clone().into());
+                map.insert(
+                    "locations".to_string(),
+                    serde_json::json!([{"line": err.pos.line, "column": err.pos.column}]),
+                );
+
+                let mut extensions = serde_json::Map::new();
+                if let Some(token) = &err.token {
+                    extensions.insert("token".to_string(), token.clone().into());
+                }
+                if !err.expected.is_empty() {
+                    extensions.insert("expected".to_string(), err.expected.clone().into());
+                }
+                if let Some(line) = &err.source_line {
+                    let caret = " ".repeat(err.pos.column.saturating_sub(1));
+                    extensions.insert(
+                        "snippet".to_string(),
+                        format!("{}\n{}^", line, caret).into(),
+                    );
+                }
+                if !extensions.is_empty() {
+                    map.insert("extensions".to_string(), extensions.into());
+                }
+
+                seq.serialize_element(&map)?;
                 seq.end()
             }
             Error::Query { pos, path, err } => {
@@ -73,10 +126,24 @@ impl Serialize for Error {
             Error::Rule { errors } => {
                 let mut seq = serializer.serialize_seq(Some(errors.len()))?;
                 for error in errors {
-                    seq.serialize_element(&serde_json::json!({
-                        "message": error.message,
-                        "locations": error.locations.iter().map(|pos| serde_json::json!({"line": pos.line, "column": pos.column})).collect_vec(),
-                    }))?;
+                    let mut map = serde_json::Map::new();
+                    map.insert("message".to_string(), error.message.clone().into());
+                    map.insert(
+                        "locations".to_string(),
+                        error
+                            .locations
+                            .iter()
+                            .map(|pos| serde_json::json!({"line": pos.line, "column": pos.column}))
+                            .collect_vec()
+                            .into(),
+                    );
+                    if !error.suggestions.is_empty() {
+                        map.insert(
+                            "extensions".to_string(),
+                            serde_json::json!({ "suggestions": error.suggestions }),
+                        );
+                    }
+                    seq.serialize_element(&serde_json::Value::Object(map))?;
                 }
                 seq.end()
             }
@@ -87,7 +154,7 @@ impl Serialize for Error {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::Pos;
+    use crate::{Pos, RuleError};
     use serde_json::json;
 
     #[test]
@@ -96,7 +163,12 @@ mod tests {
             data: json!({"ok": true}),
             extensions: None,
             cache_control: Default::default(),
+            schema_fingerprint: None,
+            query_cost: None,
+            rate_limit: None,
             error: None,
+            error_kind: None,
+            operation_id: None,
         };
         assert_eq!(
             serde_json::to_value(resp).unwrap(),
@@ -160,4 +232,36 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn test_rule_error_with_suggestions() {
+        let resp = Response::from(Error::Rule {
+            errors: vec![
+                RuleError {
+                    locations: vec![Pos { line: 1, column: 3 }],
+                    message: r#"Unknown field "nme" on type "Query". Did you mean "name"?"#
+                        .to_owned(),
+                    suggestions: vec!["name".to_owned()],
+                },
+                RuleError {
+                    locations: vec![Pos { line: 1, column: 7 }],
+                    message: r#"Unknown field "zzz" on type "Query"."#.to_owned(),
+                    suggestions: Vec::new(),
+                },
+            ],
+        });
+        assert_eq!(
+            serde_json::to_value(resp).unwrap(),
+            json!({
+                "errors": [{
+                    "message": r#"Unknown field "nme" on type "Query". Did you mean "name"?"#,
+                    "locations": [{"line": 1, "column": 3}],
+                    "extensions": {"suggestions": ["name"]}
+                }, {
+                    "message": r#"Unknown field "zzz" on type "Query"."#,
+                    "locations": [{"line": 1, "column": 7}]
+                }]
+            })
+        );
+    }
 }
@@ -0,0 +1,86 @@
+use crate::Context;
+use futures::future::BoxFuture;
+use futures::{Future, Stream};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::Poll;
+
+pub(crate) type SpawnFn = dyn Fn(BoxFuture<'static, ()>) + Send + Sync;
+
+/// A stream that runs an asynchronous cleanup when it is dropped, to release the resources used by
+/// the stream of a subscription, such as a database `LISTEN` handle, even if the client
+/// disappears without stopping the subscription.
+///
+/// The cleanup is spawned with the spawner of the schema, see
+/// [`SchemaBuilder::spawner`](struct.SchemaBuilder.html#method.spawner). Without a spawner it is
+/// run to completion on a new thread, so it must not depend on the reactor of a runtime.
+///
+/// The `cleanup` attribute of the fields of [`Subscription`](attr.Subscription.html) wraps the
+/// returned stream in a `StreamGuard`.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+/// use futures::Stream;
+///
+/// struct SubscriptionRoot;
+///
+/// #[Subscription]
+/// impl SubscriptionRoot {
+///     async fn values(&self, ctx: &Context<'_>) -> impl Stream<Item = i32> {
+///         StreamGuard::new(ctx, futures::stream::iter(0..10), async {
+///             println!("released");
+///         })
+///     }
+/// }
+/// ```
+pub struct StreamGuard<S> {
+    stream: Option<Pin<Box<S>>>,
+    cleanup: Option<BoxFuture<'static, ()>>,
+    spawner: Option<Arc<SpawnFn>>,
+}
+
+impl<S: Stream> StreamGuard<S> {
+    /// Wrap `stream`, running `cleanup` when it is dropped.
+    pub fn new(
+        ctx: &Context<'_>,
+        stream: S,
+        cleanup: impl Future<Output = ()> + Send + 'static,
+    ) -> Self {
+        Self {
+            stream: Some(Box::pin(stream)),
+            cleanup: Some(Box::pin(cleanup)),
+            spawner: ctx.schema_env.spawner.clone(),
+        }
+    }
+}
+
+impl<S: Stream> Stream for StreamGuard<S> {
+    type Item = S::Item;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        match &mut self.stream {
+            Some(stream) => stream.as_mut().poll_next(cx),
+            None => Poll::Ready(None),
+        }
+    }
+}
+
+impl<S> Drop for StreamGuard<S> {
+    fn drop(&mut self) {
+        // Release the stream before its resources.
+        self.stream = None;
+        if let Some(cleanup) = self.cleanup.take() {
+            match &self.spawner {
+                Some(spawner) => spawner(cleanup),
+                None => {
+                    std::thread::spawn(move || futures::executor::block_on(cleanup));
+                }
+            }
+        }
+    }
+}
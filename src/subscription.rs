@@ -1,7 +1,11 @@
+use crate::context::QueryEnv;
 use crate::parser::types::{Selection, TypeCondition};
 use crate::{Context, ContextSelectionSet, Result, Type};
+use futures::channel::oneshot;
 use futures::{Stream, StreamExt};
 use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
 /// Represents a GraphQL subscription object
 pub trait SubscriptionType: Type {
@@ -11,6 +15,13 @@ pub trait SubscriptionType: Type {
         false
     }
 
+    /// Returns true if the type has a subscription field with this name, used to route the fields
+    /// of merged subscriptions.
+    #[doc(hidden)]
+    fn has_field(_name: &str) -> bool {
+        false
+    }
+
     #[doc(hidden)]
     fn create_field_stream<'a>(
         &'a self,
@@ -34,7 +45,7 @@ pub(crate) fn collect_subscription_streams<'a, T: SubscriptionType + Send + Sync
                     let ctx = ctx.with_field(field);
                     let mut stream = root.create_field_stream(&ctx);
                     while let Some(item) = stream.next().await {
-                        yield item;
+                        yield item.map_err(|err| ctx.set_error_path(err));
                     }
                 }
             })),
@@ -87,3 +98,97 @@ impl<T: SubscriptionType + Send + Sync> SubscriptionType for &T {
         T::create_field_stream(*self, ctx)
     }
 }
+
+/// The reason why a subscription completed, see
+/// [`Context::on_complete`](struct.Context.html#method.on_complete).
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum CompletionReason {
+    /// The streams of the subscription ended.
+    Finished,
+
+    /// A resolver of the subscription returned an error.
+    Error,
+
+    /// The client stopped the subscription.
+    ClientComplete,
+
+    /// The connection of the client was closed, or the stream returned by
+    /// [`Schema::execute_stream`](struct.Schema.html#method.execute_stream) was dropped.
+    ConnectionClosed,
+
+    /// The server is shutting down, see [`Schema::shutdown`](struct.Schema.html#method.shutdown).
+    ServerShutdown,
+}
+
+/// The reason why a subscription completed, set by the executor or the transport before the
+/// stream of the subscription is dropped.
+#[derive(Clone)]
+pub(crate) struct CompletionHandle(Arc<spin::Mutex<CompletionReason>>);
+
+impl Default for CompletionHandle {
+    fn default() -> Self {
+        Self(Arc::new(spin::Mutex::new(
+            CompletionReason::ConnectionClosed,
+        )))
+    }
+}
+
+impl CompletionHandle {
+    pub(crate) fn set(&self, reason: CompletionReason) {
+        *self.0.lock() = reason;
+    }
+
+    pub(crate) fn reason(&self) -> CompletionReason {
+        *self.0.lock()
+    }
+}
+
+/// Calls the completion callbacks of a subscription when it is dropped.
+pub(crate) struct CompletionGuard {
+    env: QueryEnv,
+    handle: CompletionHandle,
+}
+
+impl CompletionGuard {
+    pub(crate) fn new(env: QueryEnv, handle: CompletionHandle) -> Self {
+        Self { env, handle }
+    }
+}
+
+impl Drop for CompletionGuard {
+    fn drop(&mut self) {
+        let reason = self.handle.reason();
+        let callbacks = std::mem::take(&mut *self.env.on_complete.lock());
+        for callback in callbacks {
+            callback(reason);
+        }
+    }
+}
+
+/// Notifies the running subscriptions of a schema that the server is shutting down.
+#[derive(Default)]
+pub(crate) struct Shutdown {
+    is_shutdown: AtomicBool,
+    senders: spin::Mutex<Vec<oneshot::Sender<()>>>,
+}
+
+impl Shutdown {
+    /// Returns a receiver that completes when the server is shutting down.
+    pub(crate) fn subscribe(&self) -> oneshot::Receiver<()> {
+        let (sender, receiver) = oneshot::channel();
+        let mut senders = self.senders.lock();
+        if !self.is_shutdown.load(Ordering::SeqCst) {
+            senders.retain(|sender| !sender.is_canceled());
+            senders.push(sender);
+        }
+        receiver
+    }
+
+    pub(crate) fn shutdown(&self) {
+        let mut senders = self.senders.lock();
+        self.is_shutdown.store(true, Ordering::SeqCst);
+        for sender in senders.drain(..) {
+            let _ = sender.send(());
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use crate::{Data, FieldResult};
+
+/// Ties a transaction to the execution of mutations, see
+/// [`SchemaBuilder::transaction_hook`](struct.SchemaBuilder.html#method.transaction_hook).
+///
+/// `begin` is called before the fields of the mutation root are executed, and usually inserts
+/// the transaction into the data of the request, where the resolvers get it with
+/// `Context::data`. The transaction is committed if none of the fields of the mutation root
+/// returned an error, and rolled back otherwise.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+/// use std::sync::Mutex;
+///
+/// #[derive(Default)]
+/// struct Transaction(Mutex<Vec<i32>>);
+///
+/// struct Hook {
+///     database: Mutex<Vec<i32>>,
+/// }
+///
+/// #[async_trait::async_trait]
+/// impl TransactionHook for Hook {
+///     async fn begin(&self, data: &mut Data) -> FieldResult<()> {
+///         data.insert(Transaction::default());
+///         Ok(())
+///     }
+///
+///     async fn commit(&self, data: &Data) -> FieldResult<()> {
+///         let tx = data.get::<Transaction>().unwrap();
+///         let mut database = self.database.lock().unwrap();
+///         database.extend(tx.0.lock().unwrap().drain(..));
+///         Ok(())
+///     }
+///
+///     async fn rollback(&self, _data: &Data) {}
+/// }
+/// ```
+#[async_trait::async_trait]
+pub trait TransactionHook: Send + Sync + 'static {
+    /// Begin the transaction, before the mutation root is executed.
+    ///
+    /// If an error is returned, the mutation is not executed.
+    async fn begin(&self, data: &mut Data) -> FieldResult<()>;
+
+    /// Commit the transaction, after every field of the mutation root succeeded.
+    ///
+    /// If an error is returned, the response is that error. Like the errors of the resolvers,
+    /// it is an execution error, which is passed to the `error` method of the extensions.
+    async fn commit(&self, data: &Data) -> FieldResult<()>;
+
+    /// Roll back the transaction, after a field of the mutation root failed.
+    async fn rollback(&self, data: &Data);
+}
@@ -156,6 +156,9 @@ where
                             external: false,
                             requires: None,
                             provides: None,
+                            idempotent: false,
+                            compute_complexity: None,
+                            requires_feature: None,
                         },
                     );
 
@@ -173,6 +176,9 @@ where
                             external: false,
                             requires: None,
                             provides: None,
+                            idempotent: false,
+                            compute_complexity: None,
+                            requires_feature: None,
                         },
                     );
 
@@ -0,0 +1,271 @@
+use crate::connection::{query, Connection, CursorType, Edge, EmptyFields};
+use crate::{FieldResult, OutputValueType};
+use futures::{Stream, StreamExt};
+use std::collections::VecDeque;
+use std::fmt::Display;
+
+/// A data source that can be paginated with cursors.
+///
+/// Implement `execute_query` with already decoded and validated arguments, and call `query` from
+/// the resolver.
+///
+/// Adapters are provided for slices (`&[T]`), streams ([`StreamDataSource`](struct.StreamDataSource.html))
+/// and offset/limit backends such as SQL databases ([`OffsetLimitDataSource`](struct.OffsetLimitDataSource.html)).
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+/// use async_graphql::connection::*;
+///
+/// struct QueryRoot {
+///     numbers: Vec<i32>,
+/// }
+///
+/// #[Object]
+/// impl QueryRoot {
+///     async fn numbers(&self,
+///         after: Option<String>,
+///         before: Option<String>,
+///         first: Option<i32>,
+///         last: Option<i32>
+///     ) -> FieldResult<Connection<usize, &i32>> {
+///         self.numbers.as_slice().query(after, before, first, last).await
+///     }
+/// }
+///
+/// #[async_std::main]
+/// async fn main() {
+///     let schema = Schema::new(QueryRoot { numbers: (0..10).collect() }, EmptyMutation, EmptySubscription);
+///
+///     assert_eq!(schema.execute("{ numbers(first: 2) { edges { node } } }").await.into_result().unwrap().data, serde_json::json!({
+///         "numbers": {
+///             "edges": [
+///                 {"node": 0},
+///                 {"node": 1},
+///             ]
+///         },
+///     }));
+/// }
+/// ```
+#[async_trait::async_trait]
+pub trait CursorDataSource: Sync {
+    /// Cursor type.
+    type CursorType: CursorType + Send + Sync;
+
+    /// Node type.
+    type NodeType: OutputValueType + Send + Sync;
+
+    /// Additional fields of the connection.
+    type ConnectionFieldsType: Send + Sync;
+
+    /// Additional fields of the edges.
+    type EdgeFieldsType: Send + Sync;
+
+    /// Query a page of the data source.
+    ///
+    /// `first` and `last` are never specified together.
+    async fn execute_query(
+        &self,
+        after: Option<Self::CursorType>,
+        before: Option<Self::CursorType>,
+        first: Option<usize>,
+        last: Option<usize>,
+    ) -> FieldResult<
+        Connection<
+            Self::CursorType,
+            Self::NodeType,
+            Self::ConnectionFieldsType,
+            Self::EdgeFieldsType,
+        >,
+    >;
+
+    /// Parses the parameters and executes the query.
+    async fn query(
+        &self,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>,
+    ) -> FieldResult<
+        Connection<
+            Self::CursorType,
+            Self::NodeType,
+            Self::ConnectionFieldsType,
+            Self::EdgeFieldsType,
+        >,
+    >
+    where
+        <Self::CursorType as CursorType>::Error: Display + Send + Sync + 'static,
+    {
+        query(after, before, first, last, |after, before, first, last| {
+            self.execute_query(after, before, first, last)
+        })
+        .await
+    }
+}
+
+/// Calculate the range of the items of a page, from `start` (inclusive) to `end` (exclusive).
+fn page_range(
+    after: Option<usize>,
+    before: Option<usize>,
+    first: Option<usize>,
+    last: Option<usize>,
+    len: usize,
+) -> FieldResult<(usize, usize)> {
+    let mut start = start_after(after)?.min(len);
+    let mut end = before.unwrap_or(len).min(len).max(start);
+    if let Some(first) = first {
+        end = start.saturating_add(first).min(end);
+    }
+    if let Some(last) = last {
+        start = end - last.min(end - start);
+    }
+    Ok((start, end))
+}
+
+/// The position of the first item after the `after` cursor, which comes from the client and can
+/// be the largest position.
+fn start_after(after: Option<usize>) -> FieldResult<usize> {
+    match after {
+        Some(after) => after
+            .checked_add(1)
+            .ok_or_else(|| format!("Invalid cursor \"{}\"", after).into()),
+        None => Ok(0),
+    }
+}
+
+#[async_trait::async_trait]
+impl<'a, T> CursorDataSource for &'a [T]
+where
+    T: OutputValueType + Send + Sync + 'a,
+{
+    type CursorType = usize;
+    type NodeType = &'a T;
+    type ConnectionFieldsType = EmptyFields;
+    type EdgeFieldsType = EmptyFields;
+
+    async fn execute_query(
+        &self,
+        after: Option<usize>,
+        before: Option<usize>,
+        first: Option<usize>,
+        last: Option<usize>,
+    ) -> FieldResult<Connection<usize, &'a T>> {
+        let items: &'a [T] = *self;
+        let (start, end) = page_range(after, before, first, last, items.len())?;
+        let mut connection = Connection::new(start > 0, end < items.len());
+        connection.append(
+            items[start..end]
+                .iter()
+                .enumerate()
+                .map(|(idx, item)| Edge::new(start + idx, item)),
+        );
+        Ok(connection)
+    }
+}
+
+/// A backend that can fetch a range of items by offset, such as `SELECT ... OFFSET ... LIMIT ...`.
+#[async_trait::async_trait]
+pub trait OffsetLimit: Sync {
+    /// Node type.
+    type NodeType: OutputValueType + Send + Sync;
+
+    /// Returns the total number of items.
+    async fn total_count(&self) -> FieldResult<usize>;
+
+    /// Fetch at most `limit` items starting at `offset`.
+    async fn fetch(&self, offset: usize, limit: usize) -> FieldResult<Vec<Self::NodeType>>;
+}
+
+/// A cursor data source for an [`OffsetLimit`](trait.OffsetLimit.html) backend, using the offsets
+/// of the items as cursors.
+pub struct OffsetLimitDataSource<T>(pub T);
+
+#[async_trait::async_trait]
+impl<T: OffsetLimit> CursorDataSource for OffsetLimitDataSource<T> {
+    type CursorType = usize;
+    type NodeType = T::NodeType;
+    type ConnectionFieldsType = EmptyFields;
+    type EdgeFieldsType = EmptyFields;
+
+    async fn execute_query(
+        &self,
+        after: Option<usize>,
+        before: Option<usize>,
+        first: Option<usize>,
+        last: Option<usize>,
+    ) -> FieldResult<Connection<usize, T::NodeType>> {
+        let total_count = self.0.total_count().await?;
+        let (start, end) = page_range(after, before, first, last, total_count)?;
+        let mut connection = Connection::new(start > 0, end < total_count);
+        if end > start {
+            let items = self.0.fetch(start, end - start).await?;
+            connection.append(
+                items
+                    .into_iter()
+                    .enumerate()
+                    .map(|(idx, item)| Edge::new(start + idx, item)),
+            );
+        }
+        Ok(connection)
+    }
+}
+
+/// A cursor data source for streams, using the positions of the items in the stream as cursors.
+///
+/// The function is called for every query to create a new stream. Only the items up to the end of
+/// the requested page are consumed, except for `last` without `before`, which has to read the
+/// whole stream.
+pub struct StreamDataSource<F>(pub F);
+
+#[async_trait::async_trait]
+impl<F, S, T> CursorDataSource for StreamDataSource<F>
+where
+    F: Fn() -> S + Sync,
+    S: Stream<Item = FieldResult<T>> + Send + Unpin,
+    T: OutputValueType + Send + Sync,
+{
+    type CursorType = usize;
+    type NodeType = T;
+    type ConnectionFieldsType = EmptyFields;
+    type EdgeFieldsType = EmptyFields;
+
+    async fn execute_query(
+        &self,
+        after: Option<usize>,
+        before: Option<usize>,
+        first: Option<usize>,
+        last: Option<usize>,
+    ) -> FieldResult<Connection<usize, T>> {
+        let start = start_after(after)?;
+        let end = before.map(|before| before.max(start));
+        let mut stream = (self.0)().enumerate();
+        let mut items = VecDeque::new();
+        let mut has_previous_page = start > 0;
+        let mut has_next_page = false;
+
+        while let Some((idx, item)) = stream.next().await {
+            if idx < start {
+                continue;
+            }
+            if end.map(|end| idx >= end).unwrap_or_default()
+                || first.map(|first| items.len() >= first).unwrap_or_default()
+            {
+                has_next_page = true;
+                break;
+            }
+            items.push_back((idx, item?));
+            if let Some(last) = last {
+                if items.len() > last {
+                    items.pop_front();
+                    has_previous_page = true;
+                }
+            }
+        }
+
+        let mut connection = Connection::new(has_previous_page, has_next_page);
+        connection.append(items.into_iter().map(|(idx, item)| Edge::new(idx, item)));
+        Ok(connection)
+    }
+}
@@ -7,7 +7,7 @@ use indexmap::map::IndexMap;
 use std::borrow::Cow;
 
 /// The edge type output by the data source
-pub struct Edge<C, T, E> {
+pub struct Edge<C, T, E = EmptyFields> {
     pub(crate) cursor: C,
     pub(crate) node: T,
     pub(crate) additional_fields: E,
@@ -74,6 +74,9 @@ where
                             external: false,
                             requires: None,
                             provides: None,
+                            idempotent: false,
+                            compute_complexity: None,
+                            requires_feature: None,
                         },
                     );
 
@@ -89,6 +92,9 @@ where
                             external: false,
                             requires: None,
                             provides: None,
+                            idempotent: false,
+                            compute_complexity: None,
+                            requires_feature: None,
                         },
                     );
 
@@ -2,12 +2,14 @@
 
 mod connection_type;
 mod cursor;
+mod data_source;
 mod edge;
 mod page_info;
 
 use crate::{FieldResult, SimpleObject};
 pub use connection_type::Connection;
 pub use cursor::CursorType;
+pub use data_source::{CursorDataSource, OffsetLimit, OffsetLimitDataSource, StreamDataSource};
 pub use edge::Edge;
 use futures::Future;
 pub use page_info::PageInfo;
@@ -2,6 +2,7 @@ use crate::{InputValueError, InputValueResult, Scalar, ScalarType, Value};
 use bson::oid::ObjectId;
 #[cfg(feature = "chrono")]
 use bson::DateTime as UtcDateTime;
+use bson::{Bson, Document};
 #[cfg(feature = "chrono")]
 use chrono::{DateTime, Utc};
 
@@ -14,6 +15,13 @@ impl ScalarType for ObjectId {
         }
     }
 
+    fn is_valid(value: &Value) -> bool {
+        match value {
+            Value::String(s) => ObjectId::with_string(s).is_ok(),
+            _ => false,
+        }
+    }
+
     fn to_value(&self) -> Value {
         Value::String(self.to_string())
     }
@@ -30,3 +38,37 @@ impl ScalarType for UtcDateTime {
         (**self).to_value()
     }
 }
+
+/// BSON values are represented as relaxed Extended JSON.
+#[Scalar(internal, name = "Bson")]
+impl ScalarType for Bson {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        Ok(Bson::from(value.into_json()?))
+    }
+
+    fn to_value(&self) -> Value {
+        Value::from_json(self.clone().into_relaxed_extjson()).unwrap_or_else(|_| Value::Null)
+    }
+}
+
+/// BSON documents are represented as relaxed Extended JSON objects.
+#[Scalar(internal, name = "Document")]
+impl ScalarType for Document {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match <Bson as ScalarType>::parse(value)? {
+            Bson::Document(document) => Ok(document),
+            bson => Err(InputValueError::Custom(format!(
+                "Expected a BSON document, found {}",
+                bson
+            ))),
+        }
+    }
+
+    fn is_valid(value: &Value) -> bool {
+        matches!(value, Value::Object(_))
+    }
+
+    fn to_value(&self) -> Value {
+        ScalarType::to_value(&Bson::Document(self.clone()))
+    }
+}
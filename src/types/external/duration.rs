@@ -0,0 +1,225 @@
+use crate::{InputValueError, InputValueResult, Scalar, ScalarType, Value};
+use std::time::Duration;
+
+const SECONDS_PER_MINUTE: u64 = 60;
+const SECONDS_PER_HOUR: u64 = 60 * SECONDS_PER_MINUTE;
+const SECONDS_PER_DAY: u64 = 24 * SECONDS_PER_HOUR;
+const SECONDS_PER_WEEK: u64 = 7 * SECONDS_PER_DAY;
+
+/// Parse an ISO-8601 duration such as `P1DT2H30M` or `-PT0.5S`, returns the sign and the
+/// absolute value.
+fn parse_iso8601(s: &str) -> Result<(bool, Duration), String> {
+    let (negative, rest) = match s.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, s),
+    };
+    let rest = rest
+        .strip_prefix('P')
+        .ok_or_else(|| "a duration must start with \"P\"".to_string())?;
+
+    let mut secs = 0u64;
+    let mut nanos = 0u32;
+    let mut in_time = false;
+    let mut last_rank = 0;
+    let mut number = String::new();
+
+    for c in rest.chars() {
+        match c {
+            '0'..='9' => number.push(c),
+            '.' | ',' => number.push('.'),
+            'T' if !in_time && number.is_empty() => in_time = true,
+            _ => {
+                if number.is_empty() {
+                    return Err(format!("missing value before \"{}\"", c));
+                }
+                let (rank, unit_secs) = match (in_time, c) {
+                    (false, 'W') => (1, SECONDS_PER_WEEK),
+                    (false, 'D') => (2, SECONDS_PER_DAY),
+                    (true, 'H') => (3, SECONDS_PER_HOUR),
+                    (true, 'M') => (4, SECONDS_PER_MINUTE),
+                    (true, 'S') => (5, 1),
+                    (false, 'Y') | (false, 'M') => {
+                        return Err(
+                            "years and months have no fixed length and are not supported"
+                                .to_string(),
+                        )
+                    }
+                    _ => return Err(format!("unexpected \"{}\"", c)),
+                };
+                if rank <= last_rank {
+                    return Err(format!("\"{}\" is out of order", c));
+                }
+                last_rank = rank;
+
+                let integer = match number.find('.') {
+                    Some(idx) => {
+                        let fraction = &number[idx + 1..];
+                        if c != 'S' {
+                            return Err("only seconds may have a fractional part".to_string());
+                        }
+                        if fraction.is_empty() || fraction.len() > 9 || fraction.contains('.') {
+                            return Err(format!("invalid number \"{}\"", number));
+                        }
+                        nanos = format!("{:0<9}", fraction)
+                            .parse()
+                            .map_err(|_| format!("invalid number \"{}\"", number))?;
+                        &number[..idx]
+                    }
+                    None => &number,
+                };
+                let value: u64 = integer
+                    .parse()
+                    .map_err(|_| format!("invalid number \"{}\"", number))?;
+                secs = value
+                    .checked_mul(unit_secs)
+                    .and_then(|value| secs.checked_add(value))
+                    .ok_or_else(|| "the duration is too large".to_string())?;
+                number.clear();
+            }
+        }
+    }
+
+    if !number.is_empty() {
+        return Err(format!("missing unit after \"{}\"", number));
+    }
+    if last_rank == 0 || (in_time && last_rank < 3) {
+        return Err("missing duration components".to_string());
+    }
+    Ok((negative, Duration::new(secs, nanos)))
+}
+
+/// Format the duration as ISO-8601 using days, hours, minutes and (fractional) seconds.
+fn format_iso8601(negative: bool, duration: Duration) -> String {
+    let mut s = String::new();
+    if negative && duration != Duration::from_secs(0) {
+        s.push('-');
+    }
+    s.push('P');
+
+    let secs = duration.as_secs();
+    let nanos = duration.subsec_nanos();
+    let days = secs / SECONDS_PER_DAY;
+    let hours = secs % SECONDS_PER_DAY / SECONDS_PER_HOUR;
+    let minutes = secs % SECONDS_PER_HOUR / SECONDS_PER_MINUTE;
+    let seconds = secs % SECONDS_PER_MINUTE;
+
+    if days > 0 {
+        s.push_str(&format!("{}D", days));
+    }
+    if days == 0 || hours > 0 || minutes > 0 || seconds > 0 || nanos > 0 {
+        s.push('T');
+        if hours > 0 {
+            s.push_str(&format!("{}H", hours));
+        }
+        if minutes > 0 {
+            s.push_str(&format!("{}M", minutes));
+        }
+        if seconds > 0 || nanos > 0 || (days == 0 && hours == 0 && minutes == 0) {
+            s.push_str(&seconds.to_string());
+            if nanos > 0 {
+                let fraction = format!("{:09}", nanos);
+                s.push('.');
+                s.push_str(fraction.trim_end_matches('0'));
+            }
+            s.push('S');
+        }
+    }
+    s
+}
+
+fn parse_value(value: Value) -> InputValueResult<(bool, Duration)> {
+    match value {
+        Value::String(s) => parse_iso8601(&s)
+            .map_err(|err| InputValueError::Custom(format!("Invalid duration \"{}\": {}", s, err))),
+        _ => Err(InputValueError::ExpectedType(value)),
+    }
+}
+
+/// A duration in ISO-8601 format, such as `P1DT2H30M` or `PT0.5S`.
+///
+/// Weeks, days, hours, minutes and seconds are supported, a day is always 24 hours. Years and
+/// months are rejected because they have no fixed length.
+#[Scalar(internal, name = "Duration")]
+impl ScalarType for Duration {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match parse_value(value)? {
+            (false, duration) => Ok(duration),
+            (true, _) => Err(InputValueError::Custom(
+                "Negative durations are not supported".to_string(),
+            )),
+        }
+    }
+
+    fn is_valid(value: &Value) -> bool {
+        match value {
+            Value::String(s) => matches!(parse_iso8601(s), Ok((false, _))),
+            _ => false,
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(format_iso8601(false, *self))
+    }
+}
+
+/// A duration in ISO-8601 format that can be negative, such as `P1DT2H30M` or `-PT0.5S`.
+///
+/// Weeks, days, hours, minutes and seconds are supported, a day is always 24 hours. Years and
+/// months are rejected because they have no fixed length.
+#[cfg(feature = "chrono")]
+#[Scalar(internal, name = "SignedDuration")]
+impl ScalarType for chrono::Duration {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        let (negative, duration) = parse_value(value)?;
+        let duration = chrono::Duration::from_std(duration)
+            .map_err(|_| InputValueError::Custom("The duration is too large".to_string()))?;
+        Ok(if negative { -duration } else { duration })
+    }
+
+    fn is_valid(value: &Value) -> bool {
+        match value {
+            Value::String(s) => matches!(
+                parse_iso8601(s),
+                Ok((_, duration)) if chrono::Duration::from_std(duration).is_ok()
+            ),
+            _ => false,
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        let negative = *self < chrono::Duration::zero();
+        let duration = if negative { -*self } else { *self };
+        Value::String(format_iso8601(
+            negative,
+            duration.to_std().unwrap_or_default(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_iso8601_duration() {
+        for (input, expected, output) in &[
+            ("PT0S", Duration::from_secs(0), "PT0S"),
+            ("P1W", Duration::from_secs(SECONDS_PER_WEEK), "P7D"),
+            ("P1DT2H30M", Duration::from_secs(95400), "P1DT2H30M"),
+            ("PT90M", Duration::from_secs(5400), "PT1H30M"),
+            ("PT0.5S", Duration::from_millis(500), "PT0.5S"),
+            ("PT1,25S", Duration::from_millis(1250), "PT1.25S"),
+        ] {
+            assert_eq!(parse_iso8601(input), Ok((false, *expected)));
+            assert_eq!(format_iso8601(false, *expected), *output);
+        }
+
+        assert_eq!(parse_iso8601("-PT1S"), Ok((true, Duration::from_secs(1))));
+        for input in &[
+            "", "P", "PT", "1D", "P1DT", "P1Y", "P1M", "PT1", "PT1H1H", "PT1M1H", "P0.5D", "PT1.S",
+            "PTS",
+        ] {
+            assert!(parse_iso8601(input).is_err(), "{}", input);
+        }
+    }
+}
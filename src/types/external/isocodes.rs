@@ -0,0 +1,95 @@
+use crate::{InputValueError, InputValueResult, Scalar, ScalarType, Value};
+use iso_currency::Currency;
+use isocountry::CountryCode;
+use language_tags::LanguageTag;
+
+/// An ISO 3166-1 country code.
+///
+/// Both alpha-2 (`US`) and alpha-3 (`USA`) codes are accepted as input, the output is always the
+/// alpha-2 code.
+#[Scalar(
+    internal,
+    name = "CountryCode",
+    specified_by_url = "https://www.iso.org/iso-3166-country-codes.html"
+)]
+impl ScalarType for CountryCode {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match value {
+            Value::String(s) => CountryCode::for_alpha2(&s)
+                .or_else(|_| CountryCode::for_alpha3(&s))
+                .map_err(|_| {
+                    InputValueError::Custom(format!("Invalid ISO 3166 country code \"{}\"", s))
+                }),
+            _ => Err(InputValueError::ExpectedType(value)),
+        }
+    }
+
+    fn is_valid(value: &Value) -> bool {
+        match value {
+            Value::String(s) => {
+                CountryCode::for_alpha2(s).is_ok() || CountryCode::for_alpha3(s).is_ok()
+            }
+            _ => false,
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.alpha2().to_string())
+    }
+}
+
+/// An ISO 4217 currency code, such as `EUR`.
+#[Scalar(
+    internal,
+    name = "CurrencyCode",
+    specified_by_url = "https://www.iso.org/iso-4217-currency-codes.html"
+)]
+impl ScalarType for Currency {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match value {
+            Value::String(s) => Currency::from_code(&s).ok_or_else(|| {
+                InputValueError::Custom(format!("Invalid ISO 4217 currency code \"{}\"", s))
+            }),
+            _ => Err(InputValueError::ExpectedType(value)),
+        }
+    }
+
+    fn is_valid(value: &Value) -> bool {
+        match value {
+            Value::String(s) => Currency::from_code(s).is_some(),
+            _ => false,
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.code().to_string())
+    }
+}
+
+/// A BCP 47 language tag, such as `en-US`.
+#[Scalar(
+    internal,
+    name = "LanguageTag",
+    specified_by_url = "https://tools.ietf.org/html/bcp47"
+)]
+impl ScalarType for LanguageTag {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match value {
+            Value::String(s) => s.parse().map_err(|err| {
+                InputValueError::Custom(format!("Invalid BCP 47 language tag \"{}\": {}", s, err))
+            }),
+            _ => Err(InputValueError::ExpectedType(value)),
+        }
+    }
+
+    fn is_valid(value: &Value) -> bool {
+        match value {
+            Value::String(s) => s.parse::<LanguageTag>().is_ok(),
+            _ => false,
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.to_string())
+    }
+}
@@ -1,7 +1,7 @@
 use crate::parser::types::Field;
 use crate::{
-    registry, ContextSelectionSet, InputValueResult, InputValueType, OutputValueType, Positioned,
-    Result, Type, Value,
+    registry, ContextSelectionSet, InputValueResult, InputValueType, OutputValueType, ParseContext,
+    Positioned, Result, Type, Value,
 };
 use std::borrow::Cow;
 use std::collections::LinkedList;
@@ -26,8 +26,10 @@ impl<T: InputValueType> InputValueType for LinkedList<T> {
         match value.unwrap_or_default() {
             Value::List(values) => {
                 let mut result = Self::default();
-                for elem_value in values {
-                    result.extend(std::iter::once(InputValueType::parse(Some(elem_value))?));
+                for (idx, elem_value) in values.into_iter().enumerate() {
+                    result.extend(std::iter::once(
+                        T::parse(Some(elem_value)).map_err(|err| err.with_index::<T>(idx))?,
+                    ));
                 }
                 Ok(result)
             }
@@ -39,6 +41,29 @@ impl<T: InputValueType> InputValueType for LinkedList<T> {
         }
     }
 
+    fn parse_with_context(value: Option<Value>, ctx: &ParseContext<'_>) -> InputValueResult<Self> {
+        match value.unwrap_or_default() {
+            Value::List(values) => {
+                let mut result = Self::default();
+                for (idx, elem_value) in values.into_iter().enumerate() {
+                    result.extend(std::iter::once(
+                        T::parse_with_context(Some(elem_value), &ctx.list_item(idx))
+                            .map_err(|err| err.with_index::<T>(idx))?,
+                    ));
+                }
+                Ok(result)
+            }
+            value => Ok({
+                let mut result = Self::default();
+                result.extend(std::iter::once(InputValueType::parse_with_context(
+                    Some(value),
+                    ctx,
+                )?));
+                result
+            }),
+        }
+    }
+
     fn to_value(&self) -> Value {
         Value::List(self.iter().map(InputValueType::to_value).collect())
     }
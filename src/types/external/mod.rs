@@ -6,6 +6,7 @@ mod floats;
 mod integers;
 mod json_object;
 mod list;
+mod net;
 mod non_zero_integers;
 mod optional;
 mod string;
@@ -17,7 +18,13 @@ mod bson;
 mod chrono_tz;
 #[cfg(feature = "chrono")]
 mod datetime;
+#[cfg(feature = "duration")]
+mod duration;
+#[cfg(feature = "isocodes")]
+mod isocodes;
 #[cfg(feature = "chrono")]
 mod naive_time;
+#[cfg(feature = "ulid")]
+mod ulid;
 #[cfg(feature = "url")]
 mod url;
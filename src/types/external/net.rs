@@ -0,0 +1,93 @@
+use crate::{InputValueError, InputValueResult, Scalar, ScalarType, Value};
+use std::fmt::Display;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::str::FromStr;
+
+fn parse_str<T>(name: &str, value: Value) -> InputValueResult<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    match value {
+        Value::String(s) => s
+            .parse()
+            .map_err(|err| InputValueError::Custom(format!("Invalid {} \"{}\": {}", name, s, err))),
+        _ => Err(InputValueError::ExpectedType(value)),
+    }
+}
+
+/// An IPv4 (`127.0.0.1`) or IPv6 (`::1`) address.
+#[Scalar(internal, name = "IpAddr")]
+impl ScalarType for IpAddr {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        parse_str("IpAddr", value)
+    }
+
+    fn is_valid(value: &Value) -> bool {
+        match value {
+            Value::String(s) => s.parse::<IpAddr>().is_ok(),
+            _ => false,
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.to_string())
+    }
+}
+
+/// An IPv4 address, such as `127.0.0.1`.
+#[Scalar(internal, name = "Ipv4Addr")]
+impl ScalarType for Ipv4Addr {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        parse_str("Ipv4Addr", value)
+    }
+
+    fn is_valid(value: &Value) -> bool {
+        match value {
+            Value::String(s) => s.parse::<Ipv4Addr>().is_ok(),
+            _ => false,
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.to_string())
+    }
+}
+
+/// An IPv6 address, such as `::1`.
+#[Scalar(internal, name = "Ipv6Addr")]
+impl ScalarType for Ipv6Addr {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        parse_str("Ipv6Addr", value)
+    }
+
+    fn is_valid(value: &Value) -> bool {
+        match value {
+            Value::String(s) => s.parse::<Ipv6Addr>().is_ok(),
+            _ => false,
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.to_string())
+    }
+}
+
+/// An IP address and a port, such as `127.0.0.1:8080` or `[::1]:8080`.
+#[Scalar(internal, name = "SocketAddr")]
+impl ScalarType for SocketAddr {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        parse_str("SocketAddr", value)
+    }
+
+    fn is_valid(value: &Value) -> bool {
+        match value {
+            Value::String(s) => s.parse::<SocketAddr>().is_ok(),
+            _ => false,
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.to_string())
+    }
+}
@@ -1,7 +1,7 @@
 use crate::parser::types::Field;
 use crate::{
-    registry, ContextSelectionSet, InputValueResult, InputValueType, OutputValueType, Positioned,
-    Result, Type, Value,
+    registry, ContextSelectionSet, InputValueResult, InputValueType, OutputValueType, ParseContext,
+    Positioned, Result, Type, Value,
 };
 use std::borrow::Cow;
 
@@ -28,6 +28,13 @@ impl<T: InputValueType> InputValueType for Option<T> {
         }
     }
 
+    fn parse_with_context(value: Option<Value>, ctx: &ParseContext<'_>) -> InputValueResult<Self> {
+        match value.unwrap_or_default() {
+            Value::Null => Ok(None),
+            value => Ok(Some(T::parse_with_context(Some(value), ctx)?)),
+        }
+    }
+
     fn to_value(&self) -> Value {
         match self {
             Some(value) => value.to_value(),
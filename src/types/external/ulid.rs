@@ -0,0 +1,23 @@
+use crate::{InputValueError, InputValueResult, Scalar, ScalarType, Value};
+use ulid::Ulid;
+
+#[Scalar(internal, name = "ULID")]
+impl ScalarType for Ulid {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match value {
+            Value::String(s) => Ok(Ulid::from_string(&s)?),
+            _ => Err(InputValueError::ExpectedType(value)),
+        }
+    }
+
+    fn is_valid(value: &Value) -> bool {
+        match value {
+            Value::String(s) => Ulid::from_string(s).is_ok(),
+            _ => false,
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.to_string())
+    }
+}
@@ -10,6 +10,13 @@ impl ScalarType for Url {
         }
     }
 
+    fn is_valid(value: &Value) -> bool {
+        match value {
+            Value::String(s) => Url::parse(s).is_ok(),
+            _ => false,
+        }
+    }
+
     fn to_value(&self) -> Value {
         Value::String(self.to_string())
     }
@@ -1,6 +1,9 @@
 use crate::{InputValueError, InputValueResult, Scalar, ScalarType, Value};
 use uuid::Uuid;
 
+/// Both the hyphenated (`67e55044-10b1-426f-9247-bb680e5fe0c8`) and the simple
+/// (`67e5504410b1426f9247bb680e5fe0c8`) forms are accepted as input, the output is always
+/// hyphenated.
 #[Scalar(internal, name = "UUID")]
 impl ScalarType for Uuid {
     fn parse(value: Value) -> InputValueResult<Self> {
@@ -10,6 +13,13 @@ impl ScalarType for Uuid {
         }
     }
 
+    fn is_valid(value: &Value) -> bool {
+        match value {
+            Value::String(s) => Uuid::parse_str(s).is_ok(),
+            _ => false,
+        }
+    }
+
     fn to_value(&self) -> Value {
         Value::String(self.to_string())
     }
@@ -8,7 +8,7 @@ use std::ops::{Deref, DerefMut};
 
 /// ID scalar
 ///
-/// The input is a `&str`, `String`, `usize` or `uuid::UUID`, and the output is a string.
+/// The input is a `&str`, `String`, `usize`, `uuid::Uuid` or `ulid::Ulid`, and the output is a string.
 #[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
 #[serde(transparent)]
 pub struct ID(pub String);
@@ -63,6 +63,15 @@ impl TryFrom<ID> for uuid::Uuid {
     }
 }
 
+#[cfg(feature = "ulid")]
+impl TryFrom<ID> for ulid::Ulid {
+    type Error = ulid::DecodeError;
+
+    fn try_from(id: ID) -> std::result::Result<Self, Self::Error> {
+        ulid::Ulid::from_string(&id.0)
+    }
+}
+
 #[cfg(feature = "bson")]
 impl TryFrom<ID> for ObjectId {
     type Error = oid::Error;
@@ -1,4 +1,4 @@
-use crate::{registry, InputValueResult, InputValueType, Type, Value};
+use crate::{registry, InputValueResult, InputValueType, ParseContext, Type, Value};
 use std::borrow::Cow;
 
 /// Similar to `Option`, but it has three states, `undefined`, `null` and `x`.
@@ -125,6 +125,17 @@ impl<T: InputValueType> InputValueType for MaybeUndefined<T> {
         }
     }
 
+    fn parse_with_context(value: Option<Value>, ctx: &ParseContext<'_>) -> InputValueResult<Self> {
+        match value {
+            None => Ok(MaybeUndefined::Undefined),
+            Some(Value::Null) => Ok(MaybeUndefined::Null),
+            Some(value) => Ok(MaybeUndefined::Value(T::parse_with_context(
+                Some(value),
+                ctx,
+            )?)),
+        }
+    }
+
     fn to_value(&self) -> Value {
         match self {
             MaybeUndefined::Value(value) => value.to_value(),
@@ -5,7 +5,7 @@ use crate::{
     CacheControl, Context, ContextSelectionSet, Error, OutputValueType, Positioned, QueryError,
     Result, SimpleObject, Subscription, SubscriptionType, Type,
 };
-use futures::{future::Either, stream, Stream, StreamExt};
+use futures::Stream;
 use indexmap::IndexMap;
 use std::borrow::Cow;
 use std::pin::Pin;
@@ -51,7 +51,18 @@ impl<A: Type, B: Type> Type for MergedObject<A, B> {
                 ..
             }) = registry.types.remove(&*B::type_name())
             {
-                fields.extend(b_fields);
+                for (name, field) in b_fields {
+                    // The field of the first object is kept, like its resolver.
+                    if fields.contains_key(&name) {
+                        registry.errors.push(format!(
+                            "Field \"{}\" of \"{}\" is also defined by another merged object.",
+                            name,
+                            A::type_name()
+                        ));
+                        continue;
+                    }
+                    fields.insert(name, field);
+                }
                 cc = cc.merge(&b_cc);
             }
 
@@ -105,19 +116,19 @@ where
     A: SubscriptionType + Send + Sync,
     B: SubscriptionType + Send + Sync,
 {
+    fn has_field(name: &str) -> bool {
+        A::has_field(name) || B::has_field(name)
+    }
+
     fn create_field_stream<'a>(
         &'a self,
         ctx: &'a Context<'a>,
     ) -> Pin<Box<dyn Stream<Item = Result<serde_json::Value>> + Send + 'a>> {
-        let left_stream = self.0.create_field_stream(ctx);
-        let mut right_stream = Some(self.1.create_field_stream(ctx));
-        Box::pin(left_stream.flat_map(move |res| match res {
-            Err(Error::Query {
-                err: QueryError::FieldNotFound { .. },
-                ..
-            }) if right_stream.is_some() => Either::Right(right_stream.take().unwrap()),
-            other => Either::Left(stream::once(async { other })),
-        }))
+        if A::has_field(&ctx.item.node.name.node) {
+            self.0.create_field_stream(ctx)
+        } else {
+            self.1.create_field_stream(ctx)
+        }
     }
 }
 
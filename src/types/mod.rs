@@ -11,6 +11,7 @@ mod maybe_undefined;
 mod merged_object;
 mod query_root;
 mod upload;
+mod with_data;
 
 mod external;
 
@@ -22,5 +23,6 @@ pub use json::{Json, OutputJson};
 pub use maybe_undefined::MaybeUndefined;
 pub use merged_object::{MergedObject, MergedObjectSubscriptionTail, MergedObjectTail};
 pub use upload::Upload;
+pub use with_data::WithData;
 
 pub(crate) use query_root::QueryRoot;
@@ -1,5 +1,6 @@
 use crate::model::{__Schema, __Type};
 use crate::parser::types::Field;
+use crate::request::IntrospectionOverride;
 use crate::resolver_utils::{resolve_object, ObjectType};
 use crate::{
     registry, Any, Context, ContextSelectionSet, Error, OutputValueType, Positioned, QueryError,
@@ -19,6 +20,7 @@ struct Service {
 pub(crate) struct QueryRoot<T> {
     pub(crate) inner: T,
     pub(crate) disable_introspection: bool,
+    pub(crate) federation: bool,
 }
 
 impl<T: Type> Type for QueryRoot<T> {
@@ -44,6 +46,9 @@ impl<T: Type> Type for QueryRoot<T> {
                     external: false,
                     requires: None,
                     provides: None,
+                    idempotent: false,
+                    compute_complexity: None,
+                    requires_feature: None,
                 },
             );
 
@@ -72,6 +77,9 @@ impl<T: Type> Type for QueryRoot<T> {
                     external: false,
                     requires: None,
                     provides: None,
+                    idempotent: false,
+                    compute_complexity: None,
+                    requires_feature: None,
                 },
             );
         }
@@ -79,24 +87,38 @@ impl<T: Type> Type for QueryRoot<T> {
     }
 }
 
+impl<T: Type> QueryRoot<T> {
+    /// Returns an error for the introspection field of `ctx` if introspection is disabled for
+    /// the request.
+    fn check_introspection(&self, ctx: &Context<'_>) -> Result<()> {
+        let introspection = ctx
+            .query_env
+            .ctx_data
+            .get::<IntrospectionOverride>()
+            .map(|introspection| introspection.0)
+            .unwrap_or(!self.disable_introspection);
+        if introspection {
+            return Ok(());
+        }
+        Err(Error::Query {
+            pos: ctx.item.pos,
+            path: ctx
+                .path_node
+                .as_ref()
+                .and_then(|path| serde_json::to_value(path).ok()),
+            err: QueryError::FieldNotFound {
+                field_name: ctx.item.node.name.to_string(),
+                object: Self::type_name().to_string(),
+            },
+        })
+    }
+}
+
 #[async_trait::async_trait]
 impl<T: ObjectType + Send + Sync> ObjectType for QueryRoot<T> {
     async fn resolve_field(&self, ctx: &Context<'_>) -> Result<serde_json::Value> {
         if ctx.item.node.name.node == "__schema" {
-            if self.disable_introspection {
-                return Err(Error::Query {
-                    pos: ctx.item.pos,
-                    path: ctx
-                        .path_node
-                        .as_ref()
-                        .and_then(|path| serde_json::to_value(path).ok()),
-                    err: QueryError::FieldNotFound {
-                        field_name: ctx.item.node.name.to_string(),
-                        object: Self::type_name().to_string(),
-                    },
-                });
-            }
-
+            self.check_introspection(ctx)?;
             let ctx_obj = ctx.with_selection_set(&ctx.item.node.selection_set);
             return OutputValueType::resolve(
                 &__Schema {
@@ -107,6 +129,7 @@ impl<T: ObjectType + Send + Sync> ObjectType for QueryRoot<T> {
             )
             .await;
         } else if ctx.item.node.name.node == "__type" {
+            self.check_introspection(ctx)?;
             let type_name: String = ctx.param_value("name", None)?;
             let ctx_obj = ctx.with_selection_set(&ctx.item.node.selection_set);
             return OutputValueType::resolve(
@@ -119,14 +142,14 @@ impl<T: ObjectType + Send + Sync> ObjectType for QueryRoot<T> {
                 ctx.item,
             )
             .await;
-        } else if ctx.item.node.name.node == "_entities" {
+        } else if self.federation && ctx.item.node.name.node == "_entities" {
             let representations: Vec<Any> = ctx.param_value("representations", None)?;
             let mut res = Vec::new();
             for item in representations {
                 res.push(self.inner.find_entity(ctx, &item.0).await?);
             }
             return Ok(res.into());
-        } else if ctx.item.node.name.node == "_service" {
+        } else if self.federation && ctx.item.node.name.node == "_service" {
             let ctx_obj = ctx.with_selection_set(&ctx.item.node.selection_set);
             return OutputValueType::resolve(
                 &Service {
@@ -85,6 +85,7 @@ impl Type for Upload {
             name: Self::type_name().to_string(),
             description: None,
             is_valid: |value| matches!(value, Value::Upload(_)),
+            specified_by_url: None,
         })
     }
 }
@@ -0,0 +1,112 @@
+use crate::context::ScopedData;
+use crate::parser::types::Field;
+use crate::registry::Registry;
+use crate::{ContextSelectionSet, Data, OutputValueType, Positioned, Result, Type};
+use std::any::Any;
+use std::borrow::Cow;
+
+/// A value whose fields are resolved with additional data, which overrides the data of the same
+/// types of the request and the schema for the whole sub-tree of the value.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+///
+/// struct Locale(&'static str);
+///
+/// struct Article;
+///
+/// #[Object]
+/// impl Article {
+///     async fn title(&self, ctx: &Context<'_>) -> &str {
+///         match ctx.data_unchecked::<Locale>().0 {
+///             "fr" => "Bonjour",
+///             _ => "Hello",
+///         }
+///     }
+/// }
+///
+/// struct QueryRoot;
+///
+/// #[Object]
+/// impl QueryRoot {
+///     async fn article(&self) -> Article {
+///         Article
+///     }
+///
+///     async fn french_article(&self) -> WithData<Article> {
+///         WithData::new(Article).data(Locale("fr"))
+///     }
+/// }
+///
+/// async_std::task::block_on(async move {
+///     let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+///         .data(Locale("en"))
+///         .finish();
+///     let res = schema
+///         .execute("{ article { title } frenchArticle { title } }")
+///         .await
+///         .into_result()
+///         .unwrap()
+///         .data;
+///     assert_eq!(res, serde_json::json!({
+///         "article": { "title": "Hello" },
+///         "frenchArticle": { "title": "Bonjour" },
+///     }));
+/// });
+/// ```
+pub struct WithData<T> {
+    value: T,
+    data: Data,
+}
+
+impl<T> WithData<T> {
+    /// Create a value without additional data.
+    pub fn new(value: T) -> Self {
+        Self {
+            value,
+            data: Data::default(),
+        }
+    }
+
+    /// Add data for the sub-tree of the value.
+    pub fn data<D: Any + Send + Sync>(mut self, data: D) -> Self {
+        self.data.insert(data);
+        self
+    }
+
+    /// Returns the value.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T: Type> Type for WithData<T> {
+    fn type_name() -> Cow<'static, str> {
+        T::type_name()
+    }
+
+    fn qualified_type_name() -> String {
+        T::qualified_type_name()
+    }
+
+    fn create_type_info(registry: &mut Registry) -> String {
+        T::create_type_info(registry)
+    }
+}
+
+#[async_trait::async_trait]
+impl<T: OutputValueType + Send + Sync> OutputValueType for WithData<T> {
+    async fn resolve(
+        &self,
+        ctx: &ContextSelectionSet<'_>,
+        field: &Positioned<Field>,
+    ) -> Result<serde_json::Value> {
+        let scoped_data = ScopedData {
+            data: &self.data,
+            parent: ctx.scoped_data,
+        };
+        OutputValueType::resolve(&self.value, &ctx.with_scoped_data(&scoped_data), field).await
+    }
+}
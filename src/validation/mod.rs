@@ -10,13 +10,24 @@ mod visitors;
 
 use crate::parser::types::ExecutableDocument;
 use crate::registry::Registry;
-use crate::{CacheControl, Error, Result, Variables};
+use crate::{CacheControl, Error, Pos, Result, Variables};
 use visitor::{visit, VisitorContext, VisitorNil};
 
 pub struct CheckResult {
     pub cache_control: CacheControl,
+}
+
+/// The measures of an operation that are checked against the limits of a schema.
+pub struct LimitResult {
     pub complexity: usize,
     pub depth: usize,
+    /// The position of the deepest field and the response keys of the fields that lead to it.
+    pub deepest_field: Option<(Pos, Vec<String>)>,
+    pub fragment_depth: usize,
+    /// The deepest nesting of the `__schema` and `__type` fields of an operation.
+    pub introspection_depth: usize,
+    /// The most fields selected by the `__schema` and `__type` fields of an operation.
+    pub introspection_size: usize,
 }
 
 /// Validation mode
@@ -37,8 +48,6 @@ pub fn check_rules(
 ) -> Result<CheckResult> {
     let mut ctx = VisitorContext::new(registry, doc, variables);
     let mut cache_control = CacheControl::default();
-    let mut complexity = 0;
-    let mut depth = 0;
 
     match mode {
         ValidationMode::Strict => {
@@ -65,15 +74,12 @@ pub fn check_rules(
                 .with(rules::PossibleFragmentSpreads::default())
                 .with(rules::ProvidedNonNullArguments)
                 .with(rules::KnownDirectives::default())
+                .with(rules::UniqueDirectivesPerLocation)
                 .with(rules::OverlappingFieldsCanBeMerged)
                 .with(rules::UploadFile)
                 .with(visitors::CacheControlCalculate {
                     cache_control: &mut cache_control,
-                })
-                .with(visitors::ComplexityCalculate {
-                    complexity: &mut complexity,
-                })
-                .with(visitors::DepthCalculate::new(&mut depth));
+                });
             visit(&mut visitor, &mut ctx, doc);
         }
         ValidationMode::Fast => {
@@ -82,11 +88,7 @@ pub fn check_rules(
                 .with(rules::UploadFile)
                 .with(visitors::CacheControlCalculate {
                     cache_control: &mut cache_control,
-                })
-                .with(visitors::ComplexityCalculate {
-                    complexity: &mut complexity,
-                })
-                .with(visitors::DepthCalculate::new(&mut depth));
+                });
             visit(&mut visitor, &mut ctx, doc);
         }
     }
@@ -94,9 +96,73 @@ pub fn check_rules(
     if !ctx.errors.is_empty() {
         return Err(Error::Rule { errors: ctx.errors });
     }
-    Ok(CheckResult {
-        cache_control,
+    Ok(CheckResult { cache_control })
+}
+
+/// Measure the complexity, depth and introspection of a validated document, with the variables
+/// that it is executed with.
+///
+/// The complexity of a field can depend on its arguments, so this runs for every request, even
+/// if the document was validated before. A complexity that cannot be computed is an error.
+pub fn check_limits(
+    registry: &Registry,
+    doc: &ExecutableDocument,
+    variables: &Variables,
+) -> Result<LimitResult> {
+    let mut ctx = VisitorContext::new(registry, doc, Some(variables));
+    let mut complexity = 0;
+    let mut depth = 0;
+    let mut deepest_field = None;
+    let mut fragment_depth = 0;
+    let mut introspection_depth = 0;
+    let mut introspection_size = 0;
+
+    let mut visitor = VisitorNil
+        .with(visitors::ComplexityCalculate {
+            complexity: &mut complexity,
+        })
+        .with(visitors::DepthCalculate::new(
+            &mut depth,
+            &mut deepest_field,
+        ))
+        .with(visitors::FragmentDepthCalculate::new(&mut fragment_depth))
+        .with(visitors::IntrospectionCalculate::new(
+            &mut introspection_depth,
+            &mut introspection_size,
+        ));
+    visit(&mut visitor, &mut ctx, doc);
+
+    if !ctx.errors.is_empty() {
+        return Err(Error::Rule { errors: ctx.errors });
+    }
+    Ok(LimitResult {
         complexity,
-        depth: depth as usize,
+        depth,
+        deepest_field,
+        fragment_depth,
+        introspection_depth,
+        introspection_size,
     })
 }
+
+/// Check the arguments of a document that has already been validated against `variables`, the
+/// only rule that depends on the variables of a request.
+pub fn check_variables(
+    registry: &Registry,
+    doc: &ExecutableDocument,
+    variables: &Variables,
+    mode: ValidationMode,
+) -> Result<()> {
+    if let ValidationMode::Fast = mode {
+        return Ok(());
+    }
+
+    let mut ctx = VisitorContext::new(registry, doc, Some(variables));
+    let mut visitor = VisitorNil.with(rules::ArgumentsOfCorrectType::default());
+    visit(&mut visitor, &mut ctx, doc);
+
+    if !ctx.errors.is_empty() {
+        return Err(Error::Rule { errors: ctx.errors });
+    }
+    Ok(())
+}
@@ -1,5 +1,5 @@
 use crate::parser::types::Field;
-use crate::validation::suggestion::make_suggestion;
+use crate::validation::suggestion::{format_suggestion, suggestion_list};
 use crate::validation::visitor::{Visitor, VisitorContext};
 use crate::{registry, Positioned};
 
@@ -27,24 +27,24 @@ impl<'a> Visitor<'a> for FieldsOnCorrectType {
                     .iter()
                     .any(|directive| directive.node.name.node == "ifdef")
             {
-                ctx.report_error(
+                let suggestions = suggestion_list(
+                    parent_type
+                        .fields()
+                        .iter()
+                        .map(|fields| fields.keys())
+                        .flatten()
+                        .map(|s| s.as_str()),
+                    &field.node.name.node,
+                );
+                ctx.report_error_with_suggestions(
                     vec![field.pos],
                     format!(
                         "Unknown field \"{}\" on type \"{}\".{}",
                         field.node.name,
                         parent_type.name(),
-                        make_suggestion(
-                            " Did you mean",
-                            parent_type
-                                .fields()
-                                .iter()
-                                .map(|fields| fields.keys())
-                                .flatten()
-                                .map(|s| s.as_str()),
-                            &field.node.name.node,
-                        )
-                        .unwrap_or_default()
+                        format_suggestion(" Did you mean", &suggestions).unwrap_or_default()
                     ),
+                    suggestions.into_iter().map(ToString::to_string).collect(),
                 );
             }
         }
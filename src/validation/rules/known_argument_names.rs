@@ -1,6 +1,6 @@
 use crate::parser::types::{Directive, Field, Name, Value};
 use crate::registry::MetaInputValue;
-use crate::validation::suggestion::make_suggestion;
+use crate::validation::suggestion::{format_suggestion, suggestion_list};
 use crate::validation::visitor::{Visitor, VisitorContext};
 use crate::Positioned;
 use indexmap::map::IndexMap;
@@ -19,16 +19,14 @@ pub struct KnownArgumentNames<'a> {
 }
 
 impl<'a> KnownArgumentNames<'a> {
-    fn get_suggestion(&self, name: &str) -> String {
-        make_suggestion(
-            " Did you mean",
+    fn get_suggestions(&self, name: &str) -> Vec<&'static str> {
+        suggestion_list(
             self.current_args
                 .iter()
                 .map(|(args, _)| args.iter().map(|arg| *arg.0))
                 .flatten(),
             name,
         )
-        .unwrap_or_default()
     }
 }
 
@@ -61,31 +59,32 @@ impl<'a> Visitor<'a> for KnownArgumentNames<'a> {
     ) {
         if let Some((args, arg_type)) = &self.current_args {
             if !args.contains_key(name.node.as_str()) {
+                let suggestions = self.get_suggestions(name.node.as_str());
+                let suggestion =
+                    format_suggestion(" Did you mean", &suggestions).unwrap_or_default();
+                let suggestions = suggestions.into_iter().map(ToString::to_string).collect();
                 match arg_type {
                     ArgsType::Field {
                         field_name,
                         type_name,
                     } => {
-                        ctx.report_error(
+                        ctx.report_error_with_suggestions(
                             vec![name.pos],
                             format!(
                                 "Unknown argument \"{}\" on field \"{}\" of type \"{}\".{}",
-                                name,
-                                field_name,
-                                type_name,
-                                self.get_suggestion(name.node.as_str())
+                                name, field_name, type_name, suggestion
                             ),
+                            suggestions,
                         );
                     }
                     ArgsType::Directive(directive_name) => {
-                        ctx.report_error(
+                        ctx.report_error_with_suggestions(
                             vec![name.pos],
                             format!(
                                 "Unknown argument \"{}\" on directive \"{}\".{}",
-                                name,
-                                directive_name,
-                                self.get_suggestion(name.node.as_str())
+                                name, directive_name, suggestion
                             ),
+                            suggestions,
                         );
                     }
                 }
@@ -1,10 +1,12 @@
 use crate::model::__DirectiveLocation;
 use crate::parser::types::{
     Directive, Field, FragmentDefinition, FragmentSpread, InlineFragment, OperationDefinition,
-    OperationType,
+    OperationType, VariableDefinition,
 };
+use crate::validation::suggestion::{format_suggestion, suggestion_list};
 use crate::validation::visitor::{Visitor, VisitorContext};
 use crate::Positioned;
+use itertools::Itertools;
 
 #[derive(Default)]
 pub struct KnownDirectives {
@@ -50,6 +52,23 @@ impl<'a> Visitor<'a> for KnownDirectives {
         self.location_stack.pop();
     }
 
+    fn enter_variable_definition(
+        &mut self,
+        _ctx: &mut VisitorContext<'a>,
+        _variable_definition: &'a Positioned<VariableDefinition>,
+    ) {
+        self.location_stack
+            .push(__DirectiveLocation::VARIABLE_DEFINITION);
+    }
+
+    fn exit_variable_definition(
+        &mut self,
+        _ctx: &mut VisitorContext<'a>,
+        _variable_definition: &'a Positioned<VariableDefinition>,
+    ) {
+        self.location_stack.pop();
+    }
+
     fn enter_directive(
         &mut self,
         ctx: &mut VisitorContext<'a>,
@@ -72,9 +91,18 @@ impl<'a> Visitor<'a> for KnownDirectives {
                 }
             }
         } else {
-            ctx.report_error(
+            let suggestions = suggestion_list(
+                ctx.registry.directives.keys().map(String::as_str).sorted(),
+                &directive.node.name.node,
+            );
+            ctx.report_error_with_suggestions(
                 vec![directive.pos],
-                format!("Unknown directive \"{}\"", directive.node.name.node),
+                format!(
+                    "Unknown directive \"{}\".{}",
+                    directive.node.name.node,
+                    format_suggestion(" Did you mean", &suggestions).unwrap_or_default()
+                ),
+                suggestions.into_iter().map(ToString::to_string).collect(),
             );
         }
     }
@@ -230,4 +258,16 @@ mod tests {
         "#,
         );
     }
+
+    #[test]
+    fn with_misplaced_variable_directives() {
+        expect_fails_rule!(
+            factory,
+            r#"
+          query Foo($var: Boolean @include(if: true)) {
+            name
+          }
+        "#,
+        );
+    }
 }
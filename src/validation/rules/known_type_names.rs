@@ -1,7 +1,9 @@
 use crate::parser::types::{FragmentDefinition, InlineFragment, TypeCondition, VariableDefinition};
 use crate::registry::MetaTypeName;
+use crate::validation::suggestion::{format_suggestion, suggestion_list};
 use crate::validation::visitor::{Visitor, VisitorContext};
 use crate::{Pos, Positioned};
+use itertools::Itertools;
 
 #[derive(Default)]
 pub struct KnownTypeNames;
@@ -46,7 +48,19 @@ impl<'a> Visitor<'a> for KnownTypeNames {
 
 fn validate_type(ctx: &mut VisitorContext<'_>, type_name: &str, pos: Pos) {
     if ctx.registry.types.get(type_name).is_none() {
-        ctx.report_error(vec![pos], format!(r#"Unknown type "{}""#, type_name));
+        let suggestions = suggestion_list(
+            ctx.registry.types.keys().map(String::as_str).sorted(),
+            type_name,
+        );
+        ctx.report_error_with_suggestions(
+            vec![pos],
+            format!(
+                r#"Unknown type "{}".{}"#,
+                type_name,
+                format_suggestion(" Did you mean", &suggestions).unwrap_or_default()
+            ),
+            suggestions.into_iter().map(ToString::to_string).collect(),
+        );
     }
 }
 
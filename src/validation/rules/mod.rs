@@ -16,6 +16,7 @@ mod possible_fragment_spreads;
 mod provided_non_null_arguments;
 mod scalar_leafs;
 mod unique_argument_names;
+mod unique_directives_per_location;
 mod unique_fragment_names;
 mod unique_operation_names;
 mod unique_variable_names;
@@ -41,6 +42,7 @@ pub use possible_fragment_spreads::PossibleFragmentSpreads;
 pub use provided_non_null_arguments::ProvidedNonNullArguments;
 pub use scalar_leafs::ScalarLeafs;
 pub use unique_argument_names::UniqueArgumentNames;
+pub use unique_directives_per_location::UniqueDirectivesPerLocation;
 pub use unique_fragment_names::UniqueFragmentNames;
 pub use unique_operation_names::UniqueOperationNames;
 pub use unique_variable_names::UniqueVariableNames;
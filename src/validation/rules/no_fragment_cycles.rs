@@ -2,6 +2,7 @@ use crate::error::RuleError;
 use crate::parser::types::{ExecutableDocument, FragmentDefinition, FragmentSpread};
 use crate::validation::visitor::{Visitor, VisitorContext};
 use crate::{Pos, Positioned};
+use itertools::Itertools;
 use std::collections::{HashMap, HashSet};
 
 struct CycleDetector<'a> {
@@ -12,37 +13,62 @@ struct CycleDetector<'a> {
 }
 
 impl<'a> CycleDetector<'a> {
-    fn detect_from(&mut self, from: &'a str, path: &mut Vec<(&'a str, Pos)>) {
+    fn detect_from(&mut self, from: &'a str) {
+        // The fragments are walked with an explicit stack, so that a long chain of spreads can't
+        // overflow the call stack.
+        let mut path: Vec<(&'a str, Pos)> = Vec::new();
+        let mut stack = vec![(from, self.spreads_of(from))];
         self.visited.insert(from);
-
-        if !self.spreads.contains_key(from) {
-            return;
-        }
-
-        self.path_indices.insert(from, path.len());
-
-        for (name, pos) in &self.spreads[from] {
-            let index = self.path_indices.get(name).cloned();
-
-            if let Some(index) = index {
-                let err_pos = if index < path.len() {
-                    path[index].1
-                } else {
-                    *pos
-                };
-
-                self.errors.push(RuleError {
-                    locations: vec![err_pos],
-                    message: format!("Cannot spread fragment \"{}\"", name),
-                });
-            } else if !self.visited.contains(name) {
-                path.push((name, *pos));
-                self.detect_from(name, path);
-                path.pop();
+        self.path_indices.insert(from, 0);
+
+        while let Some((name, spreads)) = stack.last_mut() {
+            match spreads.next() {
+                Some(&(spread_name, pos)) => {
+                    if let Some(&index) = self.path_indices.get(spread_name) {
+                        let (err_pos, message) = if index < path.len() {
+                            (
+                                path[index].1,
+                                format!(
+                                    "Cannot spread fragment \"{}\" within itself via {}",
+                                    spread_name,
+                                    path[index..]
+                                        .iter()
+                                        .map(|(name, _)| format!("\"{}\"", name))
+                                        .join(", ")
+                                ),
+                            )
+                        } else {
+                            (
+                                pos,
+                                format!("Cannot spread fragment \"{}\" within itself", spread_name),
+                            )
+                        };
+                        self.errors.push(RuleError {
+                            locations: vec![err_pos],
+                            message,
+                            suggestions: Vec::new(),
+                        });
+                    } else if self.visited.insert(spread_name) {
+                        path.push((spread_name, pos));
+                        self.path_indices.insert(spread_name, path.len());
+                        stack.push((spread_name, self.spreads_of(spread_name)));
+                    }
+                }
+                None => {
+                    self.path_indices.remove(*name);
+                    stack.pop();
+                    path.pop();
+                }
             }
         }
+    }
 
-        self.path_indices.remove(from);
+    fn spreads_of(&self, name: &str) -> std::slice::Iter<'a, (&'a str, Pos)> {
+        self.spreads
+            .get(name)
+            .map(Vec::as_slice)
+            .unwrap_or(&[])
+            .iter()
     }
 }
 
@@ -64,8 +90,7 @@ impl<'a> Visitor<'a> for NoFragmentCycles<'a> {
 
         for frag in &self.fragment_order {
             if !detector.visited.contains(frag) {
-                let mut path = Vec::new();
-                detector.detect_from(frag, &mut path);
+                detector.detect_from(frag);
             }
         }
 
@@ -1,7 +1,7 @@
 use crate::parser::types::{Field, Selection, SelectionSet};
 use crate::validation::visitor::{Visitor, VisitorContext};
 use crate::Positioned;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 #[derive(Default)]
 pub struct OverlappingFieldsCanBeMerged;
@@ -14,6 +14,7 @@ impl<'a> Visitor<'a> for OverlappingFieldsCanBeMerged {
     ) {
         let mut find_conflicts = FindConflicts {
             outputs: Default::default(),
+            visited: Default::default(),
             ctx,
         };
         find_conflicts.find(selection_set);
@@ -22,6 +23,9 @@ impl<'a> Visitor<'a> for OverlappingFieldsCanBeMerged {
 
 struct FindConflicts<'a, 'ctx> {
     outputs: HashMap<&'a str, &'a Positioned<Field>>,
+    /// The fragments that were already spread, which also stops at fragment cycles since those
+    /// are reported by `NoFragmentCycles`.
+    visited: HashSet<&'a str>,
     ctx: &'a mut VisitorContext<'ctx>,
 }
 
@@ -42,9 +46,11 @@ impl<'a, 'ctx> FindConflicts<'a, 'ctx> {
                     self.find(&inline_fragment.node.selection_set);
                 }
                 Selection::FragmentSpread(fragment_spread) => {
-                    if let Some(fragment) =
-                        self.ctx.fragment(&fragment_spread.node.fragment_name.node)
-                    {
+                    let name = &fragment_spread.node.fragment_name.node;
+                    if !self.visited.insert(name) {
+                        continue;
+                    }
+                    if let Some(fragment) = self.ctx.fragment(name) {
                         self.find(&fragment.node.selection_set);
                     }
                 }
@@ -0,0 +1,155 @@
+use crate::parser::types::{
+    Directive, FragmentDefinition, OperationDefinition, Selection, VariableDefinition,
+};
+use crate::validation::visitor::{Visitor, VisitorContext};
+use crate::Positioned;
+use std::collections::HashSet;
+
+#[derive(Default)]
+pub struct UniqueDirectivesPerLocation;
+
+impl UniqueDirectivesPerLocation {
+    fn check_directives(ctx: &mut VisitorContext<'_>, directives: &[Positioned<Directive>]) {
+        let mut names = HashSet::new();
+        for directive in directives {
+            let name = directive.node.name.node.as_str();
+            let is_repeatable = match ctx.registry.directives.get(name) {
+                Some(schema_directive) => schema_directive.is_repeatable,
+                None => continue,
+            };
+            if !is_repeatable && !names.insert(name) {
+                ctx.report_error(
+                    vec![directive.pos],
+                    format!(
+                        "The directive \"{}\" can only be used once at this location",
+                        name
+                    ),
+                );
+            }
+        }
+    }
+}
+
+impl<'a> Visitor<'a> for UniqueDirectivesPerLocation {
+    fn enter_operation_definition(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        operation_definition: &'a Positioned<OperationDefinition>,
+    ) {
+        Self::check_directives(ctx, &operation_definition.node.directives);
+    }
+
+    fn enter_fragment_definition(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        fragment_definition: &'a Positioned<FragmentDefinition>,
+    ) {
+        Self::check_directives(ctx, &fragment_definition.node.directives);
+    }
+
+    fn enter_variable_definition(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        variable_definition: &'a Positioned<VariableDefinition>,
+    ) {
+        Self::check_directives(ctx, &variable_definition.node.directives);
+    }
+
+    fn enter_selection(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        selection: &'a Positioned<Selection>,
+    ) {
+        Self::check_directives(ctx, selection.node.directives());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    pub fn factory() -> UniqueDirectivesPerLocation {
+        UniqueDirectivesPerLocation
+    }
+
+    #[test]
+    fn no_directives() {
+        expect_passes_rule!(
+            factory,
+            r#"
+          {
+            field
+          }
+        "#,
+        );
+    }
+
+    #[test]
+    fn unique_directives_in_different_locations() {
+        expect_passes_rule!(
+            factory,
+            r#"
+          query($skip: Boolean!) {
+            field @skip(if: $skip) {
+              ... on Dog @skip(if: $skip) {
+                name @skip(if: $skip)
+              }
+            }
+          }
+        "#,
+        );
+    }
+
+    #[test]
+    fn different_directives_in_one_location() {
+        expect_passes_rule!(
+            factory,
+            r#"
+          {
+            field @include(if: true) @skip(if: false)
+          }
+        "#,
+        );
+    }
+
+    #[test]
+    fn unknown_directives_are_ignored() {
+        expect_passes_rule!(
+            factory,
+            r#"
+          {
+            field @unknown @unknown
+          }
+        "#,
+        );
+    }
+
+    #[test]
+    fn duplicate_directives_in_one_location() {
+        expect_fails_rule!(
+            factory,
+            r#"
+          {
+            field @skip(if: true) @skip(if: false)
+          }
+        "#,
+        );
+    }
+
+    #[test]
+    fn duplicate_directives_on_fragments() {
+        expect_fails_rule!(
+            factory,
+            r#"
+          {
+            dog {
+              ...Frag @include(if: true) @include(if: true)
+            }
+          }
+          fragment Frag on Dog {
+            name
+          }
+        "#,
+        );
+    }
+}
@@ -18,7 +18,7 @@ fn levenshtein_distance(s1: &str, s2: &str) -> usize {
     column[s1.len()]
 }
 
-pub fn make_suggestion<'a, I>(prefix: &str, options: I, input: &str) -> Option<String>
+pub fn suggestion_list<'a, I>(options: I, input: &str) -> Vec<&'a str>
 where
     I: Iterator<Item = &'a str>,
 {
@@ -34,17 +34,25 @@ where
         }
     }
 
-    if selected.is_empty() {
+    selected.sort_by(|a, b| distances[a].cmp(&distances[b]));
+    selected
+}
+
+pub fn format_suggestion(prefix: &str, suggestions: &[&str]) -> Option<String> {
+    if suggestions.is_empty() {
         return None;
     }
-    selected.sort_by(|a, b| distances[a].cmp(&distances[b]));
 
     Some(format!(
         "{} {}?",
         prefix,
-        selected
-            .into_iter()
-            .map(|s| format!("\"{}\"", s))
-            .join(", ")
+        suggestions.iter().map(|s| format!("\"{}\"", s)).join(", ")
     ))
 }
+
+pub fn make_suggestion<'a, I>(prefix: &str, options: I, input: &str) -> Option<String>
+where
+    I: Iterator<Item = &'a str>,
+{
+    format_suggestion(prefix, &suggestion_list(options, input))
+}
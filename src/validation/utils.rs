@@ -1,6 +1,8 @@
 use crate::context::QueryPathNode;
 use crate::parser::types::{ConstValue, Value};
+use crate::validation::suggestion::make_suggestion;
 use crate::{registry, QueryPathSegment, Variables};
+use itertools::Itertools;
 use std::collections::HashSet;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -106,9 +108,38 @@ pub fn is_valid_input_value(
                 registry::MetaType::InputObject {
                     input_fields,
                     name: object_name,
+                    unknown_fields,
+                    oneof,
                     ..
                 } => match value {
                     ConstValue::Object(values) => {
+                        if *oneof {
+                            let mut provided = values
+                                .iter()
+                                .filter(|(name, _)| input_fields.contains_key(name.as_str()));
+                            match (provided.next(), provided.next()) {
+                                (Some((name, ConstValue::Null)), None) => {
+                                    return Some(valid_error(
+                                        &path_node,
+                                        format!(
+                                            "field \"{}\" of oneof type \"{}\" must not be null",
+                                            name, object_name
+                                        ),
+                                    ));
+                                }
+                                (Some(_), None) => {}
+                                _ => {
+                                    return Some(valid_error(
+                                        &path_node,
+                                        format!(
+                                            "exactly one field of oneof type \"{}\" must be provided",
+                                            object_name
+                                        ),
+                                    ));
+                                }
+                            }
+                        }
+
                         let mut input_names = values
                             .keys()
                             .map(|name| name.as_ref())
@@ -154,10 +185,33 @@ pub fn is_valid_input_value(
                             }
                         }
 
-                        if let Some(name) = input_names.iter().next() {
+                        let unknown_fields =
+                            unknown_fields.unwrap_or(registry.unknown_input_fields);
+                        if unknown_fields == registry::UnknownInputFields::Deny
+                            && !input_names.is_empty()
+                        {
+                            let names = input_names
+                                .iter()
+                                .sorted()
+                                .map(|name| {
+                                    match make_suggestion(
+                                        " (did you mean",
+                                        input_fields.keys().map(String::as_str),
+                                        name,
+                                    ) {
+                                        Some(suggestion) => format!("\"{}\"{})", name, suggestion),
+                                        None => format!("\"{}\"", name),
+                                    }
+                                })
+                                .join(", ");
                             return Some(valid_error(
                                 &path_node,
-                                format!("unknown field \"{}\" of type \"{}\"", name, object_name),
+                                format!(
+                                    "unknown field{} {} of type \"{}\"",
+                                    if input_names.len() > 1 { "s" } else { "" },
+                                    names,
+                                    object_name
+                                ),
                             ));
                         }
 
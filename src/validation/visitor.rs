@@ -46,6 +46,20 @@ impl<'a> VisitorContext<'a> {
         self.errors.push(RuleError {
             locations,
             message: msg.into(),
+            suggestions: Vec::new(),
+        })
+    }
+
+    pub fn report_error_with_suggestions<T: Into<String>>(
+        &mut self,
+        locations: Vec<Pos>,
+        msg: T,
+        suggestions: Vec<String>,
+    ) {
+        self.errors.push(RuleError {
+            locations,
+            message: msg.into(),
+            suggestions,
         })
     }
 
@@ -650,6 +664,7 @@ fn visit_variable_definitions<'a, V: Visitor<'a>>(
 ) {
     for d in variable_definitions {
         v.enter_variable_definition(ctx, d);
+        visit_directives(v, ctx, &d.node.directives);
         v.exit_variable_definition(ctx, d);
     }
 }
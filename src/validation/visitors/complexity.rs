@@ -1,13 +1,124 @@
-use crate::parser::types::Field;
+use super::fragment_walker::{FragmentWalker, Fragments};
+use crate::parser::types::{
+    ExecutableDefinition, ExecutableDocument, FragmentDefinition, OperationType, Selection,
+    SelectionSet, VariableDefinition,
+};
+use crate::registry::{ComplexityContext, ComplexityType, MetaType, Registry};
 use crate::validation::visitor::{Visitor, VisitorContext};
-use crate::Positioned;
+use crate::{Pos, Positioned, Variables};
 
+/// Calculates the complexity of the most complex operation, following fragment spreads.
+///
+/// A field costs 1 plus the complexity of its selection set, unless it has a
+/// `#[field(complexity = ...)]`. A complexity that cannot be computed from the arguments of a
+/// field is reported as an error, rather than guessed.
 pub struct ComplexityCalculate<'a> {
     pub complexity: &'a mut usize,
 }
 
+/// Walks an operation, the complexities of the fragments depend on its variables.
+struct ComplexityWalker<'ctx> {
+    registry: &'ctx Registry,
+    variables: Option<&'ctx Variables>,
+    variable_definitions: &'ctx [Positioned<VariableDefinition>],
+    fragments: Fragments<'ctx, usize>,
+    errors: Vec<(Pos, String)>,
+}
+
+impl<'ctx> ComplexityWalker<'ctx> {
+    fn selection_set(
+        &mut self,
+        ty: Option<&'ctx MetaType>,
+        selection_set: &'ctx SelectionSet,
+    ) -> usize {
+        let mut complexity = 0usize;
+        for selection in &selection_set.items {
+            let field_complexity = match &selection.node {
+                Selection::Field(field) => {
+                    let meta_field = ty.and_then(|ty| ty.field_by_name(&field.node.name.node));
+                    let child_ty = meta_field
+                        .and_then(|meta_field| self.registry.concrete_type_by_name(&meta_field.ty));
+                    let child_complexity =
+                        self.selection_set(child_ty, &field.node.selection_set.node);
+                    match meta_field.and_then(|meta_field| meta_field.compute_complexity) {
+                        Some(ComplexityType::Const(n)) => n.saturating_add(child_complexity),
+                        Some(ComplexityType::Fn(f)) => {
+                            let ctx = ComplexityContext {
+                                variables: self.variables,
+                                variable_definitions: self.variable_definitions,
+                                field,
+                            };
+                            f(&ctx, child_complexity).unwrap_or_else(|err| {
+                                self.errors.push((
+                                    field.pos,
+                                    format!(
+                                        "Cannot compute the complexity of field \"{}\": {}",
+                                        field.node.name.node, err
+                                    ),
+                                ));
+                                0
+                            })
+                        }
+                        None => child_complexity.saturating_add(1),
+                    }
+                }
+                Selection::InlineFragment(fragment) => {
+                    let ty = match &fragment.node.type_condition {
+                        Some(cond) => self.registry.types.get(cond.node.on.node.as_str()),
+                        None => ty,
+                    };
+                    self.selection_set(ty, &fragment.node.selection_set.node)
+                }
+                Selection::FragmentSpread(spread) => self.fragment(&spread.node.fragment_name.node),
+            };
+            complexity = complexity.saturating_add(field_complexity);
+        }
+        complexity
+    }
+}
+
+impl<'ctx> FragmentWalker<'ctx> for ComplexityWalker<'ctx> {
+    type Measure = usize;
+
+    fn fragments(&mut self) -> &mut Fragments<'ctx, usize> {
+        &mut self.fragments
+    }
+
+    fn walk_fragment(&mut self, fragment: &'ctx Positioned<FragmentDefinition>) -> usize {
+        let ty = self
+            .registry
+            .types
+            .get(fragment.node.type_condition.node.on.node.as_str());
+        self.selection_set(ty, &fragment.node.selection_set.node)
+    }
+}
+
 impl<'ctx, 'a> Visitor<'ctx> for ComplexityCalculate<'a> {
-    fn enter_field(&mut self, _ctx: &mut VisitorContext<'_>, _field: &Positioned<Field>) {
-        *self.complexity += 1;
+    fn exit_document(&mut self, ctx: &mut VisitorContext<'ctx>, doc: &'ctx ExecutableDocument) {
+        *self.complexity = 0;
+        for definition in &doc.definitions {
+            if let ExecutableDefinition::Operation(operation) = definition {
+                let root = match operation.node.ty {
+                    OperationType::Query => Some(&ctx.registry.query_type),
+                    OperationType::Mutation => ctx.registry.mutation_type.as_ref(),
+                    OperationType::Subscription => ctx.registry.subscription_type.as_ref(),
+                };
+                let mut walker = ComplexityWalker {
+                    registry: ctx.registry,
+                    variables: ctx.variables,
+                    variable_definitions: &operation.node.variable_definitions,
+                    fragments: Fragments::new(doc),
+                    errors: Vec::new(),
+                };
+                let complexity = walker.selection_set(
+                    root.and_then(|root| ctx.registry.types.get(root)),
+                    &operation.node.selection_set.node,
+                );
+                *self.complexity = (*self.complexity).max(complexity);
+                for (pos, message) in walker.errors {
+                    ctx.report_error(vec![pos], message);
+                }
+            }
+        }
     }
 }
@@ -1,69 +1,109 @@
-use crate::parser::types::{FragmentSpread, InlineFragment, SelectionSet};
+use super::fragment_walker::{FragmentWalker, Fragments};
+use crate::parser::types::{
+    ExecutableDefinition, ExecutableDocument, FragmentDefinition, Selection, SelectionSet,
+};
 use crate::validation::visitor::{Visitor, VisitorContext};
-use crate::Positioned;
+use crate::{Pos, Positioned};
 
+/// Calculates how deep the selection sets of the operations are nested, following fragment
+/// spreads, and finds the deepest field.
 pub struct DepthCalculate<'a> {
-    max_depth: &'a mut i32,
-    current_depth: i32,
+    max_depth: &'a mut usize,
+    deepest_field: &'a mut Option<(Pos, Vec<String>)>,
 }
 
 impl<'a> DepthCalculate<'a> {
-    pub fn new(max_depth: &'a mut i32) -> Self {
-        *max_depth = -1;
+    pub fn new(
+        max_depth: &'a mut usize,
+        deepest_field: &'a mut Option<(Pos, Vec<String>)>,
+    ) -> Self {
+        *max_depth = 0;
+        *deepest_field = None;
         Self {
             max_depth,
-            current_depth: -1,
+            deepest_field,
         }
     }
 }
 
-impl<'ctx, 'a> Visitor<'ctx> for DepthCalculate<'a> {
-    fn enter_selection_set(
-        &mut self,
-        _ctx: &mut VisitorContext<'ctx>,
-        _selection_set: &'ctx Positioned<SelectionSet>,
-    ) {
-        self.current_depth += 1;
-        *self.max_depth = (*self.max_depth).max(self.current_depth);
-    }
+/// The deepest field of a selection set, with its depth below the selection set and the response
+/// keys of the fields that lead to it.
+#[derive(Clone)]
+struct Deepest<'ctx> {
+    depth: usize,
+    path: Vec<&'ctx str>,
+    pos: Pos,
+}
 
-    fn exit_selection_set(
-        &mut self,
-        _ctx: &mut VisitorContext<'ctx>,
-        _selection_set: &'ctx Positioned<SelectionSet>,
-    ) {
-        self.current_depth -= 1;
-    }
+struct DepthWalker<'ctx> {
+    fragments: Fragments<'ctx, Option<Deepest<'ctx>>>,
+}
 
-    fn enter_fragment_spread(
-        &mut self,
-        _ctx: &mut VisitorContext<'ctx>,
-        _fragment_spread: &'ctx Positioned<FragmentSpread>,
-    ) {
-        self.current_depth -= 1;
+impl<'ctx> DepthWalker<'ctx> {
+    fn selection_set(&mut self, selection_set: &'ctx SelectionSet) -> Option<Deepest<'ctx>> {
+        let mut deepest: Option<Deepest<'ctx>> = None;
+        for selection in &selection_set.items {
+            let candidate = match &selection.node {
+                Selection::Field(field) => {
+                    let key = field.node.response_key().node.as_str();
+                    Some(match self.selection_set(&field.node.selection_set.node) {
+                        Some(child) => Deepest {
+                            depth: child.depth + 1,
+                            path: std::iter::once(key).chain(child.path).collect(),
+                            pos: child.pos,
+                        },
+                        None => Deepest {
+                            depth: 0,
+                            path: vec![key],
+                            pos: field.pos,
+                        },
+                    })
+                }
+                Selection::InlineFragment(fragment) => {
+                    self.selection_set(&fragment.node.selection_set.node)
+                }
+                Selection::FragmentSpread(spread) => self.fragment(&spread.node.fragment_name.node),
+            };
+            if let Some(candidate) = candidate {
+                if deepest.as_ref().map_or(true, |d| candidate.depth > d.depth) {
+                    deepest = Some(candidate);
+                }
+            }
+        }
+        deepest
     }
+}
+
+impl<'ctx> FragmentWalker<'ctx> for DepthWalker<'ctx> {
+    type Measure = Option<Deepest<'ctx>>;
 
-    fn exit_fragment_spread(
-        &mut self,
-        _ctx: &mut VisitorContext<'ctx>,
-        _fragment_spread: &'ctx Positioned<FragmentSpread>,
-    ) {
-        self.current_depth += 1;
+    fn fragments(&mut self) -> &mut Fragments<'ctx, Self::Measure> {
+        &mut self.fragments
     }
 
-    fn enter_inline_fragment(
-        &mut self,
-        _ctx: &mut VisitorContext<'ctx>,
-        _inline_fragment: &'ctx Positioned<InlineFragment>,
-    ) {
-        self.current_depth -= 1;
+    fn walk_fragment(&mut self, fragment: &'ctx Positioned<FragmentDefinition>) -> Self::Measure {
+        self.selection_set(&fragment.node.selection_set.node)
     }
+}
 
-    fn exit_inline_fragment(
-        &mut self,
-        _ctx: &mut VisitorContext<'ctx>,
-        _inline_fragment: &'ctx Positioned<InlineFragment>,
-    ) {
-        self.current_depth += 1;
+impl<'ctx, 'a> Visitor<'ctx> for DepthCalculate<'a> {
+    fn exit_document(&mut self, _ctx: &mut VisitorContext<'ctx>, doc: &'ctx ExecutableDocument) {
+        let mut walker = DepthWalker {
+            fragments: Fragments::new(doc),
+        };
+
+        for definition in &doc.definitions {
+            if let ExecutableDefinition::Operation(operation) = definition {
+                if let Some(deepest) = walker.selection_set(&operation.node.selection_set.node) {
+                    if self.deepest_field.is_none() || deepest.depth > *self.max_depth {
+                        *self.max_depth = deepest.depth;
+                        *self.deepest_field = Some((
+                            deepest.pos,
+                            deepest.path.into_iter().map(ToString::to_string).collect(),
+                        ));
+                    }
+                }
+            }
+        }
     }
 }
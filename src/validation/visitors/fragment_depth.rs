@@ -0,0 +1,93 @@
+use crate::parser::types::{ExecutableDocument, FragmentDefinition, FragmentSpread};
+use crate::validation::visitor::{Visitor, VisitorContext};
+use crate::Positioned;
+use std::collections::{HashMap, HashSet};
+
+/// Calculates the longest chain of fragment spreads that starts in an operation.
+pub struct FragmentDepthCalculate<'a, 'ctx> {
+    max_depth: &'a mut usize,
+    current_fragment: Option<&'ctx str>,
+    /// The spreads of every fragment, with the spreads of the operations under `None`.
+    spreads: HashMap<Option<&'ctx str>, Vec<&'ctx str>>,
+}
+
+impl<'a, 'ctx> FragmentDepthCalculate<'a, 'ctx> {
+    pub fn new(max_depth: &'a mut usize) -> Self {
+        *max_depth = 0;
+        Self {
+            max_depth,
+            current_fragment: None,
+            spreads: Default::default(),
+        }
+    }
+
+    fn spreads_of(&self, name: Option<&'ctx str>) -> std::slice::Iter<'_, &'ctx str> {
+        self.spreads
+            .get(&name)
+            .map(Vec::as_slice)
+            .unwrap_or(&[])
+            .iter()
+    }
+}
+
+impl<'a, 'ctx> Visitor<'ctx> for FragmentDepthCalculate<'a, 'ctx> {
+    fn exit_document(&mut self, _ctx: &mut VisitorContext<'ctx>, _doc: &'ctx ExecutableDocument) {
+        // The depth of a fragment is one more than the deepest fragment it spreads. Spreads that
+        // lead back into a fragment that is being walked are skipped, those cycles are reported by
+        // `NoFragmentCycles`.
+        let mut depths: HashMap<Option<&str>, usize> = HashMap::new();
+        let mut walking = HashSet::new();
+        let mut stack = vec![(None, self.spreads_of(None), 0)];
+        walking.insert(None);
+
+        while let Some((_, spreads, depth)) = stack.last_mut() {
+            match spreads.next() {
+                Some(&name) => {
+                    let name = Some(name);
+                    if let Some(spread_depth) = depths.get(&name) {
+                        *depth = (*depth).max(spread_depth + 1);
+                    } else if walking.insert(name) {
+                        stack.push((name, self.spreads_of(name), 0));
+                    }
+                }
+                None => {
+                    let (name, _, depth) = stack.pop().unwrap();
+                    walking.remove(&name);
+                    depths.insert(name, depth);
+                    if let Some((_, _, parent_depth)) = stack.last_mut() {
+                        *parent_depth = (*parent_depth).max(depth + 1);
+                    }
+                }
+            }
+        }
+
+        *self.max_depth = depths[&None];
+    }
+
+    fn enter_fragment_definition(
+        &mut self,
+        _ctx: &mut VisitorContext<'ctx>,
+        fragment_definition: &'ctx Positioned<FragmentDefinition>,
+    ) {
+        self.current_fragment = Some(&fragment_definition.node.name.node);
+    }
+
+    fn exit_fragment_definition(
+        &mut self,
+        _ctx: &mut VisitorContext<'ctx>,
+        _fragment_definition: &'ctx Positioned<FragmentDefinition>,
+    ) {
+        self.current_fragment = None;
+    }
+
+    fn enter_fragment_spread(
+        &mut self,
+        _ctx: &mut VisitorContext<'ctx>,
+        fragment_spread: &'ctx Positioned<FragmentSpread>,
+    ) {
+        self.spreads
+            .entry(self.current_fragment)
+            .or_insert_with(Vec::new)
+            .push(&fragment_spread.node.fragment_name.node);
+    }
+}
@@ -0,0 +1,71 @@
+use crate::parser::types::{ExecutableDefinition, ExecutableDocument, FragmentDefinition};
+use crate::Positioned;
+use std::collections::{HashMap, HashSet};
+
+/// The fragments of a document, with the measures of the fragments that have been walked.
+pub struct Fragments<'ctx, T> {
+    definitions: HashMap<&'ctx str, &'ctx Positioned<FragmentDefinition>>,
+    measures: HashMap<&'ctx str, T>,
+    walking: HashSet<&'ctx str>,
+}
+
+impl<'ctx, T> Fragments<'ctx, T> {
+    pub fn new(doc: &'ctx ExecutableDocument) -> Self {
+        Self {
+            definitions: doc
+                .definitions
+                .iter()
+                .filter_map(|definition| match definition {
+                    ExecutableDefinition::Fragment(fragment) => {
+                        Some((fragment.node.name.node.as_str(), fragment))
+                    }
+                    ExecutableDefinition::Operation(_) => None,
+                })
+                .collect(),
+            measures: HashMap::new(),
+            walking: HashSet::new(),
+        }
+    }
+
+    /// Start walking the fragment `name`, returning `None` if it is unknown or already being
+    /// walked. Spreads that lead back into a fragment that is being walked are skipped, those
+    /// cycles are reported by `NoFragmentCycles`.
+    pub fn enter(&mut self, name: &'ctx str) -> Option<&'ctx Positioned<FragmentDefinition>> {
+        let fragment = *self.definitions.get(name)?;
+        if self.walking.insert(name) {
+            Some(fragment)
+        } else {
+            None
+        }
+    }
+
+    pub fn exit(&mut self, name: &'ctx str) {
+        self.walking.remove(name);
+    }
+}
+
+/// Walks the selection sets of a document, measuring every fragment once.
+pub trait FragmentWalker<'ctx> {
+    /// The measure of a selection set, the default is the measure of a skipped fragment.
+    type Measure: Clone + Default;
+
+    fn fragments(&mut self) -> &mut Fragments<'ctx, Self::Measure>;
+
+    fn walk_fragment(&mut self, fragment: &'ctx Positioned<FragmentDefinition>) -> Self::Measure;
+
+    /// Returns the measure of the fragment `name`, walking it the first time.
+    fn fragment(&mut self, name: &'ctx str) -> Self::Measure {
+        if let Some(measure) = self.fragments().measures.get(name) {
+            return measure.clone();
+        }
+        let fragment = match self.fragments().enter(name) {
+            Some(fragment) => fragment,
+            None => return Default::default(),
+        };
+        let measure = self.walk_fragment(fragment);
+        let fragments = self.fragments();
+        fragments.exit(name);
+        fragments.measures.insert(name, measure.clone());
+        measure
+    }
+}
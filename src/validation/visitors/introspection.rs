@@ -0,0 +1,126 @@
+use super::fragment_walker::{FragmentWalker, Fragments};
+use crate::parser::types::{
+    ExecutableDefinition, ExecutableDocument, FragmentDefinition, Selection, SelectionSet,
+};
+use crate::validation::visitor::{Visitor, VisitorContext};
+use crate::Positioned;
+
+/// Calculates how deep the `__schema` and `__type` fields of the operations are nested and how
+/// many fields they select, following fragment spreads. The size of an operation is the sum of
+/// the sizes of its introspection fields.
+pub struct IntrospectionCalculate<'a> {
+    max_depth: &'a mut usize,
+    max_size: &'a mut usize,
+}
+
+impl<'a> IntrospectionCalculate<'a> {
+    pub fn new(max_depth: &'a mut usize, max_size: &'a mut usize) -> Self {
+        *max_depth = 0;
+        *max_size = 0;
+        Self {
+            max_depth,
+            max_size,
+        }
+    }
+}
+
+/// The depth and the number of fields of a selection set.
+#[derive(Copy, Clone, Default)]
+struct Measure {
+    depth: usize,
+    size: usize,
+}
+
+impl Measure {
+    fn add(&mut self, other: Measure) {
+        self.depth = self.depth.max(other.depth);
+        self.size = self.size.saturating_add(other.size);
+    }
+}
+
+struct IntrospectionWalker<'ctx> {
+    fragments: Fragments<'ctx, Measure>,
+}
+
+impl<'ctx> IntrospectionWalker<'ctx> {
+    /// Measures the introspection fields of a root selection set.
+    fn root(&mut self, selection_set: &'ctx SelectionSet) -> Measure {
+        let mut measure = Measure::default();
+        for selection in &selection_set.items {
+            match &selection.node {
+                Selection::Field(field) => {
+                    let name = field.node.name.node.as_str();
+                    if name == "__schema" || name == "__type" {
+                        let child = self.selection_set(&field.node.selection_set.node);
+                        measure.add(Measure {
+                            depth: child.depth + 1,
+                            size: child.size.saturating_add(1),
+                        });
+                    }
+                }
+                Selection::InlineFragment(fragment) => {
+                    measure.add(self.root(&fragment.node.selection_set.node))
+                }
+                Selection::FragmentSpread(spread) => {
+                    // The fragments of the root are not memoized, they are measured as roots.
+                    let name = spread.node.fragment_name.node.as_str();
+                    if let Some(fragment) = self.fragments.enter(name) {
+                        measure.add(self.root(&fragment.node.selection_set.node));
+                        self.fragments.exit(name);
+                    }
+                }
+            }
+        }
+        measure
+    }
+
+    fn selection_set(&mut self, selection_set: &'ctx SelectionSet) -> Measure {
+        let mut measure = Measure::default();
+        for selection in &selection_set.items {
+            match &selection.node {
+                Selection::Field(field) => {
+                    let child = self.selection_set(&field.node.selection_set.node);
+                    measure.add(Measure {
+                        depth: child.depth + 1,
+                        size: child.size.saturating_add(1),
+                    });
+                }
+                Selection::InlineFragment(fragment) => {
+                    measure.add(self.selection_set(&fragment.node.selection_set.node))
+                }
+                Selection::FragmentSpread(spread) => {
+                    measure.add(self.fragment(&spread.node.fragment_name.node))
+                }
+            }
+        }
+        measure
+    }
+}
+
+impl<'ctx> FragmentWalker<'ctx> for IntrospectionWalker<'ctx> {
+    type Measure = Measure;
+
+    fn fragments(&mut self) -> &mut Fragments<'ctx, Measure> {
+        &mut self.fragments
+    }
+
+    fn walk_fragment(&mut self, fragment: &'ctx Positioned<FragmentDefinition>) -> Measure {
+        self.selection_set(&fragment.node.selection_set.node)
+    }
+}
+
+impl<'ctx, 'a> Visitor<'ctx> for IntrospectionCalculate<'a> {
+    fn exit_document(&mut self, _ctx: &mut VisitorContext<'ctx>, doc: &'ctx ExecutableDocument) {
+        let mut walker = IntrospectionWalker {
+            fragments: Fragments::new(doc),
+        };
+
+        for definition in &doc.definitions {
+            if let ExecutableDefinition::Operation(operation) = definition {
+                let measure = walker.root(&operation.node.selection_set.node);
+                *self.max_depth = (*self.max_depth).max(measure.depth);
+                *self.max_size = (*self.max_size).max(measure.size);
+            }
+        }
+    }
+}
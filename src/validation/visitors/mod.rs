@@ -1,7 +1,12 @@
 mod cache_control;
 mod complexity;
 mod depth;
+mod fragment_depth;
+mod fragment_walker;
+mod introspection;
 
 pub use cache_control::CacheControlCalculate;
 pub use complexity::ComplexityCalculate;
 pub use depth::DepthCalculate;
+pub use fragment_depth::FragmentDepthCalculate;
+pub use introspection::IntrospectionCalculate;
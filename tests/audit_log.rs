@@ -0,0 +1,135 @@
+use async_graphql::extensions::{AuditEntry, AuditLog};
+use async_graphql::parser::types::Name;
+use async_graphql::*;
+use std::sync::{Arc, Mutex};
+
+#[async_std::test]
+pub async fn test_audit_log() {
+    struct CurrentUser(String);
+
+    #[derive(InputObject)]
+    struct Credentials {
+        login: String,
+        password: String,
+    }
+
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    struct Payload;
+
+    #[Object]
+    impl Payload {
+        async fn id(&self) -> i32 {
+            1
+        }
+
+        async fn owner(&self) -> FieldResult<String> {
+            Err("owner not found".into())
+        }
+    }
+
+    struct MutationRoot;
+
+    #[Object]
+    impl MutationRoot {
+        async fn login(&self, credentials: Credentials) -> bool {
+            credentials.login == "admin"
+        }
+
+        async fn delete(&self, id: i32) -> FieldResult<bool> {
+            Err(format!("cannot delete {}", id).into())
+        }
+
+        async fn create(&self) -> Payload {
+            Payload
+        }
+    }
+
+    let entries = Arc::new(Mutex::new(Vec::new()));
+    let audit_log = AuditLog::new({
+        let entries = entries.clone();
+        move |entry: AuditEntry| entries.lock().unwrap().push(entry)
+    })
+    .sanitize("password")
+    .identity(|ctx| ctx.data_opt::<CurrentUser>().map(|user| user.0.clone()));
+    let schema = Schema::build(QueryRoot, MutationRoot, EmptySubscription)
+        .extension(move || audit_log.clone())
+        .finish();
+
+    schema.execute("{ value }").await.into_result().unwrap();
+    assert!(entries.lock().unwrap().is_empty());
+
+    let query = r#"mutation Login($password: String!) {
+        login(credentials: { login: "admin", password: $password })
+    }"#;
+    schema
+        .execute(
+            Request::new(query)
+                .variables(Variables::from_json(
+                    serde_json::json!({ "password": "secret" }),
+                ))
+                .data(CurrentUser("alice".to_string())),
+        )
+        .await
+        .into_result()
+        .unwrap();
+    assert!(schema.execute("mutation { delete(id: 1) }").await.is_err());
+    assert!(schema
+        .execute("mutation { create { id owner } }")
+        .await
+        .is_err());
+
+    let mut credentials = std::collections::BTreeMap::new();
+    credentials.insert(
+        Name::new_unchecked("login".to_string()),
+        Value::String("admin".to_string()),
+    );
+    credentials.insert(
+        Name::new_unchecked("password".to_string()),
+        Value::String("[REDACTED]".to_string()),
+    );
+    let mut login_arguments = std::collections::BTreeMap::new();
+    login_arguments.insert(
+        Name::new_unchecked("credentials".to_string()),
+        Value::Object(credentials),
+    );
+    let mut delete_arguments = std::collections::BTreeMap::new();
+    delete_arguments.insert(
+        Name::new_unchecked("id".to_string()),
+        Value::Number(1.into()),
+    );
+
+    assert_eq!(
+        *entries.lock().unwrap(),
+        vec![
+            AuditEntry {
+                operation_name: Some("Login".to_string()),
+                field: "login".to_string(),
+                arguments: login_arguments,
+                caller: Some("alice".to_string()),
+                error: None,
+            },
+            AuditEntry {
+                operation_name: None,
+                field: "delete".to_string(),
+                arguments: delete_arguments,
+                caller: None,
+                error: Some("Query error: Failed to resolve field: cannot delete 1".to_string()),
+            },
+            AuditEntry {
+                operation_name: None,
+                field: "create".to_string(),
+                arguments: Default::default(),
+                caller: None,
+                error: None,
+            },
+        ]
+    );
+}
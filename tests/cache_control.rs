@@ -0,0 +1,114 @@
+use async_graphql::*;
+
+#[derive(SimpleObject)]
+#[graphql(cache_control(max_age = 60, private))]
+struct User {
+    id: i32,
+    #[field(cache_control(max_age = 300))]
+    avatar: String,
+    #[field(cache_control(public))]
+    name: String,
+}
+
+struct Query;
+
+#[Object(cache_control(max_age = 30))]
+impl Query {
+    async fn user(&self) -> User {
+        User {
+            id: 1,
+            avatar: "a.png".to_string(),
+            name: "a".to_string(),
+        }
+    }
+
+    #[field(cache_control(max_age = 600))]
+    async fn version(&self) -> i32 {
+        1
+    }
+
+    #[field(cache_control(max_age = 10))]
+    async fn time(&self) -> i32 {
+        0
+    }
+}
+
+async fn cache_control(
+    schema: &Schema<Query, EmptyMutation, EmptySubscription>,
+    query: &str,
+) -> CacheControl {
+    schema
+        .execute(query)
+        .await
+        .into_result()
+        .unwrap()
+        .cache_control
+}
+
+#[async_std::test]
+pub async fn test_cache_control_inheritance() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+
+    // Fields override the values of their object.
+    assert_eq!(
+        cache_control(&schema, "{ version }").await,
+        CacheControl {
+            public: true,
+            max_age: 600
+        }
+    );
+    assert_eq!(
+        cache_control(&schema, "{ __typename }").await,
+        CacheControl {
+            public: true,
+            max_age: 30
+        }
+    );
+
+    // Fields inherit the values that they do not set.
+    assert_eq!(
+        cache_control(&schema, "{ version user { avatar } }").await,
+        CacheControl {
+            public: false,
+            max_age: 30
+        }
+    );
+    assert_eq!(
+        cache_control(&schema, "{ version user { name } }").await,
+        CacheControl {
+            public: true,
+            max_age: 30
+        }
+    );
+}
+
+#[async_std::test]
+pub async fn test_cache_control_intersection() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+
+    assert_eq!(
+        cache_control(&schema, "{ version time }").await,
+        CacheControl {
+            public: true,
+            max_age: 10
+        }
+    );
+    assert_eq!(
+        cache_control(&schema, "{ time user { id } }").await,
+        CacheControl {
+            public: false,
+            max_age: 10
+        }
+    );
+    assert_eq!(
+        cache_control(
+            &schema,
+            "{ ... on Query { version } ...F } fragment F on Query { time }"
+        )
+        .await,
+        CacheControl {
+            public: true,
+            max_age: 10
+        }
+    );
+}
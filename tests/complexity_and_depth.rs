@@ -97,9 +97,12 @@ pub async fn test_complexity_and_depth() {
     assert_eq!(
         schema.execute(query).await.into_result().unwrap_err(),
         Error::Query {
-            pos: Pos { line: 0, column: 0 },
-            path: None,
-            err: QueryError::TooDeep,
+            pos: Pos {
+                line: 6,
+                column: 25
+            },
+            path: Some(serde_json::json!(["obj", "c", "c", "a"])),
+            err: QueryError::TooDeep { limit: 2 },
         }
     );
 
@@ -133,4 +136,262 @@ pub async fn test_complexity_and_depth() {
             }
         })
     );
+
+    // Fragments are counted at the depth of their spreads.
+    let query = r#"
+        {
+            obj { ...A }
+        }
+        fragment A on MyObj { c { ...B } }
+        fragment B on MyObj { c { x: c { a } } }"#;
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap_err(),
+        Error::Query {
+            pos: Pos {
+                line: 6,
+                column: 42
+            },
+            path: Some(serde_json::json!(["obj", "c", "c", "x", "a"])),
+            err: QueryError::TooDeep { limit: 3 },
+        }
+    );
+}
+
+#[async_std::test]
+pub async fn test_query_size_and_tokens() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            1
+        }
+    }
+
+    let query = "{ a:value b:value }";
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .limit_query_size(query.len())
+        .limit_tokens(8)
+        .finish();
+    assert_eq!(
+        schema.execute(query).await.data,
+        serde_json::json!({ "a": 1, "b": 1 })
+    );
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .limit_query_size(query.len() - 1)
+        .finish();
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap_err(),
+        Error::Query {
+            pos: Pos { line: 0, column: 0 },
+            path: None,
+            err: QueryError::TooLarge {
+                limit: query.len() - 1
+            },
+        }
+    );
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .limit_tokens(7)
+        .finish();
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap_err(),
+        Error::Query {
+            pos: Pos {
+                line: 1,
+                column: 19
+            },
+            path: None,
+            err: QueryError::TooManyTokens { limit: 7 },
+        }
+    );
+}
+
+#[async_std::test]
+pub async fn test_field_complexity() {
+    #[derive(SimpleObject)]
+    struct Item {
+        #[field(complexity = 5)]
+        expensive: i32,
+        cheap: i32,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        #[field(complexity = "first as usize * child_complexity")]
+        async fn items(&self, #[arg(default = 10)] first: i32) -> Vec<Item> {
+            (0..first)
+                .map(|i| Item {
+                    expensive: i,
+                    cheap: i,
+                })
+                .collect()
+        }
+
+        #[field(complexity = 0)]
+        async fn free(&self) -> i32 {
+            1
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .limit_complexity(20)
+        .finish();
+    let too_complex = Error::Query {
+        pos: Pos { line: 0, column: 0 },
+        path: None,
+        err: QueryError::TooComplex,
+    };
+
+    // 2 * 1 + 0 + 0
+    assert_eq!(
+        schema
+            .execute("{ items(first: 2) { cheap } free free2: free }")
+            .await
+            .data,
+        serde_json::json!({
+            "items": [{ "cheap": 0 }, { "cheap": 1 }],
+            "free": 1,
+            "free2": 1,
+        })
+    );
+
+    // 10 * 2 with the default value of the argument
+    assert!(schema
+        .execute("{ items { cheap cheap2: cheap } }")
+        .await
+        .into_result()
+        .is_ok());
+    assert_eq!(
+        schema
+            .execute("{ items { cheap cheap2: cheap cheap3: cheap } }")
+            .await
+            .into_result()
+            .unwrap_err(),
+        too_complex
+    );
+
+    // 3 * 5
+    assert!(schema
+        .execute("{ items(first: 3) { expensive } }")
+        .await
+        .into_result()
+        .is_ok());
+    assert_eq!(
+        schema
+            .execute("{ items(first: 5) { expensive } }")
+            .await
+            .into_result()
+            .unwrap_err(),
+        too_complex
+    );
+
+    // Variables and fragments
+    let query = "query($n: Int!) { items(first: $n) { ...F } } fragment F on Item { expensive }";
+    assert!(schema
+        .execute(Request::new(query).variables(Variables::from_json(serde_json::json!({ "n": 3 }))))
+        .await
+        .into_result()
+        .is_ok());
+    assert_eq!(
+        schema
+            .execute(
+                Request::new(query).variables(Variables::from_json(serde_json::json!({ "n": 5 })))
+            )
+            .await
+            .into_result()
+            .unwrap_err(),
+        too_complex
+    );
+}
+
+#[async_std::test]
+pub async fn test_field_complexity_with_variables() {
+    use async_graphql::parser::types::Name;
+    use std::collections::BTreeMap;
+
+    struct Double;
+
+    impl VariableDirective for Double {
+        fn name(&self) -> &'static str {
+            "double"
+        }
+
+        fn transform(&self, _args: &BTreeMap<Name, Value>, value: Value) -> FieldResult<Value> {
+            match value {
+                Value::Number(n) => Ok(Value::Number((n.as_i64().unwrap() * 2).into())),
+                value => Ok(value),
+            }
+        }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        #[field(complexity = "first as usize")]
+        async fn items(&self, first: i32) -> Vec<i32> {
+            (0..first).collect()
+        }
+
+        #[field(complexity = "first.unwrap_or(1) as usize")]
+        async fn optional_items(&self, first: Option<i32>) -> Vec<i32> {
+            (0..first.unwrap_or(1)).collect()
+        }
+    }
+
+    let too_complex = Error::Query {
+        pos: Pos { line: 0, column: 0 },
+        path: None,
+        err: QueryError::TooComplex,
+    };
+    let request = |query: &str, n: i32| {
+        Request::new(query).variables(Variables::from_json(serde_json::json!({ "n": n })))
+    };
+
+    // The complexity of a cached query is computed with the variables of every request.
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .limit_complexity(10)
+        .query_cache(10)
+        .finish();
+    let query = "query($n: Int!) { items(first: $n) }";
+    assert!(schema.execute(request(query, 1)).await.is_ok());
+    assert_eq!(
+        schema
+            .execute(request(query, 1000))
+            .await
+            .into_result()
+            .unwrap_err(),
+        too_complex
+    );
+
+    // A complexity that cannot be computed is an error, rather than costing 1.
+    assert!(matches!(
+        schema
+            .execute("query($n: Int) { optionalItems(first: $n) }")
+            .await
+            .into_result()
+            .unwrap_err(),
+        Error::Rule { .. }
+    ));
+
+    // The complexity is computed with the variables transformed by the variable directives.
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .limit_complexity(10)
+        .variable_directive(Double)
+        .finish();
+    let query = "query($n: Int! @double) { items(first: $n) }";
+    assert!(schema.execute(request(query, 5)).await.is_ok());
+    assert_eq!(
+        schema
+            .execute(request(query, 6))
+            .await
+            .into_result()
+            .unwrap_err(),
+        too_complex
+    );
 }
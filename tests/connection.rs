@@ -92,3 +92,211 @@ pub async fn test_connection_additional_fields() {
         })
     );
 }
+
+#[async_std::test]
+pub async fn test_connection_data_sources() {
+    struct Numbers;
+
+    #[async_trait::async_trait]
+    impl OffsetLimit for Numbers {
+        type NodeType = i32;
+
+        async fn total_count(&self) -> FieldResult<usize> {
+            Ok(10)
+        }
+
+        async fn fetch(&self, offset: usize, limit: usize) -> FieldResult<Vec<i32>> {
+            Ok((offset..offset + limit).map(|n| n as i32).collect())
+        }
+    }
+
+    struct QueryRoot {
+        numbers: Vec<i32>,
+    }
+
+    #[Object]
+    impl QueryRoot {
+        async fn slice(
+            &self,
+            after: Option<String>,
+            before: Option<String>,
+            first: Option<i32>,
+            last: Option<i32>,
+        ) -> FieldResult<Connection<usize, &i32>> {
+            self.numbers
+                .as_slice()
+                .query(after, before, first, last)
+                .await
+        }
+
+        async fn offset(
+            &self,
+            after: Option<String>,
+            before: Option<String>,
+            first: Option<i32>,
+            last: Option<i32>,
+        ) -> FieldResult<Connection<usize, i32>> {
+            OffsetLimitDataSource(Numbers)
+                .query(after, before, first, last)
+                .await
+        }
+
+        async fn stream(
+            &self,
+            after: Option<String>,
+            before: Option<String>,
+            first: Option<i32>,
+            last: Option<i32>,
+        ) -> FieldResult<Connection<usize, i32>> {
+            StreamDataSource(|| futures::stream::iter((0..10).map(Ok)))
+                .query(after, before, first, last)
+                .await
+        }
+    }
+
+    let schema = Schema::new(
+        QueryRoot {
+            numbers: (0..10).collect(),
+        },
+        EmptyMutation,
+        EmptySubscription,
+    );
+
+    for field in &["slice", "offset", "stream"] {
+        let query = |args: &str| {
+            format!(
+                "{{ {}({}) {{ edges {{ node }} pageInfo {{ hasPreviousPage hasNextPage startCursor endCursor }} }} }}",
+                field, args
+            )
+        };
+
+        assert_eq!(
+            schema.execute(query("first: 2, after: \"3\"")).await.data,
+            serde_json::json!({
+                *field: {
+                    "edges": [{ "node": 4 }, { "node": 5 }],
+                    "pageInfo": {
+                        "hasPreviousPage": true,
+                        "hasNextPage": true,
+                        "startCursor": "4",
+                        "endCursor": "5",
+                    },
+                }
+            })
+        );
+
+        assert_eq!(
+            schema.execute(query("last: 3")).await.data,
+            serde_json::json!({
+                *field: {
+                    "edges": [{ "node": 7 }, { "node": 8 }, { "node": 9 }],
+                    "pageInfo": {
+                        "hasPreviousPage": true,
+                        "hasNextPage": false,
+                        "startCursor": "7",
+                        "endCursor": "9",
+                    },
+                }
+            })
+        );
+
+        assert_eq!(
+            schema.execute(query("before: \"2\", last: 5")).await.data,
+            serde_json::json!({
+                *field: {
+                    "edges": [{ "node": 0 }, { "node": 1 }],
+                    "pageInfo": {
+                        "hasPreviousPage": false,
+                        "hasNextPage": true,
+                        "startCursor": "0",
+                        "endCursor": "1",
+                    },
+                }
+            })
+        );
+
+        let err = schema
+            .execute(query(&format!("after: \"{}\", first: 1", usize::MAX)))
+            .await
+            .into_result()
+            .unwrap_err();
+        assert!(err.to_string().contains("Invalid cursor"), "{}", err);
+    }
+}
+
+#[async_std::test]
+pub async fn test_connection_arguments() {
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {
+        async fn numbers(
+            &self,
+            after: Option<String>,
+            before: Option<String>,
+            first: Option<i32>,
+            last: Option<i32>,
+        ) -> FieldResult<Connection<usize, i32>> {
+            connection::query(
+                after,
+                before,
+                first,
+                last,
+                |after, before, first, last| async move {
+                    let start = after.map(|after| after + 1).unwrap_or(0);
+                    let end = before.unwrap_or(10);
+                    let end = first.map(|first| (start + first).min(end)).unwrap_or(end);
+                    let start = last
+                        .map(|last| end - last.min(end - start))
+                        .unwrap_or(start);
+                    let mut connection = Connection::new(start > 0, end < 10);
+                    connection.append((start..end).map(|n| Edge::<usize, i32>::new(n, n as i32)));
+                    Ok(connection)
+                },
+            )
+            .await
+        }
+    }
+
+    let schema = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
+
+    assert_eq!(
+        schema
+            .execute("{ numbers(after: \"1\", before: \"5\") { edges { cursor node } } }")
+            .await
+            .data,
+        serde_json::json!({
+            "numbers": {
+                "edges": [
+                    { "cursor": "2", "node": 2 },
+                    { "cursor": "3", "node": 3 },
+                    { "cursor": "4", "node": 4 },
+                ]
+            },
+        })
+    );
+
+    let error = |query: &'static str| {
+        let schema = &schema;
+        async move {
+            schema
+                .execute(query)
+                .await
+                .into_result()
+                .unwrap_err()
+                .to_string()
+        }
+    };
+    assert!(error("{ numbers(first: 1, last: 1) { edges { node } } }")
+        .await
+        .contains("The \"first\" and \"last\" parameters cannot exist at the same time"));
+    assert!(error("{ numbers(first: -1) { edges { node } } }")
+        .await
+        .contains("The \"first\" parameter must be a non-negative number"));
+    assert!(error("{ numbers(last: -1) { edges { node } } }")
+        .await
+        .contains("The \"last\" parameter must be a non-negative number"));
+    assert!(error("{ numbers(after: \"x\") { edges { node } } }")
+        .await
+        .contains("invalid digit"));
+}
@@ -0,0 +1,111 @@
+use async_graphql::*;
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::Arc;
+
+#[async_std::test]
+pub async fn test_data_or_insert_with() {
+    struct Transaction(i32);
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn a(&self, ctx: &Context<'_>) -> i32 {
+            self.transaction(ctx).0
+        }
+
+        async fn b(&self, ctx: &Context<'_>) -> i32 {
+            self.transaction(ctx).0
+        }
+
+        async fn c(&self) -> i32 {
+            1
+        }
+    }
+
+    impl Query {
+        fn transaction(&self, ctx: &Context<'_>) -> Arc<Transaction> {
+            ctx.data_or_insert_with(|| {
+                let counter = ctx.data_unchecked::<Arc<AtomicI32>>();
+                Transaction(counter.fetch_add(1, Ordering::SeqCst))
+            })
+        }
+    }
+
+    let counter = Arc::new(AtomicI32::new(0));
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .data(counter.clone())
+        .finish();
+
+    // The data is created once per request, and only by the requests that need it.
+    assert_eq!(
+        schema.execute("{ a b }").await.data,
+        serde_json::json!({ "a": 0, "b": 0 })
+    );
+    assert_eq!(
+        schema.execute("{ c }").await.data,
+        serde_json::json!({ "c": 1 })
+    );
+    assert_eq!(
+        schema.execute("{ b }").await.data,
+        serde_json::json!({ "b": 1 })
+    );
+    assert_eq!(counter.load(Ordering::SeqCst), 2);
+}
+
+#[async_std::test]
+pub async fn test_with_data() {
+    struct Tenant(&'static str);
+
+    struct Item;
+
+    #[Object]
+    impl Item {
+        async fn tenant(&self, ctx: &Context<'_>) -> &str {
+            ctx.data_unchecked::<Tenant>().0
+        }
+
+        async fn child(&self) -> Item {
+            Item
+        }
+
+        async fn other(&self) -> WithData<Item> {
+            WithData::new(Item).data(Tenant("c"))
+        }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn item(&self) -> Item {
+            Item
+        }
+
+        async fn scoped(&self) -> FieldResult<WithData<Item>> {
+            Ok(WithData::new(Item).data(Tenant("b")))
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .data(Tenant("a"))
+        .finish();
+    assert_eq!(
+        schema
+            .execute(
+                "{ item { tenant } scoped { tenant child { tenant other { tenant child { tenant } } } } }"
+            )
+            .await
+            .data,
+        serde_json::json!({
+            "item": { "tenant": "a" },
+            "scoped": {
+                "tenant": "b",
+                "child": {
+                    "tenant": "b",
+                    "other": { "tenant": "c", "child": { "tenant": "c" } },
+                },
+            },
+        })
+    );
+}
@@ -0,0 +1,114 @@
+use async_graphql::dataloader::{DataLoader, Loader};
+use async_graphql::*;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+#[derive(Default)]
+struct CountLoader {
+    batches: Mutex<Vec<Vec<i32>>>,
+}
+
+#[async_trait::async_trait]
+impl Loader for CountLoader {
+    type Key = i32;
+    type Value = String;
+    type Error = String;
+
+    async fn load(&self, keys: &[i32]) -> Result<HashMap<i32, String>, String> {
+        let mut batch = keys.to_vec();
+        batch.sort();
+        self.batches.lock().unwrap().push(batch);
+        if keys.contains(&-1) {
+            return Err("invalid key".to_string());
+        }
+        Ok(keys
+            .iter()
+            .filter(|key| **key % 10 != 0)
+            .map(|key| (*key, format!("value{}", key)))
+            .collect())
+    }
+}
+
+struct Item(i32);
+
+#[Object]
+impl Item {
+    async fn value(&self, ctx: &Context<'_>) -> FieldResult<Option<String>> {
+        let loader = ctx.data_unchecked::<Arc<DataLoader<CountLoader>>>();
+        Ok(loader.load_one(self.0).await?)
+    }
+}
+
+struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    async fn items(&self, ids: Vec<i32>) -> Vec<Item> {
+        ids.into_iter().map(Item).collect()
+    }
+}
+
+#[async_std::test]
+pub async fn test_dataloader() {
+    let schema = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
+    let loader = Arc::new(DataLoader::new(CountLoader::default()));
+    let res = schema
+        .execute(
+            Request::new("{ a: items(ids: [1, 2, 1, 10]) { value } b: items(ids: [3]) { value } }")
+                .data(loader.clone()),
+        )
+        .await;
+    assert_eq!(
+        res.data,
+        serde_json::json!({
+            "a": [
+                { "value": "value1" },
+                { "value": "value2" },
+                { "value": "value1" },
+                { "value": null },
+            ],
+            "b": [{ "value": "value3" }],
+        })
+    );
+    assert_eq!(
+        *loader.loader().batches.lock().unwrap(),
+        vec![vec![1, 2, 3, 10]]
+    );
+
+    // Loaded values and missing keys are cached.
+    schema
+        .execute(Request::new("{ items(ids: [2, 4, 10]) { value } }").data(loader.clone()))
+        .await;
+    assert_eq!(
+        *loader.loader().batches.lock().unwrap(),
+        vec![vec![1, 2, 3, 10], vec![4]]
+    );
+}
+
+#[async_std::test]
+pub async fn test_dataloader_max_batch_size_and_errors() {
+    let loader = DataLoader::new(CountLoader::default()).max_batch_size(2);
+    let (a, b, c) = futures::join!(
+        loader.load_one(1),
+        loader.load_many(vec![2, 3]),
+        loader.load_one(4)
+    );
+    assert_eq!(a, Ok(Some("value1".to_string())));
+    assert_eq!(b.unwrap().len(), 2);
+    assert_eq!(c, Ok(Some("value4".to_string())));
+    assert_eq!(
+        *loader.loader().batches.lock().unwrap(),
+        vec![vec![1, 2], vec![3, 4]]
+    );
+
+    loader.feed(5, "fed".to_string());
+    assert_eq!(loader.load_one(5).await, Ok(Some("fed".to_string())));
+    assert_eq!(loader.loader().batches.lock().unwrap().len(), 2);
+
+    let (a, b) = futures::join!(loader.load_one(6), loader.load_one(-1));
+    assert_eq!(a, Err("invalid key".to_string()));
+    assert_eq!(b, Err("invalid key".to_string()));
+
+    // Errors are not cached.
+    assert_eq!(loader.load_one(6).await, Ok(Some("value6".to_string())));
+}
@@ -0,0 +1,56 @@
+use async_graphql::*;
+
+#[async_std::test]
+pub async fn test_desc_file() {
+    #[derive(SimpleObject)]
+    #[graphql(desc_file = "tests/descriptions.toml")]
+    struct MyObj {
+        value: i32,
+        /// Documented
+        documented: i32,
+        other: i32,
+    }
+
+    struct Query;
+
+    #[Object(desc_file = "tests/descriptions.toml")]
+    impl Query {
+        async fn obj(&self) -> MyObj {
+            MyObj {
+                value: 1,
+                documented: 2,
+                other: 3,
+            }
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute(r#"{ __type(name: "MyObj") { fields { name description } } }"#)
+            .await
+            .data,
+        serde_json::json!({
+            "__type": {
+                "fields": [
+                    { "name": "value", "description": "Value from file" },
+                    { "name": "documented", "description": "Documented" },
+                    { "name": "other", "description": null },
+                ]
+            }
+        })
+    );
+    assert_eq!(
+        schema
+            .execute(r#"{ __type(name: "Query") { fields { name description } } }"#)
+            .await
+            .data,
+        serde_json::json!({
+            "__type": {
+                "fields": [
+                    { "name": "obj", "description": "Object from file" },
+                ]
+            }
+        })
+    );
+}
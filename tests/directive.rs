@@ -115,3 +115,355 @@ pub async fn test_directive_ifdef() {
         })
     );
 }
+
+#[async_std::test]
+pub async fn test_variable_directive() {
+    use async_graphql::parser::types::Name;
+    use std::collections::BTreeMap;
+
+    struct Trim;
+
+    impl VariableDirective for Trim {
+        fn name(&self) -> &'static str {
+            "trim"
+        }
+
+        fn transform(&self, _args: &BTreeMap<Name, Value>, value: Value) -> FieldResult<Value> {
+            match value {
+                Value::String(s) => Ok(Value::String(s.trim().to_string())),
+                value => Ok(value),
+            }
+        }
+    }
+
+    struct Lowercase;
+
+    impl VariableDirective for Lowercase {
+        fn name(&self) -> &'static str {
+            "lowercase"
+        }
+
+        fn transform(&self, _args: &BTreeMap<Name, Value>, value: Value) -> FieldResult<Value> {
+            match value {
+                Value::String(s) => Ok(Value::String(s.to_lowercase())),
+                _ => Err("Only strings can be lowercased".into()),
+            }
+        }
+    }
+
+    struct Length;
+
+    impl VariableDirective for Length {
+        fn name(&self) -> &'static str {
+            "length"
+        }
+
+        fn transform(&self, _args: &BTreeMap<Name, Value>, value: Value) -> FieldResult<Value> {
+            match value {
+                Value::String(s) => Ok(Value::Number(s.len().into())),
+                value => Ok(value),
+            }
+        }
+    }
+
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {
+        pub async fn value(&self, input: String) -> String {
+            input
+        }
+    }
+
+    let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .variable_directive(Trim)
+        .variable_directive(Lowercase)
+        .variable_directive(Length)
+        .finish();
+
+    let query = Request::new(
+        r#"query($a: String!, $b: String! = " Default " @trim @lowercase) {
+            a: value(input: $a)
+            b: value(input: $b)
+        }"#,
+    )
+    .variables(Variables::from_json(serde_json::json!({ "a": " Abc " })));
+    assert_eq!(
+        schema.execute(query).await.data,
+        serde_json::json!({
+            "a": " Abc ",
+            "b": "default",
+        })
+    );
+
+    let query = Request::new(
+        r#"query($a: String! @trim @lowercase) {
+            value(input: $a)
+        }"#,
+    )
+    .variables(Variables::from_json(serde_json::json!({ "a": " Abc " })));
+    assert_eq!(
+        schema.execute(query).await.data,
+        serde_json::json!({ "value": "abc" })
+    );
+
+    assert_eq!(
+        schema
+            .execute(r#"{ a: value(input: " Abc ") @trim b: value(input: " Abc ") }"#)
+            .await
+            .data,
+        serde_json::json!({
+            "a": "Abc",
+            "b": " Abc ",
+        })
+    );
+
+    // The transformed values are checked
+    for query in &[
+        r#"query($a: String! = "a" @length) { value(input: $a) }"#,
+        r#"{ value(input: "a") @length }"#,
+    ] {
+        assert!(
+            matches!(
+                schema.execute(*query).await.into_result(),
+                Err(Error::Rule { .. })
+            ),
+            "{}",
+            query
+        );
+    }
+
+    assert!(schema
+        .execute(r#"query($a: Int = 1 @lowercase) { value(input: "a") }"#)
+        .await
+        .is_err());
+    assert!(Schema::new(QueryRoot, EmptyMutation, EmptySubscription)
+        .execute(r#"query($a: String = "a" @trim) { value(input: "a") }"#)
+        .await
+        .is_err());
+}
+
+#[async_std::test]
+pub async fn test_repeatable_variable_directive() {
+    use async_graphql::parser::types::Name;
+    use async_graphql::registry::MetaInputValue;
+    use std::collections::BTreeMap;
+
+    #[derive(InputObject)]
+    struct ReplaceRule {
+        from: String,
+        to: String,
+    }
+
+    struct Replace;
+
+    impl VariableDirective for Replace {
+        fn name(&self) -> &'static str {
+            "replace"
+        }
+
+        fn args(&self) -> indexmap::IndexMap<&'static str, MetaInputValue> {
+            let mut args = indexmap::IndexMap::new();
+            args.insert(
+                "rule",
+                MetaInputValue {
+                    name: "rule",
+                    description: None,
+                    ty: "ReplaceRule!".to_string(),
+                    default_value: None,
+                    validator: None,
+                },
+            );
+            args
+        }
+
+        fn is_repeatable(&self) -> bool {
+            true
+        }
+
+        fn transform(&self, args: &BTreeMap<Name, Value>, value: Value) -> FieldResult<Value> {
+            let rule = match args.get("rule") {
+                Some(Value::Object(rule)) => rule,
+                _ => return Err("Missing rule".into()),
+            };
+            match (value, rule.get("from"), rule.get("to")) {
+                (Value::String(s), Some(Value::String(from)), Some(Value::String(to))) => {
+                    Ok(Value::String(s.replace(from.as_str(), to)))
+                }
+                (value, _, _) => Ok(value),
+            }
+        }
+    }
+
+    struct Trim;
+
+    impl VariableDirective for Trim {
+        fn name(&self) -> &'static str {
+            "trim"
+        }
+
+        fn transform(&self, _args: &BTreeMap<Name, Value>, value: Value) -> FieldResult<Value> {
+            match value {
+                Value::String(s) => Ok(Value::String(s.trim().to_string())),
+                value => Ok(value),
+            }
+        }
+    }
+
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {
+        pub async fn value(&self, input: String) -> String {
+            input
+        }
+    }
+
+    let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .register_type::<ReplaceRule>()
+        .variable_directive(Replace)
+        .variable_directive(Trim)
+        .finish();
+
+    let query = Request::new(
+        r#"query($a: String!
+            @replace(rule: { from: "a", to: "b" })
+            @replace(rule: { from: "c", to: "d" })) {
+            value(input: $a)
+        }"#,
+    )
+    .variables(Variables::from_json(serde_json::json!({ "a": "abc" })));
+    assert_eq!(
+        schema.execute(query).await.data,
+        serde_json::json!({ "value": "bbd" })
+    );
+
+    assert!(schema
+        .execute(r#"query($a: String = "a" @trim @trim) { value(input: "a") }"#)
+        .await
+        .is_err());
+    assert!(schema
+        .execute(r#"query($a: String = "a" @replace(rule: { from: 1 })) { value(input: "a") }"#)
+        .await
+        .is_err());
+
+    assert_eq!(
+        schema
+            .execute(r#"{ __schema { directives { name isRepeatable } } }"#)
+            .await
+            .data["__schema"]["directives"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|directive| directive["isRepeatable"] == true)
+            .collect::<Vec<_>>(),
+        vec![&serde_json::json!({ "name": "replace", "isRepeatable": true })]
+    );
+}
+
+#[async_std::test]
+pub async fn test_operation_directives() {
+    use async_graphql::extensions::{Extension, ExtensionContext};
+    use async_graphql::parser::types::{ExecutableDefinition, ExecutableDocument};
+    use async_graphql::registry::{__DirectiveLocation, MetaDirective, MetaInputValue};
+    use futures::lock::Mutex;
+    use std::sync::Arc;
+
+    type Log = Arc<Mutex<Vec<String>>>;
+
+    struct RecordDirectives(Log);
+
+    #[async_trait::async_trait]
+    impl Extension for RecordDirectives {
+        async fn parse_end(&mut self, _ctx: &ExtensionContext<'_>, document: &ExecutableDocument) {
+            let mut log = self.0.lock().await;
+            for definition in &document.definitions {
+                if let ExecutableDefinition::Operation(operation) = definition {
+                    for directive in &operation.node.directives {
+                        log.push(format!("operation @{}", directive.node.name.node));
+                    }
+                    for variable in &operation.node.variable_definitions {
+                        for directive in &variable.node.directives {
+                            log.push(format!(
+                                "${} @{}",
+                                variable.node.name.node, directive.node.name.node
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {
+        pub async fn value(&self, input: i32) -> i32 {
+            input
+        }
+    }
+
+    let log = Log::default();
+    let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .directive(MetaDirective {
+            name: "persisted",
+            description: Some("The operation is persisted by the client."),
+            locations: vec![__DirectiveLocation::QUERY, __DirectiveLocation::MUTATION],
+            args: {
+                let mut args = indexmap::IndexMap::new();
+                args.insert(
+                    "id",
+                    MetaInputValue {
+                        name: "id",
+                        description: None,
+                        ty: "String!".to_string(),
+                        default_value: None,
+                        validator: None,
+                    },
+                );
+                args
+            },
+            is_repeatable: false,
+        })
+        .directive(MetaDirective {
+            name: "sensitive",
+            description: None,
+            locations: vec![__DirectiveLocation::VARIABLE_DEFINITION],
+            args: Default::default(),
+            is_repeatable: false,
+        })
+        .extension({
+            let log = log.clone();
+            move || RecordDirectives(log.clone())
+        })
+        .finish();
+
+    let query = Request::new(
+        r#"query($a: Int! @sensitive) @persisted(id: "abc") {
+            value(input: $a)
+        }"#,
+    )
+    .variables(Variables::from_json(serde_json::json!({ "a": 10 })));
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap().data,
+        serde_json::json!({ "value": 10 })
+    );
+    assert_eq!(
+        *log.lock().await,
+        vec![
+            "operation @persisted".to_string(),
+            "$a @sensitive".to_string()
+        ]
+    );
+
+    // The directives are validated like the built-in ones.
+    assert!(schema
+        .execute(r#"query @persisted { value(input: 1) }"#)
+        .await
+        .is_err());
+    assert!(schema
+        .execute(r#"query @sensitive { value(input: 1) }"#)
+        .await
+        .is_err());
+}
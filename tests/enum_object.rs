@@ -0,0 +1,79 @@
+use async_graphql::*;
+
+#[async_std::test]
+pub async fn test_object_on_enum() {
+    enum Order {
+        Pending { id: i32 },
+        Shipped { id: i32, tracking: String },
+        Cancelled(i32, String),
+    }
+
+    #[Object]
+    impl Order {
+        async fn id(&self) -> i32 {
+            match self {
+                Order::Pending { id } | Order::Shipped { id, .. } | Order::Cancelled(id, _) => *id,
+            }
+        }
+
+        #[field(variant = "Shipped")]
+        async fn tracking(&self) -> &str {
+            match self {
+                Order::Shipped { tracking, .. } => tracking,
+                _ => unreachable!(),
+            }
+        }
+
+        #[field(variant = "Pending, Cancelled")]
+        async fn can_ship(&self) -> bool {
+            matches!(self, Order::Pending { .. })
+        }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn orders(&self) -> Vec<Order> {
+            vec![
+                Order::Pending { id: 1 },
+                Order::Shipped {
+                    id: 2,
+                    tracking: "abc".to_string(),
+                },
+                Order::Cancelled(3, "reason".to_string()),
+            ]
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute("{ orders { id tracking canShip } }")
+            .await
+            .data,
+        serde_json::json!({
+            "orders": [
+                { "id": 1, "tracking": null, "canShip": true },
+                { "id": 2, "tracking": "abc", "canShip": null },
+                { "id": 3, "tracking": null, "canShip": false },
+            ]
+        })
+    );
+
+    assert_eq!(
+        schema
+            .execute(r#"{ __type(name: "Order") { fields { name type { kind } } } }"#)
+            .await
+            .data,
+        serde_json::json!({
+            "__type": {
+                "fields": [
+                    { "name": "id", "type": { "kind": "NON_NULL" } },
+                    { "name": "tracking", "type": { "kind": "SCALAR" } },
+                    { "name": "canShip", "type": { "kind": "SCALAR" } },
+                ]
+            }
+        })
+    );
+}
@@ -0,0 +1,75 @@
+use async_graphql::extensions::{Extension, ExtensionContext, ResolveInfo};
+use async_graphql::*;
+use futures::lock::Mutex;
+use std::sync::Arc;
+
+#[async_std::test]
+pub async fn test_async_extension() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn a(&self) -> i32 {
+            1
+        }
+
+        async fn b(&self) -> i32 {
+            2
+        }
+    }
+
+    struct ClientName(&'static str);
+
+    type Log = Arc<Mutex<Vec<String>>>;
+
+    struct RecordFields {
+        log: Log,
+        client: Option<&'static str>,
+        fields: Vec<String>,
+    }
+
+    #[async_trait::async_trait]
+    impl Extension for RecordFields {
+        async fn request_start(
+            &mut self,
+            ctx: &ExtensionContext<'_>,
+            _extensions: &RequestExtensions,
+        ) {
+            self.client = ctx.data_opt::<ClientName>().map(|name| name.0);
+        }
+
+        async fn resolve_end(&mut self, _ctx: &ExtensionContext<'_>, info: &ResolveInfo<'_>) {
+            self.fields.push(info.path_node.to_string());
+        }
+
+        async fn execution_end(&mut self, _ctx: &ExtensionContext<'_>) {
+            self.fields.sort();
+            self.log.lock().await.push(format!(
+                "{}: {}",
+                self.client.unwrap_or("unknown"),
+                self.fields.join(", ")
+            ));
+        }
+    }
+
+    let log = Log::default();
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .extension({
+            let log = log.clone();
+            move || RecordFields {
+                log: log.clone(),
+                client: None,
+                fields: Vec::new(),
+            }
+        })
+        .finish();
+
+    schema
+        .execute(Request::new("{ a b }").data(ClientName("web")))
+        .await;
+    schema.execute("{ b }").await;
+    assert_eq!(
+        *log.lock().await,
+        vec!["web: a, b".to_string(), "unknown: b".to_string()]
+    );
+}
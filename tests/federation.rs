@@ -87,3 +87,115 @@ pub async fn test_federation() {
         })
     );
 }
+
+#[async_std::test]
+pub async fn test_check_federation() {
+    let schema = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema.check_federation(),
+        Err(vec![
+            "Provides of field \"Review.author\": type \"User\" has no field \"username\"."
+                .to_string()
+        ])
+    );
+
+    struct Account {
+        id: ID,
+    }
+
+    #[Object(extends)]
+    impl Account {
+        #[field(external)]
+        async fn id(&self) -> &ID {
+            &self.id
+        }
+
+        #[field(external)]
+        async fn email(&self) -> String {
+            unimplemented!()
+        }
+
+        async fn name(&self) -> String {
+            unimplemented!()
+        }
+
+        #[field(requires = "email name")]
+        async fn greeting(&self) -> String {
+            unimplemented!()
+        }
+
+        #[field(provides = "id")]
+        async fn owner(&self) -> Account {
+            unimplemented!()
+        }
+    }
+
+    struct AccountQuery;
+
+    #[Object]
+    impl AccountQuery {
+        #[entity]
+        async fn find_account_by_id(&self, id: ID) -> Account {
+            Account { id }
+        }
+    }
+
+    let schema = Schema::new(AccountQuery, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema.check_federation(),
+        Err(vec![
+            "Requires of field \"Account.greeting\": field \"Account.name\" is not marked as external."
+                .to_string()
+        ])
+    );
+}
+
+#[async_std::test]
+pub async fn test_federation_options() {
+    struct MutationRoot;
+
+    #[Object]
+    impl MutationRoot {
+        async fn add_review(&self) -> i32 {
+            unimplemented!()
+        }
+    }
+
+    let query = r#"{ _service { sdl } }"#;
+
+    let schema = Schema::new(QueryRoot, MutationRoot, EmptySubscription);
+    let sdl = schema.sdl();
+    assert!(sdl.contains("type QueryRoot {"));
+    assert!(!sdl.contains("extend type QueryRoot"));
+    assert!(sdl.contains("type MutationRoot {"));
+    assert!(!sdl.contains("extend type MutationRoot"));
+    assert!(schema.execute(query).await.into_result().is_ok());
+
+    let schema = Schema::build(QueryRoot, MutationRoot, EmptySubscription)
+        .extend_root_types()
+        .finish();
+    let sdl = schema.sdl();
+    assert!(sdl.contains("extend type QueryRoot {"));
+    assert!(sdl.contains("extend type MutationRoot {"));
+    assert!(sdl.contains("extend type Product"));
+    assert!(!sdl.contains("extend type Review"));
+
+    for validation_mode in &[ValidationMode::Strict, ValidationMode::Fast] {
+        let schema = Schema::build(QueryRoot, MutationRoot, EmptySubscription)
+            .disable_federation()
+            .validation_mode(*validation_mode)
+            .finish();
+        assert!(schema.execute(query).await.is_err());
+        assert!(schema
+            .execute(r#"{ _entities(representations: []) { __typename } }"#)
+            .await
+            .is_err());
+        assert_eq!(
+            schema
+                .execute("{ __type(name: \"QueryRoot\") { fields { name } } }")
+                .await
+                .data,
+            serde_json::json!({ "__type": { "fields": [] } })
+        );
+    }
+}
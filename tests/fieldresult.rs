@@ -65,3 +65,110 @@ pub async fn test_fieldresult() {
         }
     );
 }
+
+#[async_std::test]
+pub async fn test_error_path_with_aliases_and_indices() {
+    struct MyObject(i32);
+
+    #[Object]
+    impl MyObject {
+        async fn value(&self) -> FieldResult<i32> {
+            if self.0 == 1 {
+                Err("TestError".into())
+            } else {
+                Ok(self.0)
+            }
+        }
+
+        async fn double(&self, n: i8) -> i32 {
+            self.0 * n as i32
+        }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn objs(&self) -> Vec<MyObject> {
+            vec![MyObject(0), MyObject(1)]
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+
+    assert_eq!(
+        schema
+            .execute("{ items: objs { renamed: value } }")
+            .await
+            .into_result()
+            .unwrap_err(),
+        Error::Query {
+            pos: Pos {
+                line: 1,
+                column: 17
+            },
+            path: Some(serde_json::json!(["items", 1, "renamed"])),
+            err: QueryError::FieldError {
+                err: "TestError".to_string(),
+                extended_error: None,
+            },
+        }
+    );
+
+    assert_eq!(
+        schema
+            .execute("{ objs { a: double(n: 300) } }")
+            .await
+            .into_result()
+            .unwrap_err(),
+        Error::Query {
+            pos: Pos {
+                line: 1,
+                column: 23
+            },
+            path: Some(serde_json::json!(["objs", 0, "a"])),
+            err: QueryError::ParseInputValue {
+                reason: "Only integers from -128 to 127 are accepted.".to_string()
+            },
+        }
+    );
+}
+
+#[async_std::test]
+pub async fn test_error_kind() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            10
+        }
+
+        async fn error(&self) -> FieldResult<i32> {
+            Err("TestError".into())
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .limit_depth(2)
+        .finish();
+
+    let resp = schema.execute("{ value }").await;
+    assert_eq!(resp.error_kind, None);
+    assert!(!resp.is_request_error());
+
+    let resp = schema.execute("{ error }").await;
+    assert_eq!(resp.error_kind, Some(ErrorKind::Field));
+    assert!(!resp.is_request_error());
+
+    for query in &[
+        "{ value",
+        "{ missing }",
+        "query A { value } query B { value }",
+        "{ __schema { types { name } } }",
+    ] {
+        let resp = schema.execute(*query).await;
+        assert_eq!(resp.error_kind, Some(ErrorKind::Request), "{}", query);
+        assert!(resp.is_request_error());
+    }
+}
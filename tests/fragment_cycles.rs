@@ -0,0 +1,194 @@
+use async_graphql::parser::parse_query;
+use async_graphql::*;
+use std::collections::HashSet;
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn value(&self) -> i32 {
+        1
+    }
+
+    async fn obj(&self) -> Query {
+        Query
+    }
+}
+
+fn rule_errors(err: Error) -> Vec<RuleError> {
+    match err {
+        Error::Rule { errors } => errors,
+        err => panic!("expected rule errors, got {:?}", err),
+    }
+}
+
+#[async_std::test]
+pub async fn test_fragment_cycle_error() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+
+    let query = r#"{ ...A }
+        fragment A on Query { obj { ...B } }
+        fragment B on Query { ...C }
+        fragment C on Query { value ...A }"#;
+    let errors = rule_errors(schema.execute(query).await.into_result().unwrap_err());
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        errors[0].message,
+        r#"Cannot spread fragment "A" within itself via "B", "C""#
+    );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 2,
+            column: 37
+        }]
+    );
+
+    let query = r#"{ ...A } fragment A on Query { ...A }"#;
+    let errors = rule_errors(schema.execute(query).await.into_result().unwrap_err());
+    assert_eq!(
+        errors[0].message,
+        r#"Cannot spread fragment "A" within itself"#
+    );
+}
+
+#[async_std::test]
+pub async fn test_fragment_depth() {
+    let query = r#"{ ...A obj { ...C } }
+        fragment A on Query { ...B }
+        fragment B on Query { ...C }
+        fragment C on Query { value }"#;
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .limit_fragment_depth(3)
+        .finish();
+    assert_eq!(
+        schema.execute(query).await.data,
+        serde_json::json!({ "value": 1, "obj": { "value": 1 } })
+    );
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .limit_fragment_depth(2)
+        .finish();
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap_err(),
+        Error::Query {
+            pos: Pos::default(),
+            path: None,
+            err: QueryError::FragmentTooDeep { limit: 2 },
+        }
+    );
+}
+
+#[test]
+pub fn test_long_fragment_chains() {
+    const COUNT: usize = 1_000;
+
+    let mut query = String::from("{ ...F0 }\n");
+    for i in 0..COUNT {
+        query += &format!("fragment F{} on Query {{ ...F{} }}\n", i, (i + 1) % COUNT);
+    }
+    let document = parse_query(&query).unwrap();
+
+    for mode in &[ValidationMode::Strict, ValidationMode::Fast] {
+        let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+            .validation_mode(*mode)
+            .finish();
+        let errors = rule_errors(schema.validate(&document, &Default::default()).unwrap_err());
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0]
+            .message
+            .starts_with(r#"Cannot spread fragment "F0" within itself via "F1", "F2""#));
+    }
+}
+
+/// A xorshift generator, so that the fuzz test is reproducible.
+struct Rng(u64);
+
+impl Rng {
+    fn below(&mut self, n: u64) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0 % n
+    }
+}
+
+fn has_cycle(spreads: &[Vec<usize>]) -> bool {
+    fn visit(spreads: &[Vec<usize>], n: usize, state: &mut [u8]) -> bool {
+        match state[n] {
+            1 => return true,
+            2 => return false,
+            _ => {}
+        }
+        state[n] = 1;
+        if spreads[n].iter().any(|&m| visit(spreads, m, state)) {
+            return true;
+        }
+        state[n] = 2;
+        false
+    }
+
+    let mut state = vec![0; spreads.len()];
+    (0..spreads.len()).any(|n| visit(spreads, n, &mut state))
+}
+
+#[test]
+pub fn test_fuzz_fragment_cycles() {
+    let strict = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let fast = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .validation_mode(ValidationMode::Fast)
+        .finish();
+    let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+
+    for _ in 0..500 {
+        let count = 1 + rng.below(8) as usize;
+        let spreads: Vec<Vec<usize>> = (0..count)
+            .map(|_| {
+                (0..rng.below(4))
+                    .map(|_| rng.below(count as u64) as usize)
+                    .collect()
+            })
+            .collect();
+
+        let mut query = String::from("{");
+        for n in 0..count {
+            query += &format!(" ...F{}", n);
+        }
+        query += " }\n";
+        for (n, spreads) in spreads.iter().enumerate() {
+            query += &format!("fragment F{} on Query {{ value", n);
+            for m in spreads {
+                if rng.below(2) == 0 {
+                    query += &format!(" ...F{}", m);
+                } else {
+                    query += &format!(" obj {{ ...F{} }}", m);
+                }
+            }
+            query += " }\n";
+        }
+        let document = parse_query(&query).unwrap();
+
+        for schema in &[&strict, &fast] {
+            let res = schema.validate(&document, &Default::default());
+            assert_eq!(res.is_err(), has_cycle(&spreads), "{}", query);
+
+            for err in res.err().map(rule_errors).unwrap_or_default() {
+                // Every error names a cycle that is in the document.
+                let names: Vec<usize> = err
+                    .message
+                    .split('"')
+                    .skip(1)
+                    .step_by(2)
+                    .map(|name| name[1..].parse().unwrap())
+                    .collect();
+                let mut visited = HashSet::new();
+                for (i, n) in names.iter().enumerate() {
+                    assert!(visited.insert(n), "{}", err.message);
+                    let next = names[(i + 1) % names.len()];
+                    assert!(spreads[*n].contains(&next), "{}: {}", err.message, query);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,161 @@
+use async_graphql::*;
+
+#[async_std::test]
+pub async fn test_generic_object() {
+    struct Edge<T> {
+        cursor: String,
+        node: T,
+    }
+
+    #[Object(name = "{T}Edge")]
+    impl<T: OutputValueType + Send + Sync> Edge<T> {
+        async fn cursor(&self) -> &str {
+            &self.cursor
+        }
+
+        async fn node(&self) -> &T {
+            &self.node
+        }
+    }
+
+    #[derive(SimpleObject)]
+    #[graphql(name = "Page_{T}")]
+    struct Page<T>
+    where
+        T: OutputValueType + Send + Sync,
+    {
+        edges: Vec<T>,
+    }
+
+    #[derive(SimpleObject)]
+    struct MyObj {
+        value: i32,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn ints(&self) -> Page<Edge<i32>> {
+            Page {
+                edges: vec![Edge {
+                    cursor: "a".to_string(),
+                    node: 10,
+                }],
+            }
+        }
+
+        async fn objects(&self) -> Page<Edge<MyObj>> {
+            Page {
+                edges: vec![Edge {
+                    cursor: "b".to_string(),
+                    node: MyObj { value: 20 },
+                }],
+            }
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute(
+                r#"{
+                    ints { __typename edges { __typename cursor node } }
+                    objects { __typename edges { __typename cursor node { value } } }
+                }"#
+            )
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "ints": {
+                "__typename": "Page_IntEdge",
+                "edges": [{ "__typename": "IntEdge", "cursor": "a", "node": 10 }],
+            },
+            "objects": {
+                "__typename": "Page_MyObjEdge",
+                "edges": [{ "__typename": "MyObjEdge", "cursor": "b", "node": { "value": 20 } }],
+            },
+        })
+    );
+
+    assert_eq!(
+        schema
+            .execute(
+                r#"{ __type(name: "MyObjEdge") { fields { name type { name ofType { name } } } } }"#
+            )
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "__type": {
+                "fields": [
+                    { "name": "cursor", "type": { "name": null, "ofType": { "name": "String" } } },
+                    { "name": "node", "type": { "name": null, "ofType": { "name": "MyObj" } } },
+                ]
+            }
+        })
+    );
+}
+
+#[async_std::test]
+pub async fn test_generic_object_type_parameters() {
+    struct Edge<T> {
+        node: T,
+    }
+
+    #[Object(name = "{T}Edge")]
+    impl<T: OutputValueType + Send + Sync> Edge<T> {
+        async fn node(&self) -> &T {
+            &self.node
+        }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn int(&self) -> Edge<i32> {
+            Edge { node: 10 }
+        }
+
+        async fn optional_int(&self) -> Edge<Option<i32>> {
+            Edge { node: None }
+        }
+
+        async fn ints(&self) -> Edge<Vec<i32>> {
+            Edge { node: vec![10] }
+        }
+
+        async fn optional_ints(&self) -> Edge<Option<Vec<Option<i32>>>> {
+            Edge {
+                node: Some(vec![None]),
+            }
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute(
+                r#"{
+                    int { __typename }
+                    optionalInt { __typename }
+                    ints { __typename }
+                    optionalInts { __typename }
+                }"#
+            )
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "int": { "__typename": "IntEdge" },
+            "optionalInt": { "__typename": "NullableIntEdge" },
+            "ints": { "__typename": "ListOfIntEdge" },
+            "optionalInts": { "__typename": "NullableListOfNullableIntEdge" },
+        })
+    );
+}
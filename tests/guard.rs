@@ -308,3 +308,176 @@ pub async fn test_guard_forward_arguments() {
         }
     );
 }
+
+#[async_std::test]
+pub async fn test_requires_scopes() {
+    use async_graphql::guard::ScopeProvider;
+
+    #[derive(SimpleObject)]
+    struct MyObj {
+        #[field(requires_scopes = "read:secret")]
+        secret: i32,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        #[field(requires_scopes = "read:user write:user")]
+        async fn value(&self) -> i32 {
+            1
+        }
+
+        async fn obj(&self) -> MyObj {
+            MyObj { secret: 2 }
+        }
+    }
+
+    fn scopes(scopes: &[&str]) -> Box<dyn ScopeProvider> {
+        Box::new(scopes.iter().map(ToString::to_string).collect::<Vec<_>>())
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+
+    assert_eq!(
+        schema
+            .execute(Request::new("{ value obj { secret } }").data(scopes(&[
+                "read:user",
+                "write:user",
+                "read:secret"
+            ])))
+            .await
+            .data,
+        serde_json::json!({ "value": 1, "obj": { "secret": 2 } })
+    );
+
+    assert_eq!(
+        schema
+            .execute(Request::new("{ value }").data(scopes(&["read:user"])))
+            .await
+            .into_result()
+            .unwrap_err(),
+        Error::Query {
+            pos: Pos { line: 1, column: 3 },
+            path: Some(serde_json::json!(["value"])),
+            err: QueryError::FieldError {
+                err: "Forbidden".to_string(),
+                extended_error: Some(serde_json::json!({
+                    "code": "FORBIDDEN",
+                    "missingScopes": ["write:user"],
+                })),
+            },
+        }
+    );
+
+    assert_eq!(
+        schema
+            .execute("{ obj { secret } }")
+            .await
+            .into_result()
+            .unwrap_err(),
+        Error::Query {
+            pos: Pos { line: 1, column: 9 },
+            path: Some(serde_json::json!(["obj", "secret"])),
+            err: QueryError::FieldError {
+                err: "Forbidden".to_string(),
+                extended_error: Some(serde_json::json!({
+                    "code": "FORBIDDEN",
+                    "missingScopes": ["read:secret"],
+                })),
+            },
+        }
+    );
+}
+
+#[async_std::test]
+pub async fn test_guard_combinators() {
+    #[derive(SimpleObject)]
+    struct MyObj {
+        #[field(guard(or(RoleGuard(role = "Role::Admin"), UserGuard(username = r#""test""#))))]
+        value: i32,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        #[field(guard(or(
+            RoleGuard(role = "Role::Admin"),
+            and(RoleGuard(role = "Role::Guest"), UserGuard(username = r#""test""#))
+        )))]
+        async fn value(&self) -> i32 {
+            1
+        }
+
+        async fn obj(&self) -> MyObj {
+            MyObj { value: 99 }
+        }
+    }
+
+    struct Subscription;
+
+    #[Subscription]
+    impl Subscription {
+        #[field(guard(race(RoleGuard(role = "Role::Admin"), UserGuard(username = r#""test""#))))]
+        async fn values(&self) -> impl Stream<Item = i32> {
+            futures::stream::iter(vec![1, 2])
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, Subscription);
+
+    let execute = |role: Role, username: &str| {
+        let schema = &schema;
+        let request = Request::new("{ value obj { value } }")
+            .data(role)
+            .data(Username(username.to_string()));
+        async move { schema.execute(request).await }
+    };
+
+    assert_eq!(
+        execute(Role::Admin, "other").await.data,
+        serde_json::json!({ "value": 1, "obj": { "value": 99 } })
+    );
+    assert_eq!(
+        execute(Role::Guest, "test").await.data,
+        serde_json::json!({ "value": 1, "obj": { "value": 99 } })
+    );
+    assert_eq!(
+        execute(Role::Guest, "other")
+            .await
+            .into_result()
+            .unwrap_err(),
+        Error::Query {
+            pos: Pos { line: 1, column: 3 },
+            path: Some(serde_json::json!(["value"])),
+            err: QueryError::FieldError {
+                err: "Forbidden".to_string(),
+                extended_error: None,
+            },
+        }
+    );
+
+    assert_eq!(
+        schema
+            .execute_stream(
+                Request::new("subscription { values }")
+                    .data(Role::Guest)
+                    .data(Username("test".to_string()))
+            )
+            .map(|item| item.data)
+            .collect::<Vec<_>>()
+            .await,
+        vec![
+            serde_json::json!({ "values": 1 }),
+            serde_json::json!({ "values": 2 }),
+        ]
+    );
+    assert!(schema
+        .execute_stream(Request::new("subscription { values }").data(Role::Guest))
+        .boxed()
+        .next()
+        .await
+        .unwrap()
+        .is_err());
+}
@@ -0,0 +1,62 @@
+use async_graphql::http::{HealthCheck, HealthCheckResult};
+use async_graphql::*;
+use futures::{Stream, StreamExt};
+
+#[async_std::test]
+pub async fn test_health_check() {
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    struct SubscriptionRoot;
+
+    #[Subscription]
+    impl SubscriptionRoot {
+        async fn values(&self) -> impl Stream<Item = i32> {
+            futures::stream::iter(0..10)
+        }
+    }
+
+    let schema = Schema::new(QueryRoot, EmptyMutation, SubscriptionRoot);
+    let health = HealthCheck::new()
+        .check("cache", || async { Ok(()) })
+        .max_subscriptions(0);
+
+    let status = health.liveness(&schema);
+    assert!(status.healthy);
+    assert_eq!(status.checks.keys().collect::<Vec<_>>(), vec!["schema"]);
+
+    let status = health.readiness(&schema).await;
+    assert!(status.healthy);
+    assert_eq!(
+        status.checks["subscriptions"],
+        HealthCheckResult {
+            healthy: true,
+            message: Some("0 of 0 active subscriptions".to_string()),
+        }
+    );
+
+    let mut stream = schema.execute_stream("subscription { values }").boxed();
+    stream.next().await.unwrap();
+    assert_eq!(schema.active_subscriptions(), 1);
+    assert!(!health.readiness(&schema).await.healthy);
+    drop(stream);
+    assert_eq!(schema.active_subscriptions(), 0);
+
+    let health = health.check("database", || async { Err("timeout".to_string()) });
+    let status = health.readiness(&schema).await;
+    assert!(!status.healthy);
+    assert_eq!(
+        status.checks["database"],
+        HealthCheckResult {
+            healthy: false,
+            message: Some("timeout".to_string()),
+        }
+    );
+    assert!(status.checks["cache"].healthy);
+}
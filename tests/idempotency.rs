@@ -0,0 +1,244 @@
+use async_graphql::*;
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::time::Duration;
+
+#[async_std::test]
+pub async fn test_idempotency_key() {
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    #[derive(Default)]
+    struct MutationRoot {
+        counter: AtomicI32,
+    }
+
+    #[Object]
+    impl MutationRoot {
+        #[field(idempotent)]
+        async fn increment(&self, by: i32) -> i32 {
+            self.counter.fetch_add(by, Ordering::SeqCst) + by
+        }
+
+        async fn add(&self, by: i32) -> i32 {
+            self.counter.fetch_add(by, Ordering::SeqCst) + by
+        }
+    }
+
+    let schema = Schema::build(QueryRoot, MutationRoot::default(), EmptySubscription)
+        .idempotency_store(MemoryIdempotencyStore::default(), Duration::from_secs(60))
+        .finish();
+    let request = |by: i32, key: Option<&str>| {
+        let request = Request::new("mutation($by: Int!) { increment(by: $by) }")
+            .variables(Variables::from_json(serde_json::json!({ "by": by })));
+        match key {
+            Some(key) => request.data(IdempotencyKey(key.to_string())),
+            None => request,
+        }
+    };
+
+    assert_eq!(
+        schema.execute(request(1, Some("a"))).await.data,
+        serde_json::json!({ "increment": 1 })
+    );
+    assert_eq!(
+        schema.execute(request(1, Some("a"))).await.data,
+        serde_json::json!({ "increment": 1 })
+    );
+    assert_eq!(
+        schema.execute(request(1, Some("b"))).await.data,
+        serde_json::json!({ "increment": 2 })
+    );
+    assert_eq!(
+        schema.execute(request(1, None)).await.data,
+        serde_json::json!({ "increment": 3 })
+    );
+    assert_eq!(
+        schema
+            .execute(request(2, Some("a")))
+            .await
+            .into_result()
+            .unwrap_err(),
+        Error::Query {
+            pos: Pos::default(),
+            path: None,
+            err: QueryError::IdempotencyKeyReused {
+                key: "a".to_string()
+            },
+        }
+    );
+
+    // Fields that are not idempotent are executed again
+    let query = "mutation { add(by: 1) }";
+    assert_eq!(
+        schema
+            .execute(Request::new(query).data(IdempotencyKey("c".to_string())))
+            .await
+            .data,
+        serde_json::json!({ "add": 4 })
+    );
+    assert_eq!(
+        schema
+            .execute(Request::new(query).data(IdempotencyKey("c".to_string())))
+            .await
+            .data,
+        serde_json::json!({ "add": 5 })
+    );
+}
+
+#[async_std::test]
+pub async fn test_idempotent_field_sdl() {
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    struct MutationRoot;
+
+    #[Object]
+    impl MutationRoot {
+        #[field(idempotent)]
+        async fn set_value(&self, value: i32) -> i32 {
+            value
+        }
+    }
+
+    let schema = Schema::build(QueryRoot, MutationRoot, EmptySubscription)
+        .enable_federation()
+        .finish();
+    let resp = schema
+        .execute("{ _service { sdl } }")
+        .await
+        .into_result()
+        .unwrap();
+    assert!(resp.data["_service"]["sdl"]
+        .as_str()
+        .unwrap()
+        .contains("setValue(value: Int!): Int! @idempotent"));
+}
+
+#[async_std::test]
+pub async fn test_idempotency_key_pending() {
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    #[derive(Default)]
+    struct MutationRoot {
+        counter: AtomicI32,
+    }
+
+    #[Object]
+    impl MutationRoot {
+        #[field(idempotent)]
+        async fn increment(&self) -> i32 {
+            async_std::task::sleep(Duration::from_millis(100)).await;
+            self.counter.fetch_add(1, Ordering::SeqCst) + 1
+        }
+
+        #[field(idempotent)]
+        async fn fail(&self) -> FieldResult<i32> {
+            Err("failed".into())
+        }
+
+        #[field(idempotent)]
+        async fn hang(&self) -> i32 {
+            futures::future::pending().await
+        }
+    }
+
+    let schema = Schema::build(QueryRoot, MutationRoot::default(), EmptySubscription)
+        .idempotency_store(MemoryIdempotencyStore::default(), Duration::from_secs(60))
+        .spawner(|fut| futures::executor::block_on(fut))
+        .finish();
+    let request = |query: &str| Request::new(query).data(IdempotencyKey("a".to_string()));
+
+    let (first, second) = futures::join!(
+        schema.execute(request("mutation { increment }")),
+        schema.execute(request("mutation { increment }"))
+    );
+    assert_eq!(first.data, serde_json::json!({ "increment": 1 }));
+    assert_eq!(
+        second.into_result().unwrap_err(),
+        Error::Query {
+            pos: Pos::default(),
+            path: None,
+            err: QueryError::IdempotencyKeyPending {
+                key: "a".to_string()
+            },
+        }
+    );
+    assert_eq!(
+        schema.execute(request("mutation { increment }")).await.data,
+        serde_json::json!({ "increment": 1 })
+    );
+
+    // A failed mutation releases the key
+    let request = |query: &str| Request::new(query).data(IdempotencyKey("b".to_string()));
+    assert!(schema.execute(request("mutation { fail }")).await.is_err());
+    assert_eq!(
+        schema.execute(request("mutation { increment }")).await.data,
+        serde_json::json!({ "increment": 2 })
+    );
+
+    // A dropped request releases the key
+    let request = |query: &str| Request::new(query).data(IdempotencyKey("c".to_string()));
+    assert!(
+        futures::FutureExt::now_or_never(schema.execute(request("mutation { hang }"))).is_none()
+    );
+    assert_eq!(
+        schema.execute(request("mutation { increment }")).await.data,
+        serde_json::json!({ "increment": 3 })
+    );
+}
+
+#[async_std::test]
+pub async fn test_idempotency_key_expires() {
+    let store = MemoryIdempotencyStore::default();
+    let response = IdempotentResponse {
+        request_hash: 1,
+        data: serde_json::json!({ "value": 1 }),
+        extensions: None,
+    };
+    assert_eq!(
+        store.reserve("a", 1, Duration::from_secs(60)).await,
+        IdempotencyReservation::Reserved
+    );
+    assert_eq!(
+        store.reserve("a", 2, Duration::from_secs(60)).await,
+        IdempotencyReservation::Pending { request_hash: 1 }
+    );
+    store
+        .set("a", response.clone(), Duration::from_secs(60))
+        .await;
+    store
+        .set("b", response.clone(), Duration::from_millis(0))
+        .await;
+    assert_eq!(
+        store.reserve("a", 1, Duration::from_secs(60)).await,
+        IdempotencyReservation::Stored(response)
+    );
+    assert_eq!(
+        store.reserve("b", 1, Duration::from_secs(60)).await,
+        IdempotencyReservation::Reserved
+    );
+    store.release("b").await;
+    assert_eq!(
+        store.reserve("b", 2, Duration::from_secs(60)).await,
+        IdempotencyReservation::Reserved
+    );
+}
@@ -0,0 +1,57 @@
+use async_graphql::*;
+
+struct UserId(i32);
+
+fn current_user_id(ctx: &Context<'_>) -> FieldResult<i32> {
+    ctx.data_opt::<UserId>()
+        .map(|id| id.0)
+        .ok_or_else(|| "Not logged in".into())
+}
+
+#[async_std::test]
+pub async fn test_inject_args() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn greeting(
+            &self,
+            #[arg(inject = "current_user_id")] user_id: i32,
+            greeting: String,
+        ) -> String {
+            format!("{} user{}", greeting, user_id)
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute(Request::new(r#"{ greeting(greeting: "Hello") }"#).data(UserId(7)))
+            .await
+            .data,
+        serde_json::json!({ "greeting": "Hello user7" })
+    );
+
+    assert_eq!(
+        schema
+            .execute(r#"{ greeting(greeting: "Hello") }"#)
+            .await
+            .into_result()
+            .unwrap_err(),
+        Error::Query {
+            pos: Pos { line: 1, column: 3 },
+            path: Some(serde_json::json!(["greeting"])),
+            err: QueryError::FieldError {
+                err: "Not logged in".to_string(),
+                extended_error: None,
+            },
+        }
+    );
+
+    // The injected argument is not part of the schema.
+    let res = schema
+        .execute(r#"{ greeting(greeting: "Hello", userId: 1) }"#)
+        .await;
+    assert!(res.is_err());
+    assert!(!schema.sdl().contains("userId"));
+}
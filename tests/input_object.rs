@@ -295,3 +295,186 @@ pub async fn test_inputobject_flatten_multiple() {
         .unwrap()
     );
 }
+
+#[async_std::test]
+pub async fn test_input_object_unknown_fields() {
+    #[derive(InputObject)]
+    struct MyInput {
+        name: String,
+        value: i32,
+    }
+
+    #[derive(InputObject)]
+    #[graphql(deny_unknown_fields)]
+    struct StrictInput {
+        name: String,
+    }
+
+    #[derive(InputObject)]
+    #[graphql(ignore_unknown_fields)]
+    struct LooseInput {
+        name: String,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn test(&self, input: MyInput) -> String {
+            format!("{}={}", input.name, input.value)
+        }
+
+        async fn strict(&self, input: StrictInput) -> String {
+            input.name
+        }
+
+        async fn loose(&self, input: LooseInput) -> String {
+            input.name
+        }
+    }
+
+    fn rule_messages(err: Error) -> Vec<String> {
+        match err {
+            Error::Rule { errors } => errors.into_iter().map(|err| err.message).collect(),
+            err => panic!("unexpected error {:?}", err),
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        rule_messages(
+            schema
+                .execute(r#"{ test(input: { name: "a", value: 1, nme: "b", zzz: 2 }) }"#)
+                .await
+                .into_result()
+                .unwrap_err()
+        ),
+        vec![
+            "Invalid value for argument \"input\", unknown fields \"nme\" (did you mean \"name\"?), \"zzz\" of type \"MyInput\""
+                .to_string()
+        ]
+    );
+    let query = Request::new(r#"query($input: MyInput!) { test(input: $input) }"#).variables(
+        Variables::from_json(
+            serde_json::json!({ "input": { "name": "a", "value": 1, "valu": 2 } }),
+        ),
+    );
+    assert_eq!(
+        rule_messages(schema.execute(query).await.into_result().unwrap_err()),
+        vec![
+            "Invalid value for argument \"input\", unknown field \"valu\" (did you mean \"value\"?) of type \"MyInput\""
+                .to_string()
+        ]
+    );
+    assert_eq!(
+        schema
+            .execute(r#"{ loose(input: { name: "a", other: 1 }) }"#)
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        serde_json::json!({ "loose": "a" })
+    );
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .unknown_input_fields(UnknownInputFields::Ignore)
+        .finish();
+    assert_eq!(
+        schema
+            .execute(r#"{ test(input: { name: "a", value: 1, other: 2 }) }"#)
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        serde_json::json!({ "test": "a=1" })
+    );
+    assert!(schema
+        .execute(r#"{ strict(input: { name: "a", other: 1 }) }"#)
+        .await
+        .is_err());
+}
+
+#[async_std::test]
+pub async fn test_inputobject_flatten_registered_type() {
+    use async_graphql::validators::IntRange;
+
+    #[derive(InputObject)]
+    struct Pagination {
+        #[field(default = 10, validator(IntRange(min = "1", max = "100")))]
+        limit: i32,
+        #[field(default)]
+        offset: i32,
+    }
+
+    #[derive(InputObject)]
+    struct UserFilter {
+        #[field(name = "nameContains")]
+        name: Option<String>,
+        #[field(default_with = "vec![\"active\".to_string()]")]
+        states: Vec<String>,
+        #[field(flatten)]
+        pagination: Pagination,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn page(&self, pagination: Pagination) -> i32 {
+            pagination.limit + pagination.offset
+        }
+
+        async fn users(&self, filter: UserFilter) -> String {
+            format!(
+                "{:?} {:?} {} {}",
+                filter.name, filter.states, filter.pagination.limit, filter.pagination.offset
+            )
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+
+    assert_eq!(
+        schema
+            .execute(r#"{ page(pagination: { limit: 20 }) users(filter: { nameContains: "a", offset: 5 }) }"#)
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "page": 20,
+            "users": r#"Some("a") ["active"] 10 5"#,
+        })
+    );
+
+    assert!(schema
+        .execute("{ users(filter: { limit: 0 }) }")
+        .await
+        .into_result()
+        .is_err());
+
+    assert_eq!(
+        schema
+            .execute(
+                r#"{
+                    pagination: __type(name: "Pagination") { name }
+                    userFilter: __type(name: "UserFilter") { inputFields { name } }
+                }"#
+            )
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "pagination": { "name": "Pagination" },
+            "userFilter": {
+                "inputFields": [
+                    { "name": "nameContains" },
+                    { "name": "states" },
+                    { "name": "limit" },
+                    { "name": "offset" },
+                ]
+            }
+        })
+    );
+}
@@ -71,7 +71,8 @@ pub async fn test_input_validator_string_min_length() {
                             line: 1,
                             column: 17
                         }),
-                        message: field_error_msg
+                        message: field_error_msg,
+                        suggestions: Vec::new()
                     })
                 }
             );
@@ -88,7 +89,8 @@ pub async fn test_input_validator_string_min_length() {
                             line: 1,
                             column: 14
                         }),
-                        message: object_error_msg
+                        message: object_error_msg,
+                        suggestions: Vec::new()
                     })
                 }
             );
@@ -181,7 +183,8 @@ pub async fn test_input_validator_string_max_length() {
                             line: 1,
                             column: 17
                         }),
-                        message: field_error_msg
+                        message: field_error_msg,
+                        suggestions: Vec::new()
                     })
                 }
             );
@@ -198,7 +201,8 @@ pub async fn test_input_validator_string_max_length() {
                             line: 1,
                             column: 14
                         }),
-                        message: object_error_msg
+                        message: object_error_msg,
+                        suggestions: Vec::new()
                     })
                 }
             );
@@ -318,7 +322,8 @@ pub async fn test_input_validator_string_email() {
                             line: 1,
                             column: 17
                         }),
-                        message: field_error_msg
+                        message: field_error_msg,
+                        suggestions: Vec::new()
                     })
                 }
             );
@@ -336,7 +341,8 @@ pub async fn test_input_validator_string_email() {
                             line: 1,
                             column: 14
                         }),
-                        message: object_error_msg
+                        message: object_error_msg,
+                        suggestions: Vec::new()
                     })
                 }
             );
@@ -466,7 +472,8 @@ pub async fn test_input_validator_string_mac() {
                         line: 1,
                         column: 17
                     }),
-                    message: field_error_msg.clone()
+                    message: field_error_msg.clone(),
+                    suggestions: Vec::new()
                 })
             }
         );
@@ -484,7 +491,8 @@ pub async fn test_input_validator_string_mac() {
                         line: 1,
                         column: 14
                     }),
-                    message: object_error_msg.clone()
+                    message: object_error_msg.clone(),
+                    suggestions: Vec::new()
                 })
             }
         );
@@ -501,7 +509,8 @@ pub async fn test_input_validator_string_mac() {
                         line: 1,
                         column: 17
                     }),
-                    message: field_error_msg
+                    message: field_error_msg,
+                    suggestions: Vec::new()
                 })
             }
         );
@@ -519,7 +528,8 @@ pub async fn test_input_validator_string_mac() {
                         line: 1,
                         column: 14
                     }),
-                    message: object_error_msg
+                    message: object_error_msg,
+                    suggestions: Vec::new()
                 })
             }
         );
@@ -576,7 +586,8 @@ pub async fn test_input_validator_string_mac() {
                             line: 1,
                             column: 17
                         }),
-                        message: field_error_msg
+                        message: field_error_msg,
+                        suggestions: Vec::new()
                     })
                 }
             );
@@ -594,7 +605,8 @@ pub async fn test_input_validator_string_mac() {
                             line: 1,
                             column: 14
                         }),
-                        message: object_error_msg
+                        message: object_error_msg,
+                        suggestions: Vec::new()
                     })
                 }
             );
@@ -635,7 +647,8 @@ pub async fn test_input_validator_string_mac() {
                             line: 1,
                             column: 17
                         }),
-                        message: field_error_msg
+                        message: field_error_msg,
+                        suggestions: Vec::new()
                     })
                 }
             );
@@ -653,7 +666,8 @@ pub async fn test_input_validator_string_mac() {
                             line: 1,
                             column: 14
                         }),
-                        message: object_error_msg
+                        message: object_error_msg,
+                        suggestions: Vec::new()
                     })
                 }
             );
@@ -713,7 +727,8 @@ pub async fn test_input_validator_int_range() {
                             line: 1,
                             column: 17
                         }),
-                        message: field_error_msg
+                        message: field_error_msg,
+                        suggestions: Vec::new()
                     })
                 }
             );
@@ -730,7 +745,8 @@ pub async fn test_input_validator_int_range() {
                             line: 1,
                             column: 14
                         }),
-                        message: object_error_msg
+                        message: object_error_msg,
+                        suggestions: Vec::new()
                     })
                 }
             );
@@ -818,7 +834,8 @@ pub async fn test_input_validator_int_less_than() {
                             line: 1,
                             column: 17
                         }),
-                        message: field_error_msg
+                        message: field_error_msg,
+                        suggestions: Vec::new()
                     })
                 }
             );
@@ -835,7 +852,8 @@ pub async fn test_input_validator_int_less_than() {
                             line: 1,
                             column: 14
                         }),
-                        message: object_error_msg
+                        message: object_error_msg,
+                        suggestions: Vec::new()
                     })
                 }
             );
@@ -925,7 +943,8 @@ pub async fn test_input_validator_int_greater_than() {
                             line: 1,
                             column: 17
                         }),
-                        message: field_error_msg
+                        message: field_error_msg,
+                        suggestions: Vec::new()
                     })
                 }
             );
@@ -942,7 +961,8 @@ pub async fn test_input_validator_int_greater_than() {
                             line: 1,
                             column: 14
                         }),
-                        message: object_error_msg
+                        message: object_error_msg,
+                        suggestions: Vec::new()
                     })
                 }
             );
@@ -1025,7 +1045,8 @@ pub async fn test_input_validator_int_nonzero() {
                             line: 1,
                             column: 17
                         }),
-                        message: field_error_msg
+                        message: field_error_msg,
+                        suggestions: Vec::new()
                     })
                 }
             );
@@ -1042,7 +1063,8 @@ pub async fn test_input_validator_int_nonzero() {
                             line: 1,
                             column: 14
                         }),
-                        message: object_error_msg
+                        message: object_error_msg,
+                        suggestions: Vec::new()
                     })
                 }
             );
@@ -1126,7 +1148,8 @@ pub async fn test_input_validator_int_equal() {
                             line: 1,
                             column: 17
                         }),
-                        message: field_error_msg
+                        message: field_error_msg,
+                        suggestions: Vec::new()
                     })
                 }
             );
@@ -1143,7 +1166,8 @@ pub async fn test_input_validator_int_equal() {
                             line: 1,
                             column: 14
                         }),
-                        message: object_error_msg
+                        message: object_error_msg,
+                        suggestions: Vec::new()
                     })
                 }
             );
@@ -1242,7 +1266,8 @@ pub async fn test_input_validator_list_max_length() {
                             line: 1,
                             column: 17
                         }),
-                        message: field_error_msg
+                        message: field_error_msg,
+                        suggestions: Vec::new()
                     })
                 }
             );
@@ -1259,7 +1284,8 @@ pub async fn test_input_validator_list_max_length() {
                             line: 1,
                             column: 14
                         }),
-                        message: object_error_msg
+                        message: object_error_msg,
+                        suggestions: Vec::new()
                     })
                 }
             );
@@ -1358,7 +1384,8 @@ pub async fn test_input_validator_list_min_length() {
                             line: 1,
                             column: 17
                         }),
-                        message: field_error_msg
+                        message: field_error_msg,
+                        suggestions: Vec::new()
                     })
                 }
             );
@@ -1375,7 +1402,8 @@ pub async fn test_input_validator_list_min_length() {
                             line: 1,
                             column: 14
                         }),
-                        message: object_error_msg
+                        message: object_error_msg,
+                        suggestions: Vec::new()
                     })
                 }
             );
@@ -1482,7 +1510,8 @@ pub async fn test_input_validator_operator_or() {
                             line: 1,
                             column: 17
                         }),
-                        message: field_error_msg
+                        message: field_error_msg,
+                        suggestions: Vec::new()
                     })
                 }
             );
@@ -1499,7 +1528,8 @@ pub async fn test_input_validator_operator_or() {
                             line: 1,
                             column: 14
                         }),
-                        message: object_error_msg
+                        message: object_error_msg,
+                        suggestions: Vec::new()
                     })
                 }
             );
@@ -1599,7 +1629,8 @@ pub async fn test_input_validator_operator_and() {
                             line: 1,
                             column: 17
                         }),
-                        message: field_error_msg
+                        message: field_error_msg,
+                        suggestions: Vec::new()
                     })
                 }
             );
@@ -1616,7 +1647,8 @@ pub async fn test_input_validator_operator_and() {
                             line: 1,
                             column: 14
                         }),
-                        message: object_error_msg
+                        message: object_error_msg,
+                        suggestions: Vec::new()
                     })
                 }
             );
@@ -1722,7 +1754,8 @@ pub async fn test_input_validator_variable() {
                             line: 1,
                             column: 37
                         }),
-                        message: field_error_msg
+                        message: field_error_msg,
+                        suggestions: Vec::new()
                     })
                 }
             );
@@ -1739,7 +1772,8 @@ pub async fn test_input_validator_variable() {
                             line: 1,
                             column: 34
                         }),
-                        message: object_error_msg
+                        message: object_error_msg,
+                        suggestions: Vec::new()
                     })
                 }
             );
@@ -20,10 +20,486 @@ pub async fn test_input_value_custom_error() {
                 line: 1,
                 column: 14
             },
-            path: None,
+            path: Some(serde_json::json!(["parseInt"])),
             err: QueryError::ParseInputValue {
                 reason: "Only integers from -128 to 127 are accepted.".to_string()
             },
         }
     );
 }
+
+#[async_std::test]
+pub async fn test_input_value_uuid_forms() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn uuid(&self, id: uuid::Uuid) -> uuid::Uuid {
+            id
+        }
+
+        async fn id(&self, id: uuid::Uuid) -> ID {
+            id.into()
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let query = r#"{
+        a: uuid(id: "67e55044-10b1-426f-9247-bb680e5fe0c8")
+        b: uuid(id: "67e5504410b1426f9247bb680e5fe0c8")
+        id(id: "67e5504410b1426f9247bb680e5fe0c8")
+    }"#;
+    assert_eq!(
+        schema.execute(query).await.data,
+        serde_json::json!({
+            "a": "67e55044-10b1-426f-9247-bb680e5fe0c8",
+            "b": "67e55044-10b1-426f-9247-bb680e5fe0c8",
+            "id": "67e55044-10b1-426f-9247-bb680e5fe0c8",
+        })
+    );
+}
+
+#[cfg(feature = "isocodes")]
+#[async_std::test]
+pub async fn test_input_value_isocodes() {
+    use iso_currency::Currency;
+    use isocountry::CountryCode;
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn country(&self, code: CountryCode) -> CountryCode {
+            code
+        }
+
+        async fn currency(&self, code: Currency) -> Currency {
+            code
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let query = r#"{
+        a: country(code: "US")
+        b: country(code: "DEU")
+        currency(code: "EUR")
+        __type(name: "CountryCode") { specifiedByUrl }
+    }"#;
+    assert_eq!(
+        schema.execute(query).await.data,
+        serde_json::json!({
+            "a": "US",
+            "b": "DE",
+            "currency": "EUR",
+            "__type": { "specifiedByUrl": "https://www.iso.org/iso-3166-country-codes.html" },
+        })
+    );
+
+    assert!(schema.execute(r#"{ country(code: "XX") }"#).await.is_err());
+}
+
+#[async_std::test]
+pub async fn test_input_value_source() {
+    /// The source of the value, instead of the value.
+    struct Source(String);
+
+    #[Scalar]
+    impl ScalarType for Source {
+        fn parse(_value: Value) -> InputValueResult<Self> {
+            Ok(Source("unknown".to_string()))
+        }
+
+        fn parse_with_context(value: Value, ctx: &ParseContext<'_>) -> InputValueResult<Self> {
+            match ctx.source() {
+                InputValueSource::Literal => match value {
+                    Value::String(s) if s.len() > 3 => Err(format!(
+                        "Literals are limited to 3 characters at {}:{}",
+                        ctx.pos().line,
+                        ctx.pos().column
+                    )
+                    .into()),
+                    _ => Ok(Source("literal".to_string())),
+                },
+                InputValueSource::Variable(name) => Ok(Source(format!("${}", name))),
+            }
+        }
+
+        fn to_value(&self) -> Value {
+            Value::String(self.0.clone())
+        }
+    }
+
+    #[derive(InputObject)]
+    struct Input {
+        a: Source,
+        b: Option<Source>,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn sources(&self, value: Source, list: Vec<Source>, input: Input) -> Vec<String> {
+            let mut sources = vec![value.0];
+            sources.extend(list.into_iter().map(|source| source.0));
+            sources.push(input.a.0);
+            sources.extend(input.b.map(|source| source.0));
+            sources
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let query = r#"query($v: Source!, $l: [Source!]!, $input: Input!) {
+        a: sources(value: "x", list: ["y", $v], input: { a: "z", b: $v })
+        b: sources(value: $v, list: $l, input: $input)
+    }"#;
+    let variables = Variables::from_json(serde_json::json!({
+        "v": "long value",
+        "l": ["long value"],
+        "input": { "a": "long value" },
+    }));
+    assert_eq!(
+        schema
+            .execute(Request::new(query).variables(variables))
+            .await
+            .data,
+        serde_json::json!({
+            "a": ["literal", "literal", "$v", "literal", "$v"],
+            "b": ["$v", "$l", "$input"],
+        })
+    );
+
+    let query = r#"{ sources(value: "long", list: [], input: { a: "z" }) }"#;
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap_err(),
+        Error::Query {
+            pos: Pos {
+                line: 1,
+                column: 18
+            },
+            path: Some(serde_json::json!(["sources"])),
+            err: QueryError::ParseInputValue {
+                reason: "Literals are limited to 3 characters at 1:18".to_string()
+            },
+        }
+    );
+}
+
+#[async_std::test]
+pub async fn test_input_value_structured_error() {
+    struct Color(String);
+
+    #[Scalar]
+    impl ScalarType for Color {
+        fn parse(value: Value) -> InputValueResult<Self> {
+            match &value {
+                Value::String(s) if s.starts_with('#') => Ok(Color(s.clone())),
+                _ => Err(InputValueError::invalid("a color such as `#ff0000`", value)
+                    .with_hint("Colors are written in hexadecimal.")),
+            }
+        }
+
+        fn to_value(&self) -> Value {
+            Value::String(self.0.clone())
+        }
+    }
+
+    #[derive(InputObject)]
+    struct Input {
+        colors: Vec<Color>,
+        level: Option<i8>,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn paint(&self, input: Input) -> usize {
+            input.colors.len()
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let err = schema
+        .execute(r##"{ paint(input: { colors: ["#000", "red"] }) }"##)
+        .await
+        .into_result()
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Error::Query {
+            pos: Pos {
+                line: 1,
+                column: 16
+            },
+            path: Some(serde_json::json!(["paint"])),
+            err: QueryError::InvalidInputValue {
+                path: "input.colors[1]".to_string(),
+                expected: "a color such as `#ff0000`".to_string(),
+                actual: Value::String("red".to_string()),
+                hint: Some("Colors are written in hexadecimal.".to_string()),
+            },
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "Query error: Invalid value for \"input.colors[1]\": expected a color such as `#ff0000`, \
+         found \"red\". Colors are written in hexadecimal."
+    );
+
+    assert_eq!(
+        schema
+            .execute(r#"{ paint(input: { colors: [], level: 300 }) }"#)
+            .await
+            .into_result()
+            .unwrap_err(),
+        Error::Query {
+            pos: Pos {
+                line: 1,
+                column: 16
+            },
+            path: Some(serde_json::json!(["paint"])),
+            err: QueryError::ParseInputValue {
+                reason: "Only integers from -128 to 127 are accepted. (at \"input.level\")"
+                    .to_string()
+            },
+        }
+    );
+}
+
+#[cfg(feature = "bson")]
+#[async_std::test]
+pub async fn test_input_value_bson() {
+    use bson::{Bson, Document};
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn bson(&self, value: Bson) -> Bson {
+            value
+        }
+
+        async fn document(&self, value: Document) -> Document {
+            value
+        }
+
+        async fn json(&self, value: Json<serde_json::Value>) -> Json<serde_json::Value> {
+            value
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let query = r#"{
+        bson(value: 1)
+        document(value: { a: 1 })
+        json(value: [1])
+        b: __type(name: "Bson") { kind }
+        d: __type(name: "Document") { kind }
+    }"#;
+    assert_eq!(
+        schema.execute(query).await.data,
+        serde_json::json!({
+            "bson": 1,
+            "document": { "a": 1 },
+            "json": [1],
+            "b": { "kind": "SCALAR" },
+            "d": { "kind": "SCALAR" },
+        })
+    );
+
+    assert!(schema.execute(r#"{ document(value: 1) }"#).await.is_err());
+}
+
+#[async_std::test]
+pub async fn test_input_value_net() {
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn ip(&self, addr: IpAddr) -> IpAddr {
+            addr
+        }
+
+        async fn ipv4(&self, addr: Ipv4Addr) -> Ipv4Addr {
+            addr
+        }
+
+        async fn ipv6(&self, addr: Ipv6Addr) -> Ipv6Addr {
+            addr
+        }
+
+        async fn socket(&self, addr: SocketAddr) -> SocketAddr {
+            addr
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let query = r#"{
+        a: ip(addr: "127.0.0.1")
+        b: ip(addr: "::1")
+        ipv4(addr: "10.0.0.1")
+        ipv6(addr: "fe80::1")
+        socket(addr: "[::1]:8080")
+    }"#;
+    assert_eq!(
+        schema.execute(query).await.data,
+        serde_json::json!({
+            "a": "127.0.0.1",
+            "b": "::1",
+            "ipv4": "10.0.0.1",
+            "ipv6": "fe80::1",
+            "socket": "[::1]:8080",
+        })
+    );
+
+    for query in &[
+        r#"{ ip(addr: "localhost") }"#,
+        r#"{ ipv4(addr: "::1") }"#,
+        r#"{ ipv6(addr: "127.0.0.1") }"#,
+        r#"{ socket(addr: "127.0.0.1") }"#,
+    ] {
+        assert!(
+            matches!(
+                schema.execute(*query).await.into_result(),
+                Err(Error::Rule { .. })
+            ),
+            "{}",
+            query
+        );
+    }
+
+    let query = r#"query($addr: IpAddr!) { ip(addr: $addr) }"#;
+    assert!(schema
+        .execute(Request::new(query).variables(Variables::from_json(
+            serde_json::json!({ "addr": "not an address" })
+        )))
+        .await
+        .is_err());
+}
+
+#[cfg(feature = "url")]
+#[async_std::test]
+pub async fn test_input_value_url() {
+    use url::Url;
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn url(&self, url: Url) -> Url {
+            url
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute(r#"{ url(url: "https://example.com/a?b=c") }"#)
+            .await
+            .data,
+        serde_json::json!({ "url": "https://example.com/a?b=c" })
+    );
+    assert!(matches!(
+        schema
+            .execute(r#"{ url(url: "not a url") }"#)
+            .await
+            .into_result(),
+        Err(Error::Rule { .. })
+    ));
+}
+
+#[cfg(feature = "ulid")]
+#[async_std::test]
+pub async fn test_input_value_ulid() {
+    use ulid::Ulid;
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn ulid(&self, value: Ulid) -> Ulid {
+            value
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute(r#"{ ulid(value: "01ARZ3NDEKTSV4RRFFQ69G5FAV") }"#)
+            .await
+            .data,
+        serde_json::json!({ "ulid": "01ARZ3NDEKTSV4RRFFQ69G5FAV" })
+    );
+
+    let value = Ulid::new();
+    assert_eq!(
+        schema
+            .execute(
+                Request::new("query($value: ULID!) { ulid(value: $value) }").variables(
+                    Variables::from_json(serde_json::json!({ "value": value.to_string() }))
+                )
+            )
+            .await
+            .data,
+        serde_json::json!({ "ulid": value.to_string() })
+    );
+
+    for invalid in &[
+        "not a ulid",
+        "01ARZ3NDEKTSV4RRFFQ69G5FA",
+        "01ARZ3NDEKTSV4RRFFQ69G5FAU",
+    ] {
+        assert!(matches!(
+            schema
+                .execute(format!(r#"{{ ulid(value: "{}") }}"#, invalid))
+                .await
+                .into_result(),
+            Err(Error::Rule { .. })
+        ));
+    }
+    assert!(schema.sdl().contains("scalar ULID"));
+}
+
+#[cfg(all(feature = "duration", feature = "chrono"))]
+#[async_std::test]
+pub async fn test_input_value_duration() {
+    use std::time::Duration;
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn duration(&self, value: Duration) -> Duration {
+            value
+        }
+
+        async fn signed_duration(&self, value: chrono::Duration) -> chrono::Duration {
+            value
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema.execute(r#"{ duration(value: "PT90M") }"#).await.data,
+        serde_json::json!({ "duration": "PT1H30M" })
+    );
+    assert!(matches!(
+        schema
+            .execute(r#"{ duration(value: "-PT1S") }"#)
+            .await
+            .into_result(),
+        Err(Error::Rule { .. })
+    ));
+
+    assert_eq!(
+        schema
+            .execute(r#"{ signedDuration(value: "-PT1S") }"#)
+            .await
+            .data,
+        serde_json::json!({ "signedDuration": "-PT1S" })
+    );
+    assert!(schema.sdl().contains("scalar SignedDuration"));
+}
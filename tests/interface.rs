@@ -331,3 +331,171 @@ pub async fn test_interface_field_method() {
         })
     );
 }
+
+#[async_std::test]
+pub async fn test_interface_implements_interface() {
+    struct Image;
+
+    #[Object]
+    impl Image {
+        async fn id(&self) -> i32 {
+            1
+        }
+
+        async fn url(&self) -> String {
+            "https://example.com/image.png".to_string()
+        }
+    }
+
+    #[derive(Interface)]
+    #[graphql(field(name = "id", type = "i32"))]
+    enum Node {
+        Image(Image),
+    }
+
+    #[derive(Interface)]
+    #[graphql(
+        implements = "Node",
+        field(name = "id", type = "i32"),
+        field(name = "url", type = "String")
+    )]
+    enum Resource {
+        Image(Image),
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn resource(&self) -> Resource {
+            Image.into()
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(schema.check_interfaces(), Ok(()));
+    assert!(schema.sdl().contains("interface Resource implements Node "));
+
+    let query = r#"{
+            resource {
+                ... on Node {
+                    id
+                }
+                url
+            }
+            __type(name: "Resource") {
+                interfaces { name }
+            }
+        }"#;
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap().data,
+        serde_json::json!({
+            "resource": {
+                "id": 1,
+                "url": "https://example.com/image.png",
+            },
+            "__type": {
+                "interfaces": [{ "name": "Node" }],
+            }
+        })
+    );
+}
+
+#[async_std::test]
+pub async fn test_interface_implements_interface_check() {
+    struct User;
+
+    #[Object]
+    impl User {
+        async fn id(&self) -> i32 {
+            1
+        }
+    }
+
+    struct Image;
+
+    #[Object]
+    impl Image {
+        async fn id(&self) -> i32 {
+            2
+        }
+    }
+
+    #[derive(Interface)]
+    #[graphql(field(name = "id", type = "i32"))]
+    enum Node {
+        User(User),
+    }
+
+    // `Image` implements `Resource`, but is not a variant of `Node`.
+    #[derive(Interface)]
+    #[graphql(implements = "Node", field(name = "id", type = "i32"))]
+    enum Resource {
+        Image(Image),
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn resource(&self) -> Resource {
+            Image.into()
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema.check_interfaces(),
+        Err(vec![
+            "Type \"Image\" implements \"Resource\", so it must also implement \"Node\"."
+                .to_string()
+        ])
+    );
+}
+
+#[test]
+pub fn test_interface_incompatible_fields() {
+    struct Circle {
+        radius: f32,
+    }
+
+    #[Object]
+    impl Circle {
+        async fn area(&self, #[arg(name = "scale")] factor: f32) -> f32 {
+            3.0 * self.radius * self.radius * factor
+        }
+
+        #[field(name = "circumference")]
+        async fn perimeter(&self) -> f32 {
+            6.0 * self.radius
+        }
+    }
+
+    #[derive(Interface)]
+    #[graphql(
+        field(name = "area", type = "f32", arg(name = "factor", type = "f32")),
+        field(name = "perimeter", type = "f32")
+    )]
+    enum Shape {
+        Circle(Circle),
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn shape(&self) -> Shape {
+            Circle { radius: 1.0 }.into()
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema.check_interfaces(),
+        Err(vec![
+            "Field \"Circle.area\" has no argument \"factor\" of \"Shape.area\".".to_string(),
+            "Argument \"scale\" of \"Circle.area\" must be optional, since it is not an argument of \"Shape.area\".".to_string(),
+            "Type \"Circle\" has no field \"perimeter\" of the interface \"Shape\".".to_string(),
+        ])
+    );
+}
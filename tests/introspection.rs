@@ -982,6 +982,68 @@ pub async fn test_introspection_subscription() {
     assert_eq!(res, res_json)
 }
 
+#[async_std::test]
+pub async fn test_introspection_limits() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert!(schema.introspect().await.is_ok());
+
+    let nested = format!(
+        r#"{{ __type(name: "Query") {{ {}kind{} }} }}"#,
+        "ofType { ".repeat(DEFAULT_INTROSPECTION_DEPTH),
+        " }".repeat(DEFAULT_INTROSPECTION_DEPTH)
+    );
+    assert_eq!(
+        schema
+            .execute(nested.as_str())
+            .await
+            .into_result()
+            .unwrap_err(),
+        Error::Query {
+            pos: Pos::default(),
+            path: None,
+            err: QueryError::IntrospectionTooDeep {
+                limit: DEFAULT_INTROSPECTION_DEPTH
+            },
+        }
+    );
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .limit_introspection_depth(12)
+        .finish();
+    assert_eq!(
+        schema.introspect().await.unwrap_err(),
+        Error::Query {
+            pos: Pos::default(),
+            path: None,
+            err: QueryError::IntrospectionTooDeep { limit: 12 },
+        }
+    );
+
+    // Aliases and fragments are counted wherever they are used.
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .limit_introspection_size(5)
+        .finish();
+    let query = r#"
+        { a: __type(name: "Query") { ...Names } b: __type(name: "Query") { ...Names } }
+        fragment Names on __Type { name fields { name } }
+    "#;
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap_err(),
+        Error::Query {
+            pos: Pos::default(),
+            path: None,
+            err: QueryError::IntrospectionTooLarge { limit: 5 },
+        }
+    );
+    assert!(schema
+        .execute(r#"{ __type(name: "Query") { name fields { name } } }"#)
+        .await
+        .is_ok());
+
+    // `__typename` is not counted.
+    assert!(schema.execute("{ __typename }").await.is_ok());
+}
+
 // #[async_std::test]
 // pub async fn test_introspection_full() {
 //     let schema = Schema::new(Query, EmptyMutation, Subscription);
@@ -0,0 +1,106 @@
+use async_graphql::*;
+use futures::{FutureExt, StreamExt};
+use std::sync::{Arc, Mutex};
+
+#[async_std::test]
+pub async fn test_live_query() {
+    type Todos = Arc<Mutex<Vec<String>>>;
+
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {
+        async fn todos(&self, ctx: &Context<'_>) -> Vec<String> {
+            ctx.add_invalidation_key("todos");
+            ctx.data_unchecked::<Todos>().lock().unwrap().clone()
+        }
+
+        async fn count(&self, ctx: &Context<'_>) -> i32 {
+            ctx.data_unchecked::<Todos>().lock().unwrap().len() as i32
+        }
+    }
+
+    let todos = Todos::default();
+    todos.lock().unwrap().push("a".to_string());
+    let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .data(todos.clone())
+        .finish();
+
+    let mut stream = schema
+        .execute_stream("query @live { todos }")
+        .map(|resp| resp.into_result().unwrap().data)
+        .boxed();
+    assert_eq!(
+        stream.next().await,
+        Some(serde_json::json!({ "todos": ["a"] }))
+    );
+    assert_eq!(schema.active_subscriptions(), 1);
+
+    // The query does not depend on this key.
+    todos.lock().unwrap().push("b".to_string());
+    schema.invalidate("users");
+    assert!(stream.next().now_or_never().is_none());
+
+    schema.invalidate("todos");
+    assert_eq!(
+        stream.next().await,
+        Some(serde_json::json!({ "todos": ["a", "b"] }))
+    );
+
+    // The result is not sent again if it has not changed.
+    schema.invalidate("todos");
+    assert!(stream.next().now_or_never().is_none());
+
+    drop(stream);
+    assert_eq!(schema.active_subscriptions(), 0);
+
+    // Without invalidation keys, any invalidation re-executes the query.
+    let mut stream = schema
+        .execute_stream("query @live { count }")
+        .map(|resp| resp.into_result().unwrap().data)
+        .boxed();
+    assert_eq!(stream.next().await, Some(serde_json::json!({ "count": 2 })));
+    todos.lock().unwrap().push("c".to_string());
+    schema.invalidate("users");
+    assert_eq!(stream.next().await, Some(serde_json::json!({ "count": 3 })));
+
+    // Queries without the directive are executed once.
+    let mut stream = schema.execute_stream("{ todos }").boxed();
+    assert!(stream.next().await.is_some());
+    assert!(stream.next().await.is_none());
+}
+
+#[async_std::test]
+pub async fn test_live_query_shutdown() {
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {
+        async fn value(&self, ctx: &Context<'_>) -> i32 {
+            let reasons = ctx
+                .data_unchecked::<Arc<Mutex<Vec<CompletionReason>>>>()
+                .clone();
+            ctx.on_complete(move |reason| reasons.lock().unwrap().push(reason));
+            10
+        }
+    }
+
+    let reasons: Arc<Mutex<Vec<CompletionReason>>> = Default::default();
+    let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .data(reasons.clone())
+        .finish();
+
+    let mut stream = schema.execute_stream("query @live { value }").boxed();
+    assert!(stream.next().await.is_some());
+    assert_eq!(schema.active_subscriptions(), 1);
+
+    // Shutting down completes the live queries, like subscriptions.
+    schema.shutdown();
+    assert!(stream.next().await.is_none());
+    assert_eq!(schema.active_subscriptions(), 0);
+    drop(stream);
+    assert_eq!(
+        *reasons.lock().unwrap(),
+        vec![CompletionReason::ServerShutdown]
+    );
+}
@@ -111,6 +111,30 @@ pub async fn test_merged_object_derive() {
     );
 }
 
+#[test]
+pub fn test_merged_object_duplicate_field() {
+    #[derive(SimpleObject)]
+    struct OtherObject1 {
+        a: i32,
+    }
+
+    #[derive(MergedObject)]
+    struct Query(Object1, Object2, OtherObject1);
+
+    let errors = Schema::build(
+        Query(Object1 { a: 10 }, Object2 { b: 20 }, OtherObject1 { a: 30 }),
+        EmptyMutation,
+        EmptySubscription,
+    )
+    .try_finish()
+    .err()
+    .unwrap();
+    assert_eq!(
+        errors,
+        vec!["Field \"a\" of \"Object1\" is also defined by another merged object.".to_string()]
+    );
+}
+
 #[async_std::test]
 pub async fn test_merged_object_default() {
     mod a {
@@ -221,3 +245,78 @@ pub async fn test_merged_subscription() {
         assert!(stream.next().await.is_none());
     }
 }
+
+#[async_std::test]
+pub async fn test_merged_subscription_nested() {
+    #[derive(Default)]
+    struct ChatSubscription;
+
+    #[Subscription]
+    impl ChatSubscription {
+        async fn messages(&self) -> impl Stream<Item = FieldResult<i32>> {
+            futures::stream::iter(vec![Ok(1), Err("lost connection".into())])
+        }
+    }
+
+    #[derive(Default)]
+    struct PresenceSubscription;
+
+    #[Subscription]
+    impl PresenceSubscription {
+        async fn online(&self) -> impl Stream<Item = bool> {
+            futures::stream::iter(vec![true, false])
+        }
+    }
+
+    #[derive(Default)]
+    struct NotificationSubscription;
+
+    #[Subscription]
+    impl NotificationSubscription {
+        async fn notifications(&self) -> impl Stream<Item = i32> {
+            futures::stream::iter(0..3)
+        }
+    }
+
+    #[derive(MergedSubscription, Default)]
+    struct SocialSubscription(ChatSubscription, PresenceSubscription);
+
+    #[derive(MergedSubscription, Default)]
+    struct Subscription(SocialSubscription, NotificationSubscription);
+
+    struct Query;
+
+    #[Object]
+    impl Query {}
+
+    let schema = Schema::new(Query, EmptyMutation, Subscription::default());
+
+    let mut stream = schema.execute_stream("subscription { messages }").boxed();
+    assert_eq!(
+        stream.next().await.unwrap().into_result().unwrap().data,
+        serde_json::json!({ "messages": 1 })
+    );
+    assert!(stream.next().await.unwrap().is_err());
+    assert!(stream.next().await.is_none());
+
+    let stream = schema
+        .execute_stream("subscription { online }")
+        .map(|resp| resp.into_result().unwrap().data);
+    assert_eq!(
+        stream.collect::<Vec<_>>().await,
+        vec![
+            serde_json::json!({ "online": true }),
+            serde_json::json!({ "online": false }),
+        ]
+    );
+
+    let stream = schema
+        .execute_stream("subscription { notifications }")
+        .map(|resp| resp.into_result().unwrap().data);
+    assert_eq!(
+        stream.collect::<Vec<_>>().await,
+        (0..3)
+            .map(|i| serde_json::json!({ "notifications": i }))
+            .collect::<Vec<_>>()
+    );
+}
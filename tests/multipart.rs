@@ -0,0 +1,186 @@
+use async_graphql::http::{receive_body, receive_json, MultipartOptions};
+use async_graphql::*;
+use bytes::Bytes;
+use futures::stream::{self, Stream};
+use std::io::{self, Read};
+
+struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    async fn value(&self) -> i32 {
+        10
+    }
+}
+
+struct MutationRoot;
+
+#[Object]
+impl MutationRoot {
+    async fn upload(&self, files: Vec<Upload>) -> Vec<String> {
+        files
+            .into_iter()
+            .map(|file| {
+                let mut content = String::new();
+                let filename = file.filename().to_string();
+                file.into_read().read_to_string(&mut content).unwrap();
+                format!("{}: {}", filename, content)
+            })
+            .collect()
+    }
+}
+
+/// Split a body into small chunks, like a body that is received over the network.
+fn chunks(body: &[u8]) -> impl Stream<Item = io::Result<Bytes>> + Send + 'static {
+    stream::iter(
+        body.chunks(7)
+            .map(|chunk| Ok(Bytes::copy_from_slice(chunk)))
+            .collect::<Vec<_>>(),
+    )
+}
+
+fn body(
+    parts: &[(&str, Option<&str>, &str)],
+) -> impl Stream<Item = io::Result<Bytes>> + Send + 'static {
+    let mut body = String::new();
+    for (name, filename, content) in parts {
+        body += "--boundary\r\n";
+        match filename {
+            Some(filename) => {
+                body += &format!(
+                    "Content-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\n",
+                    name, filename
+                );
+                body += "Content-Type: text/plain\r\n";
+            }
+            None => body += &format!("Content-Disposition: form-data; name=\"{}\"\r\n", name),
+        }
+        body += &format!("\r\n{}\r\n", content);
+    }
+    body += "--boundary--\r\n";
+    chunks(body.as_bytes())
+}
+
+const OPERATIONS: &str = r#"{
+    "query": "mutation($files: [Upload!]!) { upload(files: $files) }",
+    "variables": { "files": [null, null] }
+}"#;
+const MAP: &str = r#"{ "0": ["variables.files.0"], "1": ["variables.files.1"] }"#;
+const CONTENT_TYPE: Option<&str> = Some("multipart/form-data; boundary=boundary");
+
+#[async_std::test]
+pub async fn test_multipart() {
+    let schema = Schema::new(QueryRoot, MutationRoot, EmptySubscription);
+
+    let request = receive_body(
+        CONTENT_TYPE,
+        body(&[
+            ("operations", None, OPERATIONS),
+            ("map", None, MAP),
+            ("0", Some("a.txt"), "alpha"),
+            ("unknown", Some("c.txt"), "charlie"),
+            ("1", Some("b.txt"), "bravo"),
+        ]),
+        MultipartOptions::default(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        schema.execute(request).await.data,
+        serde_json::json!({ "upload": ["a.txt: alpha", "b.txt: bravo"] })
+    );
+}
+
+#[async_std::test]
+pub async fn test_multipart_errors() {
+    let err = receive_body(
+        CONTENT_TYPE,
+        body(&[
+            ("operations", None, OPERATIONS),
+            ("0", Some("a.txt"), "alpha"),
+            ("map", None, MAP),
+        ]),
+        MultipartOptions::default(),
+    )
+    .await
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        ParseRequestError::UnexpectedPart { ref name, expected: "map" } if name == "0"
+    ));
+
+    let err = receive_body(
+        CONTENT_TYPE,
+        body(&[("operations", None, OPERATIONS)]),
+        MultipartOptions::default(),
+    )
+    .await
+    .unwrap_err();
+    assert!(matches!(err, ParseRequestError::MissingMapPart));
+
+    let err = receive_body(
+        CONTENT_TYPE,
+        body(&[
+            ("operations", None, OPERATIONS),
+            ("map", None, MAP),
+            ("0", Some("a.txt"), "alpha"),
+        ]),
+        MultipartOptions::default(),
+    )
+    .await
+    .unwrap_err();
+    assert!(matches!(err, ParseRequestError::MissingFiles));
+
+    let err = receive_body(
+        CONTENT_TYPE,
+        body(&[
+            ("operations", None, OPERATIONS),
+            ("map", None, MAP),
+            ("0", Some("a.txt"), "alpha"),
+            ("1", Some("b.txt"), "bravo"),
+        ]),
+        MultipartOptions::default().max_num_files(1),
+    )
+    .await
+    .unwrap_err();
+    assert!(matches!(err, ParseRequestError::TooManyFiles { limit: 1 }));
+
+    let large = "x".repeat(201);
+    let err = receive_body(
+        CONTENT_TYPE,
+        body(&[
+            ("operations", None, OPERATIONS),
+            ("map", None, MAP),
+            ("0", Some("a.txt"), "alpha"),
+            ("1", Some("b.txt"), large.as_str()),
+        ]),
+        MultipartOptions::default().max_file_size(200),
+    )
+    .await
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        ParseRequestError::PartTooLarge { ref name, limit: 200 } if name == "1"
+    ));
+}
+
+#[async_std::test]
+pub async fn test_receive_json() {
+    let schema = Schema::new(QueryRoot, MutationRoot, EmptySubscription);
+
+    let request = receive_json(chunks(br#"{ "query": "{ value }" }"#))
+        .await
+        .unwrap();
+    assert_eq!(
+        schema.execute(request).await.data,
+        serde_json::json!({ "value": 10 })
+    );
+
+    let err = receive_json(stream::iter(vec![
+        Ok(Bytes::from_static(b"{ \"query\"")),
+        Err(io::Error::new(io::ErrorKind::Other, "connection reset")),
+    ]))
+    .await
+    .unwrap_err();
+    assert!(matches!(err, ParseRequestError::Io(_)));
+}
@@ -0,0 +1,162 @@
+use async_graphql::validators::IntRange;
+use async_graphql::*;
+
+#[derive(InputObject, Debug, PartialEq)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(OneofObject, Debug, PartialEq)]
+enum Shape {
+    Point(Point),
+    #[item(validator(IntRange(min = "1", max = "100")))]
+    Square(i32),
+    #[item(name = "circleRadius", desc = "The radius of a circle.")]
+    Circle(i32),
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn shape(&self, shape: Shape) -> String {
+        format!("{:?}", shape)
+    }
+}
+
+#[async_std::test]
+pub async fn test_oneof_object() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+
+    assert_eq!(
+        schema
+            .execute(
+                r#"{
+                    point: shape(shape: { point: { x: 1, y: 2 } })
+                    square: shape(shape: { square: 3 })
+                    circle: shape(shape: { circleRadius: 4 })
+                }"#
+            )
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "point": "Point(Point { x: 1, y: 2 })",
+            "square": "Square(3)",
+            "circle": "Circle(4)",
+        })
+    );
+
+    let query = r#"query($shape: Shape!) { shape(shape: $shape) }"#;
+    assert_eq!(
+        schema
+            .execute(Request::new(query).variables(Variables::from_json(
+                serde_json::json!({ "shape": { "square": 5 } })
+            )))
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        serde_json::json!({ "shape": "Square(5)" })
+    );
+
+    assert_eq!(
+        Shape::parse(Some(
+            Value::from_json(serde_json::json!({ "circleRadius": 6 })).unwrap()
+        ))
+        .unwrap(),
+        Shape::Circle(6)
+    );
+    assert_eq!(
+        Shape::Point(Point { x: 1, y: 2 }).to_value(),
+        Value::from_json(serde_json::json!({ "point": { "x": 1, "y": 2 } })).unwrap()
+    );
+}
+
+#[async_std::test]
+pub async fn test_oneof_object_invalid() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+
+    let messages = |query: &'static str| {
+        let schema = &schema;
+        async move {
+            match schema.execute(query).await.into_result() {
+                Err(Error::Rule { errors }) => errors
+                    .into_iter()
+                    .map(|err| err.message)
+                    .collect::<Vec<_>>(),
+                res => panic!("unexpected result {:?}", res),
+            }
+        }
+    };
+
+    assert_eq!(
+        messages("{ shape(shape: {}) }").await,
+        vec!["Invalid value for argument \"shape\", exactly one field of oneof type \"Shape\" must be provided"]
+    );
+    assert_eq!(
+        messages("{ shape(shape: { square: 1, circleRadius: 2 }) }").await,
+        vec!["Invalid value for argument \"shape\", exactly one field of oneof type \"Shape\" must be provided"]
+    );
+    assert_eq!(
+        messages("{ shape(shape: { square: null }) }").await,
+        vec!["Invalid value for argument \"shape\", field \"square\" of oneof type \"Shape\" must not be null"]
+    );
+    assert!(schema
+        .execute("{ shape(shape: { square: 200 }) }")
+        .await
+        .is_err());
+
+    assert!(Shape::parse(Some(
+        Value::from_json(serde_json::json!({ "square": 1, "circleRadius": 2 })).unwrap()
+    ))
+    .is_err());
+    assert!(Shape::parse(Some(
+        Value::from_json(serde_json::json!({ "square": 1, "other": 2 })).unwrap()
+    ))
+    .is_ok());
+    assert!(Shape::parse(Some(
+        Value::from_json(serde_json::json!({ "square": null })).unwrap()
+    ))
+    .is_err());
+    assert!(Shape::parse(Some(Value::from_json(serde_json::json!({})).unwrap())).is_err());
+}
+
+#[async_std::test]
+pub async fn test_oneof_object_introspection() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+
+    assert_eq!(
+        schema
+            .execute(
+                r#"{
+                    shape: __type(name: "Shape") {
+                        isOneOf
+                        inputFields { name description type { kind name } }
+                    }
+                    point: __type(name: "Point") { isOneOf }
+                    query: __type(name: "Query") { isOneOf }
+                }"#
+            )
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "shape": {
+                "isOneOf": true,
+                "inputFields": [
+                    { "name": "point", "description": null, "type": { "kind": "INPUT_OBJECT", "name": "Point" } },
+                    { "name": "square", "description": null, "type": { "kind": "SCALAR", "name": "Int" } },
+                    { "name": "circleRadius", "description": "The radius of a circle.", "type": { "kind": "SCALAR", "name": "Int" } },
+                ]
+            },
+            "point": { "isOneOf": false },
+            "query": { "isOneOf": null },
+        })
+    );
+
+    assert!(schema.sdl().contains("input Shape @oneOf {"));
+}
@@ -0,0 +1,106 @@
+use async_graphql::persisted_operations::*;
+use async_graphql::*;
+use std::fs;
+use std::path::PathBuf;
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn value(&self) -> i32 {
+        10
+    }
+}
+
+fn documents(name: &str, files: &[(&str, &str)]) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("async-graphql-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    for (path, content) in files {
+        let path = dir.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+    dir
+}
+
+#[test]
+pub fn test_export_operations() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let dir = documents(
+        "export",
+        &[
+            (
+                "b/fragment.graphql",
+                "{ ...F } fragment F on Query { value }",
+            ),
+            ("a.graphql", "query GetValue { value }"),
+            ("README.md", "not a document"),
+        ],
+    );
+
+    let manifest = schema.export_operations(&dir).unwrap();
+    assert_eq!(manifest.format, "apollo-persisted-query-manifest");
+    assert_eq!(manifest.version, 1);
+    assert_eq!(
+        manifest.operations,
+        vec![
+            ManifestOperation {
+                id: "ead92496efe1a4b56974eeaca9a80683ea9bf19b8f707ce0abeaac3ab4c9b2b6".to_string(),
+                name: Some("GetValue".to_string()),
+                ty: "query".to_string(),
+                body: "query GetValue { value }".to_string(),
+            },
+            ManifestOperation {
+                id: "75870bce964458a89eb8fe7fc0f637e9503cbcd7de55a3f0bc68822d7af35573".to_string(),
+                name: None,
+                ty: "query".to_string(),
+                body: "{ ...F } fragment F on Query { value }".to_string(),
+            },
+        ]
+    );
+    assert_eq!(
+        manifest
+            .get("ead92496efe1a4b56974eeaca9a80683ea9bf19b8f707ce0abeaac3ab4c9b2b6")
+            .and_then(|operation| operation.name.as_deref()),
+        Some("GetValue")
+    );
+    assert_eq!(
+        serde_json::from_str::<OperationManifest>(&serde_json::to_string(&manifest).unwrap())
+            .unwrap(),
+        manifest
+    );
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+pub fn test_export_operations_errors() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let dir = documents(
+        "export-errors",
+        &[
+            ("invalid.graphql", "{ missing }"),
+            ("syntax.graphql", "{ value"),
+            ("two.graphql", "query A { value } query B { value }"),
+            ("valid.graphql", "{ value }"),
+        ],
+    );
+
+    let errors = schema.export_operations(&dir).unwrap_err();
+    assert_eq!(errors.len(), 3);
+    assert!(matches!(
+        &errors[0],
+        ExportError::Invalid { path, error: Error::Rule { .. } } if path.ends_with("invalid.graphql")
+    ));
+    assert!(matches!(
+        &errors[1],
+        ExportError::Invalid { path, error: Error::Parse(_) } if path.ends_with("syntax.graphql")
+    ));
+    assert!(matches!(
+        &errors[2],
+        ExportError::OperationCount { path, count: 2 } if path.ends_with("two.graphql")
+    ));
+    fs::remove_dir_all(dir).unwrap();
+
+    let errors = schema.export_operations(dir).unwrap_err();
+    assert!(matches!(&errors[..], [ExportError::Io { .. }]));
+}
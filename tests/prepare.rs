@@ -0,0 +1,192 @@
+use async_graphql::parser::types::OperationType;
+use async_graphql::validators::IntRange;
+use async_graphql::*;
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn value(&self, n: i32) -> i32 {
+        n
+    }
+
+    async fn name(&self) -> &str {
+        "a"
+    }
+
+    async fn percent(&self, #[arg(validator(IntRange(min = "0", max = "100")))] n: i32) -> i32 {
+        n
+    }
+
+    async fn fail(&self) -> FieldResult<i32> {
+        Err("Failed".into())
+    }
+}
+
+struct Mutation;
+
+#[Object]
+impl Mutation {
+    async fn increment(&self, n: i32) -> i32 {
+        n + 1
+    }
+}
+
+#[async_std::test]
+pub async fn test_prepare() {
+    let schema = Schema::new(Query, Mutation, EmptySubscription);
+
+    let operation = schema
+        .prepare(
+            Request::new(
+                r#"query A($n: Int!) { value(n: $n) ...F ... on Query { name } }
+                fragment F on Query { name }
+                mutation B { increment(n: 1) }"#,
+            )
+            .operation_name("A")
+            .variables(Variables::from_json(serde_json::json!({ "n": 10 }))),
+        )
+        .await
+        .unwrap();
+    assert_eq!(operation.operation_type(), OperationType::Query);
+    assert_eq!(operation.operation_name(), Some("A"));
+    assert_eq!(operation.variables().collect::<Vec<_>>(), vec!["n"]);
+    assert_eq!(operation.root_fields(), vec!["value", "name"]);
+    assert_eq!(
+        schema.execute_prepared(operation).await.data,
+        serde_json::json!({ "value": 10, "name": "a" })
+    );
+
+    let operation = schema
+        .prepare("mutation { increment(n: 1) }")
+        .await
+        .unwrap();
+    assert_eq!(operation.operation_type(), OperationType::Mutation);
+    assert_eq!(operation.operation_name(), None);
+    assert_eq!(operation.root_fields(), vec!["increment"]);
+    assert_eq!(
+        schema.execute_prepared(operation).await.data,
+        serde_json::json!({ "increment": 2 })
+    );
+
+    assert!(schema.prepare("{ missing }").await.is_err());
+}
+
+#[async_std::test]
+pub async fn test_execute_prepared_with() {
+    let schema = Schema::new(Query, Mutation, EmptySubscription);
+    let operation = schema
+        .prepare(
+            Request::new("query($n: Int!) { percent(n: $n) }")
+                .variables(Variables::from_json(serde_json::json!({ "n": 10 }))),
+        )
+        .await
+        .unwrap();
+
+    for n in &[20, 30] {
+        let request =
+            Request::new("ignored").variables(Variables::from_json(serde_json::json!({ "n": n })));
+        assert_eq!(
+            schema.execute_prepared_with(&operation, request).await.data,
+            serde_json::json!({ "percent": n })
+        );
+    }
+
+    // The arguments are checked against the new variables.
+    let request = Request::new("").variables(Variables::from_json(serde_json::json!({ "n": 200 })));
+    assert!(schema
+        .execute_prepared_with(&operation, request)
+        .await
+        .is_request_error());
+
+    assert_eq!(
+        schema.execute_prepared(operation).await.data,
+        serde_json::json!({ "percent": 10 })
+    );
+}
+
+#[async_std::test]
+pub async fn test_execute_prepared_with_id() {
+    let schema = Schema::new(Query, Mutation, EmptySubscription);
+    let operation = schema
+        .prepare("query Fail {\n  name\n  fail\n}")
+        .await
+        .unwrap()
+        .with_id("abc");
+    assert_eq!(operation.id(), Some("abc"));
+
+    assert_eq!(
+        serde_json::to_value(&schema.execute_prepared_with(&operation, "").await).unwrap(),
+        serde_json::json!({
+            "errors": [{
+                "message": "Failed",
+                "path": ["fail"],
+                "extensions": {
+                    "persistedOperation": {
+                        "id": "abc",
+                        "locations": [{"line": 3, "column": 3}]
+                    }
+                }
+            }]
+        })
+    );
+
+    // Without an id, the locations are not moved.
+    let operation = schema.prepare("{ fail }").await.unwrap();
+    assert_eq!(operation.id(), None);
+    assert_eq!(
+        serde_json::to_value(&schema.execute_prepared(operation).await).unwrap(),
+        serde_json::json!({
+            "errors": [{
+                "message": "Failed",
+                "locations": [{"line": 1, "column": 3}],
+                "path": ["fail"]
+            }]
+        })
+    );
+}
+
+#[async_std::test]
+pub async fn test_execute_prepared_with_complexity() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        #[field(complexity = "first as usize")]
+        async fn items(&self, first: i32) -> Vec<i32> {
+            (0..first).collect()
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .limit_complexity(10)
+        .finish();
+    let request = |n: i32| {
+        Request::new("query($n: Int!) { items(first: $n) }")
+            .variables(Variables::from_json(serde_json::json!({ "n": n })))
+    };
+    let operation = schema.prepare(request(1)).await.unwrap();
+    assert_eq!(operation.complexity(), 1);
+
+    assert_eq!(
+        schema
+            .execute_prepared_with(&operation, request(3))
+            .await
+            .data,
+        serde_json::json!({ "items": [0, 1, 2] })
+    );
+
+    // The complexity is computed again with the new variables.
+    assert_eq!(
+        schema
+            .execute_prepared_with(&operation, request(1000))
+            .await
+            .into_result()
+            .unwrap_err(),
+        Error::Query {
+            pos: Pos { line: 0, column: 0 },
+            path: None,
+            err: QueryError::TooComplex,
+        }
+    );
+}
@@ -0,0 +1,131 @@
+use async_graphql::*;
+use futures::{Stream, StreamExt};
+
+#[async_std::test]
+pub async fn test_preview_features() {
+    #[derive(SimpleObject)]
+    struct Price {
+        amount: i32,
+        #[field(requires_feature_header = "currency")]
+        currency: String,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn price(&self) -> Price {
+            Price {
+                amount: 10,
+                currency: "EUR".to_string(),
+            }
+        }
+
+        #[field(requires_feature_header = "discounts")]
+        async fn discount(&self) -> i32 {
+            5
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .query_cache(10)
+        .finish();
+    assert!(schema
+        .sdl()
+        .contains("discount: Int! @preview(feature: \"discounts\")"));
+    assert!(schema
+        .sdl()
+        .contains("currency: String! @preview(feature: \"currency\")"));
+
+    let query = "{ price { amount ...F } } fragment F on Price { currency }";
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap_err(),
+        Error::Query {
+            pos: Pos {
+                line: 1,
+                column: 49
+            },
+            path: None,
+            err: QueryError::PreviewFeatureRequired {
+                field_name: "currency".to_string(),
+                feature: "currency".to_string(),
+            },
+        }
+    );
+
+    // The query is cached, but the features are checked for each request.
+    let request = Request::new(query).data(PreviewFeatures::from_header("discounts, currency"));
+    assert_eq!(
+        schema.execute(request).await.data,
+        serde_json::json!({ "price": { "amount": 10, "currency": "EUR" } })
+    );
+    assert!(schema.execute(query).await.is_err());
+
+    let request = Request::new("{ discount }").data(PreviewFeatures::from_header("currency"));
+    assert!(schema.execute(request).await.is_err());
+    let request = Request::new("{ discount }").data(PreviewFeatures::from_header("discounts"));
+    assert_eq!(
+        schema.execute(request).await.data,
+        serde_json::json!({ "discount": 5 })
+    );
+}
+
+#[async_std::test]
+pub async fn test_preview_features_subscription() {
+    #[derive(SimpleObject)]
+    struct Price {
+        amount: i32,
+        #[field(requires_feature_header = "currency")]
+        currency: String,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    struct Subscription;
+
+    #[Subscription]
+    impl Subscription {
+        async fn prices(&self) -> impl Stream<Item = Price> {
+            futures::stream::once(async {
+                Price {
+                    amount: 10,
+                    currency: "EUR".to_string(),
+                }
+            })
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, Subscription);
+    let query = "subscription { prices { amount currency } }";
+
+    let request = Request::new(query).data(PreviewFeatures::from_header("currency"));
+    let mut stream = schema.execute_stream(request);
+    assert_eq!(
+        stream.next().await.unwrap().into_result().unwrap().data,
+        serde_json::json!({ "prices": { "amount": 10, "currency": "EUR" } })
+    );
+    assert!(stream.next().await.is_none());
+
+    let mut stream = schema.execute_stream(query);
+    assert_eq!(
+        stream.next().await.unwrap().into_result().unwrap_err(),
+        Error::Query {
+            pos: Pos {
+                line: 1,
+                column: 32
+            },
+            path: None,
+            err: QueryError::PreviewFeatureRequired {
+                field_name: "currency".to_string(),
+                feature: "currency".to_string(),
+            },
+        }
+    );
+}
@@ -0,0 +1,159 @@
+use async_graphql::extensions::{Extension, ExtensionContext};
+use async_graphql::parser::types::ExecutableDocument;
+use async_graphql::validators::IntRange;
+use async_graphql::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+#[async_std::test]
+pub async fn test_query_cache() {
+    /// Counts the parsed documents and the validations. A cached document only has the selected
+    /// operation, while the queries of this test have two.
+    struct CountParses {
+        parses: Arc<AtomicUsize>,
+        validations: Arc<AtomicUsize>,
+    }
+
+    #[async_trait::async_trait]
+    impl Extension for CountParses {
+        async fn parse_end(&mut self, _ctx: &ExtensionContext<'_>, document: &ExecutableDocument) {
+            if document.definitions.len() > 1 {
+                self.parses.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        async fn validation_end(&mut self, _ctx: &ExtensionContext<'_>) {
+            self.validations.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {
+        async fn value(&self, #[arg(validator(IntRange(min = "0", max = "10")))] n: i32) -> i32 {
+            n
+        }
+    }
+
+    let parses = Arc::new(AtomicUsize::default());
+    let validations = Arc::new(AtomicUsize::default());
+    let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .query_cache(2)
+        .extension({
+            let parses = parses.clone();
+            let validations = validations.clone();
+            move || CountParses {
+                parses: parses.clone(),
+                validations: validations.clone(),
+            }
+        })
+        .finish();
+    let query = "query A($n: Int!) { value(n: $n) } query B { value(n: 1) }";
+    let request = |operation_name: &str, n: i32| {
+        Request::new(query)
+            .operation_name(operation_name)
+            .variables(Variables::from_json(serde_json::json!({ "n": n })))
+    };
+
+    assert_eq!(
+        schema.execute(request("A", 3)).await.data,
+        serde_json::json!({ "value": 3 })
+    );
+    assert_eq!(
+        schema.execute(request("A", 5)).await.data,
+        serde_json::json!({ "value": 5 })
+    );
+    assert_eq!(parses.load(Ordering::SeqCst), 1);
+    assert_eq!(validations.load(Ordering::SeqCst), 2);
+
+    // The variables of a cached query are still validated.
+    assert!(schema.execute(request("A", 20)).await.is_err());
+    assert_eq!(parses.load(Ordering::SeqCst), 1);
+
+    // The operation name is part of the key.
+    assert_eq!(
+        schema.execute(request("B", 0)).await.data,
+        serde_json::json!({ "value": 1 })
+    );
+    assert_eq!(parses.load(Ordering::SeqCst), 2);
+
+    // The least recently used query is evicted.
+    let other_query = Request::new("query C { value(n: 2) } query D { value(n: 3) }");
+    assert_eq!(
+        schema.execute(other_query.operation_name("C")).await.data,
+        serde_json::json!({ "value": 2 })
+    );
+    assert_eq!(parses.load(Ordering::SeqCst), 3);
+    schema.execute(request("B", 0)).await;
+    assert_eq!(parses.load(Ordering::SeqCst), 3);
+    schema.execute(request("A", 1)).await;
+    assert_eq!(parses.load(Ordering::SeqCst), 4);
+
+    // Invalid queries are not cached.
+    let invalid_query = "query E { unknown } query F { value(n: 1) }";
+    assert!(schema
+        .execute(Request::new(invalid_query).operation_name("E"))
+        .await
+        .is_err());
+    assert!(schema
+        .execute(Request::new(invalid_query).operation_name("E"))
+        .await
+        .is_err());
+    assert_eq!(parses.load(Ordering::SeqCst), 6);
+}
+
+#[async_std::test]
+pub async fn test_query_cache_recency() {
+    /// Counts the parsed documents, a cached document only has the selected operation.
+    struct CountParses(Arc<AtomicUsize>);
+
+    #[async_trait::async_trait]
+    impl Extension for CountParses {
+        async fn parse_end(&mut self, _ctx: &ExtensionContext<'_>, document: &ExecutableDocument) {
+            if document.definitions.len() > 1 {
+                self.0.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+    }
+
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {
+        async fn value(&self) -> i32 {
+            1
+        }
+    }
+
+    let parses = Arc::new(AtomicUsize::default());
+    let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .query_cache(2)
+        .extension({
+            let parses = parses.clone();
+            move || CountParses(parses.clone())
+        })
+        .finish();
+    let request = |name: &str| {
+        Request::new(format!(
+            "query {} {{ value }} query Other {{ value }}",
+            name
+        ))
+        .operation_name(name)
+    };
+
+    schema.execute(request("A")).await;
+    schema.execute(request("B")).await;
+    assert_eq!(parses.load(Ordering::SeqCst), 2);
+
+    // The cache hits make `A` the most recently used query, so `B` is evicted.
+    for _ in 0..10 {
+        schema.execute(request("A")).await;
+    }
+    schema.execute(request("C")).await;
+    assert_eq!(parses.load(Ordering::SeqCst), 3);
+    schema.execute(request("A")).await;
+    assert_eq!(parses.load(Ordering::SeqCst), 3);
+    schema.execute(request("B")).await;
+    assert_eq!(parses.load(Ordering::SeqCst), 4);
+}
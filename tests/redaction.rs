@@ -0,0 +1,184 @@
+use async_graphql::*;
+use futures::{Stream, StreamExt};
+
+#[async_std::test]
+pub async fn test_redaction_policy() {
+    #[derive(Eq, PartialEq, Copy, Clone)]
+    enum Role {
+        Admin,
+        Guest,
+    }
+
+    struct MaskEmails;
+
+    impl RedactionPolicy for MaskEmails {
+        fn redact(&self, ctx: &Context<'_>, value: serde_json::Value) -> serde_json::Value {
+            if ctx.item.node.name.node == "email" && ctx.data_opt::<Role>() != Some(&Role::Admin) {
+                serde_json::Value::String("***".to_string())
+            } else {
+                value
+            }
+        }
+    }
+
+    #[derive(SimpleObject, Clone)]
+    struct User {
+        name: String,
+        email: String,
+    }
+
+    fn users() -> Vec<User> {
+        vec![
+            User {
+                name: "alice".to_string(),
+                email: "alice@example.com".to_string(),
+            },
+            User {
+                name: "bob".to_string(),
+                email: "bob@example.com".to_string(),
+            },
+        ]
+    }
+
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {
+        async fn users(&self) -> Vec<User> {
+            users()
+        }
+
+        async fn email(&self) -> String {
+            "root@example.com".to_string()
+        }
+    }
+
+    struct SubscriptionRoot;
+
+    #[Subscription]
+    impl SubscriptionRoot {
+        async fn users(&self) -> impl Stream<Item = User> {
+            futures::stream::iter(users())
+        }
+    }
+
+    let schema = Schema::build(QueryRoot, EmptyMutation, SubscriptionRoot)
+        .redaction_policy(MaskEmails)
+        .finish();
+    let query = "{ users { name mail: email } email }";
+
+    assert_eq!(
+        schema
+            .execute(Request::new(query).data(Role::Guest))
+            .await
+            .data,
+        serde_json::json!({
+            "users": [
+                { "name": "alice", "mail": "***" },
+                { "name": "bob", "mail": "***" },
+            ],
+            "email": "***",
+        })
+    );
+
+    assert_eq!(
+        schema
+            .execute(Request::new(query).data(Role::Admin))
+            .await
+            .data,
+        serde_json::json!({
+            "users": [
+                { "name": "alice", "mail": "alice@example.com" },
+                { "name": "bob", "mail": "bob@example.com" },
+            ],
+            "email": "root@example.com",
+        })
+    );
+
+    let mut stream = schema
+        .execute_stream(Request::new("subscription { users { name email } }").data(Role::Guest))
+        .map(|resp| resp.into_result().unwrap().data)
+        .boxed();
+    assert_eq!(
+        stream.next().await,
+        Some(serde_json::json!({ "users": { "name": "alice", "email": "***" } }))
+    );
+    assert_eq!(
+        stream.next().await,
+        Some(serde_json::json!({ "users": { "name": "bob", "email": "***" } }))
+    );
+    assert!(stream.next().await.is_none());
+}
+
+#[async_std::test]
+pub async fn test_redaction_policy_non_null() {
+    struct NullifyAll;
+
+    impl RedactionPolicy for NullifyAll {
+        fn redact(&self, ctx: &Context<'_>, value: serde_json::Value) -> serde_json::Value {
+            match value {
+                serde_json::Value::Array(items) if ctx.item.node.name.node == "tags" => {
+                    serde_json::Value::Array(
+                        items.into_iter().map(|_| serde_json::Value::Null).collect(),
+                    )
+                }
+                _ => serde_json::Value::Null,
+            }
+        }
+    }
+
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {
+        async fn nickname(&self) -> Option<String> {
+            Some("al".to_string())
+        }
+
+        async fn name(&self) -> String {
+            "alice".to_string()
+        }
+
+        async fn tags(&self) -> Option<Vec<String>> {
+            Some(vec!["a".to_string()])
+        }
+    }
+
+    let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .redaction_policy(NullifyAll)
+        .finish();
+
+    assert_eq!(
+        schema.execute("{ nickname }").await.data,
+        serde_json::json!({ "nickname": null })
+    );
+    assert_eq!(
+        schema
+            .execute("{ nickname name }")
+            .await
+            .into_result()
+            .unwrap_err(),
+        Error::Query {
+            pos: Pos {
+                line: 1,
+                column: 12
+            },
+            path: Some(serde_json::json!(["name"])),
+            err: QueryError::RedactedNonNull {
+                field_name: "name".to_string(),
+                ty: "String!".to_string(),
+            },
+        }
+    );
+    assert_eq!(
+        schema.execute("{ tags }").await.into_result().unwrap_err(),
+        Error::Query {
+            pos: Pos { line: 1, column: 3 },
+            path: Some(serde_json::json!(["tags"])),
+            err: QueryError::RedactedNonNull {
+                field_name: "tags".to_string(),
+                ty: "[String!]".to_string(),
+            },
+        }
+    );
+}
@@ -0,0 +1,57 @@
+use async_graphql::extensions::{Extension, ExtensionContext};
+use async_graphql::*;
+use std::sync::{Arc, Mutex};
+
+#[async_std::test]
+pub async fn test_request_extensions() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn client(&self, ctx: &Context<'_>) -> Option<String> {
+            let extensions = ctx.request_extensions();
+            Some(format!(
+                "{} {}",
+                extensions.client_name()?,
+                extensions.client_version()?
+            ))
+        }
+    }
+
+    struct ClientNames(Arc<Mutex<Vec<String>>>);
+
+    #[async_trait::async_trait]
+    impl Extension for ClientNames {
+        async fn request_start(
+            &mut self,
+            _ctx: &ExtensionContext<'_>,
+            extensions: &RequestExtensions,
+        ) {
+            if let Some(name) = extensions.client_name() {
+                self.0.lock().unwrap().push(name.to_string());
+            }
+        }
+    }
+
+    let names = Arc::new(Mutex::new(Vec::new()));
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .extension({
+            let names = names.clone();
+            move || ClientNames(names.clone())
+        })
+        .finish();
+
+    let request = Request::new("{ client }").extension(
+        "clientInfo",
+        serde_json::json!({ "clientName": "web", "clientVersion": "1.2.0" }),
+    );
+    assert_eq!(
+        schema.execute(request).await.data,
+        serde_json::json!({ "client": "web 1.2.0" })
+    );
+    assert_eq!(
+        schema.execute("{ client }").await.data,
+        serde_json::json!({ "client": null })
+    );
+    assert_eq!(*names.lock().unwrap(), vec!["web".to_string()]);
+}
@@ -0,0 +1,110 @@
+use async_graphql::parser::types::Field;
+use async_graphql::*;
+use serde_json::json;
+use std::borrow::Cow;
+
+#[allow(dead_code)]
+#[derive(SimpleObject)]
+struct Address {
+    city: String,
+}
+
+/// Only used for its type, the users are resolved from JSON documents.
+#[allow(dead_code)]
+#[derive(SimpleObject)]
+struct User {
+    id: i32,
+    name: String,
+    email: Option<String>,
+    address: Address,
+}
+
+struct UserDocument(serde_json::Value);
+
+impl Type for UserDocument {
+    fn type_name() -> Cow<'static, str> {
+        User::type_name()
+    }
+
+    fn create_type_info(registry: &mut registry::Registry) -> String {
+        User::create_type_info(registry)
+    }
+}
+
+#[async_trait::async_trait]
+impl OutputValueType for UserDocument {
+    async fn resolve(
+        &self,
+        ctx: &ContextSelectionSet<'_>,
+        field: &Positioned<Field>,
+    ) -> Result<serde_json::Value> {
+        ctx.with_field(field).resolve_selection_on(&self.0)
+    }
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn users(&self) -> Vec<UserDocument> {
+        vec![
+            UserDocument(json!({
+                "__typename": "User",
+                "id": 1,
+                "name": "Alice",
+                "email": "alice@example.com",
+                "address": { "city": "Paris", "street": "Rue de Rivoli" },
+                "passwordHash": "secret",
+            })),
+            UserDocument(json!({ "id": 2, "name": "Bob" })),
+        ]
+    }
+}
+
+#[async_std::test]
+pub async fn test_resolve_selection_on() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let query = r#"query($withEmail: Boolean!) {
+        users {
+            key: id
+            ... on User { name }
+            ...Contact
+            address { city }
+            address { town: city }
+        }
+    }
+    fragment Contact on User {
+        email @include(if: $withEmail)
+    }"#;
+
+    let res = schema
+        .execute(Request::new(query).variables(Variables::from_json(json!({ "withEmail": true }))))
+        .await;
+    assert_eq!(
+        res.data,
+        json!({
+            "users": [
+                {
+                    "key": 1,
+                    "name": "Alice",
+                    "email": "alice@example.com",
+                    "address": { "city": "Paris", "town": "Paris" },
+                },
+                { "key": 2, "name": "Bob", "email": null, "address": null },
+            ]
+        })
+    );
+
+    let res = schema
+        .execute(Request::new(query).variables(Variables::from_json(json!({ "withEmail": false }))))
+        .await;
+    assert_eq!(
+        res.data,
+        json!({
+            "users": [
+                { "key": 1, "name": "Alice", "address": { "city": "Paris", "town": "Paris" } },
+                { "key": 2, "name": "Bob", "address": null },
+            ]
+        })
+    );
+}
@@ -0,0 +1,147 @@
+#![cfg(feature = "rest")]
+
+use async_graphql::rest::*;
+use async_graphql::*;
+use serde::Deserialize;
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+
+#[derive(SimpleObject, Deserialize)]
+struct User {
+    id: i32,
+    name: String,
+}
+
+#[derive(Clone, Default)]
+struct MockClient {
+    requests: Arc<Mutex<Vec<RestRequest>>>,
+}
+
+#[async_trait::async_trait]
+impl RestClient for MockClient {
+    async fn send(&self, request: RestRequest) -> std::result::Result<RestResponse, String> {
+        self.requests.lock().unwrap().push(request.clone());
+        Ok(match request.url.as_str() {
+            "https://api.example.com/users/1" => RestResponse {
+                status: 200,
+                body: json!({ "data": { "id": 1, "name": "Alice" } }),
+            },
+            "https://api.example.com/teams/a%20b/users?limit=10" => RestResponse {
+                status: 200,
+                body: json!([{ "id": 2, "name": "Bob" }]),
+            },
+            "https://api.example.com/users/3" => RestResponse {
+                status: 500,
+                body: json!({ "message": "Internal error" }),
+            },
+            _ => RestResponse {
+                status: 404,
+                body: json!({ "message": "Not found" }),
+            },
+        })
+    }
+}
+
+struct Token(String);
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn user(&self, ctx: &Context<'_>, _id: i32) -> FieldResult<Option<User>> {
+        ctx.data_unchecked::<RestDataSource>()
+            .get("/users/{id}")
+            .pointer("/data")
+            .fetch(ctx)
+            .await
+    }
+
+    async fn team_users(
+        &self,
+        ctx: &Context<'_>,
+        _team: String,
+        #[arg(default = 10)] limit: i32,
+    ) -> FieldResult<Vec<User>> {
+        ctx.data_unchecked::<RestDataSource>()
+            .get("/teams/{team}/users?limit={limit}")
+            .arg("limit", &limit)
+            .fetch(ctx)
+            .await
+    }
+}
+
+#[async_std::test]
+pub async fn test_rest_data_source() {
+    let client = MockClient::default();
+    let source = RestDataSource::new("https://api.example.com/", client.clone())
+        .header("Accept", "application/json")
+        .header_from_data("Authorization", |token: &Token| {
+            format!("Bearer {}", token.0)
+        });
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .data(source)
+        .finish();
+
+    let res = schema
+        .execute(
+            Request::new(r#"{ user(id: 1) { id name } teamUsers(team: "a b") { name } }"#)
+                .data(Token("secret".to_string())),
+        )
+        .await;
+    assert_eq!(
+        res.data,
+        json!({
+            "user": { "id": 1, "name": "Alice" },
+            "teamUsers": [{ "name": "Bob" }],
+        })
+    );
+    let mut requests = client.requests.lock().unwrap().clone();
+    requests.sort_by(|a, b| a.url.cmp(&b.url));
+    assert_eq!(
+        requests,
+        vec![
+            RestRequest {
+                method: "GET".to_string(),
+                url: "https://api.example.com/teams/a%20b/users?limit=10".to_string(),
+                headers: vec![
+                    ("Accept".to_string(), "application/json".to_string()),
+                    ("Authorization".to_string(), "Bearer secret".to_string()),
+                ],
+            },
+            RestRequest {
+                method: "GET".to_string(),
+                url: "https://api.example.com/users/1".to_string(),
+                headers: vec![
+                    ("Accept".to_string(), "application/json".to_string()),
+                    ("Authorization".to_string(), "Bearer secret".to_string()),
+                ],
+            },
+        ]
+    );
+
+    assert_eq!(
+        schema.execute("{ user(id: 2) { id } }").await.data,
+        json!({ "user": null })
+    );
+    assert_eq!(
+        client.requests.lock().unwrap().last().unwrap().headers,
+        vec![("Accept".to_string(), "application/json".to_string())]
+    );
+
+    let err = schema
+        .execute("{ user(id: 3) { id } }")
+        .await
+        .into_result()
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Error::Query {
+            pos: Pos { line: 1, column: 3 },
+            path: Some(json!(["user"])),
+            err: QueryError::FieldError {
+                err: "The request failed with status 500".to_string(),
+                extended_error: Some(json!({ "status": 500 })),
+            },
+        }
+    );
+}
@@ -0,0 +1,60 @@
+use async_graphql::*;
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+enum Color {
+    Red,
+    LightBlue,
+}
+
+#[derive(SimpleObject)]
+struct Item {
+    id: ID,
+    display_name: Option<String>,
+    color: Color,
+}
+
+#[derive(InputObject)]
+struct Filter {
+    color: Option<Color>,
+    r#type: String,
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn items(&self, _filter: Filter, _first: Option<i32>) -> Vec<Item> {
+        vec![Item {
+            id: "1".into(),
+            display_name: None,
+            color: Color::Red,
+        }]
+    }
+}
+
+#[test]
+pub fn test_rust_client() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let code = schema
+        .rust_client(&[r#"query GetItems($filter: Filter!, $first: Int) {
+    items(filter: $filter, first: $first) { id name: displayName ...ItemColor }
+}
+fragment ItemColor on Item { color }"#])
+        .unwrap();
+
+    for expected in &[
+        "pub enum Color {\n    #[serde(rename = \"RED\")]\n    Red,\n    #[serde(rename = \"LIGHT_BLUE\")]\n    LightBlue,\n}\n",
+        "pub struct Filter {\n    #[serde(skip_serializing_if = \"Option::is_none\")]\n    pub color: Option<Color>,\n    pub r#type: String,\n}\n",
+        "pub const GET_ITEMS_QUERY: &str = \"query GetItems(",
+        "pub struct GetItemsVariables {\n    pub filter: Filter,\n    #[serde(skip_serializing_if = \"Option::is_none\")]\n    pub first: Option<i32>,\n}\n",
+        "pub struct GetItemsData {\n    pub items: Vec<GetItemsDataItems>,\n}\n",
+        "pub struct GetItemsDataItems {\n    pub id: String,\n    pub name: Option<String>,\n    pub color: Color,\n}\n",
+        "pub fn get_items(variables: &GetItemsVariables) -> serde_json::Value {",
+        "\"operationName\": \"GetItems\",",
+    ] {
+        assert!(code.contains(expected), "missing {:?} in:\n{}", expected, code);
+    }
+
+    assert!(schema.rust_client(&["{ missing }"]).is_err());
+    assert!(schema.rust_client(&["{ items"]).is_err());
+}
@@ -9,3 +9,275 @@ pub async fn test_schema_default() {
 
     let _schema = MySchema::default();
 }
+
+#[async_std::test]
+pub async fn test_schema_registry_reuse() {
+    #[derive(SimpleObject)]
+    struct Extra {
+        value: i32,
+    }
+
+    #[derive(SimpleObject, Default)]
+    struct Query {
+        value: i32,
+    }
+
+    let query = "{ __type(name: \"Extra\") { name } }";
+    let types = Schema::<Query, EmptyMutation, EmptySubscription>::types();
+
+    let schema =
+        Schema::build_with_types(&types, Query::default(), EmptyMutation, EmptySubscription)
+            .register_type::<Extra>()
+            .finish();
+    assert_eq!(
+        schema.execute(query).await.data,
+        serde_json::json!({ "__type": { "name": "Extra" } })
+    );
+
+    // Types registered on one builder must not leak into the other schemas built with the same
+    // types.
+    let schema =
+        Schema::build_with_types(&types, Query::default(), EmptyMutation, EmptySubscription)
+            .finish();
+    assert_eq!(
+        schema.execute("{ value }").await.data,
+        serde_json::json!({ "value": 0 })
+    );
+    assert_eq!(
+        schema.execute(query).await.data,
+        serde_json::json!({ "__type": null })
+    );
+}
+
+#[async_std::test]
+pub async fn test_schema_introspect() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let data = schema.introspect().await.unwrap();
+    assert_eq!(data["__schema"]["queryType"]["name"], "Query");
+    assert!(data["__schema"]["types"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|ty| ty["name"] == "Query" && ty["fields"][0]["name"] == "value"));
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .disable_introspection()
+        .finish();
+    assert!(schema.introspect().await.is_err());
+}
+
+#[async_std::test]
+pub async fn test_request_introspection_override() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    let query = "{ __schema { queryType { name } } }";
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .disable_introspection()
+        .finish();
+    assert!(schema.execute(query).await.is_err());
+    assert!(schema
+        .execute(r#"{ __type(name: "Query") { name } }"#)
+        .await
+        .is_err());
+    assert_eq!(
+        schema
+            .execute(Request::new(query).introspection(true))
+            .await
+            .data,
+        serde_json::json!({ "__schema": { "queryType": { "name": "Query" } } })
+    );
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert!(schema.execute(query).await.is_ok());
+    assert!(schema
+        .execute(Request::new(r#"{ __type(name: "Query") { name } }"#).introspection(false))
+        .await
+        .is_err());
+    assert!(schema
+        .execute(Request::new(query).introspection(false))
+        .await
+        .is_err());
+}
+
+#[async_std::test]
+pub async fn test_schema_fingerprint() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    struct OtherQuery;
+
+    #[Object(name = "Query")]
+    impl OtherQuery {
+        async fn value(&self) -> String {
+            String::new()
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema.fingerprint(),
+        Schema::new(Query, EmptyMutation, EmptySubscription).fingerprint()
+    );
+    assert_ne!(
+        schema.fingerprint(),
+        Schema::new(OtherQuery, EmptyMutation, EmptySubscription).fingerprint()
+    );
+    assert_eq!(schema.execute("{ value }").await.extensions, None);
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .expose_fingerprint()
+        .finish();
+    let resp = schema.execute("{ value }").await;
+    assert_eq!(resp.fingerprint(), Some(schema.fingerprint()));
+    assert_eq!(
+        resp.extensions,
+        Some(serde_json::json!({ "schemaFingerprint": schema.fingerprint() }))
+    );
+}
+
+#[async_std::test]
+pub async fn test_expose_query_cost() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(schema.execute("{ value }").await.query_cost, None);
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .expose_query_cost()
+        .query_cache(1)
+        .finish();
+    for _ in 0..2 {
+        let resp = schema.execute("{ a: value b: value }").await;
+        assert_eq!(resp.query_cost, Some(2));
+        assert_eq!(
+            resp.cost_headers(),
+            vec![("x-graphql-cost", "2".to_string())]
+        );
+    }
+    assert_eq!(schema.execute("{ value(a: 1) }").await.query_cost, None);
+}
+
+#[async_std::test]
+pub async fn test_parse_error_details() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self, a: i32) -> i32 {
+            a
+        }
+    }
+
+    let query = "{\n  value(: 1)\n}";
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let err = schema.execute(query).await.into_result().unwrap_err();
+    assert!(serde_json::to_value(&err).unwrap()[0]
+        .get("extensions")
+        .is_none());
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .parse_error_details()
+        .finish();
+    let err = schema.execute(query).await.into_result().unwrap_err();
+    let err = &serde_json::to_value(&err).unwrap()[0];
+    assert_eq!(
+        err["locations"],
+        serde_json::json!([{ "line": 2, "column": 9 }])
+    );
+    assert_eq!(err["extensions"]["token"], serde_json::json!(":"));
+    assert_eq!(
+        err["extensions"]["snippet"],
+        serde_json::json!("  value(: 1)\n        ^")
+    );
+    assert!(err["extensions"]["expected"]
+        .as_array()
+        .unwrap()
+        .contains(&serde_json::json!("name")));
+}
+
+#[async_std::test]
+pub async fn test_schema_description() {
+    struct Query;
+
+    /// The root of the queries.
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    struct Mutation;
+
+    /// The root of the mutations.
+    #[Object]
+    impl Mutation {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    let query = r#"{
+        __schema {
+            description
+            queryType { description }
+            mutationType { description }
+        }
+    }"#;
+
+    let schema = Schema::new(Query, Mutation, EmptySubscription);
+    assert_eq!(
+        schema.execute(query).await.data,
+        serde_json::json!({
+            "__schema": {
+                "description": null,
+                "queryType": { "description": "The root of the queries." },
+                "mutationType": { "description": "The root of the mutations." },
+            }
+        })
+    );
+    assert!(!schema.sdl().contains("schema {"));
+
+    let described = Schema::build(Query, Mutation, EmptySubscription)
+        .description("An example API.")
+        .finish();
+    assert_eq!(
+        described.execute(query).await.data["__schema"]["description"],
+        serde_json::json!("An example API.")
+    );
+    assert!(described.sdl().starts_with(
+        "\"\"\"\nAn example API.\n\"\"\"\nschema {\n\tquery: Query\n\tmutation: Mutation\n}\n"
+    ));
+    assert_ne!(described.fingerprint(), schema.fingerprint());
+}
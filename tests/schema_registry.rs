@@ -0,0 +1,133 @@
+use async_graphql::schema_registry::*;
+use async_graphql::*;
+use serde_json::{json, Value};
+use std::sync::Mutex;
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn value(&self) -> i32 {
+        10
+    }
+}
+
+struct MockTransport {
+    requests: Mutex<Vec<RegistryRequest>>,
+    response: Value,
+}
+
+impl MockTransport {
+    fn new(response: Value) -> Self {
+        Self {
+            requests: Default::default(),
+            response,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl RegistryTransport for MockTransport {
+    async fn send(&self, request: RegistryRequest) -> std::result::Result<Value, RegistryError> {
+        self.requests.lock().unwrap().push(request);
+        Ok(self.response.clone())
+    }
+}
+
+#[async_std::test]
+pub async fn test_publish_apollo_studio() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let registry = ApolloStudio::new("service:my-graph:key", "my-graph@production")
+        .subgraph("products", "https://products.example.com/graphql");
+
+    let transport = MockTransport::new(json!({
+        "data": { "graph": { "publishSubgraph": { "errors": [] } } }
+    }));
+    schema.publish(&registry, &transport).await.unwrap();
+    let request = transport.requests.lock().unwrap().pop().unwrap();
+    assert_eq!(request.url, "https://api.apollographql.com/api/graphql");
+    assert!(request
+        .headers
+        .contains(&("x-api-key".to_string(), "service:my-graph:key".to_string())));
+    assert_eq!(
+        request.body["variables"],
+        json!({
+            "graphId": "my-graph",
+            "variant": "production",
+            "name": "products",
+            "url": "https://products.example.com/graphql",
+            "sdl": schema.sdl(),
+        })
+    );
+
+    let transport = MockTransport::new(json!({
+        "data": { "graph": { "publishSubgraph": { "errors": [{ "message": "invalid" }] } } }
+    }));
+    assert_eq!(
+        schema.publish(&registry, &transport).await,
+        Err(RegistryError::Rejected("invalid".to_string()))
+    );
+
+    let transport = MockTransport::new(json!({ "data": { "graph": null } }));
+    assert_eq!(
+        schema.publish(&registry, &transport).await,
+        Err(RegistryError::Rejected(
+            "Graph \"my-graph@production\" not found".to_string()
+        ))
+    );
+}
+
+#[async_std::test]
+pub async fn test_publish_hive() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let registry = Hive::new("token", "Jane", "abc123");
+
+    let transport = MockTransport::new(json!({
+        "data": { "schemaPublish": { "__typename": "SchemaPublishSuccess" } }
+    }));
+    schema.publish(&registry, &transport).await.unwrap();
+    let request = transport.requests.lock().unwrap().pop().unwrap();
+    assert_eq!(
+        request.headers,
+        vec![("Authorization".to_string(), "Bearer token".to_string())]
+    );
+    assert_eq!(
+        request.body["variables"]["input"],
+        json!({ "sdl": schema.sdl(), "author": "Jane", "commit": "abc123" })
+    );
+
+    let transport = MockTransport::new(json!({
+        "data": { "schemaPublish": { "__typename": "SchemaPublishError" } }
+    }));
+    assert_eq!(
+        schema.publish(&registry, &transport).await,
+        Err(RegistryError::Rejected("SchemaPublishError".to_string()))
+    );
+}
+
+#[async_std::test]
+pub async fn test_publish_wundergraph() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let registry = WunderGraph::new("key", "products").namespace("staging");
+
+    let transport = MockTransport::new(json!({ "response": { "code": "OK" } }));
+    schema.publish(&registry, &transport).await.unwrap();
+    let request = transport.requests.lock().unwrap().pop().unwrap();
+    assert_eq!(
+        request.url,
+        "https://cosmo-cp.wundergraph.com/wg.cosmo.platform.v1.PlatformService/PublishFederatedSubgraph"
+    );
+    assert_eq!(
+        request.body,
+        json!({ "name": "products", "namespace": "staging", "schema": schema.sdl() })
+    );
+
+    let transport = MockTransport::new(json!({
+        "code": "unauthenticated",
+        "message": "invalid API key",
+    }));
+    assert_eq!(
+        schema.publish(&registry, &transport).await,
+        Err(RegistryError::Rejected("invalid API key".to_string()))
+    );
+}
@@ -0,0 +1,159 @@
+use async_graphql::*;
+
+#[async_std::test]
+pub async fn test_simple_object_from() {
+    struct UserRow {
+        id: i64,
+        user_name: String,
+        created_at: i64,
+        password_hash: String,
+    }
+
+    fn format_date(timestamp: i64) -> String {
+        format!("@{}", timestamp)
+    }
+
+    #[derive(SimpleObject)]
+    #[graphql(from = "UserRow")]
+    struct User {
+        id: i64,
+        #[field(from_field = "user_name")]
+        name: String,
+        #[field(from_with = "format_date", from_field = "created_at")]
+        created: String,
+        #[field(skip)]
+        secret: Option<String>,
+    }
+
+    let row = UserRow {
+        id: 1,
+        user_name: "abc".to_string(),
+        created_at: 100,
+        password_hash: "hash".to_string(),
+    };
+    assert_eq!(row.password_hash, "hash");
+    let user = User::from(row);
+    assert!(user.secret.is_none());
+
+    let schema = Schema::new(user, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema.execute("{ id name created }").await.data,
+        serde_json::json!({
+            "id": 1,
+            "name": "abc",
+            "created": "@100",
+        })
+    );
+}
+
+#[async_std::test]
+pub async fn test_complex_object() {
+    #[derive(SimpleObject)]
+    #[graphql(complex)]
+    struct User {
+        id: i64,
+        #[field(name = "login")]
+        name: String,
+        #[field(deprecation = "Use `fullName`")]
+        first_name: String,
+        #[field(skip)]
+        last_name: String,
+    }
+
+    #[ComplexObject]
+    impl User {
+        async fn full_name(&self, #[arg(default = " ")] separator: String) -> String {
+            format!("{}{}{}", self.first_name, separator, self.last_name)
+        }
+
+        #[field(name = "initials")]
+        async fn get_initials(&self) -> FieldResult<String> {
+            match (
+                self.first_name.chars().next(),
+                self.last_name.chars().next(),
+            ) {
+                (Some(first), Some(last)) => Ok(format!("{}{}", first, last)),
+                _ => Err("Empty name".into()),
+            }
+        }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn user(&self) -> User {
+            User {
+                id: 1,
+                name: "jdoe".to_string(),
+                first_name: "John".to_string(),
+                last_name: "Doe".to_string(),
+            }
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute(r#"{ user { id login firstName fullName dashed: fullName(separator: "-") initials } }"#)
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "user": {
+                "id": 1,
+                "login": "jdoe",
+                "firstName": "John",
+                "fullName": "John Doe",
+                "dashed": "John-Doe",
+                "initials": "JD",
+            }
+        })
+    );
+
+    assert!(schema.execute("{ user { lastName } }").await.is_err());
+    assert_eq!(
+        schema
+            .execute(
+                r#"{ __type(name: "User") { fields(includeDeprecated: true) { name isDeprecated } } }"#
+            )
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "__type": {
+                "fields": [
+                    { "name": "id", "isDeprecated": false },
+                    { "name": "login", "isDeprecated": false },
+                    { "name": "firstName", "isDeprecated": true },
+                    { "name": "fullName", "isDeprecated": false },
+                    { "name": "initials", "isDeprecated": false },
+                ]
+            }
+        })
+    );
+}
+
+#[test]
+#[should_panic(
+    expected = "Field \"id\" of \"User\" is defined by both the struct and its ComplexObject impl."
+)]
+pub fn test_complex_object_duplicate_field() {
+    #[derive(SimpleObject)]
+    #[graphql(complex)]
+    struct User {
+        id: i64,
+    }
+
+    #[ComplexObject]
+    impl User {
+        #[field(name = "id")]
+        async fn user_id(&self) -> i64 {
+            self.id
+        }
+    }
+
+    Schema::new(User { id: 1 }, EmptyMutation, EmptySubscription);
+}
@@ -0,0 +1,52 @@
+use async_graphql::*;
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    async fn value(&self, ctx: &Context<'_>) -> i32 {
+        let counter = ctx.data_unchecked::<Arc<AtomicI32>>().clone();
+        ctx.spawn(async move {
+            async_std::task::sleep(Duration::from_millis(50)).await;
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+        10
+    }
+}
+
+#[async_std::test]
+pub async fn test_spawn() {
+    let schema = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
+    let counter = Arc::new(AtomicI32::default());
+    let res = schema
+        .execute(Request::new("{ a: value b: value }").data(counter.clone()))
+        .await;
+    assert_eq!(res.data, serde_json::json!({ "a": 10, "b": 10 }));
+    assert_eq!(counter.load(Ordering::SeqCst), 2);
+}
+
+#[async_std::test]
+pub async fn test_spawn_with_spawner() {
+    let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .spawner(|fut| {
+            async_std::task::spawn(fut);
+        })
+        .finish();
+
+    let counter = Arc::new(AtomicI32::default());
+    let res = schema
+        .execute(Request::new("{ value }").data(counter.clone()))
+        .await;
+    assert_eq!(res.data, serde_json::json!({ "value": 10 }));
+    assert_eq!(counter.load(Ordering::SeqCst), 1);
+
+    // Dropping the request cancels the spawned work.
+    let counter = Arc::new(AtomicI32::default());
+    let fut = schema.execute(Request::new("{ value }").data(counter.clone()));
+    let _ = async_std::future::timeout(Duration::from_millis(10), fut).await;
+    async_std::task::sleep(Duration::from_millis(100)).await;
+    assert_eq!(counter.load(Ordering::SeqCst), 0);
+}
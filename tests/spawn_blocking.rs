@@ -0,0 +1,35 @@
+#![cfg(feature = "unblock")]
+
+use async_graphql::*;
+
+#[async_std::test]
+pub async fn test_spawn_blocking() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self, ctx: &Context<'_>, a: i32) -> i32 {
+            ctx.spawn_blocking(move |ctx| a + *ctx.data_unchecked::<i32>())
+                .await
+        }
+
+        async fn request_data(&self, ctx: &Context<'_>) -> String {
+            ctx.spawn_blocking(|ctx| ctx.data_unchecked::<String>().clone())
+                .await
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .data(10i32)
+        .finish();
+    assert_eq!(
+        schema
+            .execute(Request::new("{ value(a: 5) requestData }").data("abc".to_string()))
+            .await
+            .data,
+        serde_json::json!({
+            "value": 15,
+            "requestData": "abc",
+        })
+    );
+}
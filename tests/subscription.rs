@@ -425,3 +425,281 @@ pub async fn test_subscription_fieldresult() {
 
     assert!(stream.next().await.is_none());
 }
+
+#[async_std::test]
+pub async fn test_subscription_shared() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {}
+
+    #[derive(SimpleObject, Clone)]
+    struct Event {
+        a: i32,
+        b: i32,
+    }
+
+    struct SubscriptionRoot;
+
+    #[Subscription]
+    impl SubscriptionRoot {
+        #[field(shared)]
+        async fn events(&self, ctx: &Context<'_>, end: i32) -> impl Stream<Item = Event> {
+            ctx.data_unchecked::<Arc<AtomicUsize>>()
+                .fetch_add(1, Ordering::SeqCst);
+            futures::stream::iter((0..end).map(|n| Event { a: n, b: n * 10 }))
+        }
+    }
+
+    let created = Arc::new(AtomicUsize::new(0));
+    let schema = Schema::build(QueryRoot, EmptyMutation, SubscriptionRoot)
+        .data(created.clone())
+        .finish();
+
+    let mut stream1 = schema
+        .execute_stream("subscription { events(end: 3) { a } }")
+        .map(|resp| resp.into_result().unwrap().data)
+        .boxed();
+    let mut stream2 = schema
+        .execute_stream("subscription { events(end: 3) { b } }")
+        .map(|resp| resp.into_result().unwrap().data)
+        .boxed();
+    let mut stream3 = schema
+        .execute_stream("subscription { events(end: 1) { a b } }")
+        .map(|resp| resp.into_result().unwrap().data)
+        .boxed();
+
+    // The second subscription joins the source after its first item.
+    assert_eq!(
+        stream1.next().await,
+        Some(serde_json::json!({ "events": { "a": 0 } }))
+    );
+    for i in 1..3 {
+        assert_eq!(
+            stream2.next().await,
+            Some(serde_json::json!({ "events": { "b": i * 10 } }))
+        );
+    }
+    assert!(stream2.next().await.is_none());
+    for i in 1..3 {
+        assert_eq!(
+            stream1.next().await,
+            Some(serde_json::json!({ "events": { "a": i } }))
+        );
+    }
+    assert!(stream1.next().await.is_none());
+    assert_eq!(created.load(Ordering::SeqCst), 1);
+
+    // Different arguments use another source.
+    assert_eq!(
+        stream3.next().await,
+        Some(serde_json::json!({ "events": { "a": 0, "b": 0 } }))
+    );
+    assert!(stream3.next().await.is_none());
+    assert_eq!(created.load(Ordering::SeqCst), 2);
+
+    // The source is dropped with its last subscriber.
+    drop((stream1, stream2, stream3));
+    let mut stream = schema
+        .execute_stream("subscription { events(end: 3) { a } }")
+        .map(|resp| resp.into_result().unwrap().data)
+        .boxed();
+    assert_eq!(
+        stream.next().await,
+        Some(serde_json::json!({ "events": { "a": 0 } }))
+    );
+    assert_eq!(created.load(Ordering::SeqCst), 3);
+}
+
+#[async_std::test]
+pub async fn test_subscription_on_complete() {
+    use std::sync::{Arc, Mutex};
+
+    type Reasons = Arc<Mutex<Vec<CompletionReason>>>;
+
+    fn record_completion(ctx: &Context<'_>) {
+        let reasons = ctx.data_unchecked::<Reasons>().clone();
+        ctx.on_complete(move |reason| reasons.lock().unwrap().push(reason));
+    }
+
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {}
+
+    struct SubscriptionRoot;
+
+    #[Subscription]
+    impl SubscriptionRoot {
+        async fn values(&self, ctx: &Context<'_>, end: i32) -> impl Stream<Item = i32> {
+            record_completion(ctx);
+            futures::stream::iter(0..end)
+        }
+
+        async fn errors(&self, ctx: &Context<'_>) -> impl Stream<Item = FieldResult<i32>> {
+            record_completion(ctx);
+            futures::stream::once(async { Err("TestError".into()) })
+        }
+
+        async fn pending(&self, ctx: &Context<'_>) -> impl Stream<Item = i32> {
+            record_completion(ctx);
+            futures::stream::pending()
+        }
+    }
+
+    let reasons = Reasons::default();
+    let schema = Schema::build(QueryRoot, EmptyMutation, SubscriptionRoot)
+        .data(reasons.clone())
+        .finish();
+
+    let mut stream = schema
+        .execute_stream("subscription { values(end: 2) }")
+        .boxed();
+    while stream.next().await.is_some() {}
+    drop(stream);
+    assert_eq!(
+        reasons.lock().unwrap().pop(),
+        Some(CompletionReason::Finished)
+    );
+
+    let mut stream = schema.execute_stream("subscription { errors }").boxed();
+    assert!(stream.next().await.unwrap().is_err());
+    assert!(stream.next().await.is_none());
+    drop(stream);
+    assert_eq!(reasons.lock().unwrap().pop(), Some(CompletionReason::Error));
+
+    let mut stream = schema
+        .execute_stream("subscription { values(end: 2) }")
+        .boxed();
+    assert!(stream.next().await.is_some());
+    drop(stream);
+    assert_eq!(
+        reasons.lock().unwrap().pop(),
+        Some(CompletionReason::ConnectionClosed)
+    );
+
+    let mut stream = schema.execute_stream("subscription { pending }").boxed();
+    assert!(futures::FutureExt::now_or_never(stream.next()).is_none());
+    schema.shutdown();
+    assert!(stream.next().await.is_none());
+    drop(stream);
+    assert_eq!(
+        reasons.lock().unwrap().pop(),
+        Some(CompletionReason::ServerShutdown)
+    );
+    assert!(reasons.lock().unwrap().is_empty());
+}
+
+#[async_std::test]
+pub async fn test_subscription_cleanup() {
+    use futures::channel::mpsc;
+    use futures::Future;
+
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {}
+
+    fn release(ctx: &Context<'_>) -> impl Future<Output = ()> + Send + 'static {
+        let sender = ctx.data_unchecked::<mpsc::UnboundedSender<i32>>().clone();
+        let id = *ctx.data_unchecked::<i32>();
+        async move {
+            sender.unbounded_send(id).unwrap();
+        }
+    }
+
+    struct SubscriptionRoot;
+
+    #[Subscription]
+    impl SubscriptionRoot {
+        #[field(cleanup = "release")]
+        async fn values(&self) -> impl Stream<Item = i32> {
+            futures::stream::iter(0..10)
+        }
+    }
+
+    let (sender, mut receiver) = mpsc::unbounded();
+    let schema = Schema::build(QueryRoot, EmptyMutation, SubscriptionRoot)
+        .data(sender)
+        .data(42)
+        .spawner(|fut| {
+            async_std::task::spawn(fut);
+        })
+        .finish();
+
+    let mut stream = schema.execute_stream("subscription { values }").boxed();
+    assert_eq!(
+        stream.next().await.map(|resp| resp.data),
+        Some(serde_json::json!({ "values": 0 }))
+    );
+    drop(stream);
+    assert_eq!(receiver.next().await, Some(42));
+}
+
+#[async_std::test]
+pub async fn test_subscription_not_configured() {
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    struct SubscriptionRoot;
+
+    #[Subscription]
+    impl SubscriptionRoot {
+        async fn values(&self) -> impl Stream<Item = i32> {
+            futures::stream::iter(0..10)
+        }
+    }
+
+    let schema = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
+    assert!(!schema.has_subscriptions());
+    assert!(Schema::new(QueryRoot, EmptyMutation, SubscriptionRoot).has_subscriptions());
+
+    let mut stream = schema.execute_stream("subscription { values }").boxed();
+    let resp = stream.next().await.unwrap();
+    assert!(resp.is_request_error());
+    assert_eq!(
+        resp.error,
+        Some(Error::Query {
+            pos: Pos { line: 1, column: 1 },
+            path: None,
+            err: QueryError::NotConfiguredSubscriptions,
+        })
+    );
+    assert!(stream.next().await.is_none());
+
+    let resp = schema.execute("mutation { value }").await;
+    assert!(resp.is_request_error());
+    assert_eq!(
+        resp.error,
+        Some(Error::Query {
+            pos: Pos { line: 1, column: 1 },
+            path: None,
+            err: QueryError::NotConfiguredMutations,
+        })
+    );
+
+    // An anonymous operation is not selected by name
+    let resp = schema
+        .execute(Request::new("query { value } mutation Change { value }").operation_name("Change"))
+        .await;
+    assert_eq!(
+        resp.error,
+        Some(Error::Query {
+            pos: Pos {
+                line: 1,
+                column: 17
+            },
+            path: None,
+            err: QueryError::NotConfiguredMutations,
+        })
+    );
+}
@@ -1,6 +1,6 @@
 use async_graphql::*;
 use futures::channel::mpsc;
-use futures::{SinkExt, Stream, StreamExt};
+use futures::{FutureExt, SinkExt, Stream, StreamExt};
 
 #[async_std::test]
 pub async fn test_subscription_ws_transport() {
@@ -312,3 +312,818 @@ pub async fn test_query_over_websocket() {
         serde_json::from_str(&stream.next().await.unwrap()).unwrap()
     );
 }
+
+#[async_std::test]
+pub async fn test_subscription_ws_transport_replay() {
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {}
+
+    struct SubscriptionRoot;
+
+    #[Subscription]
+    impl SubscriptionRoot {
+        async fn values(&self) -> impl Stream<Item = i32> {
+            futures::stream::iter(0..3)
+        }
+    }
+
+    let schema = Schema::new(QueryRoot, EmptyMutation, SubscriptionRoot);
+    let event_buffer = std::sync::Arc::new(http::MemoryEventBuffer::new(10));
+
+    let (mut tx, rx) = mpsc::unbounded();
+    let mut stream = http::WebSocket::new(schema.clone(), rx).event_buffer(event_buffer.clone());
+
+    tx.send(
+        serde_json::to_string(&serde_json::json!({
+            "type": "connection_init",
+        }))
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+    let ack = serde_json::from_str::<serde_json::Value>(&stream.next().await.unwrap()).unwrap();
+    assert_eq!(ack["type"], "connection_ack");
+    let connection_id = ack["payload"]["connectionId"].as_str().unwrap().to_string();
+
+    tx.send(
+        serde_json::to_string(&serde_json::json!({
+            "type": "start",
+            "id": "1",
+            "payload": {
+                "query": "subscription { values }"
+            },
+        }))
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    for i in 0..3 {
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&stream.next().await.unwrap()).unwrap(),
+            serde_json::json!({
+                "type": "data",
+                "id": "1",
+                "payload": { "data": { "values": i } },
+                "eventId": i + 1,
+            }),
+        );
+    }
+
+    // Another client can't resume the connection with only an event id
+    let (mut tx, rx) = mpsc::unbounded();
+    let mut stream = http::WebSocket::new(schema.clone(), rx).event_buffer(event_buffer.clone());
+
+    tx.send(
+        serde_json::to_string(&serde_json::json!({
+            "type": "connection_init",
+            "payload": { "lastEventId": 1 },
+        }))
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+    let ack = serde_json::from_str::<serde_json::Value>(&stream.next().await.unwrap()).unwrap();
+    assert_ne!(ack["payload"]["connectionId"], connection_id.as_str());
+
+    tx.send(
+        serde_json::to_string(&serde_json::json!({
+            "type": "start",
+            "id": "1",
+            "payload": {
+                "query": "subscription { values }"
+            },
+        }))
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&stream.next().await.unwrap()).unwrap(),
+        serde_json::json!({
+            "type": "data",
+            "id": "1",
+            "payload": { "data": { "values": 0 } },
+            "eventId": 4,
+        }),
+    );
+
+    // Reconnect after receiving only the first event
+    let (mut tx, rx) = mpsc::unbounded();
+    let mut stream = http::WebSocket::new(schema, rx).event_buffer(event_buffer.clone());
+
+    tx.send(
+        serde_json::to_string(&serde_json::json!({
+            "type": "connection_init",
+            "payload": { "connectionId": connection_id, "lastEventId": 1 },
+        }))
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+    let ack = serde_json::from_str::<serde_json::Value>(&stream.next().await.unwrap()).unwrap();
+    assert_eq!(ack["payload"]["connectionId"], connection_id.as_str());
+
+    tx.send(
+        serde_json::to_string(&serde_json::json!({
+            "type": "start",
+            "id": "1",
+            "payload": {
+                "query": "subscription { values }"
+            },
+        }))
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    for i in 1..3 {
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&stream.next().await.unwrap()).unwrap(),
+            serde_json::json!({
+                "type": "data",
+                "id": "1",
+                "payload": { "data": { "values": i } },
+                "eventId": i + 1,
+            }),
+        );
+    }
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&stream.next().await.unwrap()).unwrap(),
+        serde_json::json!({
+            "type": "data",
+            "id": "1",
+            "payload": { "data": { "values": 0 } },
+            "eventId": 5,
+        }),
+    );
+
+    // The events are removed when the client closes the connection
+    tx.send(
+        serde_json::to_string(&serde_json::json!({
+            "type": "connection_terminate",
+        }))
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+    while stream.next().await.is_some() {}
+    assert!(!http::EventBuffer::contains_connection(
+        &event_buffer,
+        &connection_id
+    ));
+}
+
+#[async_std::test]
+pub async fn test_subscription_ws_transport_stop() {
+    use std::sync::{Arc, Mutex};
+
+    type Reasons = Arc<Mutex<Vec<CompletionReason>>>;
+
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {}
+
+    struct SubscriptionRoot;
+
+    #[Subscription]
+    impl SubscriptionRoot {
+        async fn values(&self, ctx: &Context<'_>) -> impl Stream<Item = i32> {
+            let reasons = ctx.data_unchecked::<Reasons>().clone();
+            ctx.on_complete(move |reason| reasons.lock().unwrap().push(reason));
+            futures::stream::iter(0..1).chain(futures::stream::pending())
+        }
+    }
+
+    let reasons = Reasons::default();
+    let schema = Schema::build(QueryRoot, EmptyMutation, SubscriptionRoot)
+        .data(reasons.clone())
+        .finish();
+    let (mut tx, rx) = mpsc::unbounded();
+    let mut stream = http::WebSocket::new(schema, rx);
+
+    tx.send(
+        serde_json::to_string(&serde_json::json!({
+            "type": "connection_init",
+        }))
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+    stream.next().await.unwrap();
+
+    tx.send(
+        serde_json::to_string(&serde_json::json!({
+            "type": "start",
+            "id": "1",
+            "payload": {
+                "query": "subscription { values }"
+            },
+        }))
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&stream.next().await.unwrap()).unwrap(),
+        serde_json::json!({
+            "type": "data",
+            "id": "1",
+            "payload": { "data": { "values": 0 } },
+        }),
+    );
+
+    tx.send(
+        serde_json::to_string(&serde_json::json!({
+            "type": "stop",
+            "id": "1",
+        }))
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&stream.next().await.unwrap()).unwrap(),
+        serde_json::json!({
+            "type": "complete",
+            "id": "1",
+        }),
+    );
+    assert_eq!(
+        *reasons.lock().unwrap(),
+        vec![CompletionReason::ClientComplete]
+    );
+}
+
+#[async_std::test]
+pub async fn test_subscription_ws_transport_messages_without_reply() {
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {}
+
+    struct SubscriptionRoot;
+
+    #[Subscription]
+    impl SubscriptionRoot {
+        async fn values(&self) -> impl Stream<Item = i32> {
+            futures::stream::pending()
+        }
+    }
+
+    let schema = Schema::new(QueryRoot, EmptyMutation, SubscriptionRoot);
+    let messages = futures::stream::iter(vec![
+        serde_json::json!({ "type": "stop", "id": "1" }),
+        serde_json::json!({ "type": "connection_init" }),
+        serde_json::json!({
+            "type": "start",
+            "id": "2",
+            "payload": { "query": "subscription { values }" },
+        }),
+    ])
+    .map(|message| serde_json::to_string(&message).unwrap());
+    let replies = http::WebSocket::new(schema, messages)
+        .map(|reply| serde_json::from_str::<serde_json::Value>(&reply).unwrap())
+        .collect::<Vec<_>>()
+        .await;
+
+    // The messages without a reply don't stall the connection.
+    assert_eq!(
+        replies,
+        vec![serde_json::json!({ "type": "connection_ack" })]
+    );
+}
+
+#[async_std::test]
+pub async fn test_subscription_ws_protocol() {
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {}
+
+    struct SubscriptionRoot;
+
+    #[Subscription]
+    impl SubscriptionRoot {
+        async fn values(&self) -> impl Stream<Item = i32> {
+            futures::stream::iter(0..2)
+        }
+    }
+
+    let schema = Schema::new(QueryRoot, EmptyMutation, SubscriptionRoot);
+    let mut protocol = http::WebSocketProtocol::new(schema);
+    let json = |message: http::OutMessage| match message {
+        http::OutMessage::Text(text) => serde_json::from_str::<serde_json::Value>(&text).unwrap(),
+        message => panic!("unexpected message {:?}", message),
+    };
+
+    assert_eq!(
+        protocol
+            .on_message(b"{")
+            .into_iter()
+            .map(json)
+            .collect::<Vec<_>>(),
+        vec![serde_json::json!({
+            "type": "connection_error",
+            "payload": {
+                "message": "EOF while parsing an object at line 1 column 1",
+                "extensions": null,
+            },
+        })]
+    );
+    assert_eq!(
+        protocol
+            .on_message(br#"{"type": "connection_init"}"#)
+            .into_iter()
+            .map(json)
+            .collect::<Vec<_>>(),
+        vec![serde_json::json!({ "type": "connection_ack" })]
+    );
+    assert_eq!(
+        protocol.on_message(
+            br#"{"type": "start", "id": "1", "payload": {"query": "subscription { values }"}}"#
+        ),
+        vec![]
+    );
+    assert_eq!(
+        protocol.on_message(br#"{"type": "stop", "id": "2"}"#),
+        vec![]
+    );
+
+    for i in 0..2 {
+        assert_eq!(
+            json(protocol.next().await.unwrap()),
+            serde_json::json!({
+                "type": "data",
+                "id": "1",
+                "payload": { "data": { "values": i } },
+            })
+        );
+    }
+    assert_eq!(
+        json(protocol.next().await.unwrap()),
+        serde_json::json!({ "type": "complete", "id": "1" })
+    );
+
+    assert_eq!(
+        protocol.on_message(br#"{"type": "connection_terminate"}"#),
+        vec![http::OutMessage::Close]
+    );
+}
+
+#[async_std::test]
+pub async fn test_subscription_ws_transport_with_connection_data() {
+    struct Token(String);
+    struct Locale(String);
+
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {}
+
+    struct SubscriptionRoot;
+
+    #[Subscription]
+    impl SubscriptionRoot {
+        async fn values(&self, ctx: &Context<'_>) -> impl Stream<Item = String> {
+            let value = format!(
+                "{} {}",
+                ctx.data_unchecked::<Token>().0,
+                ctx.data_unchecked::<Locale>().0
+            );
+            futures::stream::once(async move { value })
+        }
+    }
+
+    let schema = Schema::new(QueryRoot, EmptyMutation, SubscriptionRoot);
+    let (mut tx, rx) = mpsc::unbounded();
+    let mut connection_data = Data::default();
+    connection_data.insert(Token("123456".to_string()));
+    let mut stream = http::WebSocket::with_data(
+        schema,
+        rx,
+        Some(|value: serde_json::Value| {
+            let mut data = Data::default();
+            data.insert(Locale(value["locale"].as_str().unwrap().to_string()));
+            Ok(data)
+        }),
+    )
+    .connection_data(connection_data);
+
+    tx.send(
+        serde_json::to_string(&serde_json::json!({
+            "type": "connection_init",
+            "payload": { "locale": "en" }
+        }))
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        Some(serde_json::json!({
+            "type": "connection_ack",
+        })),
+        serde_json::from_str(&stream.next().await.unwrap()).unwrap()
+    );
+
+    tx.send(
+        serde_json::to_string(&serde_json::json!({
+            "type": "start",
+            "id": "1",
+            "payload": {
+                "query": "subscription { values }"
+            },
+        }))
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        Some(serde_json::json!({
+            "type": "data",
+            "id": "1",
+            "payload": { "data": { "values": "123456 en" } },
+        })),
+        serde_json::from_str(&stream.next().await.unwrap()).unwrap()
+    );
+}
+
+#[async_std::test]
+pub async fn test_subscription_graphql_transport_ws() {
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {}
+
+    struct SubscriptionRoot;
+
+    #[Subscription]
+    impl SubscriptionRoot {
+        async fn values(&self) -> impl Stream<Item = i32> {
+            futures::stream::iter(0..2)
+        }
+    }
+
+    assert_eq!(
+        http::Protocols::negotiate("graphql-transport-ws, graphql-ws"),
+        Some((http::Protocols::GraphQLWS, http::WebSocketEncoding::Json))
+    );
+    assert_eq!(
+        http::Protocols::negotiate("other, graphql-ws"),
+        Some((
+            http::Protocols::SubscriptionsTransportWS,
+            http::WebSocketEncoding::Json
+        ))
+    );
+    assert_eq!(http::Protocols::negotiate("other"), None);
+    assert_eq!(
+        http::Protocols::GraphQLWS.sec_websocket_protocol(http::WebSocketEncoding::Json),
+        "graphql-transport-ws"
+    );
+
+    let schema = Schema::new(QueryRoot, EmptyMutation, SubscriptionRoot);
+    let mut protocol =
+        http::WebSocketProtocol::new(schema.clone()).protocol(http::Protocols::GraphQLWS);
+    let json = |message: http::OutMessage| match message {
+        http::OutMessage::Text(text) => serde_json::from_str::<serde_json::Value>(&text).unwrap(),
+        message => panic!("unexpected message {:?}", message),
+    };
+
+    assert_eq!(
+        protocol
+            .on_message(br#"{"type": "connection_init"}"#)
+            .into_iter()
+            .map(json)
+            .collect::<Vec<_>>(),
+        vec![serde_json::json!({ "type": "connection_ack" })]
+    );
+    assert_eq!(
+        protocol
+            .on_message(br#"{"type": "ping"}"#)
+            .into_iter()
+            .map(json)
+            .collect::<Vec<_>>(),
+        vec![serde_json::json!({ "type": "pong" })]
+    );
+    assert_eq!(
+        protocol.on_message(
+            br#"{"type": "subscribe", "id": "1", "payload": {"query": "subscription { values }"}}"#
+        ),
+        vec![]
+    );
+    assert_eq!(
+        protocol.on_message(
+            br#"{"type": "subscribe", "id": "2", "payload": {"query": "subscription { missing }"}}"#
+        ),
+        vec![]
+    );
+
+    let mut messages = Vec::new();
+    for _ in 0..4 {
+        messages.push(json(protocol.next().await.unwrap()));
+    }
+    for i in 0..2 {
+        assert!(messages.contains(&serde_json::json!({
+            "type": "next",
+            "id": "1",
+            "payload": { "data": { "values": i } },
+        })));
+    }
+    assert!(messages.contains(&serde_json::json!({ "type": "complete", "id": "1" })));
+    assert!(messages.contains(&serde_json::json!({
+        "type": "error",
+        "id": "2",
+        "payload": [{
+            "message": r#"Unknown field "missing" on type "SubscriptionRoot"."#,
+            "locations": [{ "line": 1, "column": 16 }],
+        }],
+    })));
+
+    assert_eq!(
+        protocol.on_message(br#"{"type": "connection_init"}"#),
+        vec![http::OutMessage::CloseWithCode {
+            code: 4429,
+            reason: "Too many initialisation requests".to_string(),
+        }]
+    );
+    assert_eq!(
+        protocol.on_message(br#"{"type": "stop", "id": "3"}"#),
+        vec![http::OutMessage::CloseWithCode {
+            code: 4400,
+            reason: "Unexpected message type".to_string(),
+        }]
+    );
+
+    let mut protocol = http::WebSocketProtocol::new(schema).protocol(http::Protocols::GraphQLWS);
+    assert_eq!(
+        protocol.on_message(
+            br#"{"type": "subscribe", "id": "1", "payload": {"query": "subscription { values }"}}"#
+        ),
+        vec![http::OutMessage::CloseWithCode {
+            code: 4401,
+            reason: "Unauthorized".to_string(),
+        }]
+    );
+}
+
+#[async_std::test]
+pub async fn test_subscription_ws_async_initializer() {
+    struct Token(String);
+
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {}
+
+    struct SubscriptionRoot;
+
+    #[Subscription]
+    impl SubscriptionRoot {
+        async fn values(&self, ctx: &Context<'_>) -> FieldResult<impl Stream<Item = i32>> {
+            if ctx.data_unchecked::<Token>().0 != "123456" {
+                return Err("forbidden".into());
+            }
+            Ok(futures::stream::iter(0..2))
+        }
+    }
+
+    let initializer = |value: serde_json::Value| async move {
+        async_std::task::yield_now().await;
+        match value.get("token").and_then(|token| token.as_str()) {
+            Some("123456") => {
+                let mut data = Data::default();
+                data.insert(Token("123456".to_string()));
+                Ok(data)
+            }
+            _ => Err(FieldError::from("invalid token")),
+        }
+    };
+    let schema = Schema::new(QueryRoot, EmptyMutation, SubscriptionRoot);
+    let json = |message: http::OutMessage| match message {
+        http::OutMessage::Text(text) => serde_json::from_str::<serde_json::Value>(&text).unwrap(),
+        message => panic!("unexpected message {:?}", message),
+    };
+
+    // The messages received before the initializer returns are handled afterwards.
+    let mut protocol = http::WebSocketProtocol::new(schema.clone()).async_initializer(initializer);
+    assert_eq!(
+        protocol.on_message(br#"{"type": "connection_init", "payload": {"token": "123456"}}"#),
+        vec![]
+    );
+    assert_eq!(
+        protocol.on_message(
+            br#"{"type": "start", "id": "1", "payload": {"query": "subscription { values }"}}"#
+        ),
+        vec![]
+    );
+    assert_eq!(
+        json(protocol.next().await.unwrap()),
+        serde_json::json!({ "type": "connection_ack" })
+    );
+    for i in 0..2 {
+        assert_eq!(
+            json(protocol.next().await.unwrap()),
+            serde_json::json!({
+                "type": "data",
+                "id": "1",
+                "payload": { "data": { "values": i } },
+            })
+        );
+    }
+
+    let mut protocol = http::WebSocketProtocol::new(schema.clone()).async_initializer(initializer);
+    assert_eq!(
+        protocol.on_message(br#"{"type": "connection_init", "payload": {"token": "654321"}}"#),
+        vec![]
+    );
+    assert_eq!(
+        json(protocol.next().await.unwrap()),
+        serde_json::json!({
+            "type": "connection_error",
+            "payload": {
+                "message": "invalid token",
+                "extensions": null,
+            },
+        })
+    );
+
+    let mut protocol = http::WebSocketProtocol::new(schema)
+        .async_initializer(initializer)
+        .protocol(http::Protocols::GraphQLWS);
+    assert_eq!(
+        protocol.on_message(br#"{"type": "connection_init", "payload": {"token": "654321"}}"#),
+        vec![]
+    );
+    assert_eq!(
+        protocol.next().await,
+        Some(http::OutMessage::CloseWithCode {
+            code: 4403,
+            reason: "Forbidden".to_string(),
+        })
+    );
+}
+
+#[async_std::test]
+pub async fn test_subscription_ws_requires_initialization() {
+    struct Token(String);
+
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {}
+
+    struct SubscriptionRoot;
+
+    #[Subscription]
+    impl SubscriptionRoot {
+        async fn values(&self) -> impl Stream<Item = i32> {
+            futures::stream::iter(0..2)
+        }
+    }
+
+    let schema = Schema::new(QueryRoot, EmptyMutation, SubscriptionRoot);
+    let json = |message: http::OutMessage| match message {
+        http::OutMessage::Text(text) => serde_json::from_str::<serde_json::Value>(&text).unwrap(),
+        message => panic!("unexpected message {:?}", message),
+    };
+    let start = br#"{"type": "start", "id": "1", "payload": {"query": "subscription { values }"}}"#;
+
+    // A subscription can't start before the connection is initialized.
+    let mut protocol = http::WebSocketProtocol::new(schema.clone());
+    assert_eq!(
+        protocol.on_message(start),
+        vec![http::OutMessage::CloseWithCode {
+            code: 4401,
+            reason: "Unauthorized".to_string(),
+        }]
+    );
+
+    // The data initialization function also runs without a payload.
+    let mut protocol = http::WebSocketProtocol::with_data(
+        schema.clone(),
+        Some(|value: serde_json::Value| -> FieldResult<Data> {
+            let token = value
+                .get("token")
+                .and_then(|token| token.as_str())
+                .ok_or_else(|| FieldError::from("missing token"))?;
+            let mut data = Data::default();
+            data.insert(Token(token.to_string()));
+            Ok(data)
+        }),
+    );
+    let mut replies = protocol
+        .on_message(br#"{"type": "connection_init"}"#)
+        .into_iter();
+    assert_eq!(
+        json(replies.next().unwrap()),
+        serde_json::json!({
+            "type": "connection_error",
+            "payload": {
+                "message": "missing token",
+                "extensions": null,
+            },
+        })
+    );
+    assert_eq!(replies.next(), Some(http::OutMessage::Close));
+    assert_eq!(replies.next(), None);
+    assert_eq!(
+        protocol.on_message(start),
+        vec![http::OutMessage::CloseWithCode {
+            code: 4401,
+            reason: "Unauthorized".to_string(),
+        }]
+    );
+
+    // The messages received while a failing asynchronous initialization function runs are
+    // dropped, and the connection is closed.
+    let mut protocol =
+        http::WebSocketProtocol::new(schema).async_initializer(|_: serde_json::Value| async move {
+            Err::<Data, _>(FieldError::from("invalid token"))
+        });
+    assert_eq!(
+        protocol.on_message(br#"{"type": "connection_init"}"#),
+        vec![]
+    );
+    assert_eq!(protocol.on_message(start), vec![]);
+    assert_eq!(
+        json(protocol.next().await.unwrap()),
+        serde_json::json!({
+            "type": "connection_error",
+            "payload": {
+                "message": "invalid token",
+                "extensions": null,
+            },
+        })
+    );
+    assert_eq!(protocol.next().await, Some(http::OutMessage::Close));
+    assert_eq!(protocol.next().now_or_never(), None);
+}
+
+#[cfg(feature = "msgpack")]
+#[async_std::test]
+pub async fn test_subscription_ws_msgpack() {
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {}
+
+    struct SubscriptionRoot;
+
+    #[Subscription]
+    impl SubscriptionRoot {
+        async fn values(&self) -> impl Stream<Item = i32> {
+            futures::stream::iter(0..2)
+        }
+    }
+
+    let encoding = http::WebSocketEncoding::negotiate("graphql-ws+cbor, graphql-ws+msgpack");
+    assert_eq!(encoding, Some(http::WebSocketEncoding::MessagePack));
+    assert_eq!(encoding.unwrap().protocol(), "graphql-ws+msgpack");
+    assert_eq!(http::WebSocketEncoding::negotiate("other"), None);
+
+    let schema = Schema::new(QueryRoot, EmptyMutation, SubscriptionRoot);
+    let mut protocol = http::WebSocketProtocol::new(schema).encoding(encoding.unwrap());
+    let decode = |message: http::OutMessage| match message {
+        http::OutMessage::Binary(bytes) => {
+            rmp_serde::from_read_ref::<_, serde_json::Value>(&bytes).unwrap()
+        }
+        message => panic!("unexpected message {:?}", message),
+    };
+    let encode = |message: serde_json::Value| rmp_serde::to_vec_named(&message).unwrap();
+
+    assert_eq!(
+        protocol
+            .on_message(&encode(serde_json::json!({ "type": "connection_init" })))
+            .into_iter()
+            .map(decode)
+            .collect::<Vec<_>>(),
+        vec![serde_json::json!({ "type": "connection_ack" })]
+    );
+    assert!(protocol
+        .on_message(&encode(serde_json::json!({
+            "type": "start",
+            "id": "1",
+            "payload": { "query": "subscription { values }" },
+        })))
+        .is_empty());
+
+    for i in 0..2 {
+        assert_eq!(
+            decode(protocol.next().await.unwrap()),
+            serde_json::json!({
+                "type": "data",
+                "id": "1",
+                "payload": { "data": { "values": i } },
+            })
+        );
+    }
+    assert_eq!(
+        decode(protocol.next().await.unwrap()),
+        serde_json::json!({ "type": "complete", "id": "1" })
+    );
+}
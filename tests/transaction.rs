@@ -0,0 +1,92 @@
+use async_graphql::*;
+use std::sync::{Arc, Mutex};
+
+#[async_std::test]
+pub async fn test_transaction_hook() {
+    #[derive(Default)]
+    struct Transaction(Mutex<Vec<i32>>);
+
+    #[derive(Clone, Default)]
+    struct Database {
+        rows: Arc<Mutex<Vec<i32>>>,
+        rollbacks: Arc<Mutex<usize>>,
+    }
+
+    #[async_trait::async_trait]
+    impl TransactionHook for Database {
+        async fn begin(&self, data: &mut Data) -> FieldResult<()> {
+            data.insert(Transaction::default());
+            Ok(())
+        }
+
+        async fn commit(&self, data: &Data) -> FieldResult<()> {
+            let tx = data.get::<Transaction>().unwrap();
+            let mut rows = tx.0.lock().unwrap();
+            if rows.contains(&0) {
+                return Err("zero is not allowed".into());
+            }
+            self.rows.lock().unwrap().extend(rows.drain(..));
+            Ok(())
+        }
+
+        async fn rollback(&self, _data: &Data) {
+            *self.rollbacks.lock().unwrap() += 1;
+        }
+    }
+
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    struct MutationRoot;
+
+    #[Object]
+    impl MutationRoot {
+        async fn insert(&self, ctx: &Context<'_>, value: i32) -> FieldResult<i32> {
+            if value < 0 {
+                return Err("negative value".into());
+            }
+            ctx.data_unchecked::<Transaction>()
+                .0
+                .lock()
+                .unwrap()
+                .push(value);
+            Ok(value)
+        }
+    }
+
+    let database = Database::default();
+    let schema = Schema::build(QueryRoot, MutationRoot, EmptySubscription)
+        .transaction_hook(database.clone())
+        .finish();
+
+    let res = schema
+        .execute("mutation { a: insert(value: 1) b: insert(value: 2) }")
+        .await;
+    assert_eq!(res.data, serde_json::json!({ "a": 1, "b": 2 }));
+    assert_eq!(*database.rows.lock().unwrap(), vec![1, 2]);
+
+    let res = schema
+        .execute("mutation { a: insert(value: 3) b: insert(value: -1) }")
+        .await;
+    assert!(res.is_err());
+    assert_eq!(*database.rows.lock().unwrap(), vec![1, 2]);
+    assert_eq!(*database.rollbacks.lock().unwrap(), 1);
+
+    // The mutation was executed, so the error of the commit is not a request error
+    let res = schema.execute("mutation { insert(value: 0) }").await;
+    assert!(res.is_err());
+    assert!(!res.is_request_error());
+    assert_eq!(*database.rows.lock().unwrap(), vec![1, 2]);
+
+    assert_eq!(
+        schema.execute("{ value }").await.data,
+        serde_json::json!({ "value": 10 })
+    );
+    assert_eq!(*database.rollbacks.lock().unwrap(), 1);
+}
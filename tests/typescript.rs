@@ -0,0 +1,74 @@
+use async_graphql::*;
+
+/// A color
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+enum Color {
+    Red,
+    Green,
+}
+
+#[derive(SimpleObject)]
+struct Item {
+    id: ID,
+    name: Option<String>,
+    tags: Vec<Option<String>>,
+    color: Color,
+}
+
+#[derive(InputObject)]
+struct Filter {
+    color: Option<Color>,
+    #[graphql(default = 10)]
+    limit: i32,
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+    /// Find items
+    async fn items(&self, _filter: Filter, _first: Option<i32>) -> Vec<Item> {
+        vec![Item {
+            id: "1".into(),
+            name: None,
+            tags: Vec::new(),
+            color: Color::Red,
+        }]
+    }
+}
+
+#[test]
+pub fn test_typescript_definitions() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema.typescript_definitions(),
+        r#"/** A color */
+export type Color = "RED" | "GREEN";
+
+export interface Filter {
+  color?: Color | null;
+  limit?: number;
+}
+
+export interface Item {
+  __typename?: "Item";
+  id: string;
+  name?: string | null;
+  tags: Array<string | null>;
+  color: Color;
+}
+
+export interface Query {
+  __typename?: "Query";
+  /** Find items */
+  items: Item[];
+}
+
+export interface QueryItemsArgs {
+  filter: Filter;
+  first?: number | null;
+}
+
+"#
+    );
+}
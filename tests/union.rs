@@ -284,3 +284,100 @@ pub async fn test_union_field_result() {
         })
     );
 }
+
+#[async_std::test]
+pub async fn test_union_flatten() {
+    #[derive(SimpleObject)]
+    struct Data {
+        value: i32,
+    }
+
+    #[derive(SimpleObject)]
+    struct ErrorA {
+        message_a: String,
+    }
+
+    #[derive(SimpleObject)]
+    struct ErrorB {
+        message_b: String,
+    }
+
+    #[derive(Union)]
+    enum Failure {
+        ErrorA(ErrorA),
+        ErrorB(ErrorB),
+    }
+
+    #[derive(Union)]
+    enum Payload {
+        Data(Data),
+        #[graphql(flatten)]
+        Failure(Failure),
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn results(&self) -> Vec<Payload> {
+            vec![
+                Data { value: 10 }.into(),
+                Failure::from(ErrorA {
+                    message_a: "a".to_string(),
+                })
+                .into(),
+                Failure::from(ErrorB {
+                    message_b: "b".to_string(),
+                })
+                .into(),
+            ]
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+
+    let query = r#"{
+            results {
+                __typename
+                ... on Data {
+                    value
+                }
+                ... on Failure {
+                    ... on ErrorA {
+                        messageA
+                    }
+                }
+                ... on ErrorB {
+                    messageB
+                }
+            }
+        }"#;
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap().data,
+        serde_json::json!({
+            "results": [
+                { "__typename": "Data", "value": 10 },
+                { "__typename": "ErrorA", "messageA": "a" },
+                { "__typename": "ErrorB", "messageB": "b" },
+            ]
+        })
+    );
+
+    let query = r#"{
+            __type(name: "Payload") {
+                possibleTypes { name }
+            }
+        }"#;
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap().data,
+        serde_json::json!({
+            "__type": {
+                "possibleTypes": [
+                    { "name": "Data" },
+                    { "name": "ErrorA" },
+                    { "name": "ErrorB" },
+                ]
+            }
+        })
+    );
+}